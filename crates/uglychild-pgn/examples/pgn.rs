@@ -0,0 +1,76 @@
+//! Validates or reformats a PGN database, read from a file or stdin.
+//!
+//! ```text
+//! cargo run --release --example pgn -- validate games.pgn
+//! cargo run --release --example pgn -- reformat < games.pgn > clean.pgn
+//! ```
+//!
+//! `validate` replays every game with full legality checking and reports the ones that fail.
+//! `reformat` writes the games that parse back out in canonical form, keeping variations, NAGs,
+//! results, and comments both before the game and after each move; failures are reported on
+//! stderr.
+
+use std::{
+    env,
+    fs,
+    io::{self, Read},
+    process::ExitCode,
+};
+
+use uglychild_pgn::pgn::{PgnWriter, parse_games_from_bytes};
+
+/// Context stack capacity used while replaying games.
+const N: usize = 1024;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (reformat, path) = match args.as_slice() {
+        [command, rest @ ..]
+            if rest.len() <= 1 && matches!(command.as_str(), "validate" | "reformat") =>
+        {
+            (command == "reformat", rest.first())
+        }
+        _ => {
+            eprintln!("usage: pgn (validate | reformat) [<file>]");
+            return ExitCode::FAILURE;
+        }
+    };
+    let input = match path {
+        Some(path) => fs::read(path),
+        None => {
+            let mut input = Vec::new();
+            io::stdin().read_to_end(&mut input).map(|_| input)
+        }
+    };
+    let input = match input {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("cannot read input: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let games = parse_games_from_bytes::<N, _>(&input, |game| game);
+    let mut writer = PgnWriter::new(io::stdout().lock());
+    let mut invalid = 0;
+    for (index, game) in games.iter().enumerate() {
+        match game {
+            Ok(game) if reformat => {
+                if let Err(err) = writer.write_game(game) {
+                    eprintln!("cannot write output: {err}");
+                    return ExitCode::FAILURE;
+                }
+            }
+            Ok(_) => {}
+            Err(err) => {
+                invalid += 1;
+                eprintln!("game {}: {err}", index + 1);
+            }
+        }
+    }
+    eprintln!("{} games, {invalid} invalid", games.len());
+    match invalid {
+        0 => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    }
+}
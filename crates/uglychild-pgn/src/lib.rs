@@ -2,6 +2,7 @@
 //!
 //! This crate re-exports core chess types from `uglychild` and adds:
 //! - [`pgn`] for tokenization, parsing, move-tree construction, and rendering
+//! - [`tournament`] for running engine matches with adjudication
 //! - convenience re-export modules (`position`, `r#move`, `types`) for API parity.
 #![feature(const_trait_impl)]
 #![feature(derive_const)]
@@ -34,4 +35,7 @@ pub mod types {
 /// PGN parser, AST-like game object, tokens, and rendering configuration.
 pub mod pgn;
 
+/// Engine-versus-engine match runner producing PGN games.
+pub mod tournament;
+
 pub use pgn::{PgnError, PgnObject, PgnParser, PgnParsingState, PgnRenderingConfig};
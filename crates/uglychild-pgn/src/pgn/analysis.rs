@@ -0,0 +1,558 @@
+//! Per-player centipawn loss, accuracy, and error counts over a game's main line, and
+//! annotation of the main line with NAGs and `[%eval]` comments from those evaluations.
+//! [`PgnObject::from_engine_lines`] exports engine principal variations as a game.
+//!
+//! Evaluations come from the caller: an engine callback, or values read from elsewhere, such as
+//! the `[%eval]` comments the parser keeps on each move (see [`PgnObject::node_comment`]).
+
+use crate::{
+    Color,
+    MoveList,
+    TypedPosition,
+    r#move::Move,
+    pgn::{
+        error::PgnError,
+        move_data::PgnMoveData,
+        move_tree::MoveTree,
+        object::{PgnObject, play_move},
+    },
+};
+
+/// Evaluations are clamped to this many centipawns before computing losses, so that swings
+/// between two winning (or mating) evaluations do not dominate the average.
+pub const EVAL_CLAMP_CP: i32 = 1000;
+
+/// Centipawn-loss thresholds for classifying a move. A move is counted only in the most severe
+/// class it reaches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnalysisThresholds {
+    /// Minimum loss for an inaccuracy.
+    pub inaccuracy: u32,
+    /// Minimum loss for a mistake.
+    pub mistake: u32,
+    /// Minimum loss for a blunder.
+    pub blunder: u32,
+}
+
+impl Default for AnalysisThresholds {
+    fn default() -> Self {
+        AnalysisThresholds {
+            inaccuracy: 50,
+            mistake: 100,
+            blunder: 300,
+        }
+    }
+}
+
+/// Settings for [`PgnObject::annotate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnnotationOptions {
+    /// Losses at which a move is marked `?!` (`$6`), `?` (`$2`) or `??` (`$4`).
+    pub thresholds: AnalysisThresholds,
+    /// Minimum gain for the mover, compared with the evaluation before the move, to mark it
+    /// `!?` (`$5`). With a perfect evaluator no move gains, so this flags moves the evaluation
+    /// underrated. `None` never marks moves `!?`.
+    pub interesting: Option<u32>,
+    /// Whether to append an `[%eval]` comment, in pawns from White's view, to every move.
+    pub eval_comments: bool,
+}
+
+impl Default for AnnotationOptions {
+    fn default() -> Self {
+        AnnotationOptions {
+            thresholds: AnalysisThresholds::default(),
+            interesting: None,
+            eval_comments: true,
+        }
+    }
+}
+
+/// Score of an engine line, from the view of the side to move at its start, as UCI engines
+/// report it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineScore {
+    /// Evaluation in centipawns.
+    Centipawns(i32),
+    /// Forced mate in this many moves; negative when the side to move is getting mated.
+    Mate(i32),
+}
+
+impl EngineScore {
+    /// Formats the score from White's view as an `[%eval]` comment, such as `[%eval -0.35]` or
+    /// `[%eval #3]`.
+    fn eval_comment(self, side_to_move: Color) -> String {
+        let sign = match side_to_move {
+            Color::White => 1,
+            Color::Black => -1,
+        };
+        match self {
+            EngineScore::Centipawns(cp) => format!("[%eval {:.2}]", (sign * cp) as f64 / 100.0),
+            EngineScore::Mate(moves) => format!("[%eval #{}]", sign * moves),
+        }
+    }
+}
+
+/// A principal variation reported by an engine, for [`PgnObject::from_engine_lines`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineLine {
+    /// Moves of the line, starting from the analyzed position.
+    pub moves: Vec<Move>,
+    /// Score of the line.
+    pub score: EngineScore,
+}
+
+/// Aggregated statistics for one side.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PlayerAnalysis {
+    /// Number of moves analyzed.
+    pub num_moves: u32,
+    /// Sum of centipawn losses.
+    pub total_loss: u64,
+    /// Sum of per-move accuracies, in percent.
+    pub total_accuracy: f64,
+    /// Moves classified as inaccuracies.
+    pub inaccuracies: u32,
+    /// Moves classified as mistakes.
+    pub mistakes: u32,
+    /// Moves classified as blunders.
+    pub blunders: u32,
+}
+
+impl PlayerAnalysis {
+    /// Average centipawn loss, or `0.0` without moves.
+    pub fn acpl(&self) -> f64 {
+        if self.num_moves == 0 {
+            0.0
+        } else {
+            self.total_loss as f64 / self.num_moves as f64
+        }
+    }
+
+    /// Mean per-move accuracy in percent (`0.0..=100.0`), or `100.0` without moves.
+    pub fn accuracy(&self) -> f64 {
+        if self.num_moves == 0 {
+            100.0
+        } else {
+            self.total_accuracy / self.num_moves as f64
+        }
+    }
+}
+
+/// Result of analyzing a sequence of evaluations.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GameAnalysis {
+    /// White's statistics.
+    pub white: PlayerAnalysis,
+    /// Black's statistics.
+    pub black: PlayerAnalysis,
+    /// Centipawn loss of each ply, in move order.
+    pub move_losses: Vec<u32>,
+}
+
+/// Side that plays the move at zero-based `ply` when `first_mover` plays the first one.
+fn mover_of_ply(first_mover: Color, ply: usize) -> Color {
+    match ply % 2 {
+        0 => first_mover,
+        _ => first_mover.other(),
+    }
+}
+
+/// Win probability in percent for an evaluation in centipawns, from the same side's view.
+fn win_percent(cp: i32) -> f64 {
+    50.0 + 50.0 * (2.0 / (1.0 + (-0.00368208 * cp as f64).exp()) - 1.0)
+}
+
+/// Accuracy in percent of a move that drops the mover's win probability by `drop` points.
+fn move_accuracy(drop: f64) -> f64 {
+    (103.1668 * (-0.04354 * drop).exp() - 3.1669).clamp(0.0, 100.0)
+}
+
+impl GameAnalysis {
+    /// Analyzes a game from White-relative evaluations in centipawns.
+    ///
+    /// `evals[0]` is the evaluation of the starting position and `evals[i]` the evaluation
+    /// after ply `i`; `first_mover` plays ply `1`.
+    pub fn from_evals(
+        evals: &[i32],
+        first_mover: Color,
+        thresholds: AnalysisThresholds,
+    ) -> GameAnalysis {
+        let mut analysis = GameAnalysis::default();
+
+        for (ply, pair) in evals.windows(2).enumerate() {
+            let (mover, sign) = match mover_of_ply(first_mover, ply) {
+                Color::White => (&mut analysis.white, 1),
+                Color::Black => (&mut analysis.black, -1),
+            };
+            let before = sign * pair[0].clamp(-EVAL_CLAMP_CP, EVAL_CLAMP_CP);
+            let after = sign * pair[1].clamp(-EVAL_CLAMP_CP, EVAL_CLAMP_CP);
+            let loss = (before - after).max(0) as u32;
+
+            mover.num_moves += 1;
+            mover.total_loss += loss as u64;
+            mover.total_accuracy += move_accuracy(win_percent(before) - win_percent(after));
+            if loss >= thresholds.blunder {
+                mover.blunders += 1;
+            } else if loss >= thresholds.mistake {
+                mover.mistakes += 1;
+            } else if loss >= thresholds.inaccuracy {
+                mover.inaccuracies += 1;
+            }
+            analysis.move_losses.push(loss);
+        }
+
+        analysis
+    }
+
+    /// Records the results as `WhiteACPL`, `BlackACPL`, `WhiteAccuracy`, and `BlackAccuracy` tags.
+    pub fn write_tags<const N: usize>(&self, game: &mut PgnObject<N>) {
+        for (prefix, player) in [("White", &self.white), ("Black", &self.black)] {
+            game.add_tag(format!("{prefix}ACPL"), format!("{:.0}", player.acpl()));
+            game.add_tag(
+                format!("{prefix}Accuracy"),
+                format!("{:.1}", player.accuracy()),
+            );
+        }
+    }
+}
+
+impl<const N: usize> PgnObject<N> {
+    /// Analyzes the main line using `eval`, which returns a White-relative evaluation in
+    /// centipawns for each position (including the starting one).
+    ///
+    /// `M` is the context stack capacity used for replay; it must fit the main line.
+    pub fn analyze<const M: usize>(
+        &self,
+        eval: impl FnMut(&TypedPosition<M>) -> i32,
+        thresholds: AnalysisThresholds,
+    ) -> GameAnalysis {
+        GameAnalysis::from_evals(&self.main_line_evals(eval), self.first_mover(), thresholds)
+    }
+
+    /// Analyzes the main line like [`Self::analyze`] and annotates it with the results, in the
+    /// style of an online computer analysis.
+    ///
+    /// Each move gets the NAG for the most severe class its loss reaches, or `!?` per
+    /// [`AnnotationOptions::interesting`]; moves in no class keep their NAG. With
+    /// [`AnnotationOptions::eval_comments`], each move's comment also gets its evaluation, such
+    /// as `[%eval -0.35]`.
+    pub fn annotate<const M: usize>(
+        &mut self,
+        eval: impl FnMut(&TypedPosition<M>) -> i32,
+        options: AnnotationOptions,
+    ) -> GameAnalysis {
+        let evals = self.main_line_evals(eval);
+        let first_mover = self.first_mover();
+        let analysis = GameAnalysis::from_evals(&evals, first_mover, options.thresholds);
+        let thresholds = options.thresholds;
+
+        for (ply, node) in self.main_line_nodes().into_iter().enumerate() {
+            let sign = match mover_of_ply(first_mover, ply) {
+                Color::White => 1,
+                Color::Black => -1,
+            };
+            let gain = sign
+                * (evals[ply + 1].clamp(-EVAL_CLAMP_CP, EVAL_CLAMP_CP)
+                    - evals[ply].clamp(-EVAL_CLAMP_CP, EVAL_CLAMP_CP));
+            let loss = analysis.move_losses[ply];
+            let nag = if loss >= thresholds.blunder {
+                Some(4)
+            } else if loss >= thresholds.mistake {
+                Some(2)
+            } else if loss >= thresholds.inaccuracy {
+                Some(6)
+            } else if options
+                .interesting
+                .is_some_and(|interesting| gain >= interesting as i32)
+            {
+                Some(5)
+            } else {
+                None
+            };
+            if nag.is_some() {
+                self.set_node_nag(node, nag);
+            }
+            if options.eval_comments {
+                let eval = format!("[%eval {:.2}]", evals[ply + 1] as f64 / 100.0);
+                self.source = None;
+                self.tree.append_comment(node, &eval);
+            }
+        }
+
+        analysis
+    }
+
+    /// Builds a game from engine analysis of `start`: the first line becomes the main line and
+    /// every other line a variation of its first move, in order.
+    ///
+    /// Each line's first move gets an `[%eval]` comment with the line's score, shown from
+    /// White's view. `SetUp` and `FEN` tags record `start` unless it is the standard initial
+    /// position. Empty lines are skipped. `M` is the context stack capacity used to replay the
+    /// lines; `2` suffices.
+    ///
+    /// Fails with [`PgnError::IllegalMove`], holding the move in UCI notation, if a line plays
+    /// an illegal move.
+    pub fn from_engine_lines<const M: usize>(
+        start: &TypedPosition<M>,
+        lines: &[EngineLine],
+    ) -> Result<PgnObject<N>, PgnError> {
+        let mut game = PgnObject::new();
+        game.set_start_position_tags(start);
+        let side_to_move = match start {
+            TypedPosition::White(_) => Color::White,
+            TypedPosition::Black(_) => Color::Black,
+        };
+
+        for line in lines {
+            let mut position = start.clone();
+            let mut parent = MoveTree::ROOT;
+            for &move_ in &line.moves {
+                if !is_legal(&position, move_) {
+                    return Err(PgnError::IllegalMove(move_.to_string()));
+                }
+                let node = game.tree.add_continuation(
+                    parent,
+                    PgnMoveData {
+                        move_,
+                        annotation: None,
+                        nag: None,
+                        written_with_zeros: false,
+                    },
+                );
+                if parent == MoveTree::ROOT {
+                    game.tree
+                        .append_comment(node, &line.score.eval_comment(side_to_move));
+                }
+                position = play_move(&position, move_);
+                parent = node;
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// Returns the side to move in the start position.
+    fn first_mover(&self) -> Color {
+        match self.start_position::<1>() {
+            TypedPosition::White(_) => Color::White,
+            TypedPosition::Black(_) => Color::Black,
+        }
+    }
+
+    /// Returns `eval` of the start position and of the position after each main-line move.
+    fn main_line_evals<const M: usize>(
+        &self,
+        mut eval: impl FnMut(&TypedPosition<M>) -> i32,
+    ) -> Vec<i32> {
+        let mut position = self.start_position::<M>();
+        let mut evals = vec![eval(&position)];
+
+        for move_ in self.main_line() {
+            position = match position {
+                TypedPosition::White(mut p) => {
+                    p.make_move(move_);
+                    TypedPosition::Black(p.rebrand_stm())
+                }
+                TypedPosition::Black(mut p) => {
+                    p.make_move(move_);
+                    TypedPosition::White(p.rebrand_stm())
+                }
+            };
+            evals.push(eval(&position));
+        }
+
+        evals
+    }
+}
+
+/// Returns whether `move_` is legal in `position`.
+fn is_legal<const M: usize>(position: &TypedPosition<M>, move_: Move) -> bool {
+    let mut moves = MoveList::new();
+    match position {
+        TypedPosition::White(p) => p.generate_moves(&mut moves),
+        TypedPosition::Black(p) => p.generate_moves(&mut moves),
+    }
+    moves.as_slice().contains(&move_)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pgn::{PgnParser, PgnRenderingConfig};
+
+    #[test]
+    fn test_from_evals_classifies_losses() {
+        // White: 0 -> 20 (no loss), 40 -> -300 (loss 340, blunder).
+        // Black: 20 -> 40 (loss 20), -300 -> -240 (loss 60, inaccuracy).
+        let analysis = GameAnalysis::from_evals(
+            &[0, 20, 40, -300, -240],
+            Color::White,
+            AnalysisThresholds::default(),
+        );
+
+        assert_eq!(analysis.move_losses, vec![0, 20, 340, 60]);
+        assert_eq!(analysis.white.blunders, 1);
+        assert_eq!(analysis.white.acpl(), 170.0);
+        assert_eq!(analysis.black.inaccuracies, 1);
+        assert_eq!(analysis.black.acpl(), 40.0);
+        assert!(analysis.white.accuracy() < analysis.black.accuracy());
+        assert!(analysis.black.accuracy() <= 100.0);
+    }
+
+    #[test]
+    fn test_from_evals_with_black_to_move_first() {
+        // Black: 0 -> 350 (loss 350, blunder). White: 350 -> 340 (loss 10).
+        let analysis =
+            GameAnalysis::from_evals(&[0, 350, 340], Color::Black, AnalysisThresholds::default());
+        assert_eq!(analysis.move_losses, vec![350, 10]);
+        assert_eq!(analysis.black.blunders, 1);
+        assert_eq!(analysis.black.num_moves, 1);
+        assert_eq!(analysis.white.total_loss, 10);
+
+        let mut parser = PgnParser::<16>::new(
+            "[SetUp \"1\"] [FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 1\"] 1... Kd7 2. e4 *",
+        );
+        parser.parse().unwrap();
+        let mut evals = [0, 350, 340].into_iter();
+        let analysis = parser.constructed_object.analyze(
+            |_: &TypedPosition<16>| evals.next().unwrap(),
+            AnalysisThresholds::default(),
+        );
+        assert_eq!(analysis.black.blunders, 1);
+        assert_eq!(analysis.white.total_loss, 10);
+    }
+
+    #[test]
+    fn test_analyze_with_eval_callback() {
+        let mut parser = PgnParser::<16>::new("1. e4 d5 2. exd5 Qxd5 3. Nc3 Qa5 *");
+        parser.parse().unwrap();
+        let game = &mut parser.constructed_object;
+
+        // White drifts 50 centipawns worse with every ply.
+        let eval = |position: &TypedPosition<16>| {
+            let halfmove = match position {
+                TypedPosition::White(p) => p.halfmove,
+                TypedPosition::Black(p) => p.halfmove,
+            };
+            -50 * halfmove as i32
+        };
+        let analysis = game.analyze(eval, AnalysisThresholds::default());
+
+        assert_eq!(analysis.move_losses, vec![50, 0, 50, 0, 50, 0]);
+        assert_eq!(analysis.white.inaccuracies, 3);
+        assert_eq!(analysis.black.total_loss, 0);
+
+        analysis.write_tags(game);
+        assert_eq!(game.tags.get("WhiteACPL").map(String::as_str), Some("50"));
+        assert_eq!(game.tags.get("BlackACPL").map(String::as_str), Some("0"));
+        assert_eq!(
+            game.tags.get("BlackAccuracy").map(String::as_str),
+            Some("100.0")
+        );
+    }
+
+    #[test]
+    fn test_annotate_inserts_nags_and_evals() {
+        let mut parser = PgnParser::<16>::new("1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. Qxf7# 1-0");
+        parser.parse().unwrap();
+        let game = &mut parser.constructed_object;
+
+        let evals = [20, 30, 25, -60, -160, -150, 1000, 1000];
+        let mut evals_iter = evals.into_iter();
+        let options = AnnotationOptions {
+            interesting: Some(100),
+            ..AnnotationOptions::default()
+        };
+        let analysis = game.annotate(|_: &TypedPosition<16>| evals_iter.next().unwrap(), options);
+        assert_eq!(analysis.move_losses, vec![0, 0, 85, 0, 0, 1150, 0]);
+
+        let nodes = game.main_line_nodes();
+        let nags: Vec<_> = nodes.iter().map(|&node| game.node_nag(node)).collect();
+        assert_eq!(nags, [None, None, Some(6), Some(5), None, Some(4), None]);
+        assert_eq!(game.node_comment(nodes[2]), Some("[%eval -0.60]"));
+        assert_eq!(game.node_comment(nodes[6]), Some("[%eval 10.00]"));
+
+        // The annotations survive rendering and parsing again.
+        let rendered = game.render(true, PgnRenderingConfig::default());
+        let mut parser = PgnParser::<16>::new(&rendered);
+        parser.parse().unwrap();
+        let reparsed = &parser.constructed_object;
+        let reparsed_nodes = reparsed.main_line_nodes();
+        for (&node, &reparsed_node) in nodes.iter().zip(&reparsed_nodes) {
+            assert_eq!(
+                reparsed.node_comment(reparsed_node),
+                game.node_comment(node)
+            );
+            assert_eq!(reparsed.node_nag(reparsed_node), game.node_nag(node));
+        }
+    }
+
+    #[test]
+    fn test_annotate_with_black_to_move_first() {
+        let mut parser = PgnParser::<16>::new(
+            "[SetUp \"1\"] [FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 1\"] 1... Kd7 2. e4 *",
+        );
+        parser.parse().unwrap();
+        let game = &mut parser.constructed_object;
+
+        // Black's king move gains 150 for Black; White's pawn move gains 200 for White.
+        let mut evals = [0, -150, 50].into_iter();
+        let options = AnnotationOptions {
+            interesting: Some(100),
+            ..AnnotationOptions::default()
+        };
+        game.annotate(|_: &TypedPosition<16>| evals.next().unwrap(), options);
+        let nodes = game.main_line_nodes();
+        assert_eq!(game.node_nag(nodes[0]), Some(5));
+        assert_eq!(game.node_nag(nodes[1]), Some(5));
+    }
+
+    #[test]
+    fn test_from_engine_lines_builds_variations() {
+        use crate::{MoveFlag, Position, Square};
+
+        let uci = |from, to| Move::new_non_promotion(from, to, MoveFlag::NormalMove);
+        let lines = [
+            EngineLine {
+                moves: vec![uci(Square::E2, Square::E4), uci(Square::E7, Square::E5)],
+                score: EngineScore::Centipawns(35),
+            },
+            EngineLine {
+                moves: vec![],
+                score: EngineScore::Centipawns(0),
+            },
+            EngineLine {
+                moves: vec![uci(Square::D2, Square::D4)],
+                score: EngineScore::Centipawns(-5),
+            },
+        ];
+        let start = TypedPosition::White(Position::<2, { Color::White }>::initial());
+        let game = PgnObject::<8>::from_engine_lines(&start, &lines).unwrap();
+        assert_eq!(game.tag("FEN"), None);
+        assert_eq!(
+            game.render(true, PgnRenderingConfig::default()),
+            "1. e4 { [%eval 0.35] } (1. d4 { [%eval -0.05] }) 1... e5"
+        );
+
+        // Black to move: scores flip to White's view, and the start position is tagged.
+        let fen = "6k1/5ppp/8/8/8/8/5PPP/3R2K1 b - - 0 1";
+        let start = TypedPosition::<2>::from_fen(fen).unwrap();
+        let lines = [EngineLine {
+            moves: vec![uci(Square::G8, Square::F8)],
+            score: EngineScore::Mate(-2),
+        }];
+        let game = PgnObject::<8>::from_engine_lines(&start, &lines).unwrap();
+        assert_eq!(game.tag("FEN"), Some(fen));
+        let first = game.main_line_nodes()[0];
+        assert_eq!(game.node_comment(first), Some("[%eval #2]"));
+
+        let illegal = [EngineLine {
+            moves: vec![uci(Square::G8, Square::G6)],
+            score: EngineScore::Centipawns(0),
+        }];
+        assert_eq!(
+            PgnObject::<8>::from_engine_lines(&start, &illegal).err(),
+            Some(PgnError::IllegalMove("g8g6".to_string()))
+        );
+    }
+}
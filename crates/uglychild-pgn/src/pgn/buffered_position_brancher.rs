@@ -1,13 +1,11 @@
-use std::{cell::RefCell, rc::Rc};
-
 use crate::{
     Color,
+    TypedPosition,
     pgn::{
         buffered_position_context::{PgnBufferedPositionContext, PgnBufferedPositionContextDyn},
-        move_tree_node::MoveTreeNode,
+        move_tree::MoveTreeNodeId,
         position_context::PgnPositionContext,
     },
-    position::Position,
 };
 
 pub struct PgnBufferedPositionBrancher<const N: usize> {
@@ -17,19 +15,31 @@ pub struct PgnBufferedPositionBrancher<const N: usize> {
 
 impl<const N: usize> PgnBufferedPositionBrancher<N> {
     pub fn new(
-        root_node: &Rc<RefCell<MoveTreeNode<N, { Color::White }, { Color::Black }>>>,
-        initial_state: Position<N, { Color::White }>,
+        root_node: MoveTreeNodeId,
+        initial_state: TypedPosition<N>,
     ) -> PgnBufferedPositionBrancher<N> {
-        PgnBufferedPositionBrancher {
-            current_and_previous: PgnBufferedPositionContextDyn::White(
-                PgnBufferedPositionContext {
+        let current_and_previous = match initial_state {
+            TypedPosition::White(state) => {
+                PgnBufferedPositionContextDyn::White(PgnBufferedPositionContext {
                     current: PgnPositionContext::<N, { Color::White }, { Color::Black }> {
-                        node: Rc::clone(root_node),
-                        state_after_move: initial_state,
+                        node: root_node,
+                        state_after_move: state,
                     },
                     previous: None,
-                },
-            ),
+                })
+            }
+            TypedPosition::Black(state) => {
+                PgnBufferedPositionContextDyn::Black(PgnBufferedPositionContext {
+                    current: PgnPositionContext::<N, { Color::Black }, { Color::White }> {
+                        node: root_node,
+                        state_after_move: state,
+                    },
+                    previous: None,
+                })
+            }
+        };
+        PgnBufferedPositionBrancher {
+            current_and_previous,
             stack: Vec::new(),
         }
     }
@@ -1,10 +1,8 @@
-use std::{cell::RefCell, rc::Rc};
-
 use crate::{
     Color,
     pgn::{
         move_data::PgnMoveData,
-        move_tree_node::MoveTreeNode,
+        move_tree::{MoveTree, MoveTreeNodeId},
         position_context::PgnPositionContext,
     },
     position::Position,
@@ -25,15 +23,11 @@ pub(crate) struct PgnBufferedPositionContext<const N: usize, const STM: Color, c
 impl<const N: usize> PgnBufferedPositionContext<N, { Color::White }, { Color::Black }> {
     pub(crate) fn append_new_move(
         self,
+        tree: &mut MoveTree,
         new_move_data: PgnMoveData,
         new_state: Position<N, { Color::Black }>,
     ) -> PgnBufferedPositionContextDyn<N> {
-        let new_node = Rc::new(RefCell::new(MoveTreeNode::<
-            N,
-            { Color::Black },
-            { Color::White },
-        >::new(new_move_data, None)));
-        self.current.node.borrow_mut().add_continuation(&new_node);
+        let new_node = tree.add_continuation(self.current.node, new_move_data);
         let new_current = PgnPositionContext::<N, { Color::Black }, { Color::White }> {
             node: new_node,
             state_after_move: new_state,
@@ -48,15 +42,11 @@ impl<const N: usize> PgnBufferedPositionContext<N, { Color::White }, { Color::Bl
 impl<const N: usize> PgnBufferedPositionContext<N, { Color::Black }, { Color::White }> {
     pub(crate) fn append_new_move(
         self,
+        tree: &mut MoveTree,
         new_move_data: PgnMoveData,
         new_state: Position<N, { Color::White }>,
     ) -> PgnBufferedPositionContextDyn<N> {
-        let new_node = Rc::new(RefCell::new(MoveTreeNode::<
-            N,
-            { Color::White },
-            { Color::Black },
-        >::new(new_move_data, None)));
-        self.current.node.borrow_mut().add_continuation(&new_node);
+        let new_node = tree.add_continuation(self.current.node, new_move_data);
         let new_current = PgnPositionContext::<N, { Color::White }, { Color::Black }> {
             node: new_node,
             state_after_move: new_state,
@@ -86,6 +76,24 @@ impl<const N: usize> PgnBufferedPositionContextDyn<N> {
         }
     }
 
+    pub(crate) fn node(&self) -> MoveTreeNodeId {
+        match self {
+            PgnBufferedPositionContextDyn::White(ctx) => ctx.current.node,
+            PgnBufferedPositionContextDyn::Black(ctx) => ctx.current.node,
+        }
+    }
+
+    pub(crate) fn zobrist_hash(&self) -> u64 {
+        match self {
+            PgnBufferedPositionContextDyn::White(ctx) => {
+                ctx.current.state_after_move.context().zobrist_hash()
+            }
+            PgnBufferedPositionContextDyn::Black(ctx) => {
+                ctx.current.state_after_move.context().zobrist_hash()
+            }
+        }
+    }
+
     pub(crate) fn side_to_move(&self) -> Color {
         match self {
             PgnBufferedPositionContextDyn::White(_) => Color::White,
@@ -93,20 +101,21 @@ impl<const N: usize> PgnBufferedPositionContextDyn<N> {
         }
     }
 
-    pub(crate) fn append_move(self, new_move_data: PgnMoveData) -> Self {
+    pub(crate) fn append_move(self, tree: &mut MoveTree, new_move_data: PgnMoveData) -> Self {
         let move_ = new_move_data.move_;
+        // Tree nodes never unmake, so each keeps only the current context of its position.
         match self {
             PgnBufferedPositionContextDyn::White(ctx) => {
-                let mut next = ctx.current.state_after_move.clone();
+                let mut next = ctx.current.state_after_move.clone_current();
                 next.make_move(move_);
                 let next = next.rebrand_stm::<{ Color::Black }>();
-                ctx.append_new_move(new_move_data, next)
+                ctx.append_new_move(tree, new_move_data, next)
             }
             PgnBufferedPositionContextDyn::Black(ctx) => {
-                let mut next = ctx.current.state_after_move.clone();
+                let mut next = ctx.current.state_after_move.clone_current();
                 next.make_move(move_);
                 let next = next.rebrand_stm::<{ Color::White }>();
-                ctx.append_new_move(new_move_data, next)
+                ctx.append_new_move(tree, new_move_data, next)
             }
         }
     }
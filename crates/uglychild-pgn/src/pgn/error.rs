@@ -5,7 +5,12 @@ use std::{
     fmt::{Display, Formatter},
 };
 
-/// Errors that can occur during PGN tokenization or parsing.
+use crate::{logic::game_state::GameResult, pgn::parser::PgnParserLimit};
+
+/// Errors that can occur during PGN tokenization, parsing, or merging.
+///
+/// String payloads hold the offending input text (or, for [`PgnError::UnexpectedEndOfInput`],
+/// what was left open), so [`Display`] can render a readable message.
 #[derive(Debug, PartialEq, Clone)]
 pub enum PgnError {
     /// Move syntax didn't match expected SAN format.
@@ -30,11 +35,81 @@ pub enum PgnError {
     UnexpectedToken(String),
     /// Input ended before required token (e.g., missing game result).
     UnexpectedEndOfInput(String),
+    /// `Result` tag disagrees with the game-terminating token.
+    ResultMismatch {
+        /// Value of the `Result` tag.
+        tag: String,
+        /// Result given by the game-terminating token.
+        token: GameResult,
+    },
+    /// Variations are nested deeper than the parser's configured limit.
+    VariationTooDeep(usize),
+    /// The game exceeds one of the parser's [`PgnParserLimits`].
+    ///
+    /// [`PgnParserLimits`]: crate::pgn::PgnParserLimits
+    LimitExceeded(PgnParserLimit),
+    /// A tag name appeared twice under [`PgnDuplicateTagPolicy::Reject`].
+    ///
+    /// [`PgnDuplicateTagPolicy::Reject`]: crate::pgn::PgnDuplicateTagPolicy::Reject
+    DuplicateTag(String),
+    /// A move has a suffix annotation outside the standard six under
+    /// [`PgnAnnotationPolicy::Reject`].
+    ///
+    /// [`PgnAnnotationPolicy::Reject`]: crate::pgn::PgnAnnotationPolicy::Reject
+    NonstandardAnnotation(String),
+    /// The `FEN` tag does not describe a valid position.
+    InvalidFen(String),
+    /// [`PgnObject::merge`] was given a game with a different start position, whose FEN this
+    /// holds.
+    ///
+    /// [`PgnObject::merge`]: crate::pgn::PgnObject::merge
+    StartPositionMismatch(String),
 }
 
 impl Display for PgnError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            PgnError::InvalidMove(text) => write!(f, "invalid move `{text}`"),
+            PgnError::InvalidTag(text) => write!(f, "invalid tag `{text}`"),
+            PgnError::InvalidComment(text) => write!(f, "invalid comment `{text}`"),
+            PgnError::InvalidMoveNumber(text) => write!(f, "invalid move number `{text}`"),
+            PgnError::InvalidCastlingMove(text) => write!(f, "invalid castling move `{text}`"),
+            PgnError::InvalidToken(text) => write!(f, "invalid token `{text}`"),
+            PgnError::IncorrectMoveNumber(text) => write!(f, "incorrect move number `{text}`"),
+            PgnError::IllegalMove(text) => write!(f, "illegal move `{text}`"),
+            PgnError::AmbiguousMove(text) => write!(f, "ambiguous move `{text}`"),
+            PgnError::UnexpectedToken(text) => write!(f, "unexpected token `{text}`"),
+            PgnError::UnexpectedEndOfInput(reason) => {
+                write!(f, "unexpected end of input: {reason}")
+            }
+            PgnError::ResultMismatch { tag, token } => {
+                write!(
+                    f,
+                    "Result tag `{tag}` does not match result token `{token}`"
+                )
+            }
+            PgnError::VariationTooDeep(limit) => {
+                write!(f, "variations nested deeper than {limit}")
+            }
+            PgnError::LimitExceeded(limit) => match limit {
+                PgnParserLimit::Plies(max) => write!(f, "game has more than {max} moves"),
+                PgnParserLimit::Tags(max) => write!(f, "game has more than {max} tags"),
+                PgnParserLimit::CommentLength(max) => {
+                    write!(f, "comment longer than {max} bytes")
+                }
+                PgnParserLimit::Variations(max) => {
+                    write!(f, "game has more than {max} variations")
+                }
+            },
+            PgnError::DuplicateTag(name) => write!(f, "duplicate tag `{name}`"),
+            PgnError::NonstandardAnnotation(text) => {
+                write!(f, "nonstandard annotation in `{text}`")
+            }
+            PgnError::InvalidFen(fen) => write!(f, "invalid FEN tag `{fen}`"),
+            PgnError::StartPositionMismatch(fen) => {
+                write!(f, "game starts from a different position `{fen}`")
+            }
+        }
     }
 }
 
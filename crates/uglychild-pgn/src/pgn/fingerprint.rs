@@ -0,0 +1,248 @@
+//! Game fingerprinting and duplicate detection for PGN collections.
+
+use std::collections::HashMap;
+
+use crate::pgn::object::PgnObject;
+
+/// Tags that contribute to a game's fingerprint (the PGN Seven Tag Roster).
+pub const FINGERPRINT_TAGS: [&str; 7] =
+    ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+const fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+    hash
+}
+
+/// Stable identity of a game, split into its move and metadata components.
+///
+/// The move hash covers the start position and the main line only, so annotations, comments,
+/// NAGs, and variations do not affect it. The start position is that of the `FEN` tag, so the
+/// same moves from different setups hash differently. The tag hash covers [`FINGERPRINT_TAGS`], with values
+/// trimmed and lowercased and unknown values (`?`, `????.??.??`, empty) treated as missing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PgnFingerprint {
+    /// Hash of the start position and the main-line move sequence.
+    pub moves_hash: u64,
+    /// Hash of the normalized [`FINGERPRINT_TAGS`] values.
+    pub tags_hash: u64,
+}
+
+impl PgnFingerprint {
+    /// Computes the fingerprint of `game`.
+    pub fn of<const N: usize>(game: &PgnObject<N>) -> PgnFingerprint {
+        let start_fen = game.start_position::<1>().to_fen();
+        let mut moves_hash = fnv1a(FNV_OFFSET_BASIS, start_fen.as_bytes());
+        for move_ in game.main_line() {
+            moves_hash = fnv1a(moves_hash, &move_.value.to_le_bytes());
+        }
+
+        let mut tags_hash = FNV_OFFSET_BASIS;
+        for name in FINGERPRINT_TAGS {
+            let value = game
+                .tags
+                .get(name)
+                .map(|value| value.trim().to_lowercase())
+                .filter(|value| !value.is_empty() && value.chars().any(|c| c != '?' && c != '.'))
+                .unwrap_or_default();
+            tags_hash = fnv1a(tags_hash, value.as_bytes());
+            // Separator so that adjacent values cannot bleed into each other.
+            tags_hash = fnv1a(tags_hash, &[0]);
+        }
+
+        PgnFingerprint {
+            moves_hash,
+            tags_hash,
+        }
+    }
+}
+
+/// How a game relates to one seen earlier in the same stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PgnDuplicateKind {
+    /// Same main line and same normalized tags.
+    Exact,
+    /// Same main line, but the tags differ (e.g., a re-typed header or different source).
+    NearDuplicate,
+}
+
+/// A duplicate match reported by [`PgnDuplicates`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PgnDuplicate {
+    /// Whether the match is exact or only on moves.
+    pub kind: PgnDuplicateKind,
+    /// Stream index of the first game with the same main line.
+    pub original_index: usize,
+}
+
+/// Iterator adapter that annotates each game with its duplicate status.
+///
+/// Created by [`PgnDeduplicateExt::detect_duplicates`]. Yields every input game,
+/// paired with `Some(PgnDuplicate)` when an earlier game had the same main line.
+/// Exact matches take precedence over near-duplicates.
+pub struct PgnDuplicates<I> {
+    games: I,
+    index: usize,
+    first_by_moves: HashMap<u64, usize>,
+    first_by_fingerprint: HashMap<PgnFingerprint, usize>,
+}
+
+impl<const N: usize, I: Iterator<Item = PgnObject<N>>> Iterator for PgnDuplicates<I> {
+    type Item = (PgnObject<N>, Option<PgnDuplicate>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let game = self.games.next()?;
+        let index = self.index;
+        self.index += 1;
+
+        let fingerprint = PgnFingerprint::of(&game);
+        let duplicate = if let Some(&original_index) = self.first_by_fingerprint.get(&fingerprint) {
+            Some(PgnDuplicate {
+                kind: PgnDuplicateKind::Exact,
+                original_index,
+            })
+        } else {
+            self.first_by_fingerprint.insert(fingerprint, index);
+            self.first_by_moves
+                .get(&fingerprint.moves_hash)
+                .map(|&original_index| PgnDuplicate {
+                    kind: PgnDuplicateKind::NearDuplicate,
+                    original_index,
+                })
+        };
+        self.first_by_moves
+            .entry(fingerprint.moves_hash)
+            .or_insert(index);
+
+        Some((game, duplicate))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.games.size_hint()
+    }
+}
+
+/// Extension trait adding duplicate detection to iterators over parsed games.
+pub trait PgnDeduplicateExt<const N: usize>: Iterator<Item = PgnObject<N>> + Sized {
+    /// Pairs each game with its duplicate status; see [`PgnDuplicates`].
+    fn detect_duplicates(self) -> PgnDuplicates<Self> {
+        PgnDuplicates {
+            games: self,
+            index: 0,
+            first_by_moves: HashMap::new(),
+            first_by_fingerprint: HashMap::new(),
+        }
+    }
+
+    /// Drops exact duplicates, keeping the first occurrence of each game.
+    ///
+    /// Set `drop_near_duplicates` to also drop games whose main line was already seen.
+    fn deduplicate(self, drop_near_duplicates: bool) -> impl Iterator<Item = PgnObject<N>> {
+        self.detect_duplicates()
+            .filter_map(move |(game, duplicate)| match duplicate {
+                None => Some(game),
+                Some(PgnDuplicate {
+                    kind: PgnDuplicateKind::NearDuplicate,
+                    ..
+                }) if !drop_near_duplicates => Some(game),
+                Some(_) => None,
+            })
+    }
+}
+
+impl<const N: usize, I: Iterator<Item = PgnObject<N>>> PgnDeduplicateExt<N> for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pgn::PgnParser;
+
+    const N: usize = 16;
+
+    fn parse(pgn: &str) -> PgnObject<N> {
+        let mut parser = PgnParser::<N>::new(pgn);
+        parser.parse().expect("valid PGN");
+        parser.constructed_object
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_annotations_and_variations() {
+        let plain = parse("[White \"A\"] 1. e4 e5 2. Nf3 Nc6 *");
+        let annotated = parse("[White \" a \"] 1. e4! e5 $1 (1... c5) 2. Nf3 {dev} Nc6 *");
+        assert_eq!(PgnFingerprint::of(&plain), PgnFingerprint::of(&annotated));
+    }
+
+    #[test]
+    fn test_fingerprint_includes_start_position() {
+        let standard = parse("1. Nf3 *");
+        let set_up = parse("[SetUp \"1\"] [FEN \"4k3/8/8/8/8/8/8/4K1N1 w - - 0 1\"] 1. Nf3 *");
+        let set_up_again = parse("[FEN \"4k3/8/8/8/8/8/8/4K1N1  w - - 0 1\"] 1. Nf3 *");
+        assert_eq!(standard.main_line(), set_up.main_line());
+        assert_ne!(
+            PgnFingerprint::of(&standard).moves_hash,
+            PgnFingerprint::of(&set_up).moves_hash
+        );
+        assert_eq!(
+            PgnFingerprint::of(&set_up),
+            PgnFingerprint::of(&set_up_again)
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_treats_unknown_tags_as_missing() {
+        let unknown = parse("[Date \"????.??.??\"] [Site \"?\"] 1. d4 *");
+        let missing = parse("1. d4 *");
+        assert_eq!(PgnFingerprint::of(&unknown), PgnFingerprint::of(&missing));
+    }
+
+    #[test]
+    fn test_detect_duplicates() {
+        let games = vec![
+            parse("[White \"A\"] 1. e4 e5 *"),
+            parse("[White \"B\"] 1. d4 d5 *"),
+            parse("[White \"A\"] 1. e4 e5 *"),
+            parse("[White \"C\"] 1. e4 e5 *"),
+        ];
+        let statuses: Vec<_> = games
+            .into_iter()
+            .detect_duplicates()
+            .map(|(_, duplicate)| duplicate)
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                None,
+                None,
+                Some(PgnDuplicate {
+                    kind: PgnDuplicateKind::Exact,
+                    original_index: 0,
+                }),
+                Some(PgnDuplicate {
+                    kind: PgnDuplicateKind::NearDuplicate,
+                    original_index: 0,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deduplicate() {
+        let games = || {
+            vec![
+                parse("[White \"A\"] 1. e4 e5 *"),
+                parse("[White \"A\"] 1. e4 e5 *"),
+                parse("[White \"C\"] 1. e4 e5 *"),
+            ]
+            .into_iter()
+        };
+        assert_eq!(games().deduplicate(false).count(), 2);
+        assert_eq!(games().deduplicate(true).count(), 1);
+    }
+}
@@ -0,0 +1,241 @@
+//! Multi-game PGN input and output: splitting at game boundaries, parsing games independently,
+//! and writing whole databases.
+
+use std::{borrow::Cow, io};
+
+use crate::pgn::{
+    error::PgnError,
+    object::PgnObject,
+    parser::PgnParser,
+    rendering_config::PgnRenderingConfig,
+};
+
+/// Splits multi-game PGN text into one slice per game, in input order.
+///
+/// A game ends where a tag line (`[`) follows movetext, which covers both the standard
+/// blank-line-separated layout and files with the blank lines stripped. Lines inside braced
+/// comments are never treated as tags, and braces in `;` comments and `%` escape lines are
+/// ignored. Whitespace-only chunks are dropped.
+pub fn split_games(input: &str) -> Vec<&str> {
+    let mut games = Vec::new();
+    let mut game_start = 0;
+    let mut has_movetext = false;
+    let mut in_comment = false;
+    let mut line_start = 0;
+
+    for line in input.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if !in_comment && line.starts_with('%') {
+            line_start += line.len();
+            continue;
+        }
+        let is_tag_line = !in_comment && trimmed.starts_with('[');
+        if is_tag_line && has_movetext {
+            games.push(&input[game_start..line_start]);
+            game_start = line_start;
+            has_movetext = false;
+        }
+        if !is_tag_line && !trimmed.is_empty() {
+            has_movetext = true;
+        }
+        for c in line.chars() {
+            match c {
+                '{' => in_comment = true,
+                '}' => in_comment = false,
+                // A rest-of-line comment hides any braces after it.
+                ';' if !in_comment => break,
+                _ => {}
+            }
+        }
+        line_start += line.len();
+    }
+    games.push(&input[game_start..]);
+    games.retain(|game| !game.trim().is_empty());
+    games
+}
+
+/// Parses every game in `input` and passes each outcome to `map`, collecting the results in
+/// input order.
+///
+/// A game that fails to parse does not stop the others; `map` receives its error.
+pub fn parse_games<const N: usize, T>(
+    input: &str,
+    map: impl Fn(Result<PgnObject<N>, PgnError>) -> T,
+) -> Vec<T> {
+    split_games(input)
+        .into_iter()
+        .map(|game| map(parse_game(game)))
+        .collect()
+}
+
+/// Decodes PGN file contents to text, tolerating the encodings found in older databases.
+///
+/// A leading UTF-8 byte order mark is dropped. Valid UTF-8 is kept as is, and every byte that
+/// is not part of a valid UTF-8 sequence is read as Latin-1, so player names and comments
+/// written by Latin-1 tools survive even in files that mix both encodings. Input that is
+/// already valid UTF-8, with or without a BOM, is borrowed rather than copied.
+pub fn decode_pgn_bytes(bytes: &[u8]) -> Cow<'_, str> {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return Cow::Borrowed(text);
+    }
+    let mut text = String::with_capacity(bytes.len());
+    for chunk in bytes.utf8_chunks() {
+        text.push_str(chunk.valid());
+        text.extend(chunk.invalid().iter().map(|&byte| byte as char));
+    }
+    Cow::Owned(text)
+}
+
+/// Like [`parse_games`], but takes raw file contents decoded by [`decode_pgn_bytes`].
+pub fn parse_games_from_bytes<const N: usize, T>(
+    input: &[u8],
+    map: impl Fn(Result<PgnObject<N>, PgnError>) -> T,
+) -> Vec<T> {
+    parse_games(&decode_pgn_bytes(input), map)
+}
+
+/// Like [`parse_games`], but parses games concurrently on the rayon thread pool.
+///
+/// `map` runs on the worker that parsed the game; pass the identity to collect the
+/// [`PgnObject`]s themselves. Output order matches input order.
+#[cfg(feature = "parallel")]
+pub fn parse_games_parallel<const N: usize, T: Send>(
+    input: &str,
+    map: impl Fn(Result<PgnObject<N>, PgnError>) -> T + Sync,
+) -> Vec<T> {
+    use rayon::prelude::*;
+
+    split_games(input)
+        .into_par_iter()
+        .map(|game| map(parse_game(game)))
+        .collect()
+}
+
+/// Writes `games` to `writer` one after another, each rendered as by [`PgnObject::render_to`],
+/// ended by its result token (`1-0`, `*`, …) and followed by a blank line.
+///
+/// Games are written as they are reached, so a database can be streamed to a file without
+/// rendering it into memory first.
+pub fn write_games<'a, const N: usize, W: io::Write>(
+    games: impl IntoIterator<Item = &'a PgnObject<N>>,
+    mut writer: W,
+    include_variations: bool,
+    config: PgnRenderingConfig,
+) -> io::Result<()> {
+    for game in games {
+        game.write_game_to(&mut writer, include_variations, config)?;
+        writer.write_all(b"\n\n")?;
+    }
+    Ok(())
+}
+
+fn parse_game<const N: usize>(game: &str) -> Result<PgnObject<N>, PgnError> {
+    let mut parser = PgnParser::<N>::new(game);
+    parser.parse()?;
+    Ok(parser.constructed_object)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAMES: &str = "[Event \"A\"]\n[Result \"1-0\"]\n\n1. e4 e5 {see [note]\n[not a tag]} 2. Qh5 1-0\n\n\
+                         [Event \"B\"]\n1. d4 d5 *\n[Event \"C\"]\n1. e4 e4 *\n\n";
+
+    #[test]
+    fn test_split_games() {
+        let games = split_games(GAMES);
+        assert_eq!(games.len(), 3);
+        assert!(games[0].starts_with("[Event \"A\"]"));
+        assert!(games[0].contains("[not a tag]"));
+        assert!(games[1].starts_with("[Event \"B\"]"));
+        assert!(games[2].starts_with("[Event \"C\"]"));
+        assert_eq!(split_games("  \n"), Vec::<&str>::new());
+
+        let with_escapes = "%header {\n[Event \"A\"]\n1. e4 *\n%[Event \"B\"]\n";
+        assert_eq!(split_games(with_escapes), [with_escapes]);
+    }
+
+    #[test]
+    fn test_decode_pgn_bytes() {
+        assert!(matches!(
+            decode_pgn_bytes(b"1. e4 *"),
+            Cow::Borrowed("1. e4 *")
+        ));
+        assert!(matches!(
+            decode_pgn_bytes(b"\xEF\xBB\xBF1. e4 *"),
+            Cow::Borrowed("1. e4 *")
+        ));
+
+        // "Réti" in Latin-1, then "Dvořák" in UTF-8.
+        let mixed = b"[White \"R\xE9ti\"]\n[Black \"Dvo\xC5\x99\xC3\xA1k\"]\n1. Nf3 *";
+        let players = parse_games_from_bytes::<8, _>(mixed, |game| {
+            let game = game.unwrap();
+            (game.tags["White"].clone(), game.tags["Black"].clone())
+        });
+        assert_eq!(players, [("Réti".to_string(), "Dvořák".to_string())]);
+    }
+
+    #[test]
+    fn test_parse_games_keeps_order_and_errors() {
+        let events = parse_games::<8, _>(GAMES, |game| {
+            game.map(|object| object.tags["Event"].clone())
+        });
+        assert_eq!(events[0], Ok("A".to_string()));
+        assert_eq!(events[1], Ok("B".to_string()));
+        assert!(matches!(events[2], Err(PgnError::IllegalMove(_))));
+    }
+
+    #[test]
+    fn test_write_games_matches_render() {
+        let games: Vec<PgnObject<8>> = parse_games(GAMES, Result::ok)
+            .into_iter()
+            .flatten()
+            .collect();
+        let config = PgnRenderingConfig::no_markings();
+        let mut written = Vec::new();
+        write_games(&games, &mut written, true, config).unwrap();
+
+        let expected: String = games
+            .iter()
+            .map(|game| format!("{} {}\n\n", game.render(true, config), game.result()))
+            .collect();
+        assert_eq!(String::from_utf8(written).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_write_games_ends_games_with_result() {
+        let pgn = "[Event \"A\"]\n\n1. e4 e5 1-0";
+        let mut parser = PgnParser::<8>::new(pgn);
+        parser.parse().unwrap();
+        let mut lossless = PgnParser::<8>::new(pgn).with_lossless(true);
+        lossless.parse().unwrap();
+
+        for game in [&parser.constructed_object, &lossless.constructed_object] {
+            let mut written = Vec::new();
+            write_games(
+                [game],
+                &mut written,
+                true,
+                PgnRenderingConfig::all_markings(),
+            )
+            .unwrap();
+            let written = String::from_utf8(written).unwrap();
+            assert!(written.ends_with("e5 1-0\n\n"), "{written}");
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parse_games_parallel_matches_sequential() {
+        let input = GAMES.repeat(50);
+        let render = |game: Result<PgnObject<8>, PgnError>| {
+            game.map(|object| object.render(true, PgnRenderingConfig::all_markings()))
+        };
+        assert_eq!(
+            parse_games_parallel(&input, render),
+            parse_games(&input, render)
+        );
+    }
+}
@@ -0,0 +1,52 @@
+//! Source tokens retained by lossless parsing, for byte-faithful round-trips.
+
+use std::{
+    fmt::{Display, Formatter},
+    io,
+};
+
+use indexmap::IndexMap;
+
+/// One token of the original input together with the whitespace before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnSourceToken {
+    /// Whitespace and `%` escape lines between the previous token (or the start of input) and
+    /// this token.
+    pub leading_trivia: String,
+    /// The token exactly as written.
+    pub text: String,
+}
+
+/// The input of a losslessly parsed game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PgnSource {
+    pub(crate) tokens: Vec<PgnSourceToken>,
+    /// Whitespace and `%` escape lines after the last token.
+    pub(crate) trailing_trivia: String,
+    /// Tags as parsed, to detect edits made through [`PgnObject::tags`](crate::pgn::PgnObject).
+    pub(crate) tags: IndexMap<String, String>,
+}
+
+impl PgnSource {
+    /// Writes the input with castling written with zeros (`0-0`) rewritten to the letter O.
+    pub(crate) fn write_normalized_castling(&self, mut writer: impl io::Write) -> io::Result<()> {
+        for token in &self.tokens {
+            writer.write_all(token.leading_trivia.as_bytes())?;
+            match token.text.strip_prefix("0-0") {
+                Some(rest) => write!(writer, "O-O{}", rest.replacen("-0", "-O", 1))?,
+                None => writer.write_all(token.text.as_bytes())?,
+            }
+        }
+        writer.write_all(self.trailing_trivia.as_bytes())
+    }
+}
+
+impl Display for PgnSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for token in &self.tokens {
+            f.write_str(&token.leading_trivia)?;
+            f.write_str(&token.text)?;
+        }
+        f.write_str(&self.trailing_trivia)
+    }
+}
@@ -0,0 +1,172 @@
+//! Scanning parsed games for missed or played forced mates, and for moves that allow one.
+
+use crate::{TypedPosition, r#move::Move, pgn::object::PgnObject};
+
+/// A position on a game's main line where the side to move had a forced mate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PgnMateOpportunity {
+    /// Number of half-moves played before the position (`0` is the initial position).
+    pub ply: usize,
+    /// Length of the shortest forced mate, in moves of the side to move.
+    pub mate_in: u8,
+    /// First move of a shortest forced mate.
+    pub mating_move: Move,
+    /// Move actually played in the game, or `None` at the end of the main line.
+    pub move_played: Option<Move>,
+}
+
+/// A main-line move that let the opponent force mate from a position that was not already lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PgnMateBlunder {
+    /// Number of half-moves played before the blunder.
+    pub ply: usize,
+    /// The move played.
+    pub move_played: Move,
+    /// Length of the opponent's shortest forced mate after the move, in the opponent's moves.
+    pub mate_in: u8,
+    /// First move of that mate.
+    pub mating_move: Move,
+}
+
+impl<const N: usize> PgnObject<N> {
+    /// Finds every main-line position where the side to move can force mate within `max_n` moves.
+    ///
+    /// `M` is the context stack capacity used for replay and search.
+    ///
+    /// # Panics
+    /// If `M` is smaller than the main line's length plus `2 * max_n`.
+    pub fn find_forced_mates<const M: usize>(&self, max_n: u8) -> Vec<PgnMateOpportunity> {
+        let mut opportunities = Vec::new();
+        self.for_each_main_line_position::<M>(max_n, |ply, position, move_played| {
+            if let Some((mate_in, mating_move)) = shortest_mate(position, max_n) {
+                opportunities.push(PgnMateOpportunity {
+                    ply,
+                    mate_in,
+                    mating_move,
+                    move_played,
+                });
+            }
+        });
+        opportunities
+    }
+
+    /// Finds every main-line move after which the opponent can force mate within `max_n` moves,
+    /// although the mover was not already mated within `max_n` moves whatever it played.
+    ///
+    /// `M` is the context stack capacity used for replay and search.
+    ///
+    /// # Panics
+    /// If `M` is smaller than the main line's length plus `2 * max_n`.
+    pub fn find_mate_blunders<const M: usize>(&self, max_n: u8) -> Vec<PgnMateBlunder> {
+        let mut blunders = Vec::new();
+        self.for_each_main_line_position::<M>(max_n, |ply, position, move_played| {
+            let Some(move_played) = move_played else {
+                return;
+            };
+            let already_lost = match position {
+                TypedPosition::White(p) => p.is_lost_within(max_n),
+                TypedPosition::Black(p) => p.is_lost_within(max_n),
+            };
+            if already_lost {
+                return;
+            }
+            let mut child = play(position.clone(), move_played);
+            if let Some((mate_in, mating_move)) = shortest_mate(&mut child, max_n) {
+                blunders.push(PgnMateBlunder {
+                    ply,
+                    move_played,
+                    mate_in,
+                    mating_move,
+                });
+            }
+        });
+        blunders
+    }
+
+    /// Replays the main line, calling `visit` with each position's ply, the position and the
+    /// move played from it (`None` at the end of the line).
+    fn for_each_main_line_position<const M: usize>(
+        &self,
+        max_n: u8,
+        mut visit: impl FnMut(usize, &mut TypedPosition<M>, Option<Move>),
+    ) {
+        let main_line = self.main_line();
+        assert!(
+            main_line.len() + 2 * max_n as usize <= M,
+            "context stack of {M} cannot hold {} plies plus a mate search of depth {max_n}",
+            main_line.len()
+        );
+        let mut position = self.start_position::<M>();
+
+        for ply in 0..=main_line.len() {
+            let move_played = main_line.get(ply).copied();
+            visit(ply, &mut position, move_played);
+            if let Some(move_) = move_played {
+                position = play(position, move_);
+            }
+        }
+    }
+}
+
+/// The shortest forced mate within `max_n` moves for the side to move, with its first move.
+fn shortest_mate<const M: usize>(position: &mut TypedPosition<M>, max_n: u8) -> Option<(u8, Move)> {
+    (1..=max_n).find_map(|n| {
+        let mating_move = match position {
+            TypedPosition::White(p) => p.mate_in_n(n),
+            TypedPosition::Black(p) => p.mate_in_n(n),
+        };
+        mating_move.map(|mating_move| (n, mating_move))
+    })
+}
+
+fn play<const M: usize>(position: TypedPosition<M>, move_: Move) -> TypedPosition<M> {
+    match position {
+        TypedPosition::White(mut p) => {
+            p.make_move(move_);
+            TypedPosition::Black(p.rebrand_stm())
+        }
+        TypedPosition::Black(mut p) => {
+            p.make_move(move_);
+            TypedPosition::White(p.rebrand_stm())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pgn::PgnParser;
+
+    #[test]
+    fn test_find_forced_mates_in_scholars_mate() {
+        let mut parser = PgnParser::<16>::new("1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. Qxf7# 1-0");
+        parser.parse().unwrap();
+
+        let opportunities = parser.constructed_object.find_forced_mates::<24>(1);
+        assert_eq!(opportunities.len(), 1);
+        let opportunity = opportunities[0];
+        assert_eq!(opportunity.ply, 6);
+        assert_eq!(opportunity.mate_in, 1);
+        assert_eq!(Some(opportunity.mating_move), opportunity.move_played);
+    }
+
+    #[test]
+    fn test_find_mate_blunders() {
+        let mut parser = PgnParser::<16>::new("1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. Qxf7# 1-0");
+        parser.parse().unwrap();
+
+        let blunders = parser.constructed_object.find_mate_blunders::<24>(1);
+        assert_eq!(blunders.len(), 1);
+        let blunder = blunders[0];
+        assert_eq!(blunder.ply, 5);
+        assert_eq!(blunder.mate_in, 1);
+        assert_eq!(blunder.move_played.uci(), "g8f6");
+    }
+
+    #[test]
+    #[should_panic(expected = "context stack of 8 cannot hold 7 plies")]
+    fn test_find_forced_mates_checks_stack_capacity() {
+        let mut parser = PgnParser::<16>::new("1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. Qxf7# 1-0");
+        parser.parse().unwrap();
+        parser.constructed_object.find_forced_mates::<8>(1);
+    }
+}
@@ -0,0 +1,111 @@
+//! Merging games that share a start position into one tree of variations.
+
+use crate::{
+    TypedPosition,
+    pgn::{
+        error::PgnError,
+        move_tree::{MoveTree, MoveTreeNodeId},
+        object::{PgnObject, play_move, zobrist_hash_of},
+    },
+};
+
+impl<const N: usize> PgnObject<N> {
+    /// Merges the moves of `other` into this game's tree.
+    ///
+    /// Both games are walked from the start position together. A move of `other` that reaches
+    /// the same position as one of this game's continuations is matched with it, picking up its
+    /// comment, annotation and NAG where this game has none; the first move that diverges is
+    /// added, with everything after it, as a new variation (RAV) after the existing ones. This
+    /// game's main line, tags and result are kept.
+    ///
+    /// Fails with [`PgnError::StartPositionMismatch`] if the games start from different
+    /// positions.
+    pub fn merge(&mut self, other: &PgnObject<N>) -> Result<(), PgnError> {
+        let start_position = self.start_position::<N>();
+        let other_start_position = other.start_position::<N>();
+        if zobrist_hash_of(&start_position) != zobrist_hash_of(&other_start_position) {
+            return Err(PgnError::StartPositionMismatch(
+                other_start_position.to_fen(),
+            ));
+        }
+
+        self.source = None;
+        if self.tree.comment(MoveTree::ROOT).is_none() {
+            self.tree
+                .set_comment(MoveTree::ROOT, other.tree.comment(MoveTree::ROOT));
+        }
+        let mut pending: Vec<(MoveTreeNodeId, MoveTreeNodeId, TypedPosition<N>)> =
+            vec![(MoveTree::ROOT, MoveTree::ROOT, start_position)];
+        while let Some((other_id, id, position)) = pending.pop() {
+            let existing: Vec<(MoveTreeNodeId, u64)> = self
+                .tree
+                .continuations(id)
+                .map(|continuation| {
+                    let move_ = self.tree.move_at(continuation).expect("not the root");
+                    (continuation, zobrist_hash_of(&play_move(&position, move_)))
+                })
+                .collect();
+            for other_continuation in other.tree.continuations(other_id) {
+                let move_ = other
+                    .tree
+                    .move_at(other_continuation)
+                    .expect("not the root");
+                let next_position = play_move(&position, move_);
+                let key = zobrist_hash_of(&next_position);
+                match existing
+                    .iter()
+                    .find(|(_, existing_key)| *existing_key == key)
+                {
+                    Some(&(continuation, _)) => {
+                        self.tree
+                            .fill_missing_from(&other.tree, other_continuation, continuation);
+                        pending.push((other_continuation, continuation, next_position));
+                    }
+                    None => self
+                        .tree
+                        .copy_subtree_from(&other.tree, other_continuation, id),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pgn::{PgnError, PgnObject, PgnParser, PgnRenderingConfig};
+
+    fn parse(pgn: &str) -> PgnObject<16> {
+        let mut parser = PgnParser::<16>::new(pgn);
+        parser.parse().unwrap();
+        parser.constructed_object
+    }
+
+    #[test]
+    fn test_merge_adds_diverging_moves_as_variations() {
+        let mut repertoire = parse("1. e4 e5 2. Nf3 Nc6 3. Bb5 *");
+        repertoire
+            .merge(&parse("{Petrov} 1. e4 e5 2. Nf3! Nf6 3. Nxe5 *"))
+            .unwrap();
+        repertoire.merge(&parse("1. e4 c5! 2. Nf3 d6 *")).unwrap();
+        repertoire.merge(&parse("1. d4 d5 *")).unwrap();
+
+        assert_eq!(
+            repertoire.render(true, PgnRenderingConfig::all_markings()),
+            "{ Petrov } 1. e4 (1. d4 d5) 1... e5 (1... c5! 2. Nf3 d6) 2. Nf3! Nc6 \
+             (2... Nf6 3. Nxe5) 3. Bb5"
+        );
+        assert_eq!(repertoire.main_line().len(), 5);
+    }
+
+    #[test]
+    fn test_merge_requires_same_start_position() {
+        let mut game = parse("1. e4 *");
+        let fen = "rnbqkbnr/pppppppp/8/8/3P4/8/PPP1PPPP/RNBQKBNR b KQkq - 0 1";
+        let other = parse(&format!("[FEN \"{fen}\"]\n1... d5 *"));
+        assert_eq!(
+            game.merge(&other),
+            Err(PgnError::StartPositionMismatch(fen.to_string()))
+        );
+    }
+}
@@ -3,35 +3,113 @@
 //! Use `PgnParser::<'_, N>` with an `N` large enough for `Position<N>` to replay the longest line in
 //! your games (including variations).
 
+mod analysis;
 mod buffered_position_brancher;
 mod buffered_position_context;
 mod error;
+mod fingerprint;
+mod games;
+mod lossless;
+mod mate_scan;
+mod merge;
 mod move_data;
-mod move_tree_node;
+mod move_tree;
+mod navigation;
 mod object;
+mod opening_tree;
 mod parser;
 mod parsing_state;
+mod polyglot_book;
 mod position_context;
 mod rendering_config;
+mod result;
+mod san;
+mod syntax;
+mod tag_values;
 mod token;
 mod token_types;
+mod walk;
+mod writer;
 
+pub use analysis::{
+    AnalysisThresholds,
+    AnnotationOptions,
+    EVAL_CLAMP_CP,
+    EngineLine,
+    EngineScore,
+    GameAnalysis,
+    PlayerAnalysis,
+};
 pub use error::PgnError;
+pub use fingerprint::{
+    FINGERPRINT_TAGS,
+    PgnDeduplicateExt,
+    PgnDuplicate,
+    PgnDuplicateKind,
+    PgnDuplicates,
+    PgnFingerprint,
+};
+#[cfg(feature = "parallel")]
+pub use games::parse_games_parallel;
+pub use games::{decode_pgn_bytes, parse_games, parse_games_from_bytes, split_games, write_games};
+pub use lossless::PgnSourceToken;
+pub use mate_scan::{PgnMateBlunder, PgnMateOpportunity};
+pub use move_tree::{MoveTreeMemory, MoveTreeNodeId};
+pub use navigation::PgnPathStep;
 pub use object::PgnObject;
-pub use parser::PgnParser;
+pub(crate) use object::zobrist_hash_of;
+pub use opening_tree::{OpeningMoveStats, OpeningTree, OpeningTreeNode};
+pub use parser::{
+    DEFAULT_MAX_VARIATION_DEPTH,
+    PgnAnnotationPolicy,
+    PgnCommentBracePolicy,
+    PgnDuplicateTagPolicy,
+    PgnParser,
+    PgnParserLimit,
+    PgnParserLimits,
+};
 pub use parsing_state::PgnParsingState;
+pub use polyglot_book::{
+    POLYGLOT_RANDOM_COUNT,
+    POLYGLOT_TEST_VECTORS,
+    PolyglotBookBuilder,
+    PolyglotBookFilter,
+    PolyglotEntry,
+    PolyglotKeys,
+    PolyglotWeighting,
+    polyglot_move,
+};
 pub use rendering_config::PgnRenderingConfig;
+pub use result::PgnResult;
+pub use san::{SanError, SanMove, parse_san};
+pub use syntax::{PgnSyntaxGame, PgnSyntaxMove};
+pub use tag_values::{PgnDate, PgnRound};
+pub use walk::{PgnBranch, PgnWalk, PgnWalkStep};
+pub use writer::PgnWriter;
 
 #[cfg(test)]
 mod tests {
-    use crate::pgn::{PgnParser, PgnRenderingConfig};
+    use crate::{
+        logic::game_state::GameResult,
+        pgn::{
+            PgnAnnotationPolicy,
+            PgnCommentBracePolicy,
+            PgnDate,
+            PgnDuplicateTagPolicy,
+            PgnError,
+            PgnParser,
+            PgnParserLimit,
+            PgnParserLimits,
+            PgnRenderingConfig,
+            PgnRound,
+        },
+    };
 
     /// Smallest `Position<N>` stack for this fixture (`75` overflows during parse).
     const PGN_CONTEXT_STACK: usize = 76;
 
     #[test]
     fn test_pgn_parsing_and_rendering() {
-        // The PGN data with comments removed
         let pgn_input = r"1. e4 e5 2. Nf3 Nf6!!!! 3. Bc4 Nxe4 4. Nc3 Nc6 (4... Nxc3 5. dxc3??!! $20 { [%csl Gf6][%cal Gf7f6] } 5... f6 6. Nh4 $21 g6 7. f4 Qe7 8. f5 ) 5. O-O (5. Nxe4 d5 { [%cal Gd5e4,Gd5c4] } ) 5... Nxc3 6. dxc3 f6 7. Re1 d6 8. Nh4 g6 9. f4 Qe7 10. f5 Qg7 11. Qf3 Bd7 (11... g5 { [%csl Ge8] } 12. Qh5+ Kd8 { [%cal Gg5h4] } 13. Nf3 Bxf5 ) 12. b4 Be7 { [%csl Ge7][%cal Gf8e7] } (12... O-O-O 13. Bd5 b6 (13... g5 ) ) 13. Qe4 { [%csl Gg6][%cal Gf5g6] } 13... g5 (13... Nd8 ) 14. Nf3 O-O-O (14... Nd8 ) 15. a4 g4 16. Nh4 g3 17. h3 Rdf8 18. a5 Nd8 19. a6 Bc6 20. axb7+ Bxb7 21. Bd5 c6 22. Qc4 a6 23. Be3 Kd7 24. Be6+ Ke8 25. Rxa6 Bxa6 26. Qxa6 Rf7 27. Qc8 Bf8 28. Ra1 Rd7 29. Ra8 Qe7 30. Bb6 Bh6 31. Bxd7+ Kf8 32. Bxd8 Be3+ 33. Kf1 Kg7 34. Bxe7 Rxc8 35. Rxc8 d5 36. Nf3 d4 37. Bf8+ Kf7 38. Be6# { White wins by checkmate. } 1-0";
 
         let mut parser = PgnParser::<PGN_CONTEXT_STACK>::new(pgn_input);
@@ -40,7 +118,7 @@ mod tests {
 
         let rendered_pgn = parser
             .constructed_object
-            .render(true, PgnRenderingConfig::default());
+            .render(true, *PgnRenderingConfig::default().comments(false));
 
         // Expected PGN after parsing and rendering
         // This will need to be adjusted based on your actual expected output format
@@ -58,5 +136,561 @@ mod tests {
 
         // Optional: Print the rendered PGN for manual inspection
         println!("Rendered PGN:\n{}", rendered_pgn);
+
+        // With comments, the movetext comes back as it was written.
+        let rendered_pgn = parser
+            .constructed_object
+            .render(true, PgnRenderingConfig::default());
+        assert_eq!(
+            rendered_pgn.replace(" ", ""),
+            pgn_input.trim_end_matches(" 1-0").replace(" ", "")
+        );
+    }
+
+    #[test]
+    fn test_comments_survive_render_and_reparse() {
+        let pgn_input = "1. e4 {good [%eval 0.3]} e5 {ok} (1... c5 {sharp}) 2. Nf3 *";
+        let mut parser = PgnParser::<8>::new(pgn_input);
+        parser.parse().unwrap();
+        let object = &parser.constructed_object;
+        let nodes = object.main_line_nodes();
+        assert_eq!(object.node_comment(nodes[0]), Some("good [%eval 0.3]"));
+        assert_eq!(object.node_comment(nodes[1]), Some("ok"));
+
+        let rendered = object.render(true, PgnRenderingConfig::default());
+        assert_eq!(
+            rendered,
+            "1. e4 { good [%eval 0.3] } 1... e5 { ok } (1... c5 { sharp }) 2. Nf3"
+        );
+        let mut parser = PgnParser::<8>::new(&rendered);
+        parser.parse().unwrap();
+        assert_eq!(
+            parser
+                .constructed_object
+                .render(true, PgnRenderingConfig::default()),
+            rendered
+        );
+    }
+
+    #[test]
+    fn test_leading_comment_is_attached_to_root() {
+        let pgn_input = "[Event \"Casual\"]\n{ Annotated by a friend } {Rapid} 1. e4 e5 *";
+        let mut parser = PgnParser::<8>::new(pgn_input);
+        parser.parse().unwrap();
+
+        let object = &parser.constructed_object;
+        assert_eq!(
+            object.game_comment().as_deref(),
+            Some("Annotated by a friend Rapid")
+        );
+        assert_eq!(
+            object.render(true, PgnRenderingConfig::default()),
+            "[Event \"Casual\"]\n{ Annotated by a friend Rapid } 1. e4 e5"
+        );
+        assert_eq!(
+            object.render(true, PgnRenderingConfig::no_markings()),
+            "[Event \"Casual\"]\n1. e4 e5"
+        );
+    }
+
+    #[test]
+    fn test_result_tag_consistency() {
+        let mut parser = PgnParser::<8>::new("[Result \"0-1\"] 1. e4 e5 0-1 0-1");
+        parser.parse().unwrap();
+        assert_eq!(parser.constructed_object.result(), GameResult::BlackWins);
+
+        let mut parser = PgnParser::<8>::new("[Result \"?\"] 1. e4 e5 1-0");
+        parser.parse().unwrap();
+        assert_eq!(parser.constructed_object.result(), GameResult::WhiteWins);
+
+        let mut parser = PgnParser::<8>::new("[Result \"0-1\"] 1. e4 e5 1-0");
+        let err = parser.parse().unwrap_err();
+        assert_eq!(
+            err,
+            PgnError::ResultMismatch {
+                tag: "0-1".to_string(),
+                token: GameResult::WhiteWins,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Result tag `0-1` does not match result token `1-0`"
+        );
+
+        let mut parser =
+            PgnParser::<8>::new("[Result \"0-1\"] 1. e4 e5 1-0").with_result_normalization(true);
+        parser.parse().unwrap();
+        let object = &parser.constructed_object;
+        assert_eq!(object.result(), GameResult::WhiteWins);
+        assert_eq!(object.tags.get("Result").map(String::as_str), Some("1-0"));
+
+        let mut parser = PgnParser::<8>::new("1. e4 e5 1/2-1/2 0-1");
+        assert_eq!(
+            parser.parse(),
+            Err(PgnError::UnexpectedToken("0-1".to_string()))
+        );
+
+        let mut parser = PgnParser::<8>::new("[Result \"1/2-1/2\"] 1. e4 e5");
+        parser.parse().unwrap();
+        assert_eq!(parser.constructed_object.result(), GameResult::Draw);
+    }
+
+    fn nested_variations(depth: usize) -> String {
+        format!("1. e4 {}{} *", "(1. d4 ".repeat(depth), ")".repeat(depth))
+    }
+
+    #[test]
+    fn test_error_messages_quote_input() {
+        let mut parser = PgnParser::<8>::new("1. e4 e5 2. Ke3 *");
+        let err = parser.parse().unwrap_err();
+        assert_eq!(err, PgnError::IllegalMove("Ke3".to_string()));
+        assert_eq!(err.to_string(), "illegal move `Ke3`");
+
+        let mut parser = PgnParser::<8>::new("1. e4 e5 2. @ *");
+        assert_eq!(parser.parse(), Err(PgnError::InvalidToken("@".to_string())));
+    }
+
+    #[test]
+    fn test_variation_depth_limit() {
+        let pgn_input = nested_variations(1000);
+
+        let mut parser = PgnParser::<8>::new(&pgn_input);
+        assert!(matches!(parser.parse(), Err(PgnError::VariationTooDeep(_))));
+
+        let mut parser = PgnParser::<8>::new(&pgn_input).with_max_variation_depth(1000);
+        parser.parse().unwrap();
+        let rendered = parser
+            .constructed_object
+            .render(true, PgnRenderingConfig::default());
+        assert_eq!(rendered.matches("1. d4").count(), 1000);
+
+        let pgn_input = nested_variations(3);
+        let mut parser = PgnParser::<8>::new(&pgn_input).with_max_variation_depth(2);
+        assert_eq!(parser.parse(), Err(PgnError::VariationTooDeep(2)));
+    }
+
+    #[test]
+    fn test_lossless_round_trip() {
+        let pgn_input = "[Event \"Casual\"]\n[Result \"1-0\"]\n\n1.e4  e5 {main}\n2. Nf3 $1 (2. Bc4 ) 2... Nc6!? 1-0\n";
+
+        let mut parser = PgnParser::<8>::new(pgn_input).with_lossless(true);
+        parser.parse().unwrap();
+        let object = &mut parser.constructed_object;
+        assert_eq!(
+            object.render(true, PgnRenderingConfig::default()),
+            pgn_input
+        );
+        assert_eq!(object.source_tokens().unwrap()[2].text, "1.");
+        assert_ne!(
+            object.render(false, PgnRenderingConfig::default()),
+            pgn_input
+        );
+
+        object.tags.insert("Site".to_string(), "Home".to_string());
+        assert!(object.source_tokens().is_none());
+        assert_ne!(
+            object.render(true, PgnRenderingConfig::default()),
+            pgn_input
+        );
+
+        let mut parser = PgnParser::<8>::new(pgn_input);
+        parser.parse().unwrap();
+        assert!(parser.constructed_object.source_tokens().is_none());
+
+        let with_escapes =
+            "%exported by tool\n[Event \"Casual\"]\n\n1. e4 e5\n% eval 0.3\n*\n%end\n";
+        let mut parser = PgnParser::<8>::new(with_escapes).with_lossless(true);
+        parser.parse().unwrap();
+        let object = &parser.constructed_object;
+        assert_eq!(
+            object.render(true, PgnRenderingConfig::default()),
+            with_escapes
+        );
+        assert_eq!(
+            object.render(true, PgnRenderingConfig::no_markings()),
+            "[Event \"Casual\"]\n1. e4 e5"
+        );
+    }
+
+    #[test]
+    fn test_custom_tags_survive_round_trip() {
+        let pgn_input = "[Event \"Casual\"]\n[WhiteElo \"2412\"]\n[LichessURL \"https://lichess.org/abcd1234\"]\n[Annotator \"A \\\"Quoted\\\" Name\"]\n1. e4 *";
+        let mut parser = PgnParser::<8>::new(pgn_input);
+        parser.parse().unwrap();
+        let object = &mut parser.constructed_object;
+
+        let names: Vec<&str> = object.tag_pairs().map(|(name, _)| name).collect();
+        assert_eq!(names, ["Event", "WhiteElo", "LichessURL", "Annotator"]);
+        assert_eq!(object.tag("whiteelo"), Some("2412"));
+        assert_eq!(object.tag("Annotator"), Some("A \"Quoted\" Name"));
+        assert_eq!(
+            object.render(true, PgnRenderingConfig::no_markings()),
+            pgn_input.trim_end_matches(" *")
+        );
+
+        assert_eq!(object.white_elo(), Some(2412));
+        assert_eq!(object.black_elo(), None);
+        assert_eq!(object.date(), PgnDate::default());
+        assert_eq!(object.round(), PgnRound::Unknown);
+
+        object.add_tag("Round".to_string(), "4.1".to_string());
+        assert_eq!(object.round(), PgnRound::Numbered(vec![4, 1]));
+        object.add_tag("EVENT".to_string(), "Blitz".to_string());
+        assert_eq!(object.tag_pairs().next(), Some(("Event", "Blitz")));
+        assert_eq!(
+            object.remove_tag("lichessurl").as_deref(),
+            Some("https://lichess.org/abcd1234")
+        );
+        assert_eq!(object.tag_pairs().count(), 4);
+    }
+
+    #[test]
+    fn test_semicolon_and_nested_brace_comments() {
+        let pgn_input = "; exported by engine {v2}\n{eval {+0.3}} 1. e4 ; depth 20\ne5 *";
+        let mut parser = PgnParser::<8>::new(pgn_input);
+        // The standard reading ends the comment at the first `}`, leaving a stray brace.
+        assert_eq!(parser.parse(), Err(PgnError::InvalidToken("}".to_string())));
+
+        let mut parser =
+            PgnParser::<8>::new(pgn_input).with_comment_braces(PgnCommentBracePolicy::Nested);
+        parser.parse().unwrap();
+        let object = &parser.constructed_object;
+        assert_eq!(
+            object.game_comment().as_deref(),
+            Some("exported by engine {v2} eval {+0.3}")
+        );
+        let rendered = object.render(true, PgnRenderingConfig::default());
+        assert_eq!(
+            rendered,
+            "; exported by engine {v2} eval {+0.3}\n 1. e4 { depth 20 } 1... e5"
+        );
+        let mut parser = PgnParser::<8>::new(&rendered);
+        parser.parse().unwrap();
+        assert_eq!(
+            parser.constructed_object.game_comment(),
+            object.game_comment()
+        );
+
+        let mut parser =
+            PgnParser::<8>::new(pgn_input).with_comment_braces(PgnCommentBracePolicy::Reject);
+        assert_eq!(
+            parser.parse(),
+            Err(PgnError::InvalidComment("{eval {".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parser_limits() {
+        let pgn_input =
+            "[Event \"A\"]\n[Site \"B\"]\n{intro} 1. e4 (1. d4) (1. c4) 1... e5 2. Nf3 *";
+        let parse = |limits| PgnParser::<8>::new(pgn_input).with_limits(limits).parse();
+        let exact = PgnParserLimits {
+            max_plies: Some(5),
+            max_tags: Some(2),
+            max_comment_len: Some(5),
+            max_variations: Some(2),
+        };
+        assert_eq!(parse(exact), Ok(()));
+        assert_eq!(parse(PgnParserLimits::default()), Ok(()));
+
+        let cases = [
+            (
+                PgnParserLimits {
+                    max_plies: Some(4),
+                    ..exact
+                },
+                PgnParserLimit::Plies(4),
+            ),
+            (
+                PgnParserLimits {
+                    max_tags: Some(1),
+                    ..exact
+                },
+                PgnParserLimit::Tags(1),
+            ),
+            (
+                PgnParserLimits {
+                    max_comment_len: Some(4),
+                    ..exact
+                },
+                PgnParserLimit::CommentLength(4),
+            ),
+            (
+                PgnParserLimits {
+                    max_variations: Some(1),
+                    ..exact
+                },
+                PgnParserLimit::Variations(1),
+            ),
+        ];
+        for (limits, exceeded) in cases {
+            assert_eq!(parse(limits), Err(PgnError::LimitExceeded(exceeded)));
+        }
+        assert_eq!(
+            PgnError::LimitExceeded(PgnParserLimit::Plies(4)).to_string(),
+            "game has more than 4 moves"
+        );
+    }
+
+    #[test]
+    fn test_duplicate_tag_policy() {
+        let pgn_input = "[Event \"First\"]\n[event \"Second\"]\n1. e4 *";
+        let parse = |policy| {
+            let mut parser = PgnParser::<8>::new(pgn_input).with_duplicate_tags(policy);
+            parser.parse().map(|()| {
+                let object = parser.constructed_object;
+                object
+                    .tag_pairs()
+                    .map(|(name, value)| format!("{name}={value}"))
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(
+            parse(PgnDuplicateTagPolicy::KeepLast),
+            Ok(vec!["Event=Second".to_string()])
+        );
+        assert_eq!(
+            parse(PgnDuplicateTagPolicy::KeepFirst),
+            Ok(vec!["Event=First".to_string()])
+        );
+        assert_eq!(
+            parse(PgnDuplicateTagPolicy::Reject),
+            Err(PgnError::DuplicateTag("event".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_annotation_policy() {
+        let pgn_input = "1. e4!!! e5?!? 2. Nf3!? Nc6??? *";
+        let parse = |policy| {
+            let mut parser = PgnParser::<8>::new(pgn_input).with_annotations(policy);
+            parser.parse().map(|()| {
+                parser
+                    .constructed_object
+                    .render(false, PgnRenderingConfig::all_markings())
+            })
+        };
+        let rendered = parse(PgnAnnotationPolicy::Keep).unwrap();
+        assert!(
+            rendered.contains("1. e4!!! e5?!? 2. Nf3!? Nc6???"),
+            "{rendered}"
+        );
+        let rendered = parse(PgnAnnotationPolicy::Normalize).unwrap();
+        assert!(
+            rendered.contains("1. e4!! e5?! 2. Nf3!? Nc6??"),
+            "{rendered}"
+        );
+        assert_eq!(
+            parse(PgnAnnotationPolicy::Reject),
+            Err(PgnError::NonstandardAnnotation("e4!!!".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_legacy_san_requires_lenient_mode() {
+        let pgn_input = "1. e4 d5 2. e:d5 c6 3. d:c6 Nf6 4. c:b7 e5 5. b:a8Q e4 6. d4 e:d3 e.p. *";
+        let mut parser = PgnParser::<16>::new(pgn_input);
+        assert_eq!(
+            parser.parse(),
+            Err(PgnError::InvalidMove("e:d5".to_string()))
+        );
+
+        let mut parser = PgnParser::<16>::new(pgn_input).with_lenient(true);
+        parser.parse().unwrap();
+        let rendered = parser
+            .constructed_object
+            .render(false, PgnRenderingConfig::no_markings());
+        assert!(rendered.ends_with("5. bxa8=Q e4 6. d4 exd3"));
+    }
+
+    #[test]
+    fn test_castling_form_is_kept_or_normalized() {
+        let pgn_input = "1. e4 e5 2. Nf3 Nc6 3. Bc4 Bc5 4. 0-0 Nf6 5. d3 0-0 *";
+        let mut parser = PgnParser::<16>::new(pgn_input);
+        parser.parse().unwrap();
+        let object = &parser.constructed_object;
+        assert!(
+            object
+                .render(false, PgnRenderingConfig::all_markings())
+                .ends_with("4. 0-0 Nf6 5. d3 0-0")
+        );
+        assert!(
+            object
+                .render(false, PgnRenderingConfig::no_markings())
+                .ends_with("4. O-O Nf6 5. d3 O-O")
+        );
+
+        let mut parser = PgnParser::<16>::new(pgn_input).with_lossless(true);
+        parser.parse().unwrap();
+        let mut config = PgnRenderingConfig::all_markings();
+        config.normalize_castling(true);
+        assert_eq!(
+            parser.constructed_object.render(true, config),
+            pgn_input.replace("0-0", "O-O")
+        );
+    }
+
+    #[test]
+    fn test_fen_tag_sets_start_position() {
+        let fen = "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2";
+        let pgn_input =
+            format!("[SetUp \"1\"]\n[FEN \"{fen}\"]\n\n{{Petrov}} 2... Nf6 3. Nxe5 d6 *");
+        let mut parser = PgnParser::<16>::new(&pgn_input);
+        parser.parse().unwrap();
+        let object = parser.constructed_object;
+        assert_eq!(object.start_position::<16>().to_fen(), fen);
+        assert!(
+            object
+                .render(true, PgnRenderingConfig::all_markings())
+                .ends_with("{ Petrov } 2... Nf6 3. Nxe5 d6")
+        );
+
+        let mut parser = PgnParser::<16>::new("[FEN \"8/8/8/8 w - - 0 1\"]\n1. e4 *");
+        assert_eq!(
+            parser.parse(),
+            Err(PgnError::InvalidFen("8/8/8/8 w - - 0 1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_black_first_numbering() {
+        let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 12";
+        let movetext = "12... c5 (12... e5 13. Nf3 (13. Nc3)) 13. Nf3 d6";
+        let pgn_input = format!("[SetUp \"1\"]\n[FEN \"{fen}\"]\n\n{movetext} *");
+        let mut parser = PgnParser::<16>::new(&pgn_input);
+        parser.parse().unwrap();
+        assert!(
+            parser
+                .constructed_object
+                .render(true, PgnRenderingConfig::all_markings())
+                .ends_with(movetext)
+        );
+
+        // Black's first move must carry its own number, not White's next one.
+        let wrong_number = format!("[SetUp \"1\"]\n[FEN \"{fen}\"]\n\n13... c5 *");
+        assert_eq!(
+            PgnParser::<16>::new(&wrong_number).parse(),
+            Err(PgnError::IncorrectMoveNumber("13...".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_slice_and_extract_variation() {
+        let pgn_input = "[Event \"Prep\"]\n[Result \"1-0\"]\n\n\
+                         1. e4 e5 2. Nf3 (2. Bc4 Nf6) 2... Nc6 3. Bb5 a6 (3... Nf6 4. O-O) 4. Ba4 1-0";
+        let mut parser = PgnParser::<16>::new(pgn_input);
+        parser.parse().unwrap();
+        let object = parser.constructed_object;
+        let render = |game: &crate::pgn::PgnObject<16>| {
+            let rendered = game.render(true, PgnRenderingConfig::no_markings());
+            rendered.rsplit('\n').next().unwrap().to_string()
+        };
+
+        let opening = object.slice(0, 3);
+        assert_eq!(opening.tag("FEN"), None);
+        assert_eq!(opening.result(), GameResult::Ongoing);
+        assert_eq!(opening.tag("Result"), Some("*"));
+        assert_eq!(render(&opening), "1. e4 e5 2. Nf3 (2. Bc4 Nf6)");
+
+        let ending = object.slice(4, 100);
+        assert_eq!(ending.tag("SetUp"), Some("1"));
+        assert_eq!(
+            ending.tag("FEN"),
+            Some("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3")
+        );
+        assert_eq!(ending.result(), GameResult::WhiteWins);
+        assert_eq!(ending.tag("Event"), Some("Prep"));
+        assert_eq!(render(&ending), "3. Bb5 a6 (3... Nf6 4. O-O) 4. Ba4");
+
+        // The sliced game stands on its own: it re-parses to the same moves.
+        let rendered = ending.render(true, PgnRenderingConfig::default());
+        let mut parser = PgnParser::<16>::new(&rendered);
+        parser.parse().unwrap();
+        assert_eq!(parser.constructed_object.main_line(), ending.main_line());
+
+        let bb5 = object.main_line_nodes()[4];
+        let variation = object.continuations(bb5).nth(1).unwrap();
+        let extracted = object.extract_variation(variation).unwrap();
+        assert_eq!(extracted.result(), GameResult::Ongoing);
+        assert_eq!(render(&extracted), "3... Nf6 4. O-O");
+        assert!(object.extract_variation(object.root_node()).is_none());
+    }
+
+    #[test]
+    fn test_find_position_with_and_without_recorded_keys() {
+        use crate::TypedPosition;
+
+        // Both lines transpose into the same four-knights position.
+        let pgn_input = "1. Nf3 (1. Nc3 Nf6 2. Nf3 Nc6) 1... Nf6 2. Nc3 Nc6 (2... d5) 3. e4 *";
+        let TypedPosition::White(target) = TypedPosition::<1>::from_fen(
+            "r1bqkb1r/pppppppp/2n2n2/8/8/2N2N2/PPPPPPPP/R1BQKB1R w KQkq - 4 3",
+        )
+        .unwrap() else {
+            unreachable!()
+        };
+        let zobrist = target.context().zobrist_hash();
+
+        let mut parser = PgnParser::<16>::new(pgn_input).with_position_keys(true);
+        parser.parse().unwrap();
+        let mut object = parser.constructed_object;
+        let found = object.find_position(zobrist);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[1], object.main_line_nodes()[3]);
+
+        // Edits leave later nodes without a key, so lookups fall back to replaying.
+        let mut parser = PgnParser::<16>::new("1. Nf3 Nf6 2. Nc3 Nc6 3. e4 e5 *");
+        parser.parse().unwrap();
+        object.push_main_line_move(parser.constructed_object.main_line()[5]);
+        assert_eq!(object.find_position(zobrist), found);
+
+        let mut parser = PgnParser::<16>::new(pgn_input);
+        parser.parse().unwrap();
+        assert_eq!(parser.constructed_object.find_position(zobrist), found);
+        assert!(parser.constructed_object.find_position(0).is_empty());
+    }
+
+    #[test]
+    fn test_edit_parsed_comments() {
+        let mut parser = PgnParser::<8>::new("{intro} 1. e4 {best by test} e5 (1... c5 {sharp}) *");
+        parser.parse().unwrap();
+        let mut object = parser.constructed_object;
+        let nodes = object.main_line_nodes();
+        let sicilian = object.continuations(nodes[0]).nth(1).unwrap();
+        assert_eq!(object.node_comment(object.root_node()), Some("intro"));
+        assert_eq!(object.node_comment(nodes[0]), Some("best by test"));
+        assert_eq!(object.node_comment(nodes[1]), None);
+        assert_eq!(object.node_comment(sicilian), Some("sharp"));
+
+        object.set_node_comment(nodes[0], None);
+        object.set_node_comment(nodes[1], Some("solid".to_string()));
+        assert_eq!(
+            object.render(true, PgnRenderingConfig::default()),
+            "{ intro } 1. e4 e5 { solid } (1... c5 { sharp })"
+        );
+    }
+
+    #[test]
+    fn test_edit_annotations_after_parse() {
+        let mut parser = PgnParser::<8>::new("1. e4 e5 (1... c5) 2. Nf3 *");
+        parser.parse().unwrap();
+        let mut object = parser.constructed_object;
+        let nodes = object.main_line_nodes();
+        let sicilian = object.continuations(nodes[0]).nth(1).unwrap();
+
+        object.set_node_nag(nodes[0], Some(14));
+        object.set_node_annotation(nodes[2], Some("!?".to_string()));
+        object.set_node_comment(nodes[1], Some("solid".to_string()));
+        object.set_node_comment(sicilian, Some("sharp".to_string()));
+        object.set_node_nag(object.root_node(), Some(1));
+        assert_eq!(object.node_nag(nodes[0]), Some(14));
+        assert_eq!(object.node_annotation(nodes[2]), Some("!?"));
+        assert_eq!(object.node_nag(object.root_node()), None);
+        assert_eq!(object.node_comment(sicilian), Some("sharp"));
+
+        let rendered = object.render(true, PgnRenderingConfig::all_markings());
+        assert!(
+            rendered.ends_with("1. e4 $14 e5 { solid } (1... c5 { sharp }) 2. Nf3!?"),
+            "{rendered}"
+        );
     }
 }
@@ -1,17 +1,21 @@
 //! Internal move representation with PGN annotations.
 
-use crate::{Piece, r#move::Move};
+use crate::{Piece, r#move::Move, pgn::move_tree::TextId};
 
 /// A move with its associated PGN metadata (text annotations and NAGs).
+///
+/// The annotation is interned in the owning [`MoveTree`](crate::pgn::move_tree::MoveTree).
 #[derive(Debug, Clone)]
 pub(crate) struct PgnMoveData {
     pub(crate) move_: Move,
-    pub(crate) annotation: Option<String>,
+    pub(crate) annotation: Option<TextId>,
     pub(crate) nag: Option<u8>,
+    /// Castling written as `0-0`/`0-0-0` in the input.
+    pub(crate) written_with_zeros: bool,
 }
 
 impl PgnMoveData {
-    /// Renders the move with SAN notation plus optional annotations.
+    /// Renders the move with SAN notation plus the given annotation text and optional NAG.
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn render(
         &self,
@@ -20,8 +24,9 @@ impl PgnMoveData {
         is_check: bool,
         is_checkmate: bool,
         is_capture: bool,
-        include_annotations: bool,
+        annotation: Option<&str>,
         include_nags: bool,
+        normalize_castling: bool,
     ) -> String {
         let mut result = self.move_.san(
             moved_piece,
@@ -30,8 +35,11 @@ impl PgnMoveData {
             is_checkmate,
             is_capture,
         );
+        if self.written_with_zeros && !normalize_castling {
+            result = result.replace('O', "0");
+        }
 
-        if include_annotations && let Some(annotation) = &self.annotation {
+        if let Some(annotation) = annotation {
             result.push_str(annotation);
         }
 
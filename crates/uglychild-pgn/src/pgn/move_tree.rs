@@ -0,0 +1,727 @@
+//! Arena-allocated move tree of a parsed game.
+//!
+//! Nodes live in one `Vec` and link to each other by index (first continuation plus next
+//! sibling), so a node costs a few dozen bytes and no allocation of its own. Comment and
+//! annotation text is interned in a shared pool. The tree holds no `Rc`s and is `Send + Sync`.
+
+use std::{fmt, io, mem};
+
+use indexmap::IndexSet;
+
+use crate::{
+    Color,
+    Piece,
+    TypedPosition,
+    r#move::Move,
+    pgn::{
+        move_data::PgnMoveData,
+        rendering_config::PgnRenderingConfig,
+        token_types::render_comment,
+    },
+    position::Position,
+};
+
+/// Opaque handle to a node of a game's move tree, valid only for the [`PgnObject`] it came from.
+///
+/// The root node stands for the start position; every other node holds the move that leads to
+/// it. Ids order by when their nodes were added.
+///
+/// An id is a plain arena index, so it can be stored, sent to another thread or written out via
+/// [`Self::index`] and turned back into an id with [`PgnObject::node_at`].
+///
+/// [`PgnObject`]: crate::pgn::PgnObject
+/// [`PgnObject::node_at`]: crate::pgn::PgnObject::node_at
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MoveTreeNodeId(u32);
+
+impl MoveTreeNodeId {
+    /// Position of the node in its tree's arena; the root is `0`.
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Index of an interned string in a [`MoveTree`]'s text pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct TextId(u32);
+
+#[derive(Debug, Clone)]
+struct MoveTreeNode {
+    move_data: Option<PgnMoveData>, // None for the root node
+    comment: Option<TextId>, // Root node may have a comment, so this is not part of MoveData
+    first_continuation: Option<MoveTreeNodeId>,
+    next_sibling: Option<MoveTreeNodeId>,
+}
+
+/// Memory used by a game's move tree, from [`PgnObject::move_tree_memory`].
+///
+/// [`PgnObject::move_tree_memory`]: crate::pgn::PgnObject::move_tree_memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveTreeMemory {
+    /// Number of nodes, including the root.
+    pub nodes: usize,
+    /// Bytes reserved for the node arena, including any recorded position keys.
+    pub node_bytes: usize,
+    /// Number of distinct comment and annotation strings.
+    pub text_entries: usize,
+    /// Bytes of interned text plus the pool's bookkeeping.
+    pub text_bytes: usize,
+}
+
+impl MoveTreeMemory {
+    /// Total bytes attributed to the tree.
+    pub fn total_bytes(&self) -> usize {
+        self.node_bytes + self.text_bytes
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct MoveTree {
+    nodes: Vec<MoveTreeNode>,
+    text: IndexSet<Box<str>>,
+    /// Zobrist key of the position after each node, by node index, when the parser recorded
+    /// them. Nodes added later have none, which leaves the list short.
+    position_keys: Vec<u64>,
+}
+
+impl MoveTree {
+    /// The root node, standing for the start position.
+    pub(crate) const ROOT: MoveTreeNodeId = MoveTreeNodeId(0);
+
+    pub(crate) fn new() -> MoveTree {
+        MoveTree {
+            nodes: vec![MoveTreeNode {
+                move_data: None,
+                comment: None,
+                first_continuation: None,
+                next_sibling: None,
+            }],
+            text: IndexSet::new(),
+            position_keys: Vec::new(),
+        }
+    }
+
+    fn node(&self, id: MoveTreeNodeId) -> &MoveTreeNode {
+        &self.nodes[id.0 as usize]
+    }
+
+    fn node_mut(&mut self, id: MoveTreeNodeId) -> &mut MoveTreeNode {
+        &mut self.nodes[id.0 as usize]
+    }
+
+    /// Interns `text`, returning the id of the existing entry if it was seen before.
+    pub(crate) fn intern(&mut self, text: &str) -> TextId {
+        let index = match self.text.get_index_of(text) {
+            Some(index) => index,
+            None => self.text.insert_full(text.into()).0,
+        };
+        TextId(index as u32)
+    }
+
+    pub(crate) fn text(&self, id: TextId) -> &str {
+        &self.text[id.0 as usize]
+    }
+
+    /// Adds a node for `move_data` after all existing continuations of `parent`.
+    pub(crate) fn add_continuation(
+        &mut self,
+        parent: MoveTreeNodeId,
+        move_data: PgnMoveData,
+    ) -> MoveTreeNodeId {
+        let id = MoveTreeNodeId(self.nodes.len() as u32);
+        self.nodes.push(MoveTreeNode {
+            move_data: Some(move_data),
+            comment: None,
+            first_continuation: None,
+            next_sibling: None,
+        });
+        match self.continuations(parent).last() {
+            Some(last) => self.node_mut(last).next_sibling = Some(id),
+            None => self.node_mut(parent).first_continuation = Some(id),
+        }
+        id
+    }
+
+    /// Returns the continuations of `id`, main continuation first.
+    pub(crate) fn continuations(
+        &self,
+        id: MoveTreeNodeId,
+    ) -> impl Iterator<Item = MoveTreeNodeId> + '_ {
+        let mut next = self.node(id).first_continuation;
+        std::iter::from_fn(move || {
+            let current = next?;
+            next = self.node(current).next_sibling;
+            Some(current)
+        })
+    }
+
+    /// Records the Zobrist key of the position after `id`; nodes must be recorded in creation
+    /// order, starting with the root.
+    pub(crate) fn record_position_key(&mut self, id: MoveTreeNodeId, key: u64) {
+        debug_assert_eq!(id.0 as usize, self.position_keys.len());
+        self.position_keys.push(key);
+    }
+
+    /// Returns the recorded position key of every node, by node index, if all nodes have one.
+    pub(crate) fn position_keys(&self) -> Option<&[u64]> {
+        (self.position_keys.len() == self.nodes.len()).then_some(self.position_keys.as_slice())
+    }
+
+    /// Returns the ids of all nodes, in creation order.
+    pub(crate) fn node_ids(&self) -> impl Iterator<Item = MoveTreeNodeId> {
+        (0..self.nodes.len() as u32).map(MoveTreeNodeId)
+    }
+
+    /// Returns the id of the node at `index`, or `None` if the tree has no such node.
+    pub(crate) fn node_id(&self, index: usize) -> Option<MoveTreeNodeId> {
+        (index < self.nodes.len()).then_some(MoveTreeNodeId(index as u32))
+    }
+
+    /// Returns the move leading to `id`, or `None` for the root.
+    pub(crate) fn move_at(&self, id: MoveTreeNodeId) -> Option<Move> {
+        self.node(id)
+            .move_data
+            .as_ref()
+            .map(|move_data| move_data.move_)
+    }
+
+    /// Returns the nodes of the main line below `id`, excluding `id` itself.
+    pub(crate) fn main_line_nodes(&self, id: MoveTreeNodeId) -> Vec<MoveTreeNodeId> {
+        let mut nodes = Vec::new();
+        let mut next = self.node(id).first_continuation;
+        while let Some(current) = next {
+            nodes.push(current);
+            next = self.node(current).first_continuation;
+        }
+        nodes
+    }
+
+    /// Returns the nodes from the root down to `id`, excluding the root, or `None` if `id` is
+    /// not in this tree.
+    pub(crate) fn path_to(&self, id: MoveTreeNodeId) -> Option<Vec<MoveTreeNodeId>> {
+        if id.0 as usize >= self.nodes.len() {
+            return None;
+        }
+        let mut parents = vec![None; self.nodes.len()];
+        for (index, node) in self.nodes.iter().enumerate() {
+            let mut next = node.first_continuation;
+            while let Some(child) = next {
+                parents[child.0 as usize] = Some(MoveTreeNodeId(index as u32));
+                next = self.node(child).next_sibling;
+            }
+        }
+        let mut path = Vec::new();
+        let mut current = id;
+        while let Some(parent) = parents[current.0 as usize] {
+            path.push(current);
+            current = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Copies the move and comment of `id` from `source` as a new continuation of `parent`,
+    /// without any of its continuations.
+    pub(crate) fn copy_node_from(
+        &mut self,
+        source: &MoveTree,
+        id: MoveTreeNodeId,
+        parent: MoveTreeNodeId,
+    ) -> MoveTreeNodeId {
+        let node = source.node(id);
+        let move_data = node.move_data.as_ref().expect("only the root has no move");
+        let move_data = PgnMoveData {
+            annotation: move_data
+                .annotation
+                .map(|annotation| self.intern(source.text(annotation))),
+            ..move_data.clone()
+        };
+        let copy = self.add_continuation(parent, move_data);
+        self.set_comment(copy, source.comment(id));
+        copy
+    }
+
+    /// Gives `target` the comment, annotation and NAG of `id` in `source` where it has none.
+    pub(crate) fn fill_missing_from(
+        &mut self,
+        source: &MoveTree,
+        id: MoveTreeNodeId,
+        target: MoveTreeNodeId,
+    ) {
+        if self.node(target).comment.is_none() {
+            self.set_comment(target, source.comment(id));
+        }
+        let (Some(source_data), Some(target_data)) =
+            (&source.node(id).move_data, &self.node(target).move_data)
+        else {
+            return;
+        };
+        let nag = target_data.nag.or(source_data.nag);
+        let annotation = match (target_data.annotation, source_data.annotation) {
+            (None, Some(annotation)) => Some(self.intern(source.text(annotation))),
+            (annotation, _) => annotation,
+        };
+        let target_data = self
+            .node_mut(target)
+            .move_data
+            .as_mut()
+            .expect("checked above");
+        target_data.annotation = annotation;
+        target_data.nag = nag;
+    }
+
+    /// Copies `id` and everything below it from `source` as a new continuation of `parent`.
+    pub(crate) fn copy_subtree_from(
+        &mut self,
+        source: &MoveTree,
+        id: MoveTreeNodeId,
+        parent: MoveTreeNodeId,
+    ) {
+        // Children are pushed in reverse so that siblings are re-added in their original order.
+        let mut pending = vec![(id, parent)];
+        while let Some((id, parent)) = pending.pop() {
+            let copy = self.copy_node_from(source, id, parent);
+            let children: Vec<MoveTreeNodeId> = source.continuations(id).collect();
+            pending.extend(children.into_iter().rev().map(|child| (child, copy)));
+        }
+    }
+
+    /// Returns the moves of the main line below `id`.
+    pub(crate) fn main_line(&self, id: MoveTreeNodeId) -> Vec<Move> {
+        let mut moves = Vec::new();
+        let mut next = self.node(id).first_continuation;
+        while let Some(current) = next {
+            let node = self.node(current);
+            moves.extend(node.move_data.as_ref().map(|move_data| move_data.move_));
+            next = node.first_continuation;
+        }
+        moves
+    }
+
+    /// Returns the comment attached after this node's move (before the first move for the root).
+    pub(crate) fn comment(&self, id: MoveTreeNodeId) -> Option<&str> {
+        self.node(id).comment.map(|text| self.text(text))
+    }
+
+    /// Replaces the comment of `id`.
+    pub(crate) fn set_comment(&mut self, id: MoveTreeNodeId, comment: Option<&str>) {
+        let comment = comment.map(|comment| self.intern(comment));
+        self.node_mut(id).comment = comment;
+    }
+
+    /// Appends `comment` to the comment of `id`, separated by a space.
+    pub(crate) fn append_comment(&mut self, id: MoveTreeNodeId, comment: &str) {
+        let combined = match self.comment(id) {
+            Some(existing) => format!("{existing} {comment}"),
+            None => comment.to_string(),
+        };
+        self.set_comment(id, Some(&combined));
+    }
+
+    /// Returns the move suffix annotation of `id`, such as `!?`.
+    pub(crate) fn annotation(&self, id: MoveTreeNodeId) -> Option<&str> {
+        let move_data = self.node(id).move_data.as_ref()?;
+        move_data.annotation.map(|text| self.text(text))
+    }
+
+    /// Replaces the annotation of `id`; does nothing for the root.
+    pub(crate) fn set_annotation(&mut self, id: MoveTreeNodeId, annotation: Option<&str>) {
+        let annotation = annotation.map(|annotation| self.intern(annotation));
+        if let Some(move_data) = &mut self.node_mut(id).move_data {
+            move_data.annotation = annotation;
+        }
+    }
+
+    /// Returns the NAG of `id`.
+    pub(crate) fn nag(&self, id: MoveTreeNodeId) -> Option<u8> {
+        self.node(id).move_data.as_ref()?.nag
+    }
+
+    /// Replaces the NAG of `id`; does nothing for the root.
+    pub(crate) fn set_nag(&mut self, id: MoveTreeNodeId, nag: Option<u8>) {
+        if let Some(move_data) = &mut self.node_mut(id).move_data {
+            move_data.nag = nag;
+        }
+    }
+
+    /// Appends `move_` after the last move of the main line starting at `id`.
+    pub(crate) fn push_main_line_move(&mut self, id: MoveTreeNodeId, move_: Move) {
+        let mut last = id;
+        while let Some(next) = self.node(last).first_continuation {
+            last = next;
+        }
+        self.add_continuation(
+            last,
+            PgnMoveData {
+                move_,
+                annotation: None,
+                nag: None,
+                written_with_zeros: false,
+            },
+        );
+    }
+
+    pub(crate) fn memory(&self) -> MoveTreeMemory {
+        MoveTreeMemory {
+            nodes: self.nodes.len(),
+            node_bytes: self.nodes.capacity() * mem::size_of::<MoveTreeNode>()
+                + self.position_keys.capacity() * mem::size_of::<u64>(),
+            text_entries: self.text.len(),
+            text_bytes: self.text.iter().map(|text| text.len()).sum::<usize>()
+                + self.text.capacity() * (mem::size_of::<Box<str>>() + 2 * mem::size_of::<usize>()),
+        }
+    }
+
+    /// Renders the move of `move_data` played in `state`, without a move number.
+    fn render_move_data<const N: usize, const STM: Color>(
+        &self,
+        move_data: &PgnMoveData,
+        state: &Position<N, STM>,
+        is_check: bool,
+        is_checkmate: bool,
+        config: PgnRenderingConfig,
+    ) -> String {
+        let move_ = move_data.move_;
+        let moved_piece = state.board().piece_at(move_.from());
+        if moved_piece == Piece::Null {
+            panic!("Invalid piece type");
+        }
+        let disambiguation_str = move_.san_disambiguation(state);
+
+        let annotation = move_data
+            .annotation
+            .filter(|_| config.include_annotations)
+            .map(|annotation| self.text(annotation));
+        move_data.render(
+            moved_piece,
+            disambiguation_str.as_str(),
+            is_check,
+            is_checkmate,
+            move_.is_capture(state),
+            annotation,
+            config.include_nags,
+            config.normalize_castling,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn write_white<const N: usize, W: io::Write>(
+        &self,
+        out: &mut MovetextWriter<W>,
+        id: MoveTreeNodeId,
+        state: Position<N, { Color::White }>,
+        last_continuations: &[MoveTreeNodeId],
+        include_variations: bool,
+        config: PgnRenderingConfig,
+        depth: u16,
+        _remind_fullmove: bool,
+    ) -> io::Result<()> {
+        let node = self.node(id);
+        let mut next_state_after_move: Option<Position<N, { Color::Black }>> = None;
+        if let Some(move_data) = &node.move_data {
+            let (next_position, is_check, is_checkmate) =
+                apply_white_move(state.clone(), move_data.move_);
+            let rendered = self.render_move_data(move_data, &state, is_check, is_checkmate, config);
+            // Every white move gets its number.
+            out.token(format_args!("{}. {}", state.get_fullmove(), rendered))?;
+            next_state_after_move = Some(next_position);
+        }
+
+        let comment = self.comment(id).filter(|_| config.include_comments);
+        if let Some(comment) = comment {
+            out.token(format_args!("{}", render_comment(comment, true)))?;
+        }
+
+        for &continuation in last_continuations {
+            out.open_variation()?;
+            self.write_white(
+                out,
+                continuation,
+                state.clone(),
+                &[],
+                include_variations,
+                config,
+                depth + 1,
+                true,
+            )?;
+            out.close_variation()?;
+        }
+
+        let mut continuations = self.continuations(id);
+        let Some(main_continuation) = continuations.next() else {
+            return Ok(());
+        };
+        let alternative_continuations: Vec<MoveTreeNodeId> = match include_variations {
+            true => continuations.collect(),
+            false => Vec::with_capacity(0),
+        };
+        match next_state_after_move {
+            // Black's move number is repeated after a comment or a variation.
+            Some(next_state) => self.write_black(
+                out,
+                main_continuation,
+                next_state,
+                &alternative_continuations,
+                include_variations,
+                config,
+                depth + 1,
+                comment.is_some() || !last_continuations.is_empty(),
+            ),
+            None => self.write_white(
+                out,
+                main_continuation,
+                state,
+                &alternative_continuations,
+                include_variations,
+                config,
+                depth + 1,
+                !last_continuations.is_empty(),
+            ),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn write_black<const N: usize, W: io::Write>(
+        &self,
+        out: &mut MovetextWriter<W>,
+        id: MoveTreeNodeId,
+        state: Position<N, { Color::Black }>,
+        last_continuations: &[MoveTreeNodeId],
+        include_variations: bool,
+        config: PgnRenderingConfig,
+        depth: u16,
+        remind_fullmove: bool,
+    ) -> io::Result<()> {
+        let node = self.node(id);
+        let mut next_state_after_move: Option<Position<N, { Color::White }>> = None;
+        if let Some(move_data) = &node.move_data {
+            let (next_position, is_check, is_checkmate) =
+                apply_black_move(state.clone(), move_data.move_);
+            let rendered = self.render_move_data(move_data, &state, is_check, is_checkmate, config);
+            match remind_fullmove {
+                true => out.token(format_args!("{}... {}", state.get_fullmove(), rendered))?,
+                false => out.token(format_args!("{}", rendered))?,
+            }
+            next_state_after_move = Some(next_position);
+        }
+
+        if config.include_comments
+            && let Some(comment) = self.comment(id)
+        {
+            out.token(format_args!("{}", render_comment(comment, true)))?;
+        }
+
+        for &continuation in last_continuations {
+            out.open_variation()?;
+            self.write_black(
+                out,
+                continuation,
+                state.clone(),
+                &[],
+                include_variations,
+                config,
+                depth + 1,
+                true,
+            )?;
+            out.close_variation()?;
+        }
+
+        let mut continuations = self.continuations(id);
+        let Some(main_continuation) = continuations.next() else {
+            return Ok(());
+        };
+        let alternative_continuations: Vec<MoveTreeNodeId> = match include_variations {
+            true => continuations.collect(),
+            false => Vec::with_capacity(0),
+        };
+        match next_state_after_move {
+            Some(next_state) => self.write_white(
+                out,
+                main_continuation,
+                next_state,
+                &alternative_continuations,
+                include_variations,
+                config,
+                depth + 1,
+                !last_continuations.is_empty(),
+            ),
+            // Only the root has no move; its first move is Black's and needs a number.
+            None => self.write_black(
+                out,
+                main_continuation,
+                state,
+                &alternative_continuations,
+                include_variations,
+                config,
+                depth + 1,
+                remind_fullmove,
+            ),
+        }
+    }
+
+    /// Writes the movetext below the root, starting from `state`.
+    pub(crate) fn render_to<const N: usize, W: io::Write>(
+        &self,
+        writer: W,
+        state: TypedPosition<N>,
+        include_variations: bool,
+        config: PgnRenderingConfig,
+    ) -> io::Result<()> {
+        let out = &mut MovetextWriter::new(writer);
+        match state {
+            TypedPosition::White(state) => self.write_white(
+                out,
+                MoveTree::ROOT,
+                state,
+                &[],
+                include_variations,
+                config,
+                0,
+                false,
+            ),
+            TypedPosition::Black(state) => self.write_black(
+                out,
+                MoveTree::ROOT,
+                state,
+                &[],
+                include_variations,
+                config,
+                0,
+                true,
+            ),
+        }
+    }
+}
+
+/// Writes space-separated movetext tokens, opening and closing variations without padding
+/// inside the parentheses.
+struct MovetextWriter<W: io::Write> {
+    writer: W,
+    /// Whether the next token starts the movetext or a variation, so needs no leading space.
+    at_sequence_start: bool,
+}
+
+impl<W: io::Write> MovetextWriter<W> {
+    fn new(writer: W) -> Self {
+        Self {
+            writer,
+            at_sequence_start: true,
+        }
+    }
+
+    fn token(&mut self, token: fmt::Arguments<'_>) -> io::Result<()> {
+        if !self.at_sequence_start {
+            self.writer.write_all(b" ")?;
+        }
+        self.at_sequence_start = false;
+        self.writer.write_fmt(token)
+    }
+
+    fn open_variation(&mut self) -> io::Result<()> {
+        self.token(format_args!("("))?;
+        self.at_sequence_start = true;
+        Ok(())
+    }
+
+    fn close_variation(&mut self) -> io::Result<()> {
+        self.at_sequence_start = false;
+        self.writer.write_all(b")")
+    }
+}
+
+fn apply_white_move<const N: usize>(
+    mut state: Position<N, { Color::White }>,
+    move_: Move,
+) -> (Position<N, { Color::Black }>, bool, bool) {
+    state.make_move(move_);
+    let next = state.rebrand_stm::<{ Color::Black }>();
+    let is_check = next.is_current_side_in_check();
+    let is_checkmate = is_check && !next.has_legal_moves();
+    (next, is_check, is_checkmate)
+}
+
+fn apply_black_move<const N: usize>(
+    mut state: Position<N, { Color::Black }>,
+    move_: Move,
+) -> (Position<N, { Color::White }>, bool, bool) {
+    state.make_move(move_);
+    let next = state.rebrand_stm::<{ Color::White }>();
+    let is_check = next.is_current_side_in_check();
+    let is_checkmate = is_check && !next.has_legal_moves();
+    (next, is_check, is_checkmate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{r#move::MoveList, pgn::PgnParser};
+
+    fn move_data(move_: Move) -> PgnMoveData {
+        PgnMoveData {
+            move_,
+            annotation: None,
+            nag: None,
+            written_with_zeros: false,
+        }
+    }
+
+    #[test]
+    fn test_continuations_and_main_line() {
+        let position = Position::<1, { Color::White }>::initial();
+        let mut moves = MoveList::new();
+        position.generate_moves(&mut moves);
+        let [first, second, third, ..] = moves.as_slice() else {
+            unreachable!()
+        };
+        let (first, second, third) = (*first, *second, *third);
+
+        let mut tree = MoveTree::new();
+        let main = tree.add_continuation(MoveTree::ROOT, move_data(first));
+        let alternative = tree.add_continuation(MoveTree::ROOT, move_data(second));
+        tree.add_continuation(MoveTree::ROOT, move_data(third));
+        assert_eq!(
+            tree.continuations(MoveTree::ROOT).collect::<Vec<_>>()[..2],
+            [main, alternative]
+        );
+        assert_eq!(tree.main_line(MoveTree::ROOT), [first]);
+    }
+
+    #[test]
+    fn test_parsed_game_is_send_and_reports_memory() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let mut parser = PgnParser::<8>::new("{Start} 1. e4! e5 (1... c5!) 2. Nf3! *");
+        parser.parse().unwrap();
+        let object = parser.constructed_object;
+        assert_send_sync(&object);
+
+        let memory = object.move_tree_memory();
+        assert_eq!(memory.nodes, 5);
+        // "Start" and a single shared "!".
+        assert_eq!(memory.text_entries, 2);
+        assert!(memory.total_bytes() >= memory.nodes * mem::size_of::<MoveTreeNode>());
+    }
+
+    #[test]
+    fn test_node_ids_round_trip_through_index() {
+        let mut parser = PgnParser::<8>::new("1. e4 e5 (1... c5) 2. Nf3 *");
+        parser.parse().unwrap();
+        let object = parser.constructed_object;
+
+        assert_eq!(object.root_node().index(), 0);
+        for node in object.tree.node_ids() {
+            assert_eq!(object.node_at(node.index()), Some(node));
+        }
+        assert_eq!(object.node_at(object.move_tree_memory().nodes), None);
+    }
+
+    #[test]
+    fn test_comments_are_interned() {
+        let mut tree = MoveTree::new();
+        tree.append_comment(MoveTree::ROOT, "good");
+        tree.append_comment(MoveTree::ROOT, "move");
+        assert_eq!(tree.comment(MoveTree::ROOT), Some("good move"));
+        assert_eq!(tree.intern("good"), tree.intern("good"));
+        assert_ne!(tree.intern("good"), tree.intern("move"));
+    }
+}
@@ -0,0 +1,103 @@
+//! Finding move tree nodes by typed path or by SAN move sequence, the way lines are referred to
+//! in chess literature.
+
+use crate::{
+    TypedPosition,
+    pgn::{
+        move_tree::MoveTreeNodeId,
+        object::{PgnObject, play_move},
+        san::parse_san,
+    },
+};
+
+/// One step of a path for [`PgnObject::follow`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PgnPathStep {
+    /// Plays this many moves along the current line, always taking the main continuation.
+    MainLine(usize),
+    /// Plays one move, entering this variation of the next move; `1` is the first variation and
+    /// `0` the main continuation itself, as in [`PgnBranch::variation`].
+    ///
+    /// [`PgnBranch::variation`]: crate::pgn::PgnBranch::variation
+    Variation(usize),
+}
+
+impl<const N: usize> PgnObject<N> {
+    /// Returns the node reached by following `path` from the root, or `None` if it leads past
+    /// the end of a line or into a variation that does not exist.
+    ///
+    /// For `1. e4 e5 (1... c5 2. Nf3) 2. Nf3`, `[MainLine(1), Variation(1), MainLine(1)]`
+    /// reaches `2. Nf3` in the Sicilian. An empty path gives the root.
+    pub fn follow(&self, path: &[PgnPathStep]) -> Option<MoveTreeNodeId> {
+        let mut node = self.root_node();
+        for &step in path {
+            node = match step {
+                PgnPathStep::MainLine(moves) => {
+                    (0..moves).try_fold(node, |node, _| self.continuations(node).next())?
+                }
+                PgnPathStep::Variation(variation) => self.continuations(node).nth(variation)?,
+            };
+        }
+        Some(node)
+    }
+
+    /// Returns the node reached by playing `moves`, given in SAN, from the start position along
+    /// whichever lines contain them, or `None` if no line does.
+    ///
+    /// Check markers and annotations in `moves` are ignored, and the main continuation is
+    /// preferred where several continuations match. `["e4", "c5"]` reaches `1... c5` whether it
+    /// is the main line or a variation.
+    pub fn follow_san(&self, moves: &[&str]) -> Option<MoveTreeNodeId> {
+        let mut node = self.root_node();
+        let mut position = self.start_position::<2>();
+        for text in moves {
+            let san = parse_san(text).ok()?;
+            let board = match &position {
+                TypedPosition::White(p) => p.board(),
+                TypedPosition::Black(p) => p.board(),
+            };
+            let (next, move_) = self.continuations(node).find_map(|continuation| {
+                let move_ = self.node_move(continuation)?;
+                san.pattern
+                    .matches_on_board(move_, board)
+                    .then_some((continuation, move_))
+            })?;
+            position = play_move(&position, move_);
+            node = next;
+        }
+        Some(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PgnPathStep::*;
+    use crate::pgn::PgnParser;
+
+    #[test]
+    fn test_follow_path_and_san() {
+        let mut parser =
+            PgnParser::<8>::new("1. e4 e5 (1... c5 2. Nf3 (2. Nc3) 2... d6) 2. Nf3 Nc6 *");
+        parser.parse().unwrap();
+        let game = &parser.constructed_object;
+        let main = game.main_line_nodes();
+
+        assert_eq!(game.follow(&[]), Some(game.root_node()));
+        assert_eq!(game.follow(&[MainLine(3)]), Some(main[2]));
+        assert_eq!(game.follow(&[MainLine(1), Variation(0)]), Some(main[1]));
+        assert_eq!(game.follow(&[MainLine(5)]), None);
+        assert_eq!(game.follow(&[MainLine(1), Variation(2)]), None);
+
+        let nc3 = game.follow(&[MainLine(1), Variation(1), Variation(1)]);
+        assert_eq!(nc3, game.follow_san(&["e4", "c5", "Nc3"]));
+        assert_eq!(game.node_move(nc3.unwrap()).unwrap().to_string(), "b1c3");
+
+        let d6 = game.follow(&[MainLine(1), Variation(1), MainLine(2)]);
+        assert_eq!(d6, game.follow_san(&["e4", "c5", "Nf3", "d6"]));
+        assert!(d6.is_some());
+
+        assert_eq!(game.follow_san(&["e4", "e5", "Nf3+!"]), Some(main[2]));
+        assert_eq!(game.follow_san(&["e4", "d5"]), None);
+        assert_eq!(game.follow_san(&["e4", "not a move"]), None);
+    }
+}
@@ -1,12 +1,21 @@
 //! Parsed PGN game object with tag pairs and move tree.
 
-use std::{cell::RefCell, rc::Rc};
+use std::io;
 
 use indexmap::IndexMap;
 
 use crate::{
     Color,
-    pgn::{move_tree_node::MoveTreeNode, rendering_config::PgnRenderingConfig},
+    TypedPosition,
+    logic::{fen::INITIAL_FEN, game_state::GameResult},
+    r#move::Move,
+    pgn::{
+        lossless::{PgnSource, PgnSourceToken},
+        move_tree::{MoveTree, MoveTreeMemory, MoveTreeNodeId},
+        rendering_config::PgnRenderingConfig,
+        tag_values::{PgnDate, PgnRound, parse_elo},
+        token_types::escape_tag_value,
+    },
     position::Position,
 };
 
@@ -14,10 +23,16 @@ use crate::{
 ///
 /// `N` is the position stack capacity that must fit the longest variation
 /// in the parsed game. Use [`PgnParser`](crate::pgn::PgnParser) to construct.
+///
+/// The move tree is stored as an index-linked arena, so a `PgnObject` is `Send` and `Sync` and
+/// can be handed between threads.
+#[derive(Clone)]
 pub struct PgnObject<const N: usize> {
-    pub(crate) tree_root: Rc<RefCell<MoveTreeNode<N, { Color::White }, { Color::Black }>>>,
+    pub(crate) tree: MoveTree,
     /// PGN tag pairs (e.g., `[Event "World Championship"]`).
     pub tags: IndexMap<String, String>,
+    pub(crate) result: GameResult,
+    pub(crate) source: Option<PgnSource>,
 }
 
 impl<const N: usize> Default for PgnObject<N> {
@@ -31,36 +46,445 @@ impl<const N: usize> PgnObject<N> {
     pub fn new() -> PgnObject<N> {
         PgnObject {
             tags: IndexMap::new(),
-            tree_root: Rc::new(RefCell::new(MoveTreeNode::<
-                N,
-                { Color::White },
-                { Color::Black },
-            >::new_root(None))),
+            result: GameResult::Ongoing,
+            source: None,
+            tree: MoveTree::new(),
         }
     }
 
-    /// Inserts a tag pair (overwrites existing key).
+    /// Inserts a tag pair.
+    ///
+    /// An existing tag with the same name, compared ASCII case-insensitively, is overwritten in
+    /// place and keeps its original spelling and position; otherwise the tag is appended.
     pub fn add_tag(&mut self, key: String, value: String) {
-        self.tags.insert(key, value);
+        self.source = None;
+        match self.tag_index(&key) {
+            Some(index) => self.tags[index] = value,
+            None => {
+                self.tags.insert(key, value);
+            }
+        }
+    }
+
+    /// Returns the value of the tag called `name`, compared ASCII case-insensitively.
+    ///
+    /// Values are returned as written, with any `\"` and `\\` escapes intact.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tag_index(name).map(|index| self.tags[index].as_str())
+    }
+
+    /// Iterates over all tag pairs in input order, including non-standard ones such as
+    /// `WhiteElo` or `LichessURL`.
+    pub fn tag_pairs(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.tags
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Removes the tag called `name`, compared ASCII case-insensitively, keeping the order of
+    /// the others.
+    pub fn remove_tag(&mut self, name: &str) -> Option<String> {
+        let index = self.tag_index(name)?;
+        self.source = None;
+        self.tags.shift_remove_index(index).map(|(_, value)| value)
+    }
+
+    /// Returns the `WhiteElo` rating, if present and numeric.
+    pub fn white_elo(&self) -> Option<u16> {
+        self.tag("WhiteElo").and_then(parse_elo)
+    }
+
+    /// Returns the `BlackElo` rating, if present and numeric.
+    pub fn black_elo(&self) -> Option<u16> {
+        self.tag("BlackElo").and_then(parse_elo)
+    }
+
+    /// Returns the `Date` tag; a missing or malformed tag yields an all-unknown date.
+    pub fn date(&self) -> PgnDate {
+        self.tag("Date")
+            .and_then(PgnDate::from_tag_value)
+            .unwrap_or_default()
+    }
+
+    /// Returns the `Round` tag; a missing tag yields [`PgnRound::Unknown`].
+    pub fn round(&self) -> PgnRound {
+        self.tag("Round")
+            .map(PgnRound::from_tag_value)
+            .unwrap_or_default()
+    }
+
+    /// Index of the tag called `name`, preferring an exact match over a case-insensitive one.
+    pub(crate) fn tag_index(&self, name: &str) -> Option<usize> {
+        self.tags.get_index_of(name).or_else(|| {
+            self.tags
+                .keys()
+                .position(|key| key.eq_ignore_ascii_case(name))
+        })
+    }
+
+    /// Returns the game result from the game-terminating token, or from the `Result` tag if the
+    /// movetext had none.
+    pub fn result(&self) -> GameResult {
+        self.result
+    }
+
+    /// Sets the game result and the matching `Result` tag.
+    pub fn set_result(&mut self, result: GameResult) {
+        self.source = None;
+        self.result = result;
+        self.add_tag("Result".to_string(), result.to_string());
+    }
+
+    /// Returns the comment before the first move, if any.
+    pub fn game_comment(&self) -> Option<String> {
+        self.tree.comment(MoveTree::ROOT).map(str::to_string)
+    }
+
+    /// Sets or clears the comment before the first move.
+    pub fn set_game_comment(&mut self, comment: Option<String>) {
+        self.source = None;
+        self.tree.set_comment(MoveTree::ROOT, comment.as_deref());
+    }
+
+    /// Returns the position before the first move: the `FEN` tag's position, unless `SetUp` is
+    /// `0`, or the standard initial position.
+    ///
+    /// A `FEN` tag that does not parse is ignored; [`PgnParser`](crate::pgn::PgnParser) rejects
+    /// such games, so this only happens after editing the tags.
+    pub fn start_position<const M: usize>(&self) -> TypedPosition<M> {
+        self.tag("FEN")
+            .filter(|_| self.tag("SetUp") != Some("0"))
+            .and_then(|fen| TypedPosition::from_fen(fen).ok())
+            .unwrap_or_else(|| TypedPosition::White(Position::<M, { Color::White }>::initial()))
+    }
+
+    /// Returns the moves of the main line, ignoring variations and annotations.
+    pub fn main_line(&self) -> Vec<Move> {
+        self.tree.main_line(MoveTree::ROOT)
+    }
+
+    /// Returns the root of the move tree, which stands for [`Self::start_position`].
+    pub fn root_node(&self) -> MoveTreeNodeId {
+        MoveTree::ROOT
+    }
+
+    /// Returns the node whose [`MoveTreeNodeId::index`] is `index`, or `None` if this game has
+    /// no such node.
+    pub fn node_at(&self, index: usize) -> Option<MoveTreeNodeId> {
+        self.tree.node_id(index)
+    }
+
+    /// Returns the nodes of the main line in order, one per ply.
+    pub fn main_line_nodes(&self) -> Vec<MoveTreeNodeId> {
+        self.tree.main_line_nodes(MoveTree::ROOT)
+    }
+
+    /// Returns the moves that may follow `node`, main continuation first and then the
+    /// variations in the order they were written.
+    pub fn continuations(&self, node: MoveTreeNodeId) -> impl Iterator<Item = MoveTreeNodeId> {
+        self.tree.continuations(node)
+    }
+
+    /// Returns the move leading to `node`, or `None` for the root.
+    pub fn node_move(&self, node: MoveTreeNodeId) -> Option<Move> {
+        self.tree.move_at(node)
+    }
+
+    /// Returns the comment after the move of `node`, or before the first move for the root.
+    pub fn node_comment(&self, node: MoveTreeNodeId) -> Option<&str> {
+        self.tree.comment(node)
+    }
+
+    /// Sets or clears the comment after the move of `node`.
+    ///
+    /// For the root this is the same as [`Self::set_game_comment`].
+    pub fn set_node_comment(&mut self, node: MoveTreeNodeId, comment: Option<String>) {
+        self.source = None;
+        self.tree.set_comment(node, comment.as_deref());
+    }
+
+    /// Returns the suffix annotation written after the move of `node`, such as `!?`.
+    pub fn node_annotation(&self, node: MoveTreeNodeId) -> Option<&str> {
+        self.tree.annotation(node)
+    }
+
+    /// Sets or clears the suffix annotation of `node`, rendered directly after the move.
+    ///
+    /// The text is written as given, so it should be one of `!`, `?`, `!!`, `??`, `!?` or `?!`
+    /// for the output to parse again. The root has no move and is left unchanged.
+    pub fn set_node_annotation(&mut self, node: MoveTreeNodeId, annotation: Option<String>) {
+        self.source = None;
+        self.tree.set_annotation(node, annotation.as_deref());
+    }
+
+    /// Returns the numeric annotation glyph of `node`, such as `14` for `$14`.
+    pub fn node_nag(&self, node: MoveTreeNodeId) -> Option<u8> {
+        self.tree.nag(node)
+    }
+
+    /// Sets or clears the numeric annotation glyph of `node`.
+    ///
+    /// The root has no move and is left unchanged.
+    pub fn set_node_nag(&mut self, node: MoveTreeNodeId, nag: Option<u8>) {
+        self.source = None;
+        self.tree.set_nag(node, nag);
+    }
+
+    /// Returns every node, main line or variation, after which the position has Zobrist key
+    /// `zobrist` (as in [`PositionContext::zobrist_hash()`]), in the order the moves were added.
+    /// The root is included when the start position matches.
+    ///
+    /// Uses the keys recorded by [`PgnParser::with_position_keys`] when every node has one, and
+    /// otherwise replays the whole tree.
+    ///
+    /// [`PositionContext::zobrist_hash()`]: crate::types::PositionContext::zobrist_hash()
+    /// [`PgnParser::with_position_keys`]: crate::pgn::PgnParser::with_position_keys
+    pub fn find_position(&self, zobrist: u64) -> Vec<MoveTreeNodeId> {
+        if let Some(keys) = self.tree.position_keys() {
+            return self
+                .tree
+                .node_ids()
+                .zip(keys)
+                .filter(|&(_, &key)| key == zobrist)
+                .map(|(id, _)| id)
+                .collect();
+        }
+
+        let mut found = Vec::new();
+        let mut pending = vec![(MoveTree::ROOT, self.start_position::<N>())];
+        while let Some((id, position)) = pending.pop() {
+            if zobrist_hash_of(&position) == zobrist {
+                found.push(id);
+            }
+            for continuation in self.tree.continuations(id) {
+                let move_ = self.tree.move_at(continuation).expect("not the root");
+                pending.push((continuation, play_move(&position, move_)));
+            }
+        }
+        found.sort();
+        found
+    }
+
+    /// Cuts plies `from_ply..to_ply` of the main line out into a standalone game.
+    ///
+    /// The new game starts from the position after `from_ply` plies, recorded in `SetUp` and
+    /// `FEN` tags unless that is the standard initial position. Variations branching off the
+    /// kept plies come along; the rest of the game does not. Other tags are copied, and the
+    /// result is kept only if the slice runs to the end of the main line. Ply numbers past the
+    /// end of the main line are clamped.
+    pub fn slice(&self, from_ply: usize, to_ply: usize) -> PgnObject<N> {
+        let main_line = self.main_line_nodes();
+        let to_ply = to_ply.min(main_line.len());
+        let from_ply = from_ply.min(to_ply);
+        let mut sliced = self.derived_game(&main_line[..from_ply], to_ply == main_line.len());
+        let mut source_parent = match from_ply {
+            0 => {
+                sliced
+                    .tree
+                    .set_comment(MoveTree::ROOT, self.tree.comment(MoveTree::ROOT));
+                MoveTree::ROOT
+            }
+            ply => main_line[ply - 1],
+        };
+        let mut parent = MoveTree::ROOT;
+        for &main_node in &main_line[from_ply..to_ply] {
+            let mut next_parent = parent;
+            for continuation in self.tree.continuations(source_parent) {
+                if continuation == main_node {
+                    next_parent = sliced.tree.copy_node_from(&self.tree, continuation, parent);
+                } else {
+                    sliced
+                        .tree
+                        .copy_subtree_from(&self.tree, continuation, parent);
+                }
+            }
+            source_parent = main_node;
+            parent = next_parent;
+        }
+        sliced
+    }
+
+    /// Extracts the line starting with the move of `node` into a standalone game.
+    ///
+    /// The new game starts from the position before that move, recorded in `SetUp` and `FEN`
+    /// tags unless that is the standard initial position, and holds `node` with everything that
+    /// follows it, including nested variations. Other tags are copied, and the result is kept
+    /// only if `node` lies on the main line. Returns `None` if `node` is the root or does not
+    /// belong to this game.
+    pub fn extract_variation(&self, node: MoveTreeNodeId) -> Option<PgnObject<N>> {
+        let path = self.tree.path_to(node)?;
+        let (_, before) = path.split_last()?;
+        let main_line = self.main_line_nodes();
+        let on_main_line = path.iter().all(|id| main_line.contains(id));
+
+        let mut extracted = self.derived_game(before, on_main_line);
+        extracted
+            .tree
+            .copy_subtree_from(&self.tree, node, MoveTree::ROOT);
+        Some(extracted)
+    }
+
+    /// Creates a game with no moves that starts after playing the moves of `path` from
+    /// [`Self::start_position`], with this game's tags and, if `keep_result` is set, its result.
+    fn derived_game(&self, path: &[MoveTreeNodeId], keep_result: bool) -> PgnObject<N> {
+        let mut position = self.start_position::<N>();
+        for &id in path {
+            let move_ = self.tree.move_at(id).expect("only the root has no move");
+            position = play_move(&position, move_);
+        }
+
+        let mut derived = PgnObject::new();
+        derived.tags = self.tags.clone();
+        derived.set_start_position_tags(&position);
+        derived.set_result(match keep_result {
+            true => self.result,
+            false => GameResult::Ongoing,
+        });
+        derived
+    }
+
+    /// Replaces the `SetUp` and `FEN` tags with ones recording `position`, or removes them if it
+    /// is the standard initial position.
+    pub(crate) fn set_start_position_tags<const M: usize>(&mut self, position: &TypedPosition<M>) {
+        self.remove_tag("SetUp");
+        self.remove_tag("FEN");
+        let fen = position.to_fen();
+        if fen != INITIAL_FEN {
+            self.add_tag("SetUp".to_string(), "1".to_string());
+            self.add_tag("FEN".to_string(), fen);
+        }
+    }
+
+    /// Appends `move_` to the end of the main line.
+    ///
+    /// The move must be legal in the position at the end of the main line.
+    pub fn push_main_line_move(&mut self, move_: Move) {
+        self.source = None;
+        self.tree.push_main_line_move(MoveTree::ROOT, move_);
+    }
+
+    /// Reports the memory held by the move tree, including interned comments and annotations.
+    pub fn move_tree_memory(&self) -> MoveTreeMemory {
+        self.tree.memory()
+    }
+
+    /// Returns the original tokens of a game parsed in lossless mode, unless it has been edited
+    /// since.
+    ///
+    /// See [`PgnParser::with_lossless`](crate::pgn::PgnParser::with_lossless).
+    pub fn source_tokens(&self) -> Option<&[PgnSourceToken]> {
+        self.unedited_source()
+            .map(|source| source.tokens.as_slice())
+    }
+
+    pub(crate) fn set_source(&mut self, source: PgnSource) {
+        self.source = Some(source);
+    }
+
+    fn unedited_source(&self) -> Option<&PgnSource> {
+        self.source
+            .as_ref()
+            .filter(|source| source.tags == self.tags)
+    }
+
+    /// Returns the source that [`Self::render_to`] reproduces instead of rendering the tree, if
+    /// any.
+    fn reproduced_source(
+        &self,
+        include_variations: bool,
+        config: PgnRenderingConfig,
+    ) -> Option<&PgnSource> {
+        let written_config = PgnRenderingConfig {
+            normalize_castling: false,
+            ..config
+        };
+        (include_variations && written_config == PgnRenderingConfig::all_markings())
+            .then(|| self.unedited_source())
+            .flatten()
+    }
+
+    /// Like [`Self::render_to`], but ends the movetext with the result token, as a game in a
+    /// database must be. A reproduced lossless source already ends with its own.
+    pub(crate) fn write_game_to<W: io::Write>(
+        &self,
+        mut writer: W,
+        include_variations: bool,
+        config: PgnRenderingConfig,
+    ) -> io::Result<()> {
+        let has_result = self.reproduced_source(include_variations, config).is_some();
+        self.render_to(&mut writer, include_variations, config)?;
+        match has_result {
+            true => Ok(()),
+            false => write!(writer, " {}", self.result.as_pgn_str()),
+        }
     }
 
     /// Renders the game back to PGN format.
     ///
     /// Set `include_variations` to `false` for main line only.
     /// `N` must match the position stack capacity used during parsing.
+    ///
+    /// A game parsed in lossless mode and not edited since is reproduced exactly as written when
+    /// rendering with variations and [`PgnRenderingConfig::all_markings`]; with
+    /// [`PgnRenderingConfig::normalize_castling`] also set, only castling tokens are rewritten.
     pub fn render(&self, include_variations: bool, config: PgnRenderingConfig) -> String {
-        let mut result = String::new();
+        let mut rendered = Vec::new();
+        self.render_to(&mut rendered, include_variations, config)
+            .expect("writing to a Vec cannot fail");
+        String::from_utf8(rendered).expect("PGN text is UTF-8")
+    }
+
+    /// Like [`Self::render`], but writes the game to `writer` as it goes instead of building a
+    /// `String`, so memory use does not grow with the size of the game.
+    ///
+    /// Wrap unbuffered writers such as files in an [`io::BufWriter`].
+    pub fn render_to<W: io::Write>(
+        &self,
+        mut writer: W,
+        include_variations: bool,
+        config: PgnRenderingConfig,
+    ) -> io::Result<()> {
+        if let Some(source) = self.reproduced_source(include_variations, config) {
+            return match config.normalize_castling {
+                true => source.write_normalized_castling(writer),
+                false => write!(writer, "{source}"),
+            };
+        }
         for (key, value) in self.tags.iter() {
-            result.push_str(&format!("[{} \"{}\"]\n", key, value));
+            writeln!(writer, "[{} \"{}\"]", key, escape_tag_value(value))?;
         }
-        result.push_str(&self.tree_root.borrow().render(
-            Position::<N, { Color::White }>::initial(),
-            &[],
+        self.tree.render_to(
+            writer,
+            self.start_position::<N>(),
             include_variations,
             config,
-            0,
-            false,
-        ));
-        result
+        )
+    }
+}
+
+/// Returns the Zobrist hash of `position`.
+pub(crate) fn zobrist_hash_of<const N: usize>(position: &TypedPosition<N>) -> u64 {
+    match position {
+        TypedPosition::White(p) => p.context().zobrist_hash(),
+        TypedPosition::Black(p) => p.context().zobrist_hash(),
+    }
+}
+
+/// Returns the position after playing `move_`, keeping only the current context.
+pub(crate) fn play_move<const N: usize>(
+    position: &TypedPosition<N>,
+    move_: Move,
+) -> TypedPosition<N> {
+    match position {
+        TypedPosition::White(p) => {
+            let mut p = p.clone_current::<N>();
+            p.make_move(move_);
+            TypedPosition::Black(p.rebrand_stm())
+        }
+        TypedPosition::Black(p) => {
+            let mut p = p.clone_current::<N>();
+            p.make_move(move_);
+            TypedPosition::White(p.rebrand_stm())
+        }
     }
 }
@@ -0,0 +1,220 @@
+//! Explorer-style aggregation of many games into a tree of positions.
+
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+
+use crate::{Color, TypedPosition, r#move::Move, pgn::object::PgnObject, position::Position};
+
+/// Aggregated statistics for one move played from a position.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpeningMoveStats {
+    /// Number of games in which the move was played.
+    pub count: u32,
+    /// Games won by White (`1-0`).
+    pub white_wins: u32,
+    /// Drawn games (`1/2-1/2`).
+    pub draws: u32,
+    /// Games won by Black (`0-1`).
+    pub black_wins: u32,
+    /// Sum of the mover's rating (`WhiteElo` / `BlackElo`) over games that had one.
+    pub rating_sum: u64,
+    /// Number of games that contributed to [`Self::rating_sum`].
+    pub rated_count: u32,
+}
+
+impl OpeningMoveStats {
+    /// Average rating of the players who chose this move, if any game had a rating tag.
+    pub fn average_rating(&self) -> Option<f64> {
+        (self.rated_count > 0).then(|| self.rating_sum as f64 / self.rated_count as f64)
+    }
+
+    /// Games with an unknown or unfinished result (`*` or no `Result` tag).
+    pub fn unknown_results(&self) -> u32 {
+        self.count - self.white_wins - self.draws - self.black_wins
+    }
+}
+
+/// Moves played from one position, in first-seen order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpeningTreeNode {
+    /// Per-move statistics.
+    pub moves: IndexMap<Move, OpeningMoveStats>,
+}
+
+impl OpeningTreeNode {
+    /// Total number of games that reached this position and continued.
+    pub fn count(&self) -> u32 {
+        self.moves.values().map(|stats| stats.count).sum()
+    }
+}
+
+/// Positions reached by a set of games, with statistics for every move played from them.
+///
+/// Transpositions share a node, except that the en-passant file after a double pawn push is part
+/// of the key even when no capture is possible. Only main lines are counted, up to `N - 1` plies per game;
+/// `N` is the position stack capacity used to replay games.
+pub struct OpeningTree<const N: usize> {
+    nodes: HashMap<u64, OpeningTreeNode>,
+    num_games: usize,
+}
+
+impl<const N: usize> Default for OpeningTree<N> {
+    fn default() -> Self {
+        OpeningTree::new()
+    }
+}
+
+impl<const N: usize> OpeningTree<N> {
+    /// Creates an empty tree.
+    pub fn new() -> OpeningTree<N> {
+        OpeningTree {
+            nodes: HashMap::new(),
+            num_games: 0,
+        }
+    }
+
+    /// Builds a tree from `games`.
+    pub fn from_games<'a, const M: usize>(
+        games: impl IntoIterator<Item = &'a PgnObject<M>>,
+    ) -> Self {
+        let mut tree = OpeningTree::new();
+        for game in games {
+            tree.add_game(game);
+        }
+        tree
+    }
+
+    /// Number of games added.
+    pub fn num_games(&self) -> usize {
+        self.num_games
+    }
+
+    /// Number of distinct positions with at least one recorded move.
+    pub fn num_positions(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Merges the main line of `game` into the tree.
+    pub fn add_game<const M: usize>(&mut self, game: &PgnObject<M>) {
+        self.num_games += 1;
+
+        let result = game.tags.get("Result").map(|value| value.trim());
+        let rating = |tag: &str| {
+            game.tags
+                .get(tag)
+                .and_then(|value| value.trim().parse::<u32>().ok())
+        };
+        let white_rating = rating("WhiteElo");
+        let black_rating = rating("BlackElo");
+
+        let mut position = game.start_position::<N>();
+        for move_ in game.main_line().into_iter().take(N - 1) {
+            let (key, mover_rating) = match &position {
+                TypedPosition::White(p) => (p.context().zobrist_hash(), white_rating),
+                TypedPosition::Black(p) => (p.context().zobrist_hash(), black_rating),
+            };
+
+            let stats = self
+                .nodes
+                .entry(key)
+                .or_default()
+                .moves
+                .entry(move_)
+                .or_default();
+            stats.count += 1;
+            match result {
+                Some("1-0") => stats.white_wins += 1,
+                Some("1/2-1/2") => stats.draws += 1,
+                Some("0-1") => stats.black_wins += 1,
+                _ => {}
+            }
+            if let Some(rating) = mover_rating {
+                stats.rating_sum += rating as u64;
+                stats.rated_count += 1;
+            }
+
+            position = match position {
+                TypedPosition::White(mut p) => {
+                    p.make_move(move_);
+                    TypedPosition::Black(p.rebrand_stm())
+                }
+                TypedPosition::Black(mut p) => {
+                    p.make_move(move_);
+                    TypedPosition::White(p.rebrand_stm())
+                }
+            };
+        }
+    }
+
+    /// Returns the recorded moves from `position`, if any game continued from it.
+    pub fn node<const M: usize, const STM: Color>(
+        &self,
+        position: &Position<M, STM>,
+    ) -> Option<&OpeningTreeNode> {
+        self.nodes.get(&position.context().zobrist_hash())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Square, pgn::PgnParser};
+
+    const N: usize = 16;
+
+    fn parse(pgn: &str) -> PgnObject<N> {
+        let mut parser = PgnParser::<N>::new(pgn);
+        parser.parse().expect("valid PGN");
+        parser.constructed_object
+    }
+
+    #[test]
+    fn test_opening_tree_statistics() {
+        let games = [
+            parse("[Result \"1-0\"] [WhiteElo \"2000\"] 1. e4 e5 2. Nf3 1-0"),
+            parse("[Result \"0-1\"] [WhiteElo \"2200\"] 1. e4 c5 0-1"),
+            parse("[Result \"1/2-1/2\"] 1. d4 d5 1/2-1/2"),
+        ];
+        let tree = OpeningTree::<N>::from_games(&games);
+        assert_eq!(tree.num_games(), 3);
+
+        let root = tree
+            .node(&Position::<N, { Color::White }>::initial())
+            .unwrap();
+        assert_eq!(root.count(), 3);
+        assert_eq!(root.moves.len(), 2);
+
+        let (&e4, stats) = root.moves.first().unwrap();
+        assert_eq!(e4.to(), Square::E4);
+        assert_eq!(stats.count, 2);
+        assert_eq!((stats.white_wins, stats.draws, stats.black_wins), (1, 0, 1));
+        assert_eq!(stats.average_rating(), Some(2100.0));
+
+        let (_, d4_stats) = root.moves.get_index(1).unwrap();
+        assert_eq!(d4_stats.draws, 1);
+        assert_eq!(d4_stats.average_rating(), None);
+    }
+
+    #[test]
+    fn test_opening_tree_merges_transpositions() {
+        let games = [
+            parse("1. e4 e5 2. Nf3 Nc6 3. Bc4 Nf6 *"),
+            parse("1. e4 e5 2. Bc4 Nc6 3. Nf3 Bc5 *"),
+        ];
+        let tree = OpeningTree::<N>::from_games(&games);
+
+        let position = Position::<N, { Color::Black }>::from_fen(
+            "r1bqkbnr/pppp1ppp/2n5/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 3 3",
+        )
+        .unwrap();
+        let node = tree.node(&position).unwrap();
+        assert_eq!(node.count(), 2);
+        assert_eq!(node.moves.len(), 2);
+        assert!(
+            node.moves
+                .values()
+                .all(|stats| stats.unknown_results() == 1)
+        );
+    }
+}
@@ -4,12 +4,16 @@ use logos::{Lexer, Logos};
 
 use crate::{
     Color,
+    TypedPosition,
+    logic::game_state::GameResult,
     r#move::MoveList,
     pgn::{
         buffered_position_brancher::PgnBufferedPositionBrancher,
         buffered_position_context::PgnBufferedPositionContextDyn,
         error::PgnError,
+        lossless::{PgnSource, PgnSourceToken},
         move_data::PgnMoveData,
+        move_tree::MoveTree,
         object::PgnObject,
         parsing_state::PgnParsingState,
         token::PgnToken,
@@ -25,11 +29,117 @@ use crate::{
     position::Position,
 };
 
+/// Default for [`PgnParser::with_max_variation_depth`].
+pub const DEFAULT_MAX_VARIATION_DEPTH: usize = 256;
+
+/// What [`PgnParser`] does when a game repeats a tag name (compared ASCII case-insensitively).
+///
+/// See [`PgnParser::with_duplicate_tags`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PgnDuplicateTagPolicy {
+    /// The later value replaces the earlier one, which keeps its position and spelling.
+    #[default]
+    KeepLast,
+    /// The later value is ignored.
+    KeepFirst,
+    /// Parsing fails with [`PgnError::DuplicateTag`].
+    Reject,
+}
+
+/// What [`PgnParser`] does with a suffix annotation other than the standard `!`, `?`, `!!`,
+/// `??`, `!?` and `?!`, such as `!!!` or `?!?`.
+///
+/// See [`PgnParser::with_annotations`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PgnAnnotationPolicy {
+    /// The annotation is kept as written.
+    #[default]
+    Keep,
+    /// The annotation is replaced with the nearest standard one: repeats of one mark become
+    /// `!!` or `??`, and mixed marks become `!?` or `?!` after their first mark.
+    Normalize,
+    /// Parsing fails with [`PgnError::NonstandardAnnotation`].
+    Reject,
+}
+
+/// Suffix annotations defined by the PGN standard.
+const STANDARD_ANNOTATIONS: [&str; 6] = ["!", "?", "!!", "??", "!?", "?!"];
+
+/// Returns the standard annotation nearest to `annotation`, a non-empty run of `!` and `?`.
+fn nearest_standard_annotation(annotation: &str) -> &'static str {
+    let good = annotation.starts_with('!');
+    let mixed = annotation.contains('!') && annotation.contains('?');
+    match (good, mixed) {
+        (true, false) => "!!",
+        (false, false) => "??",
+        (true, true) => "!?",
+        (false, true) => "?!",
+    }
+}
+
+/// Resource limits for untrusted input; see [`PgnParser::with_limits`].
+///
+/// Each limit is `None` (unlimited) by default. Exceeding one fails the parse with
+/// [`PgnError::LimitExceeded`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PgnParserLimits {
+    /// Most moves in the game, counting the main line and all variations.
+    pub max_plies: Option<usize>,
+    /// Most tag pairs, counting repeated names.
+    pub max_tags: Option<usize>,
+    /// Longest comment, in bytes.
+    pub max_comment_len: Option<usize>,
+    /// Most variations in the game, nested or not.
+    pub max_variations: Option<usize>,
+}
+
+/// Which [`PgnParserLimits`] entry was exceeded, with its configured value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PgnParserLimit {
+    /// [`PgnParserLimits::max_plies`].
+    Plies(usize),
+    /// [`PgnParserLimits::max_tags`].
+    Tags(usize),
+    /// [`PgnParserLimits::max_comment_len`].
+    CommentLength(usize),
+    /// [`PgnParserLimits::max_variations`].
+    Variations(usize),
+}
+
+/// Fails with `limit` once `count` exceeds the configured `max`.
+fn check_limit(
+    count: usize,
+    max: Option<usize>,
+    limit: fn(usize) -> PgnParserLimit,
+) -> Result<(), PgnError> {
+    match max {
+        Some(max) if count > max => Err(PgnError::LimitExceeded(limit(max))),
+        _ => Ok(()),
+    }
+}
+
+/// How a `{` inside a braced comment is treated; see [`PgnParser::with_comment_braces`].
+///
+/// The PGN standard does not nest comments, but engine logs often put braces inside them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PgnCommentBracePolicy {
+    /// As in the standard: `{` is ordinary text and the first `}` ends the comment.
+    #[default]
+    Standard,
+    /// Braces nest, so the comment ends at the `}` matching its opening brace.
+    Nested,
+    /// Parsing fails with [`PgnError::InvalidComment`].
+    Reject,
+}
+
 /// Streaming PGN parser with integrated position validation.
 ///
 /// `N` is the position stack depth; choose a value that fits your longest
 /// main line plus deepest variation nesting. The parser validates moves
 /// against the current position and builds a traversable move tree.
+///
+/// Variations are tracked on an explicit stack rather than by recursion, so nesting depth is
+/// bounded only by [`Self::with_max_variation_depth`].
 pub struct PgnParser<'a, const N: usize> {
     /// Logos lexer producing PGN tokens.
     pub lexer: Lexer<'a, PgnToken>,
@@ -38,36 +148,185 @@ pub struct PgnParser<'a, const N: usize> {
     /// Accumulated parse result being constructed.
     pub constructed_object: PgnObject<N>,
     buffered_position_manager: PgnBufferedPositionBrancher<N>,
+    normalize_result_mismatch: bool,
+    max_variation_depth: usize,
+    duplicate_tags: PgnDuplicateTagPolicy,
+    annotations: PgnAnnotationPolicy,
+    lenient: bool,
+    limits: PgnParserLimits,
+    num_plies: usize,
+    num_tags: usize,
+    num_variations: usize,
+    record_position_keys: bool,
+    source_tokens: Option<Vec<PgnSourceToken>>,
+    normalized_result: bool,
 }
 
 impl<'a, const N: usize> PgnParser<'a, N> {
     /// Creates a parser over the provided PGN string.
     ///
     /// The parser starts in [`PgnParsingState::Tags`] and initializes
-    /// a fresh game tree at the standard chess initial position.
+    /// a fresh game tree at the standard chess initial position, or at the position of the
+    /// `FEN` tag once the tags have been read (see [`PgnObject::start_position`]).
     pub fn new(pgn: &str) -> PgnParser<'_, N> {
         let lexer = PgnToken::lexer(pgn);
         let pgn_object = PgnObject::new();
-        let current_node = &pgn_object.tree_root;
         let buffered_position_manager = PgnBufferedPositionBrancher::new(
-            current_node,
-            Position::<N, { Color::White }>::initial(),
+            MoveTree::ROOT,
+            TypedPosition::White(Position::<N, { Color::White }>::initial()),
         );
         PgnParser {
             lexer,
             parse_state: PgnParsingState::Tags,
             constructed_object: pgn_object,
             buffered_position_manager,
+            normalize_result_mismatch: false,
+            max_variation_depth: DEFAULT_MAX_VARIATION_DEPTH,
+            duplicate_tags: PgnDuplicateTagPolicy::default(),
+            annotations: PgnAnnotationPolicy::default(),
+            lenient: false,
+            limits: PgnParserLimits::default(),
+            num_plies: 0,
+            num_tags: 0,
+            num_variations: 0,
+            record_position_keys: false,
+            source_tokens: None,
+            normalized_result: false,
         }
     }
 
+    /// Builder-style setter for lossless parsing.
+    ///
+    /// In lossless mode every token is kept with the whitespace around it, so that
+    /// [`PgnObject::render`] reproduces the input byte for byte until the game is edited.
+    pub fn with_lossless(mut self, lossless: bool) -> Self {
+        self.source_tokens = lossless.then(Vec::new);
+        self
+    }
+
+    /// Builder-style setter for the maximum number of simultaneously open variations.
+    ///
+    /// Opening a variation beyond this depth fails with [`PgnError::VariationTooDeep`].
+    pub fn with_max_variation_depth(mut self, max_depth: usize) -> Self {
+        self.max_variation_depth = max_depth;
+        self
+    }
+
+    /// Builder-style setter for lenient mode, which accepts legacy SAN found in older databases:
+    /// `:` as the capture marker (`B:c6`), promotions without `=` (`e8Q`), and an `e.p.` suffix
+    /// on en passant captures (`exd6 e.p.`).
+    ///
+    /// Without it such moves fail with [`PgnError::InvalidMove`]. Either way, rendering writes
+    /// standard SAN.
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Builder-style setter for recording the Zobrist key of the position after every move, so
+    /// that [`PgnObject::find_position`] answers without replaying the game.
+    ///
+    /// Costs eight bytes per move; see [`PgnObject::move_tree_memory`].
+    pub fn with_position_keys(mut self, record: bool) -> Self {
+        self.record_position_keys = record;
+        self
+    }
+
+    /// Builder-style setter for how repeated tag names are handled.
+    ///
+    /// Defaults to [`PgnDuplicateTagPolicy::KeepLast`].
+    pub fn with_duplicate_tags(mut self, policy: PgnDuplicateTagPolicy) -> Self {
+        self.duplicate_tags = policy;
+        self
+    }
+
+    /// Builder-style setter for how nonstandard suffix annotations such as `!!!` are handled.
+    ///
+    /// Defaults to [`PgnAnnotationPolicy::Keep`]; exports meant for other tools should normalize
+    /// or reject them.
+    pub fn with_annotations(mut self, policy: PgnAnnotationPolicy) -> Self {
+        self.annotations = policy;
+        self
+    }
+
+    /// Builder-style setter for resource limits, to bound the work and memory spent on
+    /// adversarial input such as games with a hundred thousand moves.
+    ///
+    /// Variation nesting is limited separately by [`Self::with_max_variation_depth`].
+    pub fn with_limits(mut self, limits: PgnParserLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Builder-style setter for how a `{` inside a braced comment is handled.
+    ///
+    /// Defaults to [`PgnCommentBracePolicy::Standard`]. Rest-of-line (`;`) comments are always
+    /// accepted and may contain braces.
+    pub fn with_comment_braces(mut self, policy: PgnCommentBracePolicy) -> Self {
+        self.lexer.extras = policy;
+        self
+    }
+
+    /// Builder-style setter for how a `Result` tag that disagrees with the game-terminating
+    /// token is handled.
+    ///
+    /// By default such games fail with [`PgnError::ResultMismatch`]. When `normalize` is `true`,
+    /// the tag is overwritten with the token's result instead.
+    pub fn with_result_normalization(mut self, normalize: bool) -> Self {
+        self.normalize_result_mismatch = normalize;
+        self
+    }
+
     /// Parses the token stream into [`PgnObject`], validating legality of every move.
     ///
     /// Returns an error for malformed tokens, illegal/ambiguous moves, or
     /// incomplete variation structure.
+    ///
+    /// With the `tracing` feature, parsing runs inside a `pgn_game` debug span (carrying the
+    /// `Event`, `White`, and `Black` tags once seen), and a rejected token is logged with its byte
+    /// offset before the error is returned.
     pub fn parse(&mut self) -> Result<(), PgnError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "pgn_game",
+            event = tracing::field::Empty,
+            white = tracing::field::Empty,
+            black = tracing::field::Empty,
+        )
+        .entered();
+
+        let result = self.parse_tokens();
+        #[cfg(feature = "tracing")]
+        if let Err(err) = &result {
+            tracing::debug!(
+                offset = self.lexer.span().start,
+                token = self.lexer.slice().trim(),
+                fullmove = self.buffered_position_manager.current_and_previous.fullmove(),
+                error = %err,
+                "rejected PGN token"
+            );
+        }
+        result
+    }
+
+    fn parse_tokens(&mut self) -> Result<(), PgnError> {
+        let mut source_end = 0;
         while let Some(token) = self.lexer.next() {
-            let token = token?;
+            let token = token.map_err(|err| match err {
+                PgnError::InvalidToken(text) if text.is_empty() => {
+                    PgnError::InvalidToken(self.token_text())
+                }
+                err => err,
+            })?;
+            if let Some(source_tokens) = &mut self.source_tokens {
+                let span = self.lexer.span();
+                let source = self.lexer.source();
+                source_tokens.push(PgnSourceToken {
+                    leading_trivia: source[source_end..span.start].to_string(),
+                    text: source[span.clone()].to_string(),
+                });
+                source_end = span.end;
+            }
             match token {
                 PgnToken::Tag(tag) => {
                     self.process_tag(tag)?;
@@ -90,37 +349,71 @@ impl<'a, const N: usize> PgnParser<'a, N> {
                 PgnToken::Comment(comment) => {
                     self.process_comment(comment)?;
                 }
-                PgnToken::Result(result) => {
-                    self.process_result(result)?;
+                PgnToken::Result(winner) => {
+                    self.process_result(GameResult::from_winner(winner))?;
                 }
                 PgnToken::Incomplete => {
-                    self.process_incomplete()?;
+                    self.process_result(GameResult::Ongoing)?;
                 }
             }
         }
 
         if !self.buffered_position_manager.stack.is_empty() {
             Err(PgnError::UnexpectedEndOfInput(
-                "Unclosed variation".to_string(),
+                "unclosed variation".to_string(),
             ))
         } else if let PgnParsingState::Moves {
             move_number_just_seen: true,
         } = self.parse_state
         {
             Err(PgnError::UnexpectedEndOfInput(
-                "End of input after move number".to_string(),
+                "missing move after move number".to_string(),
             ))
         } else {
+            if self.parse_state != PgnParsingState::ResultFound {
+                // Without a game-terminating token, fall back to the tag.
+                self.constructed_object.result = self
+                    .constructed_object
+                    .tag("Result")
+                    .and_then(GameResult::from_pgn_str)
+                    .unwrap_or_default();
+            }
+            // A normalized game no longer matches its input.
+            if let Some(tokens) = self.source_tokens.take()
+                && !self.normalized_result
+            {
+                self.constructed_object.set_source(PgnSource {
+                    tokens,
+                    trailing_trivia: self.lexer.source()[source_end..].to_string(),
+                    tags: self.constructed_object.tags.clone(),
+                });
+            }
             Ok(())
         }
     }
 
+    /// Text of the token being processed, for error payloads.
+    fn token_text(&self) -> String {
+        self.lexer.slice().trim().to_string()
+    }
+
     fn process_tag(&mut self, tag: PgnTag) -> Result<(), PgnError> {
         if self.parse_state != PgnParsingState::Tags {
-            return Err(PgnError::UnexpectedToken(format!(
-                "Unexpected tag token: {:?}",
-                tag
-            )));
+            return Err(PgnError::UnexpectedToken(self.token_text()));
+        }
+        self.num_tags += 1;
+        check_limit(self.num_tags, self.limits.max_tags, PgnParserLimit::Tags)?;
+        #[cfg(feature = "tracing")]
+        if let field @ ("Event" | "White" | "Black") = tag.name.as_str() {
+            tracing::Span::current()
+                .record(field.to_ascii_lowercase().as_str(), tag.value.as_str());
+        }
+        if self.constructed_object.tag_index(&tag.name).is_some() {
+            match self.duplicate_tags {
+                PgnDuplicateTagPolicy::KeepLast => {}
+                PgnDuplicateTagPolicy::KeepFirst => return Ok(()),
+                PgnDuplicateTagPolicy::Reject => return Err(PgnError::DuplicateTag(tag.name)),
+            }
         }
         self.constructed_object.add_tag(tag.name, tag.value);
         Ok(())
@@ -129,6 +422,7 @@ impl<'a, const N: usize> PgnParser<'a, N> {
     fn process_move_number(&mut self, pgn_move_number: PgnMoveNumber) -> Result<(), PgnError> {
         match self.parse_state {
             PgnParsingState::Tags => {
+                self.set_up_start_position()?;
                 self.parse_state = PgnParsingState::Moves {
                     move_number_just_seen: false,
                 };
@@ -138,10 +432,7 @@ impl<'a, const N: usize> PgnParser<'a, N> {
                 move_number_just_seen,
             } => {
                 if move_number_just_seen {
-                    Err(PgnError::UnexpectedToken(format!(
-                        "Unexpected move number token: {:?}",
-                        pgn_move_number
-                    )))
+                    Err(PgnError::UnexpectedToken(self.token_text()))
                 } else {
                     let expected_fullmove = self
                         .buffered_position_manager
@@ -153,17 +444,37 @@ impl<'a, const N: usize> PgnParser<'a, N> {
                         };
                         Ok(())
                     } else {
-                        Err(PgnError::IncorrectMoveNumber(format!(
-                            "{:?}",
-                            pgn_move_number
-                        )))
+                        Err(PgnError::IncorrectMoveNumber(self.token_text()))
                     }
                 }
             }
-            PgnParsingState::ResultFound => Err(PgnError::UnexpectedToken(format!(
-                "Unexpected move number token: {:?}",
-                pgn_move_number
-            ))),
+            PgnParsingState::ResultFound => Err(PgnError::UnexpectedToken(self.token_text())),
+        }
+    }
+
+    /// Moves the root to the `FEN` tag's position, if the tags set one up.
+    fn set_up_start_position(&mut self) -> Result<(), PgnError> {
+        let object = &self.constructed_object;
+        if let Some(fen) = object
+            .tag("FEN")
+            .filter(|_| object.tag("SetUp") != Some("0"))
+        {
+            let start_position = TypedPosition::<N>::from_fen(fen)
+                .map_err(|_| PgnError::InvalidFen(fen.to_string()))?;
+            self.buffered_position_manager =
+                PgnBufferedPositionBrancher::new(MoveTree::ROOT, start_position);
+        }
+        self.record_position_key();
+        Ok(())
+    }
+
+    /// Records the key of the current position for its node, in position-key mode.
+    fn record_position_key(&mut self) {
+        if self.record_position_keys {
+            let current = &self.buffered_position_manager.current_and_previous;
+            self.constructed_object
+                .tree
+                .record_position_key(current.node(), current.zobrist_hash());
         }
     }
 
@@ -175,13 +486,13 @@ impl<'a, const N: usize> PgnParser<'a, N> {
             PgnParsingState::Moves {
                 move_number_just_seen,
             } => {
+                if pgn_move.uses_legacy_notation() && !self.lenient {
+                    return Err(PgnError::InvalidMove(self.token_text()));
+                }
                 let current_state = &self.buffered_position_manager.current_and_previous;
                 let side_to_move = current_state.side_to_move();
                 if !move_number_just_seen && side_to_move == Color::White {
-                    return Err(PgnError::UnexpectedToken(format!(
-                        "Unexpected move token: {:?}",
-                        pgn_move
-                    )));
+                    return Err(PgnError::UnexpectedToken(self.token_text()));
                 }
                 let mut possible_moves = MoveList::new();
                 match current_state {
@@ -199,16 +510,19 @@ impl<'a, const N: usize> PgnParser<'a, N> {
                 for &possible_move in possible_moves.as_slice() {
                     let is_match = match current_state {
                         PgnBufferedPositionContextDyn::White(ctx) => pgn_move
-                            .matches_move(possible_move, &ctx.current.state_after_move.board),
+                            .matches_move(possible_move, ctx.current.state_after_move.board()),
                         PgnBufferedPositionContextDyn::Black(ctx) => pgn_move
-                            .matches_move(possible_move, &ctx.current.state_after_move.board),
+                            .matches_move(possible_move, ctx.current.state_after_move.board()),
                     };
                     if is_match {
-                        if matched_move.is_some() {
-                            return Err(PgnError::AmbiguousMove(format!(
-                                "Ambiguous move: {:?}",
-                                pgn_move
-                            )));
+                        if let Some(_first_match) = matched_move {
+                            #[cfg(feature = "tracing")]
+                            tracing::debug!(
+                                token = self.lexer.slice().trim(),
+                                candidates = ?[_first_match, possible_move],
+                                "ambiguous PGN move"
+                            );
+                            return Err(PgnError::AmbiguousMove(self.token_text()));
                         } else {
                             matched_move = Some(possible_move);
                         }
@@ -216,32 +530,50 @@ impl<'a, const N: usize> PgnParser<'a, N> {
                 }
 
                 if let Some(matched_move) = matched_move {
+                    self.num_plies += 1;
+                    check_limit(self.num_plies, self.limits.max_plies, PgnParserLimit::Plies)?;
+                    let annotation = match pgn_move
+                        .get_common_move_info()
+                        .annotation
+                        .as_deref()
+                        .filter(|annotation| !annotation.is_empty())
+                    {
+                        Some(annotation) if !STANDARD_ANNOTATIONS.contains(&annotation) => {
+                            match self.annotations {
+                                PgnAnnotationPolicy::Keep => Some(annotation),
+                                PgnAnnotationPolicy::Normalize => {
+                                    Some(nearest_standard_annotation(annotation))
+                                }
+                                PgnAnnotationPolicy::Reject => {
+                                    return Err(PgnError::NonstandardAnnotation(self.token_text()));
+                                }
+                            }
+                        }
+                        annotation => annotation,
+                    };
                     let move_data = PgnMoveData {
                         move_: matched_move,
-                        annotation: pgn_move.get_common_move_info().annotation.clone(),
+                        annotation: annotation
+                            .map(|annotation| self.constructed_object.tree.intern(annotation)),
                         nag: pgn_move.get_common_move_info().nag,
+                        written_with_zeros: pgn_move.written_with_zeros(),
                     };
                     let new_context = self
                         .buffered_position_manager
                         .current_and_previous
                         .clone()
-                        .append_move(move_data);
+                        .append_move(&mut self.constructed_object.tree, move_data);
                     self.buffered_position_manager.current_and_previous = new_context;
+                    self.record_position_key();
                     self.parse_state = PgnParsingState::Moves {
                         move_number_just_seen: false,
                     };
                     Ok(())
                 } else {
-                    Err(PgnError::IllegalMove(format!(
-                        "Illegal move: {:?}",
-                        pgn_move
-                    )))
+                    Err(PgnError::IllegalMove(self.token_text()))
                 }
             }
-            _ => Err(PgnError::UnexpectedToken(format!(
-                "Unexpected move token: {:?}",
-                pgn_move
-            ))),
+            _ => Err(PgnError::UnexpectedToken(self.token_text())),
         }
     }
 
@@ -256,17 +588,21 @@ impl<'a, const N: usize> PgnParser<'a, N> {
                     .previous_as_current()
                     .is_none()
                 {
-                    Err(PgnError::UnexpectedToken(
-                        "Unexpected start variation token".to_string(),
-                    ))
+                    Err(PgnError::UnexpectedToken(self.token_text()))
+                } else if self.buffered_position_manager.stack.len() >= self.max_variation_depth {
+                    Err(PgnError::VariationTooDeep(self.max_variation_depth))
                 } else {
+                    self.num_variations += 1;
+                    check_limit(
+                        self.num_variations,
+                        self.limits.max_variations,
+                        PgnParserLimit::Variations,
+                    )?;
                     self.buffered_position_manager.create_branch_from_previous();
                     Ok(())
                 }
             }
-            _ => Err(PgnError::UnexpectedToken(
-                "Unexpected start variation token".to_string(),
-            )),
+            _ => Err(PgnError::UnexpectedToken(self.token_text())),
         }
     }
 
@@ -276,49 +612,70 @@ impl<'a, const N: usize> PgnParser<'a, N> {
                 move_number_just_seen: false,
             } => {
                 if self.buffered_position_manager.stack.is_empty() {
-                    Err(PgnError::UnexpectedToken(
-                        "Unexpected end variation token".to_string(),
-                    ))
+                    Err(PgnError::UnexpectedToken(self.token_text()))
                 } else {
                     self.buffered_position_manager.end_branch();
                     Ok(())
                 }
             }
-            _ => Err(PgnError::UnexpectedToken(
-                "Unexpected end variation token".to_string(),
-            )),
+            _ => Err(PgnError::UnexpectedToken(self.token_text())),
         }
     }
 
-    fn process_comment(&mut self, _comment: PgnComment) -> Result<(), PgnError> {
-        Ok(()) // TODO
+    fn process_comment(&mut self, comment: PgnComment) -> Result<(), PgnError> {
+        check_limit(
+            comment.comment.len(),
+            self.limits.max_comment_len,
+            PgnParserLimit::CommentLength,
+        )?;
+        // A comment belongs to the move before it, or to the game when no move precedes it.
+        let node = match &self.buffered_position_manager.current_and_previous {
+            PgnBufferedPositionContextDyn::White(ctx) => ctx.current.node,
+            PgnBufferedPositionContextDyn::Black(ctx) => ctx.current.node,
+        };
+        let comment = comment.comment.trim();
+        if !comment.is_empty() && self.parse_state != PgnParsingState::ResultFound {
+            self.constructed_object.tree.append_comment(node, comment);
+        }
+        Ok(())
     }
 
-    fn process_result(&mut self, _result: Option<Color>) -> Result<(), PgnError> {
+    fn process_result(&mut self, result: GameResult) -> Result<(), PgnError> {
         match self.parse_state {
             PgnParsingState::Moves {
                 move_number_just_seen: false,
-            } => {
-                self.parse_state = PgnParsingState::ResultFound;
-                Ok(())
+            } => {}
+            // Some exporters repeat the result token; tolerate exact repeats.
+            PgnParsingState::ResultFound if self.constructed_object.result == result => {
+                return Ok(());
+            }
+            _ => {
+                return Err(PgnError::UnexpectedToken(self.token_text()));
             }
-            _ => Err(PgnError::UnexpectedToken(
-                "Unexpected result token".to_string(),
-            )),
         }
-    }
 
-    fn process_incomplete(&mut self) -> Result<(), PgnError> {
-        match self.parse_state {
-            PgnParsingState::Moves {
-                move_number_just_seen: false,
-            } => {
-                self.parse_state = PgnParsingState::ResultFound;
-                Ok(())
+        // A `?` tag records an unknown result, so any token agrees with it.
+        let tag = self
+            .constructed_object
+            .tag("Result")
+            .filter(|value| value.trim() != "?");
+        match tag.map(GameResult::from_pgn_str) {
+            Some(tag_result) if tag_result != Some(result) => {
+                if !self.normalize_result_mismatch {
+                    return Err(PgnError::ResultMismatch {
+                        tag: tag.unwrap_or_default().to_string(),
+                        token: result,
+                    });
+                }
+                self.constructed_object
+                    .add_tag("Result".to_string(), result.to_string());
+                self.normalized_result = true;
             }
-            _ => Err(PgnError::UnexpectedToken(
-                "Unexpected incomplete token".to_string(),
-            )),
+            _ => {}
         }
+
+        self.constructed_object.result = result;
+        self.parse_state = PgnParsingState::ResultFound;
+        Ok(())
     }
 }
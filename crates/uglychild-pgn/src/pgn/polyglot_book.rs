@@ -0,0 +1,577 @@
+//! Building Polyglot (`.bin`) opening books from parsed games.
+//!
+//! A Polyglot book is a list of 16-byte big-endian entries (position key, move, weight, learn
+//! value) sorted by key. The position key is an XOR of values from the 781-entry `Random64` table
+//! published with the format (`Random64` in Polyglot's `book.c`). This crate does not bundle that
+//! table, so callers copy it from there and pass it to [`PolyglotKeys::new`], then confirm the
+//! copy with [`PolyglotKeys::is_standard`], which checks the key test vectors of the format
+//! ([`POLYGLOT_TEST_VECTORS`]). Any other table yields a well-formed book that only this crate's
+//! keys can look up.
+//!
+//! [`PolyglotKeys::zobrist_keys`] converts a table into a schedule for
+//! [`WithCustomZobrist`](crate::types::WithCustomZobrist), whose incremental hash then equals
+//! [`PolyglotKeys::key`].
+
+use std::{collections::HashMap, io};
+
+use crate::{
+    Color,
+    ColoredPiece,
+    ConstDoublePawnPushFile,
+    File,
+    Flank,
+    MoveFlag,
+    Piece,
+    Square,
+    TypedPosition,
+    logic::{game_state::GameResult, zobrist_hash::ZobristKeys},
+    r#move::Move,
+    pgn::{object::PgnObject, tag_values::PgnDate},
+    position::Position,
+    utilities::IterableEnum,
+};
+
+/// Number of values in a Polyglot random table.
+pub const POLYGLOT_RANDOM_COUNT: usize = 781;
+
+/// Positions and their keys under the standard `Random64` table, from the Polyglot book format
+/// description: the start position, then `1. e4 d5 2. e5 f5 3. Ke2 Kf7`, then
+/// `1. a4 b5 2. h4 b4 3. c4 bxc3 4. Ra3`, one entry per step listed there.
+pub const POLYGLOT_TEST_VECTORS: [(&str, u64); 9] = [
+    (
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        0x463b96181691fc9c,
+    ),
+    (
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+        0x823c9b50fd114196,
+    ),
+    (
+        "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2",
+        0x0756b94461c50fb0,
+    ),
+    (
+        "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2",
+        0x662fafb965db29d4,
+    ),
+    (
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        0x22a48b5a8e47ff78,
+    ),
+    (
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR b kq - 1 3",
+        0x652a607ca3f242c1,
+    ),
+    (
+        "rnbq1bnr/ppp1pkpp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR w - - 2 4",
+        0x00fdd303c946bdd9,
+    ),
+    (
+        "rnbqkbnr/p1pppppp/8/8/PpP4P/8/1P1PPPP1/RNBQKBNR b KQkq c3 0 3",
+        0x3c8123ea7b067637,
+    ),
+    (
+        "rnbqkbnr/p1pppppp/8/8/P6P/R1p5/1P1PPPP1/1NBQKBNR b Kkq - 1 4",
+        0x5c3f9b829b279560,
+    ),
+];
+
+const CASTLING_OFFSET: usize = 768;
+const EN_PASSANT_OFFSET: usize = 772;
+const TURN_OFFSET: usize = 780;
+
+/// Computes Polyglot position keys from a random table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolyglotKeys {
+    randoms: Box<[u64; POLYGLOT_RANDOM_COUNT]>,
+}
+
+impl PolyglotKeys {
+    /// Uses `randoms` as the table, laid out as in the Polyglot format: 768 piece-square values,
+    /// then 4 castling, 8 en-passant file, and 1 side-to-move value.
+    pub fn new(randoms: [u64; POLYGLOT_RANDOM_COUNT]) -> PolyglotKeys {
+        PolyglotKeys {
+            randoms: Box::new(randoms),
+        }
+    }
+
+    /// Returns whether this is the standard Polyglot table, by keying every position of
+    /// [`POLYGLOT_TEST_VECTORS`]; only then can other Polyglot tools read the books it keys.
+    pub fn is_standard(&self) -> bool {
+        POLYGLOT_TEST_VECTORS.iter().all(|&(fen, expected)| {
+            match TypedPosition::<1>::from_fen(fen).expect("test vector FENs are valid") {
+                TypedPosition::White(position) => self.key(&position) == expected,
+                TypedPosition::Black(position) => self.key(&position) == expected,
+            }
+        })
+    }
+
+    /// Returns the same table as a Zobrist key schedule, so positions hashed with it carry their
+    /// Polyglot key as their Zobrist hash.
+    pub fn zobrist_keys(&self) -> ZobristKeys {
+        let mut piece_square = [[0; 64]; 12];
+        for (index, colored_piece) in ColoredPiece::ALL.into_iter().enumerate() {
+            let kind = 2 * (colored_piece.piece() as usize - 1)
+                + (colored_piece.color() == Color::White) as usize;
+            for square in Square::ALL {
+                piece_square[index][square as usize] = self.randoms
+                    [64 * kind + 8 * square.rank_index() as usize + square.file_index() as usize];
+            }
+        }
+
+        // Castling-rights bits are K = 8, Q = 4, k = 2, q = 1; Polyglot lists K, Q, k, q.
+        let castling_rights = std::array::from_fn(|bits| {
+            (0..4)
+                .filter(|index| bits & (8 >> index) != 0)
+                .fold(0, |key, index| key ^ self.randoms[CASTLING_OFFSET + index])
+        });
+
+        ZobristKeys {
+            piece_square,
+            castling_rights,
+            en_passant_file: std::array::from_fn(|file| self.randoms[EN_PASSANT_OFFSET + file]),
+            en_passant_needs_capturer: true,
+            side_to_move: [self.randoms[TURN_OFFSET], 0],
+        }
+    }
+
+    /// Returns the Polyglot key of `position`.
+    ///
+    /// As the format requires, the en-passant file only contributes when a pawn of the side to
+    /// move stands next to the double-pushed pawn, whether or not the capture is legal.
+    pub fn key<const N: usize, const STM: Color>(&self, position: &Position<N, STM>) -> u64 {
+        let board = position.board();
+        let mut key = 0;
+        for square in Square::ALL {
+            let piece = board.piece_at(square);
+            if piece == Piece::Null {
+                continue;
+            }
+            let kind = 2 * (piece as usize - 1) + (board.color_at(square) == Color::White) as usize;
+            key ^= self.randoms
+                [64 * kind + 8 * square.rank_index() as usize + square.file_index() as usize];
+        }
+
+        let context = position.context();
+        let castling = [
+            (Color::White, Flank::Kingside),
+            (Color::White, Flank::Queenside),
+            (Color::Black, Flank::Kingside),
+            (Color::Black, Flank::Queenside),
+        ];
+        for (index, (color, flank)) in castling.into_iter().enumerate() {
+            if context.castling_rights().has(flank, color) {
+                key ^= self.randoms[CASTLING_OFFSET + index];
+            }
+        }
+
+        let double_pawn_push_file = context.double_pawn_push_file();
+        if let Some(file) = double_pawn_push_file.file() {
+            let own_pawns = board.piece_mask::<{ Piece::Pawn }>() & board.color_mask_at(STM);
+            if double_pawn_push_file.ep_possible_src_mask(STM) & own_pawns != 0 {
+                key ^= self.randoms[EN_PASSANT_OFFSET + file as usize];
+            }
+        }
+
+        if STM == Color::White {
+            key ^= self.randoms[TURN_OFFSET];
+        }
+        key
+    }
+}
+
+/// Encodes `move_` as a Polyglot move: destination file and rank in bits 0-5, origin in bits
+/// 6-11, and the promotion piece (knight = 1 … queen = 4) in bits 12-14.
+///
+/// Castling is written as the king capturing its own rook (`e1h1`, `e1a1`, …).
+pub fn polyglot_move(move_: Move) -> u16 {
+    let from = move_.from();
+    let mut to = move_.to();
+    if move_.flag() == MoveFlag::Castling {
+        let rook_file = if to.file_index() > from.file_index() {
+            File::H
+        } else {
+            File::A
+        };
+        to = Square::from_rank_and_file(to.rank(), rook_file);
+    }
+    let promotion = match move_.flag() {
+        MoveFlag::Promotion => move_.promotion() as u16 - Piece::Pawn as u16,
+        _ => 0,
+    };
+    to.file_index() as u16
+        | (to.rank_index() as u16) << 3
+        | (from.file_index() as u16) << 6
+        | (from.rank_index() as u16) << 9
+        | promotion << 12
+}
+
+/// One book entry: a move from the position with key [`Self::key`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PolyglotEntry {
+    /// Polyglot key of the position.
+    pub key: u64,
+    /// Move in [`polyglot_move`] encoding.
+    pub move_: u16,
+    /// Relative weight among the position's moves.
+    pub weight: u16,
+    /// Learning data; always `0` in built books.
+    pub learn: u32,
+}
+
+impl PolyglotEntry {
+    /// The 16-byte big-endian on-disk form.
+    pub fn to_bytes(&self) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&self.key.to_be_bytes());
+        bytes[8..10].copy_from_slice(&self.move_.to_be_bytes());
+        bytes[10..12].copy_from_slice(&self.weight.to_be_bytes());
+        bytes[12..].copy_from_slice(&self.learn.to_be_bytes());
+        bytes
+    }
+}
+
+/// How book weights are derived from the games a move was played in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PolyglotWeighting {
+    /// One point per game.
+    Frequency,
+    /// Two points per win and one per draw for the side that played the move, as Polyglot's own
+    /// `make-book` does. Moves that only lost get no entry.
+    #[default]
+    Results,
+}
+
+/// Which games and moves go into a book.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PolyglotBookFilter {
+    /// Skip games unless both `WhiteElo` and `BlackElo` are at least this.
+    pub min_elo: Option<u16>,
+    /// Skip games dated before this. Unknown date fields sort first, so undated games are
+    /// skipped too.
+    pub min_date: Option<PgnDate>,
+    /// Skip games dated after this.
+    pub max_date: Option<PgnDate>,
+    /// Only record the first `max_ply` plies of each game.
+    pub max_ply: usize,
+    /// Drop moves played in fewer games than this.
+    pub min_games: u32,
+}
+
+impl Default for PolyglotBookFilter {
+    fn default() -> Self {
+        PolyglotBookFilter {
+            min_elo: None,
+            min_date: None,
+            max_date: None,
+            max_ply: 40,
+            min_games: 1,
+        }
+    }
+}
+
+impl PolyglotBookFilter {
+    /// Whether `game` passes the rating and date filters.
+    pub fn accepts<const N: usize>(&self, game: &PgnObject<N>) -> bool {
+        let rated = self.min_elo.is_none_or(|min_elo| {
+            game.white_elo().is_some_and(|elo| elo >= min_elo)
+                && game.black_elo().is_some_and(|elo| elo >= min_elo)
+        });
+        let date = game.date();
+        rated
+            && self.min_date.is_none_or(|min_date| date >= min_date)
+            && self.max_date.is_none_or(|max_date| date <= max_date)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct BookMoveStats {
+    games: u32,
+    wins: u32,
+    draws: u32,
+}
+
+/// Accumulates the main lines of many games into a Polyglot book.
+///
+/// `N` is the position stack capacity used to replay games; at most `N - 1` plies of each game
+/// are recorded.
+pub struct PolyglotBookBuilder<const N: usize> {
+    keys: PolyglotKeys,
+    filter: PolyglotBookFilter,
+    weighting: PolyglotWeighting,
+    moves: HashMap<(u64, u16), BookMoveStats>,
+    num_games: usize,
+}
+
+impl<const N: usize> PolyglotBookBuilder<N> {
+    /// Creates an empty builder with the default filter and weighting.
+    pub fn new(keys: PolyglotKeys) -> PolyglotBookBuilder<N> {
+        PolyglotBookBuilder {
+            keys,
+            filter: PolyglotBookFilter::default(),
+            weighting: PolyglotWeighting::default(),
+            moves: HashMap::new(),
+            num_games: 0,
+        }
+    }
+
+    /// Sets the game and move filter.
+    pub fn with_filter(mut self, filter: PolyglotBookFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Sets how weights are computed.
+    pub fn with_weighting(mut self, weighting: PolyglotWeighting) -> Self {
+        self.weighting = weighting;
+        self
+    }
+
+    /// Number of games that passed the filter.
+    pub fn num_games(&self) -> usize {
+        self.num_games
+    }
+
+    /// Records the main line of `game` if it passes the filter; returns whether it did.
+    pub fn add_game<const M: usize>(&mut self, game: &PgnObject<M>) -> bool {
+        if !self.filter.accepts(game) {
+            return false;
+        }
+        self.num_games += 1;
+
+        let result = game.result();
+        let mut position = game.start_position::<N>();
+        let num_plies = self.filter.max_ply.min(N - 1);
+        for move_ in game.main_line().into_iter().take(num_plies) {
+            let (key, mover) = match &position {
+                TypedPosition::White(p) => (self.keys.key(p), Color::White),
+                TypedPosition::Black(p) => (self.keys.key(p), Color::Black),
+            };
+            let stats = self.moves.entry((key, polyglot_move(move_))).or_default();
+            stats.games += 1;
+            match (result, mover) {
+                (GameResult::WhiteWins, Color::White) | (GameResult::BlackWins, Color::Black) => {
+                    stats.wins += 1
+                }
+                (GameResult::Draw, _) => stats.draws += 1,
+                _ => {}
+            }
+
+            position = match position {
+                TypedPosition::White(mut p) => {
+                    p.make_move(move_);
+                    TypedPosition::Black(p.rebrand_stm())
+                }
+                TypedPosition::Black(mut p) => {
+                    p.make_move(move_);
+                    TypedPosition::White(p.rebrand_stm())
+                }
+            };
+        }
+        true
+    }
+
+    /// Returns the book entries sorted by key, then by decreasing weight.
+    ///
+    /// Weights are scaled down proportionally if the largest one does not fit in a `u16`;
+    /// entries whose weight would be zero are left out.
+    pub fn entries(&self) -> Vec<PolyglotEntry> {
+        let raw_weight = |stats: &BookMoveStats| -> u64 {
+            match self.weighting {
+                PolyglotWeighting::Frequency => stats.games as u64,
+                PolyglotWeighting::Results => 2 * stats.wins as u64 + stats.draws as u64,
+            }
+        };
+        let kept = || {
+            self.moves
+                .iter()
+                .filter(|(_, stats)| stats.games >= self.filter.min_games)
+        };
+        let max_weight = kept().map(|(_, stats)| raw_weight(stats)).max();
+        let scale = |weight: u64| match max_weight {
+            Some(max_weight) if max_weight > u16::MAX as u64 => {
+                weight * u16::MAX as u64 / max_weight
+            }
+            _ => weight,
+        };
+
+        let mut entries: Vec<PolyglotEntry> = kept()
+            .map(|(&(key, move_), stats)| PolyglotEntry {
+                key,
+                move_,
+                weight: scale(raw_weight(stats)) as u16,
+                learn: 0,
+            })
+            .filter(|entry| entry.weight > 0)
+            .collect();
+        entries.sort_by(|a, b| {
+            a.key
+                .cmp(&b.key)
+                .then(b.weight.cmp(&a.weight))
+                .then(a.move_.cmp(&b.move_))
+        });
+        entries
+    }
+
+    /// Writes the book in Polyglot `.bin` format.
+    pub fn write_to<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        for entry in self.entries() {
+            writer.write_all(&entry.to_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pgn::PgnParser;
+
+    const N: usize = 16;
+
+    /// A stand-in table; real books need the published Polyglot values.
+    fn test_keys() -> PolyglotKeys {
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        PolyglotKeys::new(std::array::from_fn(|_| {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        }))
+    }
+
+    fn parse(pgn: &str) -> PgnObject<N> {
+        let mut parser = PgnParser::<N>::new(pgn);
+        parser.parse().expect("valid PGN");
+        parser.constructed_object
+    }
+
+    fn key_of(keys: &PolyglotKeys, fen: &str) -> u64 {
+        match TypedPosition::<1>::from_fen(fen).unwrap() {
+            TypedPosition::White(position) => keys.key(&position),
+            TypedPosition::Black(position) => keys.key(&position),
+        }
+    }
+
+    #[test]
+    fn test_polyglot_move_encoding() {
+        let game = parse("1. e4 e5 2. Nf3 Nc6 3. Bc4 Nf6 4. O-O *");
+        let moves: Vec<u16> = game.main_line().into_iter().map(polyglot_move).collect();
+        // e2e4: from (file 4, row 1), to (file 4, row 3).
+        assert_eq!(moves[0], 4 | 3 << 3 | 4 << 6 | 1 << 9);
+        // O-O is encoded as e1h1.
+        assert_eq!(moves[6], 7 | 4 << 6);
+
+        let promotion = parse("[FEN \"8/P6k/8/8/8/8/8/K7 w - - 0 1\"] [SetUp \"1\"] 1. a8=N *");
+        assert_eq!(
+            polyglot_move(promotion.main_line()[0]),
+            7 << 3 | 6 << 9 | 1 << 12
+        );
+    }
+
+    #[test]
+    fn test_key_en_passant_only_when_capturable() {
+        let keys = test_keys();
+        let with_ep = "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3";
+        let without_ep = "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq - 0 3";
+        assert_ne!(key_of(&keys, with_ep), key_of(&keys, without_ep));
+
+        let no_capturer = "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2";
+        let no_capturer_plain = "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2";
+        assert_eq!(key_of(&keys, no_capturer), key_of(&keys, no_capturer_plain));
+    }
+
+    #[test]
+    fn test_vectors_follow_the_spec_move_sequences() {
+        let lines = [
+            "1. e4 d5 2. e5 f5 3. Ke2 Kf7 *",
+            "1. a4 b5 2. h4 b4 3. c4 bxc3 4. Ra3 *",
+        ];
+        let mut fens = Vec::new();
+        for line in lines {
+            let game = parse(line);
+            let mut position = game.start_position::<N>();
+            if fens.is_empty() {
+                fens.push(position.to_fen());
+            }
+            for move_ in game.main_line() {
+                position = crate::pgn::object::play_move(&position, move_);
+                fens.push(position.to_fen());
+            }
+        }
+        // The spec lists the second line after `3. c4` and `4. Ra3` only.
+        let spec_fens: Vec<String> = [0, 1, 2, 3, 4, 5, 6, 11, 13]
+            .into_iter()
+            .map(|index| fens[index].clone())
+            .collect();
+        let vector_fens: Vec<&str> = POLYGLOT_TEST_VECTORS.iter().map(|&(fen, _)| fen).collect();
+        assert_eq!(spec_fens, vector_fens);
+        assert!(!test_keys().is_standard());
+    }
+
+    #[test]
+    fn test_zobrist_keys_match_polyglot_keys() {
+        let keys = test_keys();
+        let zobrist_keys = keys.zobrist_keys();
+        let vector_fens = POLYGLOT_TEST_VECTORS.iter().map(|&(fen, _)| fen);
+        for fen in [
+            "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2",
+            "r3k2r/8/8/8/3pP3/8/8/R3K2R b Qk e3 0 1",
+            "4k3/8/8/8/8/8/8/4K3 b - - 0 1",
+        ]
+        .into_iter()
+        .chain(vector_fens)
+        {
+            let position = TypedPosition::<1>::from_fen(fen).unwrap();
+            let (board, context, side_to_move, key) = match &position {
+                TypedPosition::White(p) => (p.board(), p.context(), Color::White, keys.key(p)),
+                TypedPosition::Black(p) => (p.board(), p.context(), Color::Black, keys.key(p)),
+            };
+            let hash = zobrist_keys.calc_position_hash(
+                board,
+                context.castling_rights(),
+                context.double_pawn_push_file(),
+                side_to_move,
+            );
+            assert_eq!(hash, key, "{fen}");
+        }
+    }
+
+    #[test]
+    fn test_book_weights_and_filters() {
+        let games = [
+            parse("[WhiteElo \"2400\"] [BlackElo \"2300\"] [Date \"2020.01.01\"] 1. e4 e5 1-0"),
+            parse("[WhiteElo \"2400\"] [BlackElo \"2300\"] [Date \"2021.05.01\"] 1. e4 c5 0-1"),
+            parse("[WhiteElo \"2400\"] [BlackElo \"2300\"] [Date \"2022.01.01\"] 1. d4 d5 1/2-1/2"),
+            parse("[WhiteElo \"1500\"] [BlackElo \"1500\"] 1. b4 e5 1-0"),
+        ];
+        let filter = PolyglotBookFilter {
+            min_elo: Some(2000),
+            min_date: PgnDate::from_tag_value("2020.06.01"),
+            ..PolyglotBookFilter::default()
+        };
+        let mut builder = PolyglotBookBuilder::<N>::new(test_keys()).with_filter(filter);
+        let accepted: Vec<bool> = games.iter().map(|game| builder.add_game(game)).collect();
+        assert_eq!(accepted, [false, true, true, false]);
+
+        let root = builder
+            .keys
+            .key(&Position::<1, { Color::White }>::initial());
+        let entries = builder.entries();
+        // 1. e4 lost and only 1. d4 (a draw) keeps an entry from the root; both black replies
+        // scored points.
+        let root_entries: Vec<_> = entries.iter().filter(|entry| entry.key == root).collect();
+        assert_eq!(root_entries.len(), 1);
+        assert_eq!(root_entries[0].weight, 1);
+        assert_eq!(entries.len(), 3);
+        assert!(entries.windows(2).all(|pair| pair[0].key <= pair[1].key));
+
+        let builder = builder.with_weighting(PolyglotWeighting::Frequency);
+        assert_eq!(builder.entries().len(), 4);
+        let mut bytes = Vec::new();
+        builder.write_to(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 4 * 16);
+        assert_eq!(bytes[..8], builder.entries()[0].key.to_be_bytes());
+    }
+}
@@ -1,9 +1,7 @@
-use std::{cell::RefCell, rc::Rc};
-
-use crate::{Color, pgn::move_tree_node::MoveTreeNode, position::Position};
+use crate::{Color, pgn::move_tree::MoveTreeNodeId, position::Position};
 
 #[derive(Clone)]
 pub(crate) struct PgnPositionContext<const N: usize, const STM: Color, const OPP: Color> {
-    pub(crate) node: Rc<RefCell<MoveTreeNode<N, STM, OPP>>>,
+    pub(crate) node: MoveTreeNodeId,
     pub(crate) state_after_move: Position<N, STM>,
 }
@@ -10,6 +10,8 @@ pub struct PgnRenderingConfig {
     pub include_nags: bool,
     /// Include `{comments}` in output.
     pub include_comments: bool,
+    /// Write castling as `O-O`/`O-O-O` even where the input used zeros (`0-0`).
+    pub normalize_castling: bool,
 }
 
 impl Default for PgnRenderingConfig {
@@ -19,21 +21,25 @@ impl Default for PgnRenderingConfig {
 }
 
 impl PgnRenderingConfig {
-    /// Configuration that includes all markings (annotations, NAGs, comments).
+    /// Configuration that includes all markings (annotations, NAGs, comments) and keeps
+    /// castling as written.
     pub const fn all_markings() -> PgnRenderingConfig {
         PgnRenderingConfig {
             include_annotations: true,
             include_nags: true,
             include_comments: true,
+            normalize_castling: false,
         }
     }
 
-    /// Configuration for clean output without any annotations.
+    /// Configuration for clean output without any annotations, with castling normalized to
+    /// the letter O.
     pub const fn no_markings() -> PgnRenderingConfig {
         PgnRenderingConfig {
             include_annotations: false,
             include_nags: false,
             include_comments: false,
+            normalize_castling: true,
         }
     }
 
@@ -54,4 +60,10 @@ impl PgnRenderingConfig {
         self.include_comments = include;
         self
     }
+
+    /// Builder-style setter for castling normalization.
+    pub fn normalize_castling(&mut self, normalize: bool) -> &mut Self {
+        self.normalize_castling = normalize;
+        self
+    }
 }
@@ -0,0 +1,9 @@
+//! Typed PGN game result.
+
+use crate::logic::game_state::GameResult;
+
+/// Outcome of a PGN game, as given by its game-terminating token or `Result` tag.
+///
+/// The same type as the core [`GameResult`], so parsed games, game states and match games agree;
+/// `*` and unknown results map to [`GameResult::Ongoing`].
+pub type PgnResult = GameResult;
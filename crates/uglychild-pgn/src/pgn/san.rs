@@ -0,0 +1,140 @@
+//! Standalone SAN parsing, using the same grammar as the PGN lexer but without a game around it.
+
+use std::{error::Error, fmt};
+
+use crate::{
+    logic::move_pattern::MovePattern,
+    pgn::token_types::{PgnCastlingMove, PgnMove, PgnNonCastlingMove},
+};
+
+/// A SAN move parsed by [`parse_san`], without a position to resolve it in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SanMove {
+    /// Moved piece, source disambiguation, destination and promotion, or the castling flank.
+    ///
+    /// The capture marker must match exactly: `Nxe5` only matches captures and `Ne5` only
+    /// non-captures.
+    pub pattern: MovePattern,
+    /// `true` when the move is marked as check (`+` or `#`).
+    pub is_check: bool,
+    /// `true` when the move is marked as checkmate (`#`).
+    pub is_checkmate: bool,
+    /// Symbolic annotation (`!`, `?!`, ...), if present.
+    pub annotation: Option<String>,
+    /// Numeric annotation glyph (`$N`), if present.
+    pub nag: Option<u8>,
+    /// Whether the text uses a form only a lenient [`PgnParser`] accepts: `:` for captures, a
+    /// promotion without `=` (`e8Q`), or an `e.p.` suffix.
+    ///
+    /// [`PgnParser`]: crate::pgn::PgnParser
+    pub legacy_notation: bool,
+    /// Byte length of the move proper, before any check marker, annotation or NAG.
+    pub san_len: usize,
+}
+
+/// Why [`parse_san`] rejected its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SanError {
+    /// The input is empty.
+    Empty,
+    /// The input does not start with a SAN move.
+    Invalid,
+    /// A SAN move ends at this byte offset, but more text follows it.
+    TrailingText(usize),
+}
+
+impl fmt::Display for SanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SanError::Empty => write!(f, "empty SAN move"),
+            SanError::Invalid => write!(f, "not a SAN move"),
+            SanError::TrailingText(offset) => {
+                write!(f, "unexpected text after SAN move at byte {offset}")
+            }
+        }
+    }
+}
+
+impl Error for SanError {}
+
+/// Parses one SAN move such as `Nbxd7+`, `e8=Q#`, `O-O-O!?` or `exd6 e.p. $1`.
+///
+/// The move must start at the first byte of `text`; trailing whitespace is allowed. The grammar
+/// is the PGN lexer's, so everything [`PgnParser`] reads as a move parses here, including the
+/// legacy forms flagged by [`SanMove::legacy_notation`].
+///
+/// [`PgnParser`]: crate::pgn::PgnParser
+pub fn parse_san(text: &str) -> Result<SanMove, SanError> {
+    if text.is_empty() {
+        return Err(SanError::Empty);
+    }
+    let (pattern, info, legacy_notation, end) = match PgnCastlingMove::parse_prefix(text) {
+        Some((move_, end)) => (
+            MovePattern::Castling(move_.flank),
+            move_.get_common_move_info().clone(),
+            false,
+            end,
+        ),
+        None => {
+            let (move_, end) = PgnNonCastlingMove::parse_prefix(text).ok_or(SanError::Invalid)?;
+            (
+                move_.pattern(),
+                move_.get_common_move_info().clone(),
+                move_.legacy_notation,
+                end,
+            )
+        }
+    };
+    if !text[end..].trim().is_empty() {
+        return Err(SanError::TrailingText(end));
+    }
+
+    let san_len = text[..end].find(['+', '#', '!', '?', '$']).unwrap_or(end);
+    Ok(SanMove {
+        pattern,
+        is_check: info.is_check,
+        is_checkmate: info.is_checkmate,
+        annotation: info.annotation.filter(|annotation| !annotation.is_empty()),
+        nag: info.nag,
+        legacy_notation,
+        san_len: text[..san_len].trim_end().len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{File, Flank, Piece, Square};
+
+    #[test]
+    fn test_parse_san() {
+        let parsed = parse_san("Nbxd7+!? $3").unwrap();
+        assert_eq!(
+            parsed.pattern,
+            MovePattern::Normal {
+                piece: Piece::Knight,
+                from_file: Some(File::B),
+                from_rank: None,
+                to: Square::D7,
+                promotion: None,
+                capture: Some(true),
+            }
+        );
+        assert!(parsed.is_check && !parsed.is_checkmate);
+        assert_eq!(parsed.annotation.as_deref(), Some("!?"));
+        assert_eq!(parsed.nag, Some(3));
+        assert_eq!(parsed.san_len, "Nbxd7".len());
+
+        let castling = parse_san("0-0-0#").unwrap();
+        assert_eq!(castling.pattern, MovePattern::Castling(Flank::Queenside));
+        assert!(castling.is_checkmate);
+        assert_eq!(castling.annotation, None);
+
+        assert!(parse_san("e8Q").unwrap().legacy_notation);
+        assert!(!parse_san("e8=Q ").unwrap().legacy_notation);
+        assert_eq!(parse_san(""), Err(SanError::Empty));
+        assert_eq!(parse_san("Ze4"), Err(SanError::Invalid));
+        assert_eq!(parse_san(" e4"), Err(SanError::Invalid));
+        assert_eq!(parse_san("e4 e5"), Err(SanError::TrailingText(3)));
+    }
+}
@@ -0,0 +1,479 @@
+//! Syntax-only PGN parsing: the move tree as written, without replaying positions.
+
+use indexmap::IndexMap;
+use logos::Logos;
+
+use crate::{
+    Color,
+    TypedPosition,
+    logic::{fen::FenFields, game_state::GameResult},
+    r#move::{Move, MoveList},
+    pgn::{
+        error::PgnError,
+        move_data::PgnMoveData,
+        move_tree::{MoveTree, MoveTreeNodeId},
+        object::{PgnObject, play_move},
+        parser::DEFAULT_MAX_VARIATION_DEPTH,
+        rendering_config::PgnRenderingConfig,
+        san::parse_san,
+        token::PgnToken,
+        token_types::{PgnCommonMoveInfo, escape_tag_value, render_comment},
+    },
+};
+
+/// A move as written in the movetext; never checked for legality.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnSyntaxMove {
+    /// SAN text including any check or mate marker (e.g. `Nbd7`, `exd8=Q+`, `O-O`).
+    pub san: String,
+    /// Symbolic annotation (`!`, `?!`, ...), if present.
+    pub annotation: Option<String>,
+    /// Numeric annotation glyph (`$N`), if present.
+    pub nag: Option<u8>,
+    /// Comment following the move.
+    pub comment: Option<String>,
+    /// Alternatives to this move, each a line starting at this move's ply.
+    pub variations: Vec<Vec<PgnSyntaxMove>>,
+}
+
+/// A PGN game parsed for structure only, via [`PgnSyntaxGame::parse`].
+///
+/// Parsing skips move generation entirely, so it is much faster than [`PgnParser`] and accepts
+/// illegal or ambiguous moves. Call [`PgnSyntaxGame::resolve`] to validate the game and obtain a
+/// [`PgnObject`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PgnSyntaxGame {
+    /// PGN tag pairs, in input order.
+    pub tags: IndexMap<String, String>,
+    /// Comment before the first move.
+    pub game_comment: Option<String>,
+    /// Main line.
+    pub moves: Vec<PgnSyntaxMove>,
+    /// Game-terminating token, or [`GameResult::Ongoing`] if absent.
+    pub result: GameResult,
+}
+
+impl PgnSyntaxGame {
+    /// Parses the tags and move tree of a single game without checking move legality.
+    ///
+    /// Move numbers are skipped rather than verified, and comments at the start of a variation
+    /// are dropped.
+    pub fn parse(pgn: &str) -> Result<PgnSyntaxGame, PgnError> {
+        let mut game = PgnSyntaxGame::default();
+        // Innermost line last; lines other than the main line are open variations.
+        let mut lines: Vec<Vec<PgnSyntaxMove>> = vec![Vec::new()];
+        let mut has_result = false;
+
+        let mut lexer = PgnToken::lexer(pgn);
+        while let Some(token) = lexer.next() {
+            let text = lexer.slice().trim();
+            let token = token.map_err(|err| match err {
+                PgnError::InvalidToken(empty) if empty.is_empty() => {
+                    PgnError::InvalidToken(text.to_string())
+                }
+                err => err,
+            })?;
+            let unexpected = || PgnError::UnexpectedToken(text.to_string());
+            if has_result && !matches!(token, PgnToken::Comment(_)) {
+                return Err(unexpected());
+            }
+
+            let open_variations = lines.len() - 1;
+            let current_line = lines.last_mut().expect("main line is never closed");
+            match token {
+                PgnToken::Tag(tag) => {
+                    if open_variations > 0 || !current_line.is_empty() {
+                        return Err(unexpected());
+                    }
+                    game.tags.insert(tag.name, tag.value);
+                }
+                PgnToken::MoveNumber(_) => {}
+                PgnToken::NonCastlingMove(pgn_move) => {
+                    current_line.push(syntax_move(text, pgn_move.common_move_info));
+                }
+                PgnToken::CastlingMove(pgn_move) => {
+                    current_line.push(syntax_move(text, pgn_move.common_move_info));
+                }
+                PgnToken::StartVariation => {
+                    if current_line.is_empty() {
+                        return Err(unexpected());
+                    }
+                    if open_variations >= DEFAULT_MAX_VARIATION_DEPTH {
+                        return Err(PgnError::VariationTooDeep(DEFAULT_MAX_VARIATION_DEPTH));
+                    }
+                    lines.push(Vec::new());
+                }
+                PgnToken::EndVariation => {
+                    if open_variations == 0 || current_line.is_empty() {
+                        return Err(unexpected());
+                    }
+                    let variation = lines.pop().expect("checked above");
+                    let varied_move = lines
+                        .last_mut()
+                        .and_then(|line| line.last_mut())
+                        .expect("variations start after a move");
+                    varied_move.variations.push(variation);
+                }
+                PgnToken::Comment(comment) => {
+                    let comment = comment.comment.trim();
+                    if comment.is_empty() {
+                        continue;
+                    }
+                    let target = match current_line.last_mut() {
+                        Some(last_move) => &mut last_move.comment,
+                        None if open_variations == 0 => &mut game.game_comment,
+                        None => continue,
+                    };
+                    match target {
+                        Some(existing) => {
+                            existing.push(' ');
+                            existing.push_str(comment);
+                        }
+                        None => *target = Some(comment.to_string()),
+                    }
+                }
+                PgnToken::Result(winner) => {
+                    game.result = GameResult::from_winner(winner);
+                    has_result = true;
+                }
+                PgnToken::Incomplete => {
+                    game.result = GameResult::Ongoing;
+                    has_result = true;
+                }
+            }
+            if has_result && lines.len() > 1 {
+                return Err(unexpected());
+            }
+        }
+
+        if lines.len() > 1 {
+            return Err(PgnError::UnexpectedEndOfInput(
+                "unclosed variation".to_string(),
+            ));
+        }
+        game.moves = lines.pop().expect("main line is never closed");
+        Ok(game)
+    }
+
+    /// Renders the game as PGN, numbering moves from the side to move and fullmove number of
+    /// the `FEN` tag, or from the standard initial position if there is none.
+    pub fn render(&self, include_variations: bool, config: PgnRenderingConfig) -> String {
+        let mut result = String::new();
+        for (key, value) in self.tags.iter() {
+            result.push_str(&format!("[{} \"{}\"]\n", key, escape_tag_value(value)));
+        }
+        let mut movetext = Vec::new();
+        if config.include_comments
+            && let Some(comment) = &self.game_comment
+        {
+            movetext.push(render_comment(comment, false));
+        }
+        render_line(
+            &self.moves,
+            self.start_ply(),
+            include_variations,
+            config,
+            &mut movetext,
+        );
+        movetext.push(self.result.to_string());
+        result.push_str(&movetext.join(" "));
+        result
+    }
+
+    /// Plies from the standard initial position to the start position, as numbered by the `FEN`
+    /// tag; `0` if the tags set up no valid FEN.
+    fn start_ply(&self) -> usize {
+        self.tags
+            .get("FEN")
+            .filter(|_| self.tags.get("SetUp").map(String::as_str) != Some("0"))
+            .and_then(|fen| FenFields::parse(fen).ok())
+            .map_or(0, |fields| {
+                let black_to_move = fields.side_to_move() == Color::Black;
+                2 * (fields.fullmove_number() as usize).saturating_sub(1) + black_to_move as usize
+            })
+    }
+
+    /// Replays the game with full legality checking, as [`PgnParser`] would with its default
+    /// settings.
+    ///
+    /// [`PgnParser`]: crate::pgn::PgnParser
+    pub fn resolve<const N: usize>(&self) -> Result<PgnObject<N>, PgnError> {
+        let mut object = PgnObject::<N>::new();
+        for (key, value) in &self.tags {
+            object.add_tag(key.clone(), value.clone());
+        }
+        let start_position = match object
+            .tag("FEN")
+            .filter(|_| object.tag("SetUp") != Some("0"))
+        {
+            Some(fen) => TypedPosition::<N>::from_fen(fen)
+                .map_err(|_| PgnError::InvalidFen(fen.to_string()))?,
+            None => object.start_position(),
+        };
+        object.set_game_comment(self.game_comment.clone());
+        replay_line(
+            &mut object.tree,
+            MoveTree::ROOT,
+            &start_position,
+            &self.moves,
+            0,
+        )?;
+
+        // A `?` tag records an unknown result, so any token agrees with it.
+        if let Some(tag) = object.tag("Result").filter(|value| value.trim() != "?")
+            && GameResult::from_pgn_str(tag) != Some(self.result)
+        {
+            return Err(PgnError::ResultMismatch {
+                tag: tag.to_string(),
+                token: self.result,
+            });
+        }
+        object.result = self.result;
+        Ok(object)
+    }
+}
+
+/// Adds the moves of `line`, played from `position`, as a line continuing `parent`.
+///
+/// `open_variations` counts the variations enclosing `line`, as the parser limits it.
+fn replay_line<const N: usize>(
+    tree: &mut MoveTree,
+    mut parent: MoveTreeNodeId,
+    position: &TypedPosition<N>,
+    line: &[PgnSyntaxMove],
+    open_variations: usize,
+) -> Result<(), PgnError> {
+    let mut position = position;
+    let mut after_move: TypedPosition<N>;
+    for syntax_move in line {
+        let move_ = resolve_san(position, &syntax_move.san)?;
+        let annotation = syntax_move
+            .annotation
+            .as_deref()
+            .map(|annotation| tree.intern(annotation));
+        let node = tree.add_continuation(
+            parent,
+            PgnMoveData {
+                move_,
+                annotation,
+                nag: syntax_move.nag,
+                written_with_zeros: syntax_move.san.starts_with('0'),
+            },
+        );
+        tree.set_comment(node, syntax_move.comment.as_deref());
+        for variation in &syntax_move.variations {
+            if open_variations >= DEFAULT_MAX_VARIATION_DEPTH {
+                return Err(PgnError::VariationTooDeep(DEFAULT_MAX_VARIATION_DEPTH));
+            }
+            if variation.is_empty() {
+                return Err(PgnError::UnexpectedToken(")".to_string()));
+            }
+            replay_line(tree, parent, position, variation, open_variations + 1)?;
+        }
+        after_move = play_move(position, move_);
+        position = &after_move;
+        parent = node;
+    }
+    Ok(())
+}
+
+/// Finds the single legal move in `position` that `san` describes.
+fn resolve_san<const N: usize>(position: &TypedPosition<N>, san: &str) -> Result<Move, PgnError> {
+    let invalid = || PgnError::InvalidMove(san.to_string());
+    let san_move = parse_san(san).map_err(|_| invalid())?;
+    if san_move.legacy_notation {
+        return Err(invalid());
+    }
+    let mut moves = MoveList::new();
+    let board = match position {
+        TypedPosition::White(p) => {
+            p.generate_moves(&mut moves);
+            p.board()
+        }
+        TypedPosition::Black(p) => {
+            p.generate_moves(&mut moves);
+            p.board()
+        }
+    };
+    let mut matches = moves
+        .as_slice()
+        .iter()
+        .filter(|&&move_| san_move.pattern.matches_on_board(move_, board));
+    match (matches.next(), matches.next()) {
+        (Some(&move_), None) => Ok(move_),
+        (Some(_), Some(_)) => Err(PgnError::AmbiguousMove(san.to_string())),
+        (None, _) => Err(PgnError::IllegalMove(san.to_string())),
+    }
+}
+
+/// Splits a move token into its SAN and the already-parsed annotation and NAG.
+fn syntax_move(text: &str, common_move_info: PgnCommonMoveInfo) -> PgnSyntaxMove {
+    let san = text
+        .split('$')
+        .next()
+        .unwrap_or_default()
+        .trim_end()
+        .trim_end_matches(['?', '!']);
+    PgnSyntaxMove {
+        san: san.to_string(),
+        annotation: common_move_info.annotation.filter(|a| !a.is_empty()),
+        nag: common_move_info.nag,
+        comment: None,
+        variations: Vec::new(),
+    }
+}
+
+/// Appends the tokens of `line`, whose first move is at `ply` from the initial position.
+fn render_line(
+    line: &[PgnSyntaxMove],
+    first_ply: usize,
+    include_variations: bool,
+    config: PgnRenderingConfig,
+    tokens: &mut Vec<String>,
+) {
+    let mut remind_fullmove = true;
+    for (ply, syntax_move) in (first_ply..).zip(line) {
+        let fullmove = ply / 2 + 1;
+        let mut token = match (ply % 2, remind_fullmove) {
+            (0, _) => format!("{fullmove}. "),
+            (_, true) => format!("{fullmove}... "),
+            (_, false) => String::new(),
+        };
+        token.push_str(&syntax_move.san);
+        if config.include_annotations
+            && let Some(annotation) = &syntax_move.annotation
+        {
+            token.push_str(annotation);
+        }
+        if config.include_nags
+            && let Some(nag) = syntax_move.nag
+        {
+            token.push_str(&format!(" ${nag}"));
+        }
+        tokens.push(token);
+        remind_fullmove = false;
+
+        if config.include_comments
+            && let Some(comment) = &syntax_move.comment
+        {
+            tokens.push(render_comment(comment, false));
+            remind_fullmove = true;
+        }
+        if include_variations {
+            for variation in &syntax_move.variations {
+                let mut variation_tokens = Vec::new();
+                render_line(variation, ply, true, config, &mut variation_tokens);
+                tokens.push(format!("({})", variation_tokens.join(" ")));
+                remind_fullmove = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pgn::PgnParser;
+
+    #[test]
+    fn test_syntax_parse_keeps_structure_without_legality() {
+        let pgn = "[Event \"Test\"]\n{Opening} 1. e4 e5!? $1 (1... c5 {Sicilian} 2. Nf3) 2. Ke3 {illegal} 1-0";
+        let game = PgnSyntaxGame::parse(pgn).unwrap();
+        assert_eq!(game.tags["Event"], "Test");
+        assert_eq!(game.game_comment.as_deref(), Some("Opening"));
+        assert_eq!(game.result, GameResult::WhiteWins);
+
+        let sans: Vec<&str> = game.moves.iter().map(|m| m.san.as_str()).collect();
+        assert_eq!(sans, ["e4", "e5", "Ke3"]);
+        assert_eq!(game.moves[1].annotation.as_deref(), Some("!?"));
+        assert_eq!(game.moves[1].nag, Some(1));
+        assert_eq!(game.moves[1].variations.len(), 1);
+        assert_eq!(game.moves[1].variations[0][0].san, "c5");
+        assert_eq!(
+            game.moves[1].variations[0][0].comment.as_deref(),
+            Some("Sicilian")
+        );
+
+        assert_eq!(
+            game.render(true, PgnRenderingConfig::all_markings()),
+            "[Event \"Test\"]\n{Opening} 1. e4 e5!? $1 (1... c5 {Sicilian} 2. Nf3) 2. Ke3 {illegal} 1-0"
+        );
+        assert!(matches!(game.resolve::<8>(), Err(PgnError::IllegalMove(_))));
+    }
+
+    #[test]
+    fn test_syntax_resolve() {
+        let pgn = "[Result \"*\"]\n{Open} 1. e4 e5!? $1 (1... c5 {Sicilian} 2. Nf3 (2. Nc3 d6) Nc6) \
+                   2. Nf3 Nc6 3. Bc4 Nf6 4. 0-0 {castled} *";
+        let object = PgnSyntaxGame::parse(pgn).unwrap().resolve::<8>().unwrap();
+        assert_eq!(object.main_line().len(), 7);
+
+        let mut parser = PgnParser::<8>::new(pgn);
+        parser.parse().unwrap();
+        assert_eq!(
+            object.render(true, PgnRenderingConfig::all_markings()),
+            parser
+                .constructed_object
+                .render(true, PgnRenderingConfig::all_markings())
+        );
+
+        let mismatch = PgnSyntaxGame::parse("[Result \"1-0\"]\n1. e4 *").unwrap();
+        assert!(matches!(
+            mismatch.resolve::<8>(),
+            Err(PgnError::ResultMismatch { .. })
+        ));
+        let ambiguous = PgnSyntaxGame::parse(
+            "[SetUp \"1\"]\n[FEN \"k7/8/8/8/8/8/8/KN3N2 w - - 0 1\"]\n1. Nd2 *",
+        )
+        .unwrap();
+        assert!(matches!(
+            ambiguous.resolve::<8>(),
+            Err(PgnError::AmbiguousMove(_))
+        ));
+    }
+
+    #[test]
+    fn test_syntax_variation_depth_matches_parser() {
+        let nested =
+            |depth: usize| format!("1. e4 {}{} *", "(1. d4 ".repeat(depth), ")".repeat(depth));
+        let deepest = nested(DEFAULT_MAX_VARIATION_DEPTH);
+        assert!(PgnParser::<8>::new(&deepest).parse().is_ok());
+        let game = PgnSyntaxGame::parse(&deepest).unwrap();
+        assert!(game.resolve::<8>().is_ok());
+
+        let too_deep = nested(DEFAULT_MAX_VARIATION_DEPTH + 1);
+        assert!(matches!(
+            PgnParser::<8>::new(&too_deep).parse(),
+            Err(PgnError::VariationTooDeep(DEFAULT_MAX_VARIATION_DEPTH))
+        ));
+        assert!(matches!(
+            PgnSyntaxGame::parse(&too_deep),
+            Err(PgnError::VariationTooDeep(DEFAULT_MAX_VARIATION_DEPTH))
+        ));
+    }
+
+    #[test]
+    fn test_syntax_render_numbers_black_first_games() {
+        let pgn = "[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 12\"]\n\
+                   12... Kd7 (12... Ke7 13. e4) 13. e4 Kc6 *";
+        let game = PgnSyntaxGame::parse(pgn).unwrap();
+        assert_eq!(game.render(true, PgnRenderingConfig::no_markings()), pgn);
+        assert_eq!(game.resolve::<8>().unwrap().main_line().len(), 3);
+    }
+
+    #[test]
+    fn test_syntax_parse_rejects_bad_structure() {
+        assert!(matches!(
+            PgnSyntaxGame::parse("1. e4 (1. d4"),
+            Err(PgnError::UnexpectedEndOfInput(_))
+        ));
+        assert!(matches!(
+            PgnSyntaxGame::parse("(1. d4) 1. e4"),
+            Err(PgnError::UnexpectedToken(_))
+        ));
+        assert!(matches!(
+            PgnSyntaxGame::parse("1. e4 1-0 e5"),
+            Err(PgnError::UnexpectedToken(_))
+        ));
+    }
+}
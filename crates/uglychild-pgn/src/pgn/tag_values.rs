@@ -0,0 +1,188 @@
+//! Typed values of the standard `Date` and `Round` tags.
+
+use std::fmt::{Display, Formatter};
+
+/// A `Date` tag value (`YYYY.MM.DD`), where any field may be unknown (`????`, `??`).
+///
+/// Dates order chronologically, with an unknown field sorting before every known value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PgnDate {
+    /// Year, if known.
+    pub year: Option<u16>,
+    /// Month (1-12), if known.
+    pub month: Option<u8>,
+    /// Day of the month (1-31), if known.
+    pub day: Option<u8>,
+}
+
+impl PgnDate {
+    /// Parses a `Date` tag value, ignoring surrounding whitespace.
+    ///
+    /// Fields written with `?` are unknown, as are missing trailing fields (`1992`,
+    /// `1992.11`). Returns `None` for values that are not dates, including out-of-range months
+    /// and days.
+    pub fn from_tag_value(value: &str) -> Option<PgnDate> {
+        let mut fields = value.trim().split('.');
+        let year = parse_field(fields.next()?, 4)?;
+        let month = fields
+            .next()
+            .map_or(Some(None), |field| parse_field(field, 2))?;
+        let day = fields
+            .next()
+            .map_or(Some(None), |field| parse_field(field, 2))?;
+        if fields.next().is_some() {
+            return None;
+        }
+        let date = PgnDate {
+            year,
+            month: month.map(|month| month as u8),
+            day: day.map(|day| day as u8),
+        };
+        let month_ok = date.month.is_none_or(|month| (1..=12).contains(&month));
+        let day_ok = date.day.is_none_or(|day| (1..=31).contains(&day));
+        (month_ok && day_ok).then_some(date)
+    }
+}
+
+/// Parses a date field of `width` digits, or of `?`s for an unknown value.
+fn parse_field(field: &str, width: usize) -> Option<Option<u16>> {
+    if field.len() != width {
+        return None;
+    }
+    if field.bytes().all(|b| b == b'?') {
+        return Some(None);
+    }
+    if !field.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    field.parse().ok().map(Some)
+}
+
+impl Display for PgnDate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.year {
+            Some(year) => write!(f, "{year:04}.")?,
+            None => f.write_str("????.")?,
+        }
+        match self.month {
+            Some(month) => write!(f, "{month:02}.")?,
+            None => f.write_str("??.")?,
+        }
+        match self.day {
+            Some(day) => write!(f, "{day:02}"),
+            None => f.write_str("??"),
+        }
+    }
+}
+
+/// A `Round` tag value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum PgnRound {
+    /// `?`, an empty value, or no tag.
+    #[default]
+    Unknown,
+    /// `-`, for games not played as part of a round.
+    NotApplicable,
+    /// Dot-separated round numbers, e.g. `[4, 1]` for `4.1` (round 4, board or game 1).
+    Numbered(Vec<u32>),
+    /// Anything else, such as `Final`, as written.
+    Other(String),
+}
+
+impl PgnRound {
+    /// Parses a `Round` tag value, ignoring surrounding whitespace.
+    pub fn from_tag_value(value: &str) -> PgnRound {
+        match value.trim() {
+            "" | "?" => PgnRound::Unknown,
+            "-" => PgnRound::NotApplicable,
+            value => value
+                .split('.')
+                .map(|part| part.parse().ok())
+                .collect::<Option<Vec<u32>>>()
+                .map_or_else(|| PgnRound::Other(value.to_string()), PgnRound::Numbered),
+        }
+    }
+}
+
+impl Display for PgnRound {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PgnRound::Unknown => f.write_str("?"),
+            PgnRound::NotApplicable => f.write_str("-"),
+            PgnRound::Numbered(parts) => {
+                for (i, part) in parts.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(".")?;
+                    }
+                    write!(f, "{part}")?;
+                }
+                Ok(())
+            }
+            PgnRound::Other(value) => f.write_str(value),
+        }
+    }
+}
+
+/// Parses an Elo tag value (`WhiteElo`, `BlackElo`); `?`, `-`, and empty values are unknown.
+pub(crate) fn parse_elo(value: &str) -> Option<u16> {
+    value.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_parsing() {
+        let date = PgnDate::from_tag_value("1992.11.04").unwrap();
+        assert_eq!(
+            date,
+            PgnDate {
+                year: Some(1992),
+                month: Some(11),
+                day: Some(4),
+            }
+        );
+        assert_eq!(date.to_string(), "1992.11.04");
+
+        let partial = PgnDate::from_tag_value("1992.??.??").unwrap();
+        assert_eq!(
+            (partial.year, partial.month, partial.day),
+            (Some(1992), None, None)
+        );
+        assert_eq!(PgnDate::from_tag_value("1992"), Some(partial));
+        assert_eq!(
+            PgnDate::from_tag_value("????.??.??"),
+            Some(PgnDate::default())
+        );
+        assert!(partial < date);
+
+        assert_eq!(PgnDate::from_tag_value("1992.13.01"), None);
+        assert_eq!(PgnDate::from_tag_value("92.11.04"), None);
+        assert_eq!(PgnDate::from_tag_value("1992.11.04.1"), None);
+        assert_eq!(PgnDate::from_tag_value("Nov 1992"), None);
+    }
+
+    #[test]
+    fn test_round_parsing() {
+        assert_eq!(PgnRound::from_tag_value("4"), PgnRound::Numbered(vec![4]));
+        assert_eq!(
+            PgnRound::from_tag_value("4.1"),
+            PgnRound::Numbered(vec![4, 1])
+        );
+        assert_eq!(PgnRound::from_tag_value("?"), PgnRound::Unknown);
+        assert_eq!(PgnRound::from_tag_value("-"), PgnRound::NotApplicable);
+        assert_eq!(
+            PgnRound::from_tag_value("Final"),
+            PgnRound::Other("Final".to_string())
+        );
+        assert_eq!(PgnRound::Numbered(vec![4, 1]).to_string(), "4.1");
+    }
+
+    #[test]
+    fn test_elo_parsing() {
+        assert_eq!(parse_elo(" 2712 "), Some(2712));
+        assert_eq!(parse_elo("?"), None);
+        assert_eq!(parse_elo(""), None);
+    }
+}
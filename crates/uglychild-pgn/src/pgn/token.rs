@@ -6,17 +6,16 @@ use crate::{
     Color,
     pgn::{
         error::PgnError,
+        parser::PgnCommentBracePolicy,
         token_types::{PgnCastlingMove, PgnComment, PgnMoveNumber, PgnNonCastlingMove, PgnTag},
     },
 };
 
-pub(crate) const TAG_REGEX: &str = r#"\[\s*([A-Za-z0-9_]+)\s+"([^"]*)"\s*\]"#;
+pub(crate) const TAG_REGEX: &str = r#"\[\s*([A-Za-z0-9_]+)\s+"((?:[^"\\]|\\.)*)"\s*\]"#;
 pub(crate) const MOVE_NUMBER_REGEX: &str = r"([0-9]+)\.+";
-pub(crate) const NON_CASTLING_MOVE_REGEX: &str =
-    r"([PNBRQK])?([a-h])?([1-8])?(x)?([a-h])([1-8])(?:=([NBRQ]))?([+#])?([?!]*)\s*(?:\$([0-9]+))?";
+pub(crate) const NON_CASTLING_MOVE_REGEX: &str = r"([PNBRQK])?([a-h])?([1-8])?([x:])?([a-h])([1-8])(?:(=)?([NBRQ]))?(\s*e\.p\.)?([+#])?([?!]*)\s*(?:\$([0-9]+))?";
 pub(crate) const CASTLING_MOVE_REGEX: &str =
-    r"(?:(O-O-O|0-0-0)|(O-O|0-0))([+#])?([?!]+)?\s*(?:\$([0-9]+))?";
-pub(crate) const COMMENT_REGEX: &str = r"\{([^}]*)\}";
+    r"(?:(O-O-O|0-0-0)|(O-O|0-0))([+#])?([?!]+)?([+#])?\s*(?:\$([0-9]+))?";
 
 /// Trait implemented by token payload types that can parse themselves from a lexer slice.
 pub trait ParsablePgnToken: Sized {
@@ -24,12 +23,24 @@ pub trait ParsablePgnToken: Sized {
     fn parse(lex: &mut Lexer<PgnToken>) -> Result<Self, PgnError>;
 }
 
+/// Skips a `%` escape line, which the PGN standard only recognizes in the first column.
+fn skip_escape_line(lex: &mut Lexer<PgnToken>) -> Result<(), PgnError> {
+    let start = lex.span().start;
+    if start == 0 || lex.source()[..start].ends_with('\n') {
+        Ok(())
+    } else {
+        Err(PgnError::InvalidToken(lex.slice().to_string()))
+    }
+}
+
 #[derive(Logos, Debug, PartialEq, Clone)]
 #[logos(skip r"\s+")]
+#[logos(skip(r"%[^\n]*", skip_escape_line, allow_greedy = true))]
 #[logos(error = PgnError)]
+#[logos(extras = PgnCommentBracePolicy)]
 pub enum PgnToken {
     // Tags [Name "Value"]
-    #[regex(r#"\[\s*([A-Za-z0-9_]+)\s+"([^"]*)"\s*\]"#, PgnTag::parse)]
+    #[regex(r#"\[\s*([A-Za-z0-9_]+)\s+"((?:[^"\\]|\\.)*)"\s*\]"#, PgnTag::parse)]
     /// Tag pair like `[Event "WCC"]`.
     Tag(PgnTag),
 
@@ -39,20 +50,21 @@ pub enum PgnToken {
     MoveNumber(PgnMoveNumber),
 
     // Moves like g4, Nf6, exd5+?!, etc.
-    #[regex(r"([PNBRQK])?([a-h])?([1-8])?(x)?([a-h])([1-8])(?:=([NBRQ]))?([+#])?([?!]*)\s*(?:\$([0-9]+))?", PgnNonCastlingMove::parse)]
+    #[regex(r"([PNBRQK])?([a-h])?([1-8])?([x:])?([a-h])([1-8])(?:(=)?([NBRQ]))?(\s*e\.p\.)?([+#])?([?!]*)\s*(?:\$([0-9]+))?", PgnNonCastlingMove::parse)]
     /// Non-castling move token.
     NonCastlingMove(PgnNonCastlingMove),
 
     #[regex(
-        r"(?:(O-O-O|0-0-0)|(O-O|0-0))([+#])?([?!]+)?\s*(?:\$([0-9]+))?",
+        r"(?:(O-O-O|0-0-0)|(O-O|0-0))([+#])?([?!]+)?([+#])?\s*(?:\$([0-9]+))?",
         PgnCastlingMove::parse
     )]
     /// Castling move token (`O-O`, `O-O-O`, and `0-0` variants).
     CastlingMove(PgnCastlingMove),
 
-    // Comments in { }
-    #[regex(r"\{([^}]*)\}", PgnComment::parse)]
-    /// Braced comment token.
+    // Comments in { }, read up to the closing brace by the callback, or from ; to end of line
+    #[token("{", PgnComment::parse)]
+    #[regex(r";[^\n]*", PgnComment::parse, allow_greedy = true)]
+    /// Braced (`{...}`) or rest-of-line (`; ...`) comment token.
     Comment(PgnComment),
 
     // Start of variation
@@ -77,6 +89,7 @@ pub enum PgnToken {
 }
 
 #[cfg(test)]
+#[allow(clippy::cmp_owned, clippy::while_let_on_iterator)]
 mod tests {
     use super::*;
     use crate::{Flank, Piece, Square};
@@ -108,6 +121,22 @@ mod tests {
         assert!(matches!(lexer.next(), Some(Ok(PgnToken::Result(None)))));
     }
 
+    #[test]
+    fn test_escape_lines_are_skipped() {
+        let mut lexer = PgnToken::lexer("% exported {by} tool\n*\n%\n");
+        assert!(matches!(lexer.next(), Some(Ok(PgnToken::Incomplete))));
+        assert_eq!(lexer.next(), None);
+
+        let mut lexer = PgnToken::lexer("* % not at line start");
+        lexer.next();
+        assert_eq!(
+            lexer.next(),
+            Some(Err(PgnError::InvalidToken(
+                "% not at line start".to_string()
+            )))
+        );
+    }
+
     #[test]
     fn test_lexing_incomplete() {
         let mut lexer = PgnToken::lexer("*");
@@ -1,44 +1,74 @@
 //! Metadata tokens: tags, move numbers, and comments.
 
-use std::sync::LazyLock;
+use std::{borrow::Cow, sync::LazyLock};
 
 use logos::Lexer;
 use regex::Regex;
 
 use crate::pgn::{
     error::PgnError,
-    token::{COMMENT_REGEX, MOVE_NUMBER_REGEX, ParsablePgnToken, PgnToken, TAG_REGEX},
+    parser::PgnCommentBracePolicy,
+    token::{MOVE_NUMBER_REGEX, ParsablePgnToken, PgnToken, TAG_REGEX},
 };
 
 static COMPILED_TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(TAG_REGEX).unwrap());
 static COMPILED_MOVE_NUMBER_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(MOVE_NUMBER_REGEX).unwrap());
-static COMPILED_COMMENT_REGEX: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(COMMENT_REGEX).unwrap());
 
 #[derive(Clone, Debug, PartialEq)]
 /// PGN tag pair (`[Name "Value"]`).
 pub struct PgnTag {
     /// Tag key (for example `Event`, `Site`, `Date`).
     pub name: String,
-    /// Tag value string without surrounding quotes.
+    /// Tag value without surrounding quotes, with `\"` and `\\` escapes resolved.
     pub value: String,
 }
 
 impl PgnTag {
-    /// Renders this tag back to PGN text.
+    /// Renders this tag back to PGN text, escaping quotes and backslashes in the value.
     pub fn render(&self) -> String {
-        format!("[{} \"{}\"]", self.name, self.value)
+        format!("[{} \"{}\"]", self.name, escape_tag_value(&self.value))
     }
 }
 
+/// Escapes `"` and `\` in a tag value as `\"` and `\\`, as the PGN standard requires.
+pub(crate) fn escape_tag_value(value: &str) -> Cow<'_, str> {
+    if !value.contains(['"', '\\']) {
+        return Cow::Borrowed(value);
+    }
+    let mut escaped = String::with_capacity(value.len() + 2);
+    for c in value.chars() {
+        if matches!(c, '"' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    Cow::Owned(escaped)
+}
+
+/// Resolves the `\"` and `\\` escapes of a quoted tag value. Any other backslash is kept.
+fn unescape_tag_value(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some(next @ ('"' | '\\'))) => {
+                unescaped.push(next);
+                chars.next();
+            }
+            _ => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
 impl ParsablePgnToken for PgnTag {
     fn parse(lex: &mut Lexer<PgnToken>) -> Result<Self, PgnError> {
         let text = lex.slice();
 
         if let Some(captures) = COMPILED_TAG_REGEX.captures(text) {
             let name = captures.get(1).unwrap().as_str().to_string();
-            let value = captures.get(2).unwrap().as_str().to_string();
+            let value = unescape_tag_value(captures.get(2).unwrap().as_str());
             Ok(Self { name, value })
         } else {
             Err(PgnError::InvalidTag(text.to_string()))
@@ -78,30 +108,67 @@ impl ParsablePgnToken for PgnMoveNumber {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-/// Braced PGN comment token.
+/// PGN comment token, braced (`{...}`) or rest-of-line (`; ...`).
 pub struct PgnComment {
-    /// Comment body without surrounding braces.
+    /// Comment body without the braces or the leading `;`.
     pub comment: String,
 }
 
 impl PgnComment {
-    /// Renders this comment back to PGN text.
+    /// Renders this comment back to PGN text (see [`render_comment`]).
     pub fn render(&self) -> String {
-        format!("{{{}}}", self.comment)
+        render_comment(&self.comment, false)
+    }
+}
+
+/// Renders `comment` as a braced comment, padded with spaces inside the braces if `padded`.
+///
+/// Braced comments cannot contain `}`, so such a comment is written as a rest-of-line `;`
+/// comment instead, with any line breaks replaced by spaces.
+pub(crate) fn render_comment(comment: &str, padded: bool) -> String {
+    if comment.contains('}') {
+        format!("; {}\n", comment.replace(['\r', '\n'], " "))
+    } else if padded {
+        format!("{{ {comment} }}")
+    } else {
+        format!("{{{comment}}}")
     }
 }
 
 impl ParsablePgnToken for PgnComment {
+    /// Called with a whole `;` comment or only the opening `{` as the slice; a braced comment
+    /// is then consumed up to its closing brace as set by the lexer's
+    /// [`PgnCommentBracePolicy`].
     fn parse(lex: &mut Lexer<PgnToken>) -> Result<Self, PgnError> {
-        let text = lex.slice();
+        if let Some(rest_of_line) = lex.slice().strip_prefix(';') {
+            return Ok(Self {
+                comment: rest_of_line.trim_end_matches('\r').to_string(),
+            });
+        }
 
-        match COMPILED_COMMENT_REGEX.captures(text) {
-            Some(captures) => {
-                let comment = captures.get(1).unwrap().as_str().to_string();
-                Ok(Self { comment })
+        let body = lex.remainder();
+        let mut depth = 1;
+        for (index, byte) in body.bytes().enumerate() {
+            match byte {
+                b'{' => match lex.extras {
+                    PgnCommentBracePolicy::Standard => {}
+                    PgnCommentBracePolicy::Nested => depth += 1,
+                    PgnCommentBracePolicy::Reject => {
+                        return Err(PgnError::InvalidComment(format!("{{{}", &body[..=index])));
+                    }
+                },
+                b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        let comment = body[..index].to_string();
+                        lex.bump(index + 1);
+                        return Ok(Self { comment });
+                    }
+                }
+                _ => {}
             }
-            None => Err(PgnError::InvalidComment(text.to_string())),
         }
+        Err(PgnError::InvalidComment(format!("{{{body}")))
     }
 }
 
@@ -121,6 +188,28 @@ mod tests {
         assert_eq!(tag.value, "F/S Return Match");
     }
 
+    #[test]
+    fn test_pgn_tag_escapes() {
+        let source = r#"[Annotator "A \"Quoted\" Name \\ C:\\dir\\ \n"]"#;
+        let mut lex = PgnToken::lexer(source);
+        lex.next();
+        let tag = PgnTag::parse(&mut lex).unwrap();
+        assert_eq!(tag.value, r#"A "Quoted" Name \ C:\dir\ \n"#);
+        assert_eq!(
+            tag.render(),
+            r#"[Annotator "A \"Quoted\" Name \\ C:\\dir\\ \\n"]"#
+        );
+
+        let rendered = tag.render();
+        let mut lex = PgnToken::lexer(&rendered);
+        lex.next();
+        assert_eq!(PgnTag::parse(&mut lex).unwrap(), tag);
+
+        let mut lex = PgnToken::lexer(r#"[Event "ends with \\"]"#);
+        lex.next();
+        assert_eq!(PgnTag::parse(&mut lex).unwrap().value, "ends with \\");
+    }
+
     #[test]
     fn test_pgn_tag_invalid() {
         let mut lex = PgnToken::lexer(" [Event \"F/S Return Match] ");
@@ -129,17 +218,60 @@ mod tests {
         assert!(result.is_err());
     }
 
+    fn lex_comment(
+        source: &str,
+        policy: PgnCommentBracePolicy,
+    ) -> Option<Result<PgnToken, PgnError>> {
+        let mut lex = PgnToken::lexer_with_extras(source, policy);
+        lex.next()
+    }
+
+    fn comment(text: &str) -> Option<Result<PgnToken, PgnError>> {
+        Some(Ok(PgnToken::Comment(PgnComment {
+            comment: text.to_string(),
+        })))
+    }
+
     #[test]
     fn test_comment() {
-        let mut lex = PgnToken::lexer("{This is a comment}");
-        lex.next();
-        let token = PgnComment::parse(&mut lex).unwrap();
-        assert_eq!(token.comment, "This is a comment");
+        let standard = PgnCommentBracePolicy::Standard;
+        assert_eq!(
+            lex_comment("{This is a comment}", standard),
+            comment("This is a comment")
+        );
+        assert!(matches!(
+            lex_comment("{This is an invalid comment", standard),
+            Some(Err(PgnError::InvalidComment(_)))
+        ));
+    }
 
-        let mut lex = PgnToken::lexer("{This is an invalid comment");
-        lex.next();
-        let token = PgnComment::parse(&mut lex);
-        assert!(token.is_err());
+    #[test]
+    fn test_rest_of_line_comment() {
+        let mut lex = PgnToken::lexer("; engine: depth 20 {pv}\r\n1.");
+        assert_eq!(lex.next(), comment(" engine: depth 20 {pv}"));
+        assert!(matches!(lex.next(), Some(Ok(PgnToken::MoveNumber(_)))));
+        assert_eq!(render_comment(" a } b", true), ";  a } b\n");
+    }
+
+    #[test]
+    fn test_comment_brace_policies() {
+        let source = "{eval {+0.3} depth 20} e4";
+        assert_eq!(
+            lex_comment(source, PgnCommentBracePolicy::Standard),
+            comment("eval {+0.3")
+        );
+        assert_eq!(
+            lex_comment(source, PgnCommentBracePolicy::Nested),
+            comment("eval {+0.3} depth 20")
+        );
+        assert_eq!(
+            lex_comment(source, PgnCommentBracePolicy::Reject),
+            Some(Err(PgnError::InvalidComment("{eval {".to_string())))
+        );
+        assert!(matches!(
+            lex_comment("{a {b}", PgnCommentBracePolicy::Nested),
+            Some(Err(PgnError::InvalidComment(_)))
+        ));
     }
 
     #[test]
@@ -9,8 +9,10 @@ use crate::{
     File,
     Flank,
     Piece,
+    Rank,
     Square,
-    r#move::{Move, MoveFlag},
+    logic::move_pattern::MovePattern,
+    r#move::Move,
     pgn::{
         error::PgnError,
         token::{CASTLING_MOVE_REGEX, NON_CASTLING_MOVE_REGEX, ParsablePgnToken, PgnToken},
@@ -30,6 +32,18 @@ pub trait PgnMove: Debug {
 
     /// Returns shared check/annotation/NAG metadata.
     fn get_common_move_info(&self) -> &PgnCommonMoveInfo;
+
+    /// Returns whether the token uses a legacy form that only lenient parsing accepts.
+    ///
+    /// See [`PgnParser::with_lenient`](crate::pgn::PgnParser::with_lenient).
+    fn uses_legacy_notation(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the token is castling written with zeros (`0-0`, `0-0-0`).
+    fn written_with_zeros(&self) -> bool {
+        false
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -117,93 +131,99 @@ pub struct PgnNonCastlingMove {
     pub piece_moved: Piece,
     /// Promotion target piece or [`Piece::Null`] for non-promotions.
     pub promoted_to: Piece,
-    /// Whether SAN contains a capture marker (`x`) or implies en-passant capture.
+    /// Whether SAN contains a capture marker (`x`, or legacy `:`) or implies en-passant capture.
     pub is_capture: bool,
+    /// Whether the token uses a legacy form: `:` for captures, a promotion without `=`
+    /// (`e8Q`), or an `e.p.` suffix.
+    pub legacy_notation: bool,
     /// Shared check/annotation/NAG metadata.
     pub common_move_info: PgnCommonMoveInfo,
 }
 
-impl PgnMove for PgnNonCastlingMove {
-    fn matches_move(&self, move_: Move, board: &Board) -> bool {
-        let to = move_.to();
-        let from = move_.from();
-        let flag = move_.flag();
-        let promotion = match flag {
-            MoveFlag::Promotion => move_.promotion(),
-            _ => Piece::Null,
-        };
-
-        if self.to != to
-            || self.promoted_to != promotion
-            || self.piece_moved != board.piece_at(from)
-            || self.is_capture != move_.is_capture_on_board(board)
-        {
-            return false;
-        }
-
-        if let Some(file) = self.disambiguation_file
-            && from.file() as u8 != file as u8 - b'a'
-        {
-            return false;
-        }
-
-        if let Some(rank) = self.disambiguation_rank
-            && from.rank() as u8 != rank as u8 - b'1'
-        {
-            return false;
+impl PgnNonCastlingMove {
+    /// The move pattern this token describes; capture marking must match exactly.
+    pub fn pattern(&self) -> MovePattern {
+        MovePattern::Normal {
+            piece: self.piece_moved,
+            from_file: self.disambiguation_file.and_then(File::from_char),
+            from_rank: self.disambiguation_rank.and_then(Rank::from_char),
+            to: self.to,
+            promotion: match self.promoted_to {
+                Piece::Null => None,
+                piece => Some(piece),
+            },
+            capture: Some(self.is_capture),
         }
+    }
+}
 
-        true
+impl PgnMove for PgnNonCastlingMove {
+    fn matches_move(&self, move_: Move, board: &Board) -> bool {
+        self.pattern().matches_on_board(move_, board)
     }
 
     fn get_common_move_info(&self) -> &PgnCommonMoveInfo {
         &self.common_move_info
     }
+
+    fn uses_legacy_notation(&self) -> bool {
+        self.legacy_notation
+    }
+}
+
+impl PgnNonCastlingMove {
+    /// Parses the move at the start of `text`, returning it with the number of bytes it spans
+    /// (including any suffixes and NAG), or `None` if no move starts there.
+    pub(crate) fn parse_prefix(text: &str) -> Option<(PgnNonCastlingMove, usize)> {
+        let captures = COMPILED_NON_CASTLING_MOVE_REGEX
+            .captures(text)
+            .filter(|captures| captures.get(0).unwrap().start() == 0)?;
+        let piece_moved = match captures.get(1).map(|m| m.as_str().chars().next().unwrap()) {
+            None => Piece::Pawn,
+            Some(c) => Piece::from_uppercase_char(c)?,
+        };
+
+        let disambiguation_file = captures.get(2).map(|m| m.as_str().chars().next().unwrap());
+        let disambiguation_rank = captures.get(3).map(|m| m.as_str().chars().next().unwrap());
+
+        let to_file = File::from_char(captures.get(5).unwrap().as_str().chars().next().unwrap())?;
+        let to_rank = Rank::from_char(captures.get(6).unwrap().as_str().chars().next().unwrap())?;
+        let to = Square::from_rank_and_file(to_rank, to_file);
+
+        let promoted_to = captures
+            .get(8)
+            .and_then(|m| Piece::from_uppercase_char(m.as_str().chars().next().unwrap()))
+            .unwrap_or(Piece::Null);
+
+        let capture_marker = captures.get(4).map(|m| m.as_str());
+        let is_capture = capture_marker.is_some();
+        let legacy_notation = capture_marker == Some(":")
+            || (promoted_to != Piece::Null && captures.get(7).is_none())
+            || captures.get(9).is_some();
+        let check_or_checkmate = captures.get(10);
+        let annotation = captures.get(11);
+        let nag = captures.get(12);
+
+        let move_ = PgnNonCastlingMove {
+            disambiguation_file,
+            disambiguation_rank,
+            to,
+            piece_moved,
+            promoted_to,
+            is_capture,
+            legacy_notation,
+            common_move_info: PgnCommonMoveInfo::from(check_or_checkmate, annotation, nag),
+        };
+        Some((move_, captures.get(0).unwrap().end()))
+    }
 }
 
 impl ParsablePgnToken for PgnNonCastlingMove {
     fn parse(lex: &mut Lexer<PgnToken>) -> Result<Self, PgnError> {
         let text = lex.slice();
-        if let Some(captures) = COMPILED_NON_CASTLING_MOVE_REGEX.captures(text) {
-            let piece_moved = match captures.get(1).map(|m| m.as_str().chars().next().unwrap()) {
-                None => Piece::Pawn,
-                Some(c) => Piece::from_uppercase_char(c),
-            };
-
-            let disambiguation_file = captures.get(2).map(|m| m.as_str().chars().next().unwrap());
-            let disambiguation_rank = captures.get(3).map(|m| m.as_str().chars().next().unwrap());
-
-            let to_file_char = captures.get(5).unwrap().as_str().chars().next().unwrap();
-            let to_rank_char = captures.get(6).unwrap().as_str().chars().next().unwrap();
-            let to_file = to_file_char as u8 - b'a';
-            let to_rank = to_rank_char as u8 - b'1';
-            let to = Square::from_rank_and_file(
-                unsafe { to_rank.try_into().unwrap_unchecked() },
-                unsafe { File::try_from(to_file).unwrap_unchecked() },
-            );
-
-            let promoted_to = match captures.get(7) {
-                Some(m) => Piece::from_uppercase_char(m.as_str().chars().next().unwrap()),
-                None => Piece::Null,
-            };
-
-            let is_capture = captures.get(4).is_some();
-            let check_or_checkmate = captures.get(8);
-            let annotation = captures.get(9);
-            let nag = captures.get(10);
-
-            Ok(PgnNonCastlingMove {
-                disambiguation_file,
-                disambiguation_rank,
-                to,
-                piece_moved,
-                promoted_to,
-                is_capture,
-                common_move_info: PgnCommonMoveInfo::from(check_or_checkmate, annotation, nag),
-            })
-        } else {
-            Err(PgnError::InvalidMove(text.to_string()))
-        }
+        PgnNonCastlingMove::parse_prefix(text)
+            .map(|(move_, _)| move_)
+            .ok_or_else(|| PgnError::InvalidMove(text.to_string()))
     }
 }
 
@@ -212,54 +232,67 @@ impl ParsablePgnToken for PgnNonCastlingMove {
 pub struct PgnCastlingMove {
     /// Castling side (`O-O` -> kingside, `O-O-O` -> queenside).
     pub flank: Flank,
+    /// Whether the token was written with zeros (`0-0`) rather than the letter O.
+    pub written_with_zeros: bool,
     /// Shared check/annotation/NAG metadata.
     pub common_move_info: PgnCommonMoveInfo,
 }
 
 impl PgnMove for PgnCastlingMove {
-    fn matches_move(&self, move_: Move, _from_board: &Board) -> bool {
-        let flag = move_.flag();
-        let matches_flank = match self.flank {
-            Flank::Kingside => move_.to().file() == File::G,
-            Flank::Queenside => move_.to().file() == File::C,
-        };
-        if flag != MoveFlag::Castling || !matches_flank {
-            return false;
-        }
-
-        true
+    fn matches_move(&self, move_: Move, board: &Board) -> bool {
+        MovePattern::Castling(self.flank).matches_on_board(move_, board)
     }
 
     fn get_common_move_info(&self) -> &PgnCommonMoveInfo {
         &self.common_move_info
     }
+
+    fn written_with_zeros(&self) -> bool {
+        self.written_with_zeros
+    }
+}
+
+impl PgnCastlingMove {
+    /// Parses the castling move at the start of `text`, returning it with the number of bytes it
+    /// spans (including any suffixes and NAG), or `None` if no castling move starts there.
+    pub(crate) fn parse_prefix(text: &str) -> Option<(PgnCastlingMove, usize)> {
+        let captures = COMPILED_CASTLING_MOVE_REGEX
+            .captures(text)
+            .filter(|captures| captures.get(0).unwrap().start() == 0)?;
+        let (flank, castling) = match (captures.get(1), captures.get(2)) {
+            (_, Some(kingside)) => (Flank::Kingside, kingside),
+            (Some(queenside), _) => (Flank::Queenside, queenside),
+            (None, None) => return None,
+        };
+
+        // The check marker may also follow the annotation (`O-O-O!?+`), but not both.
+        let check_or_checkmate = match (captures.get(3), captures.get(5)) {
+            (Some(_), Some(_)) => return None,
+            (before, after) => before.or(after),
+        };
+        let annotation = captures.get(4);
+        let nag = captures.get(6);
+
+        let move_ = PgnCastlingMove {
+            flank,
+            written_with_zeros: castling.as_str().starts_with('0'),
+            common_move_info: PgnCommonMoveInfo::from(check_or_checkmate, annotation, nag),
+        };
+        Some((move_, captures.get(0).unwrap().end()))
+    }
 }
 
 impl ParsablePgnToken for PgnCastlingMove {
     fn parse(lex: &mut Lexer<PgnToken>) -> Result<Self, PgnError> {
         let text = lex.slice();
-        if let Some(captures) = COMPILED_CASTLING_MOVE_REGEX.captures(text) {
-            let flank = if captures.get(2).is_some() {
-                Flank::Kingside
-            } else {
-                Flank::Queenside
-            };
-
-            let check_or_checkmate = captures.get(3);
-            let annotation = captures.get(4);
-            let nag = captures.get(5);
-
-            Ok(PgnCastlingMove {
-                flank,
-                common_move_info: PgnCommonMoveInfo::from(check_or_checkmate, annotation, nag),
-            })
-        } else {
-            Err(PgnError::InvalidCastlingMove(text.to_string()))
-        }
+        PgnCastlingMove::parse_prefix(text)
+            .map(|(move_, _)| move_)
+            .ok_or_else(|| PgnError::InvalidCastlingMove(text.to_string()))
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use logos::Logos;
 
@@ -267,6 +300,7 @@ mod tests {
     use crate::{
         Color,
         Square,
+        r#move::MoveFlag,
         pgn::token::PgnToken,
         position::{INITIAL_FEN, Position},
     };
@@ -447,6 +481,31 @@ mod tests {
         assert_eq!(move_data.common_move_info.nag, Some(2));
     }
 
+    #[test]
+    fn test_parse_legacy_forms() {
+        let parse = |text: &str| {
+            let mut lex = PgnToken::lexer(text);
+            lex.next();
+            assert_eq!(lex.slice().trim(), text);
+            PgnNonCastlingMove::parse(&mut lex).unwrap()
+        };
+
+        let colon_capture = parse("B:c6");
+        assert!(colon_capture.is_capture);
+        assert!(colon_capture.legacy_notation);
+
+        let bare_promotion = parse("e8Q+");
+        assert_eq!(bare_promotion.promoted_to, Piece::Queen);
+        assert!(bare_promotion.common_move_info.is_check);
+        assert!(bare_promotion.legacy_notation);
+
+        let en_passant = parse("exd6 e.p.");
+        assert_eq!(en_passant.to, Square::D6);
+        assert!(en_passant.legacy_notation);
+
+        assert!(!parse("exd8=N").legacy_notation);
+    }
+
     #[test]
     fn test_matches_move() {
         let state = Position::<1, { Color::White }>::from_fen(
@@ -462,6 +521,7 @@ mod tests {
             to: Square::D4,
             promoted_to: Piece::Null,
             is_capture: false,
+            legacy_notation: false,
             common_move_info: PgnCommonMoveInfo {
                 is_check: false,
                 is_checkmate: false,
@@ -471,7 +531,7 @@ mod tests {
         };
 
         let actual_move = Move::new_non_promotion(Square::F3, Square::D4, MoveFlag::NormalMove);
-        assert!(knight_move.matches_move(actual_move, &state.board));
+        assert!(knight_move.matches_move(actual_move, state.board()));
 
         // Test with disambiguation
         let knight_move_with_file = {
@@ -479,7 +539,7 @@ mod tests {
             knight_move.disambiguation_file = Some('f');
             knight_move
         };
-        assert!(knight_move_with_file.matches_move(actual_move, &state.board));
+        assert!(knight_move_with_file.matches_move(actual_move, state.board()));
 
         // Test with incorrect file disambiguation
         let knight_move_with_wrong_file = {
@@ -487,7 +547,7 @@ mod tests {
             knight_move.disambiguation_file = Some('e');
             knight_move
         };
-        assert!(!knight_move_with_wrong_file.matches_move(actual_move, &state.board));
+        assert!(!knight_move_with_wrong_file.matches_move(actual_move, state.board()));
     }
 
     #[test]
@@ -561,6 +621,24 @@ mod tests {
         assert_eq!(castling_move.get_common_move_info().nag, None);
     }
 
+    #[test]
+    fn test_parse_castling_suffix_orderings_and_zeros() {
+        let parse = |text: &str| {
+            let mut lex = PgnToken::lexer(text);
+            lex.next();
+            assert_eq!(lex.slice().trim(), text);
+            PgnCastlingMove::parse(&mut lex)
+        };
+
+        let check_first = parse("O-O-O+!?").unwrap();
+        let check_last = parse("O-O-O!?+").unwrap();
+        assert_eq!(check_first, check_last);
+        assert!(check_last.common_move_info.is_check);
+        assert!(!check_last.written_with_zeros);
+        assert!(parse("0-0#").unwrap().written_with_zeros);
+        assert!(parse("O-O+!+").is_err());
+    }
+
     #[test]
     fn test_parse_queenside_castling_move_with_nag() {
         let mut lex = PgnToken::lexer("O-O-O $1");
@@ -597,6 +675,7 @@ mod tests {
     fn test_castling_matches_move() {
         let castling_move = PgnCastlingMove {
             flank: Flank::Kingside,
+            written_with_zeros: false,
             common_move_info: PgnCommonMoveInfo {
                 is_check: false,
                 is_checkmate: false,
@@ -609,8 +688,8 @@ mod tests {
             Move::new_non_promotion(Square::E8, Square::G8, MoveFlag::Castling);
         let queenside_castling_move =
             Move::new_non_promotion(Square::E8, Square::C8, MoveFlag::Castling);
-        let kingside_match = castling_move.matches_move(kingside_castling_move, &state.board);
-        let queenside_match = castling_move.matches_move(queenside_castling_move, &state.board);
+        let kingside_match = castling_move.matches_move(kingside_castling_move, state.board());
+        let queenside_match = castling_move.matches_move(queenside_castling_move, state.board());
         assert!(kingside_match);
         assert!(!queenside_match);
     }
@@ -0,0 +1,186 @@
+//! Lazy iteration over the moves of a game together with the positions they are played from.
+
+use crate::{
+    TypedPosition,
+    r#move::Move,
+    pgn::{
+        move_tree::MoveTreeNodeId,
+        object::{PgnObject, play_move},
+    },
+};
+
+/// A point where a line leaves its parent line, as part of [`PgnWalkStep::path`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PgnBranch {
+    /// Number of moves played in the parent line before the variation's first move.
+    pub ply: usize,
+    /// Index of the variation among the alternatives at that ply; `1` is the first variation,
+    /// since `0` would be the parent line's own move.
+    pub variation: usize,
+}
+
+/// One move of a game, yielded by [`PgnObject::walk_main_line`] and
+/// [`PgnObject::walk_all_lines`].
+#[derive(Debug, Clone)]
+pub struct PgnWalkStep<'a, const M: usize> {
+    /// Tree node of the move.
+    pub node: MoveTreeNodeId,
+    /// Variations entered from the main line to reach this move, outermost first; empty on the
+    /// main line.
+    pub path: Vec<PgnBranch>,
+    /// Number of moves played before this one along its line.
+    pub ply: usize,
+    /// Position the move is played from.
+    pub position: TypedPosition<M>,
+    /// The move itself.
+    pub move_: Move,
+    /// SAN of the move, with its check or mate marker but no annotation.
+    pub san: String,
+    /// Suffix annotation (`!`, `?!`, ...), if any.
+    pub annotation: Option<&'a str>,
+    /// Numeric annotation glyph, if any.
+    pub nag: Option<u8>,
+    /// Comment after the move, if any.
+    pub comment: Option<&'a str>,
+}
+
+/// Iterator returned by [`PgnObject::walk_main_line`] and [`PgnObject::walk_all_lines`].
+pub struct PgnWalk<'a, const N: usize, const M: usize> {
+    object: &'a PgnObject<N>,
+    /// Nodes still to visit, each with the position before its move, its ply and its path.
+    pending: Vec<(MoveTreeNodeId, TypedPosition<M>, usize, Vec<PgnBranch>)>,
+    all_lines: bool,
+}
+
+impl<'a, const N: usize, const M: usize> PgnWalk<'a, N, M> {
+    fn new(object: &'a PgnObject<N>, all_lines: bool) -> Self {
+        let mut walk = PgnWalk {
+            object,
+            pending: Vec::new(),
+            all_lines,
+        };
+        walk.push_continuations(object.root_node(), object.start_position(), 0, &[]);
+        walk
+    }
+
+    /// Queues the continuations of `node`, the main continuation last so that it is visited
+    /// first.
+    fn push_continuations(
+        &mut self,
+        node: MoveTreeNodeId,
+        position: TypedPosition<M>,
+        ply: usize,
+        path: &[PgnBranch],
+    ) {
+        let continuations: Vec<MoveTreeNodeId> = match self.all_lines {
+            true => self.object.continuations(node).collect(),
+            false => self.object.continuations(node).take(1).collect(),
+        };
+        for (variation, &child) in continuations.iter().enumerate().rev() {
+            let mut child_path = path.to_vec();
+            if variation > 0 {
+                child_path.push(PgnBranch { ply, variation });
+            }
+            self.pending
+                .push((child, position.clone(), ply, child_path));
+        }
+    }
+}
+
+impl<'a, const N: usize, const M: usize> Iterator for PgnWalk<'a, N, M> {
+    type Item = PgnWalkStep<'a, M>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, position, ply, path) = self.pending.pop()?;
+        let move_ = self
+            .object
+            .node_move(node)
+            .expect("only the root has no move");
+        let after = play_move(&position, move_);
+        let san = match &position {
+            TypedPosition::White(before) => move_.display_with(before).to_string(),
+            TypedPosition::Black(before) => move_.display_with(before).to_string(),
+        };
+        self.push_continuations(node, after, ply + 1, &path);
+
+        Some(PgnWalkStep {
+            node,
+            path,
+            ply,
+            position,
+            move_,
+            san,
+            annotation: self.object.node_annotation(node),
+            nag: self.object.node_nag(node),
+            comment: self.object.node_comment(node),
+        })
+    }
+}
+
+impl<const N: usize> PgnObject<N> {
+    /// Lazily walks the main line, yielding each move with the position before it, its SAN and
+    /// its annotations.
+    ///
+    /// `M` is the context stack capacity of the yielded positions; each holds only its current
+    /// context, so `2` suffices (one for the position and one to play its move in).
+    pub fn walk_main_line<const M: usize>(&self) -> PgnWalk<'_, N, M> {
+        PgnWalk::new(self, false)
+    }
+
+    /// Like [`Self::walk_main_line`], but also walks every variation.
+    ///
+    /// Lines are walked depth first: each is followed to its end before returning to the most
+    /// recent variation not yet visited. [`PgnWalkStep::path`] tells the lines apart.
+    pub fn walk_all_lines<const M: usize>(&self) -> PgnWalk<'_, N, M> {
+        PgnWalk::new(self, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pgn::PgnParser;
+
+    #[test]
+    fn test_walk_main_line_and_all_lines() {
+        let mut parser =
+            PgnParser::<8>::new("1. e4 e5 (1... c5 2. Nf3 (2. Nc3) 2... d6) 2. Qh5!? Nc6 $6 *");
+        parser.parse().unwrap();
+        let game = &parser.constructed_object;
+
+        let main: Vec<_> = game.walk_main_line::<2>().collect();
+        let sans: Vec<&str> = main.iter().map(|step| step.san.as_str()).collect();
+        assert_eq!(sans, ["e4", "e5", "Qh5", "Nc6"]);
+        assert_eq!(main[2].annotation, Some("!?"));
+        assert_eq!(main[3].nag, Some(6));
+        assert_eq!(main[3].ply, 3);
+        assert!(matches!(main[1].position, TypedPosition::Black(_)));
+        assert!(main.iter().all(|step| step.path.is_empty()));
+
+        let all: Vec<(String, Vec<PgnBranch>)> = game
+            .walk_all_lines::<2>()
+            .map(|step| (step.san, step.path))
+            .collect();
+        let first = PgnBranch {
+            ply: 1,
+            variation: 1,
+        };
+        let nested = PgnBranch {
+            ply: 2,
+            variation: 1,
+        };
+        assert_eq!(
+            all,
+            [
+                ("e4".to_string(), vec![]),
+                ("e5".to_string(), vec![]),
+                ("Qh5".to_string(), vec![]),
+                ("Nc6".to_string(), vec![]),
+                ("c5".to_string(), vec![first]),
+                ("Nf3".to_string(), vec![first]),
+                ("d6".to_string(), vec![first]),
+                ("Nc3".to_string(), vec![first, nested]),
+            ]
+        );
+    }
+}
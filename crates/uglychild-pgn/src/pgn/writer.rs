@@ -0,0 +1,163 @@
+//! Game-at-a-time PGN output for long-running jobs, flushed after every game.
+
+use std::io;
+
+use crate::pgn::{object::PgnObject, rendering_config::PgnRenderingConfig};
+
+/// Appends games to a writer one at a time, flushing after each.
+///
+/// Every game is rendered in full before any of it is written and is followed by a blank line,
+/// so if the process stops between games, the output is a valid PGN database of the games
+/// written so far. Unlike [`write_games`], games need not all be available up front.
+///
+/// With the `gzip` feature, [`Self::with_gzip`] compresses each game as its own gzip member;
+/// concatenated members form a valid gzip file, so the same guarantee holds for compressed
+/// output.
+///
+/// [`write_games`]: crate::pgn::write_games
+pub struct PgnWriter<W: io::Write> {
+    writer: W,
+    include_variations: bool,
+    config: PgnRenderingConfig,
+    #[cfg(feature = "gzip")]
+    gzip: bool,
+    games_written: usize,
+}
+
+impl<W: io::Write> PgnWriter<W> {
+    /// Creates a writer appending to `writer`, rendering variations with all markings.
+    ///
+    /// `writer` should be empty or end at a game boundary, such as a file previously written
+    /// by a `PgnWriter`.
+    pub fn new(writer: W) -> PgnWriter<W> {
+        PgnWriter {
+            writer,
+            include_variations: true,
+            config: PgnRenderingConfig::all_markings(),
+            #[cfg(feature = "gzip")]
+            gzip: false,
+            games_written: 0,
+        }
+    }
+
+    /// Sets whether variations are written; `false` writes main lines only.
+    pub fn with_variations(mut self, include_variations: bool) -> Self {
+        self.include_variations = include_variations;
+        self
+    }
+
+    /// Sets how games are rendered.
+    pub fn with_rendering_config(mut self, config: PgnRenderingConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sets whether games are gzip-compressed, each as a separate gzip member.
+    #[cfg(feature = "gzip")]
+    pub fn with_gzip(mut self, gzip: bool) -> Self {
+        self.gzip = gzip;
+        self
+    }
+
+    /// Renders `game`, writes it with its result token and a blank line after it, and flushes
+    /// the underlying writer.
+    ///
+    /// On error, part of the game may have reached the writer.
+    pub fn write_game<const N: usize>(&mut self, game: &PgnObject<N>) -> io::Result<()> {
+        let mut rendered = Vec::new();
+        game.write_game_to(&mut rendered, self.include_variations, self.config)?;
+        rendered.extend_from_slice(b"\n\n");
+        #[cfg(feature = "gzip")]
+        if self.gzip {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            io::Write::write_all(&mut encoder, &rendered)?;
+            rendered = encoder.finish()?;
+        }
+        self.writer.write_all(&rendered)?;
+        self.writer.flush()?;
+        self.games_written += 1;
+        Ok(())
+    }
+
+    /// Number of games written so far.
+    pub fn games_written(&self) -> usize {
+        self.games_written
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pgn::{parse_games, write_games};
+
+    const GAMES: &str =
+        "[Event \"A\"]\n\n1. e4 e5 (1... c5) 2. Nf3 *\n\n[Event \"B\"]\n\n1. d4 d5 *\n\n";
+
+    #[test]
+    fn test_writer_matches_write_games() {
+        let games: Vec<PgnObject<8>> = parse_games(GAMES, Result::ok)
+            .into_iter()
+            .flatten()
+            .collect();
+
+        let mut writer = PgnWriter::new(Vec::new()).with_variations(false);
+        writer.write_game(&games[0]).unwrap();
+        let first_only = String::from_utf8(writer.get_ref().clone()).unwrap();
+        assert_eq!(
+            parse_games::<8, _>(&first_only, |game| game.is_ok()),
+            [true]
+        );
+        writer.write_game(&games[1]).unwrap();
+        assert_eq!(writer.games_written(), 2);
+
+        let mut expected = Vec::new();
+        write_games(
+            &games,
+            &mut expected,
+            false,
+            PgnRenderingConfig::all_markings(),
+        )
+        .unwrap();
+        assert_eq!(writer.into_inner(), expected);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip_members_concatenate() {
+        use std::io::Read;
+
+        let games: Vec<PgnObject<8>> = parse_games(GAMES, Result::ok)
+            .into_iter()
+            .flatten()
+            .collect();
+        let mut writer = PgnWriter::new(Vec::new()).with_gzip(true);
+        for game in &games {
+            writer.write_game(game).unwrap();
+        }
+
+        let mut decompressed = String::new();
+        flate2::read::MultiGzDecoder::new(writer.into_inner().as_slice())
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        let mut expected = Vec::new();
+        write_games(
+            &games,
+            &mut expected,
+            true,
+            PgnRenderingConfig::all_markings(),
+        )
+        .unwrap();
+        assert_eq!(decompressed.into_bytes(), expected);
+    }
+}
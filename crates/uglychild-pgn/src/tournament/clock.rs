@@ -0,0 +1,201 @@
+//! Time controls and a two-sided chess clock driven by measured move times.
+
+use std::time::Duration;
+
+use crate::Color;
+
+/// How a per-move delay protects the mover's clock.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClockDelay {
+    /// No delay.
+    #[default]
+    None,
+    /// Bronstein delay: the clock runs from the start of the move, and afterwards the time used,
+    /// up to the delay, is given back.
+    Bronstein(Duration),
+    /// Simple (US) delay: the clock only starts once the delay has passed.
+    Simple(Duration),
+}
+
+/// Base time plus increment and delay per move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeControl {
+    /// Initial time per side.
+    pub base: Duration,
+    /// Fischer increment, added after each move.
+    pub increment: Duration,
+    /// Delay applied to each move.
+    pub delay: ClockDelay,
+}
+
+impl TimeControl {
+    /// Base time with a Fischer increment.
+    pub fn fischer(base: Duration, increment: Duration) -> TimeControl {
+        TimeControl {
+            base,
+            increment,
+            delay: ClockDelay::None,
+        }
+    }
+
+    /// Base time with a Bronstein delay.
+    pub fn bronstein(base: Duration, delay: Duration) -> TimeControl {
+        TimeControl {
+            base,
+            increment: Duration::ZERO,
+            delay: ClockDelay::Bronstein(delay),
+        }
+    }
+
+    /// Base time with a simple delay.
+    pub fn simple_delay(base: Duration, delay: Duration) -> TimeControl {
+        TimeControl {
+            base,
+            increment: Duration::ZERO,
+            delay: ClockDelay::Simple(delay),
+        }
+    }
+}
+
+/// A side ran out of time; returned by [`ChessClock::record_move`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockFlag {
+    /// The side whose flag fell.
+    pub color: Color,
+}
+
+/// Both players' remaining time under a [`TimeControl`], with White to move first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChessClock {
+    time_control: TimeControl,
+    remaining: [Duration; 2],
+    side_to_move: Color,
+    flagged: Option<Color>,
+}
+
+impl ChessClock {
+    /// Starts both sides on `time_control.base`.
+    pub fn new(time_control: TimeControl) -> ChessClock {
+        ChessClock {
+            time_control,
+            remaining: [time_control.base; 2],
+            side_to_move: Color::White,
+            flagged: None,
+        }
+    }
+
+    /// The time control in force.
+    pub fn time_control(&self) -> TimeControl {
+        self.time_control
+    }
+
+    /// Time shown on `color`'s clock.
+    pub fn remaining(&self, color: Color) -> Duration {
+        self.remaining[color as usize]
+    }
+
+    /// The side whose clock is running.
+    pub fn side_to_move(&self) -> Color {
+        self.side_to_move
+    }
+
+    /// The side that ran out of time, if any.
+    pub fn flagged(&self) -> Option<Color> {
+        self.flagged
+    }
+
+    /// How long the side to move may think before its flag falls, counting a simple delay.
+    pub fn time_until_flag(&self) -> Duration {
+        let remaining = self.remaining(self.side_to_move);
+        match self.time_control.delay {
+            ClockDelay::Simple(delay) => remaining + delay,
+            ClockDelay::None | ClockDelay::Bronstein(_) => remaining,
+        }
+    }
+
+    /// Whether the side to move has flagged after thinking for `elapsed`.
+    pub fn is_flagged_after(&self, elapsed: Duration) -> bool {
+        elapsed > self.time_until_flag()
+    }
+
+    /// Charges `elapsed` to the side to move, applies its delay and increment, and passes the
+    /// turn to the other side.
+    ///
+    /// If the move took longer than [`Self::time_until_flag`], the mover's clock is left at zero,
+    /// the turn does not pass, and the flag is returned; later calls keep returning it.
+    pub fn record_move(&mut self, elapsed: Duration) -> Result<(), ClockFlag> {
+        if let Some(color) = self.flagged {
+            return Err(ClockFlag { color });
+        }
+        let color = self.side_to_move;
+        if self.is_flagged_after(elapsed) {
+            self.remaining[color as usize] = Duration::ZERO;
+            self.flagged = Some(color);
+            return Err(ClockFlag { color });
+        }
+
+        let remaining = &mut self.remaining[color as usize];
+        match self.time_control.delay {
+            ClockDelay::None => *remaining -= elapsed,
+            ClockDelay::Bronstein(delay) => *remaining = *remaining - elapsed + elapsed.min(delay),
+            ClockDelay::Simple(delay) => *remaining -= elapsed.saturating_sub(delay),
+        }
+        *remaining += self.time_control.increment;
+        self.side_to_move = color.other();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn test_increment_and_delays() {
+        let mut fischer = ChessClock::new(TimeControl::fischer(secs(60), secs(2)));
+        fischer.record_move(secs(5)).unwrap();
+        assert_eq!(fischer.remaining(Color::White), secs(57));
+        fischer.record_move(secs(1)).unwrap();
+        assert_eq!(fischer.remaining(Color::Black), secs(61));
+        assert_eq!(fischer.side_to_move(), Color::White);
+
+        let mut bronstein = ChessClock::new(TimeControl::bronstein(secs(60), secs(3)));
+        bronstein.record_move(secs(5)).unwrap();
+        assert_eq!(bronstein.remaining(Color::White), secs(58));
+        bronstein.record_move(secs(1)).unwrap();
+        assert_eq!(bronstein.remaining(Color::Black), secs(60));
+
+        let mut simple = ChessClock::new(TimeControl::simple_delay(secs(60), secs(3)));
+        simple.record_move(secs(5)).unwrap();
+        assert_eq!(simple.remaining(Color::White), secs(58));
+        simple.record_move(secs(1)).unwrap();
+        assert_eq!(simple.remaining(Color::Black), secs(60));
+    }
+
+    #[test]
+    fn test_flag_detection() {
+        // A simple delay extends the budget of the current move; a Bronstein delay does not.
+        let mut simple = ChessClock::new(TimeControl::simple_delay(secs(2), secs(3)));
+        assert_eq!(simple.time_until_flag(), secs(5));
+        assert!(!simple.is_flagged_after(secs(5)));
+        simple.record_move(secs(5)).unwrap();
+        assert_eq!(simple.remaining(Color::White), Duration::ZERO);
+
+        let mut bronstein = ChessClock::new(TimeControl::bronstein(secs(2), secs(3)));
+        assert!(bronstein.is_flagged_after(secs(3)));
+        assert_eq!(
+            bronstein.record_move(secs(3)),
+            Err(ClockFlag {
+                color: Color::White
+            })
+        );
+        assert_eq!(bronstein.flagged(), Some(Color::White));
+        assert_eq!(bronstein.remaining(Color::White), Duration::ZERO);
+        assert_eq!(bronstein.side_to_move(), Color::White);
+        assert!(bronstein.record_move(Duration::ZERO).is_err());
+    }
+}
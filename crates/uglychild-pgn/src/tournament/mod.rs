@@ -0,0 +1,745 @@
+//! Engine-versus-engine match runner with time controls and adjudication.
+//!
+//! Engines implement [`MatchEngine`]; [`play_match`] plays a series of games with alternating
+//! colors and returns every game as a [`MatchGame`] (including its PGN) plus the score.
+//! [`stats`] turns the score into Elo estimates and SPRT decisions. A [`MatchObserver`] passed
+//! to [`play_match_observed`] or [`play_game_observed`] hears of moves, captures, checks and
+//! results as they happen.
+
+use std::time::{Duration, Instant};
+
+mod clock;
+/// Elo estimates and SPRT computations over match results.
+pub mod stats;
+
+pub use clock::{ChessClock, ClockDelay, ClockFlag, TimeControl};
+
+use crate::{
+    Board,
+    Color,
+    Piece,
+    TypedPosition,
+    logic::game_state::{GameResult, TerminalReason},
+    r#move::{Move, MoveList},
+    pgn::{PgnObject, PgnRenderingConfig, zobrist_hash_of},
+    position::Position,
+};
+
+/// A move chosen by an engine, with its optional evaluation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EngineMove {
+    /// The chosen move; must be legal in the searched position.
+    pub move_: Move,
+    /// Evaluation in centipawns from the mover's point of view, used for adjudication.
+    pub score_cp: Option<i32>,
+}
+
+/// Remaining time passed to [`MatchEngine::search`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchClock {
+    /// Time left for the side to move, or `None` without a time control.
+    pub time_left: Option<Duration>,
+    /// Time left for the opponent, or `None` without a time control.
+    pub opponent_time_left: Option<Duration>,
+    /// Increment added after each move.
+    pub increment: Duration,
+    /// Delay applied to each move.
+    pub delay: ClockDelay,
+}
+
+/// A player in a match.
+///
+/// `N` is the position context capacity; it bounds the game length (see [`play_game`]).
+pub trait MatchEngine<const N: usize> {
+    /// Name used for the `White` / `Black` tags.
+    fn name(&self) -> &str;
+
+    /// Called before each game.
+    fn new_game(&mut self) {}
+
+    /// Chooses a move for the side to move in `position`, reached from the game's start position
+    /// by `moves`. Returning `None` resigns.
+    fn search(
+        &mut self,
+        position: &TypedPosition<N>,
+        moves: &[Move],
+        clock: SearchClock,
+    ) -> Option<EngineMove>;
+}
+
+/// Rules for ending games early.
+///
+/// Score-based rules use [`EngineMove::score_cp`] and require both engines to report scores.
+#[derive(Debug, Clone, Copy)]
+pub struct Adjudication {
+    /// Draw once the halfmove clock reaches 100.
+    pub fifty_move_rule: bool,
+    /// Draw when neither side can mate (see
+    /// [`Board::are_both_sides_insufficient_material`]).
+    pub insufficient_material: bool,
+    /// Draw on the third occurrence of a position.
+    pub threefold_repetition: bool,
+    /// Loss for a side once both engines agree it is down at least `score_cp` for `move_count`
+    /// consecutive moves each. Scores are taken after the move is played.
+    pub resign: Option<ScoreAdjudication>,
+    /// Draw once both engines report scores within `score_cp` of zero for `move_count`
+    /// consecutive moves each.
+    pub draw: Option<ScoreAdjudication>,
+    /// Probe returning the game result from White's view for a board and side to move, or
+    /// `None` when the position is not covered. [`GameResult::Ongoing`] is treated like `None`.
+    pub tablebase: Option<fn(&Board, Color) -> Option<GameResult>>,
+    /// Draw after this many plies.
+    pub max_plies: Option<usize>,
+}
+
+impl Default for Adjudication {
+    fn default() -> Self {
+        Adjudication {
+            fifty_move_rule: true,
+            insufficient_material: true,
+            threefold_repetition: true,
+            resign: None,
+            draw: None,
+            tablebase: None,
+            max_plies: None,
+        }
+    }
+}
+
+/// Score threshold held for a number of consecutive moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoreAdjudication {
+    /// Score threshold in centipawns.
+    pub score_cp: i32,
+    /// Consecutive moves per side the threshold must hold.
+    pub move_count: u32,
+}
+
+/// Match settings.
+#[derive(Debug, Clone)]
+pub struct MatchConfig {
+    /// Number of games; the first engine has White in even-numbered games (starting at 0).
+    pub games: usize,
+    /// Time control, or `None` for untimed games.
+    pub time_control: Option<TimeControl>,
+    /// Adjudication rules.
+    pub adjudication: Adjudication,
+    /// Value of the `Event` tag.
+    pub event: String,
+}
+
+impl Default for MatchConfig {
+    fn default() -> Self {
+        MatchConfig {
+            games: 2,
+            time_control: None,
+            adjudication: Adjudication::default(),
+            event: "Engine match".to_string(),
+        }
+    }
+}
+
+/// One finished game.
+#[derive(Debug, Clone)]
+pub struct MatchGame {
+    /// Name of the White engine.
+    pub white: String,
+    /// Name of the Black engine.
+    pub black: String,
+    /// Result of the game; never [`GameResult::Ongoing`].
+    pub result: GameResult,
+    /// Why the game ended; engine resignations, time forfeits and illegal moves are charged to
+    /// the side to move, and every adjudication rule reports [`TerminalReason::Adjudication`].
+    pub termination: TerminalReason,
+    /// Moves played.
+    pub moves: Vec<Move>,
+    /// The game as PGN, including tags and the result token.
+    pub pgn: String,
+}
+
+/// Outcome of [`play_match`], scored from the first engine's point of view.
+#[derive(Debug, Clone, Default)]
+pub struct MatchReport {
+    /// Every game in order.
+    pub games: Vec<MatchGame>,
+    /// Games won by the first engine.
+    pub wins: u32,
+    /// Drawn games.
+    pub draws: u32,
+    /// Games lost by the first engine.
+    pub losses: u32,
+}
+
+/// A move just played in a match game, passed to [`MatchObserver`].
+#[derive(Debug, Clone, Copy)]
+pub struct MatchMoveEvent<'a, const N: usize> {
+    /// Zero-based game number within the match.
+    pub round: usize,
+    /// Number of moves played before this one.
+    pub ply: usize,
+    /// Side that played the move.
+    pub mover: Color,
+    /// The move itself.
+    pub move_: Move,
+    /// Score the engine reported with the move, from the mover's point of view.
+    pub score_cp: Option<i32>,
+    /// Piece captured by the move, if any (a pawn for en passant).
+    pub captured: Option<Piece>,
+    /// Whether the move gives check.
+    pub gives_check: bool,
+    /// Position after the move.
+    pub position: &'a TypedPosition<N>,
+}
+
+/// Callbacks for match progress, so GUIs and bots can react without polling.
+///
+/// Every method defaults to doing nothing. For each move, [`Self::on_move`] runs first, then
+/// [`Self::on_capture`] and [`Self::on_check`] when they apply. `()` is an observer that
+/// ignores everything.
+pub trait MatchObserver<const N: usize> {
+    /// Called after every move.
+    fn on_move(&mut self, _event: &MatchMoveEvent<'_, N>) {}
+
+    /// Called after a move that captures `captured`.
+    fn on_capture(&mut self, _event: &MatchMoveEvent<'_, N>, _captured: Piece) {}
+
+    /// Called after a move that gives check, including checkmate.
+    fn on_check(&mut self, _event: &MatchMoveEvent<'_, N>) {}
+
+    /// Called once a game has finished, with the finished game.
+    fn on_result(&mut self, _round: usize, _game: &MatchGame) {}
+}
+
+impl<const N: usize> MatchObserver<N> for () {}
+
+/// Tracks consecutive moves satisfying a score condition, per side.
+#[derive(Default)]
+struct ScoreStreak {
+    streaks: [u32; 2],
+}
+
+impl ScoreStreak {
+    /// Records whether `color`'s latest move satisfied the condition; returns whether both
+    /// sides have now held it for `move_count` moves.
+    fn record(&mut self, color: Color, holds: bool, move_count: u32) -> bool {
+        let streak = &mut self.streaks[color as usize];
+        *streak = if holds { *streak + 1 } else { 0 };
+        self.streaks.iter().all(|&streak| streak >= move_count)
+    }
+}
+
+/// Plays one game from `start_position`; only its current context is kept. A start position
+/// other than the initial one is recorded in the `SetUp` and `FEN` tags.
+///
+/// Games are adjudicated as draws once they reach `N - 1` plies, since the
+/// position cannot hold more contexts.
+pub fn play_game<const N: usize>(
+    white: &mut dyn MatchEngine<N>,
+    black: &mut dyn MatchEngine<N>,
+    start_position: &TypedPosition<N>,
+    config: &MatchConfig,
+    round: usize,
+) -> MatchGame {
+    play_game_observed(white, black, start_position, config, round, &mut ())
+}
+
+/// Like [`play_game`], but reports moves and the result to `observer` as the game goes.
+pub fn play_game_observed<const N: usize>(
+    white: &mut dyn MatchEngine<N>,
+    black: &mut dyn MatchEngine<N>,
+    start_position: &TypedPosition<N>,
+    config: &MatchConfig,
+    round: usize,
+    observer: &mut dyn MatchObserver<N>,
+) -> MatchGame {
+    white.new_game();
+    black.new_game();
+
+    let adjudication = &config.adjudication;
+    let mut clock = config.time_control.map(ChessClock::new);
+    let mut position = match start_position {
+        TypedPosition::White(p) => TypedPosition::White(p.clone_current::<N>()),
+        TypedPosition::Black(p) => TypedPosition::Black(p.clone_current::<N>()),
+    };
+    let mut moves = Vec::new();
+    let mut seen = vec![zobrist_hash_of(&position)];
+    let mut white_lost_streak = ScoreStreak::default();
+    let mut black_lost_streak = ScoreStreak::default();
+    let mut draw_streak = ScoreStreak::default();
+    let max_plies = adjudication.max_plies.unwrap_or(usize::MAX).min(N - 1);
+
+    let (result, termination) = loop {
+        let stm = match &position {
+            TypedPosition::White(_) => Color::White,
+            TypedPosition::Black(_) => Color::Black,
+        };
+        let (board, halfmove_clock, is_in_check, legal_moves) = match &position {
+            TypedPosition::White(p) => {
+                let mut legal_moves = MoveList::new();
+                p.generate_moves(&mut legal_moves);
+                (
+                    p.board(),
+                    p.context().halfmove_clock(),
+                    p.is_current_side_in_check(),
+                    legal_moves,
+                )
+            }
+            TypedPosition::Black(p) => {
+                let mut legal_moves = MoveList::new();
+                p.generate_moves(&mut legal_moves);
+                (
+                    p.board(),
+                    p.context().halfmove_clock(),
+                    p.is_current_side_in_check(),
+                    legal_moves,
+                )
+            }
+        };
+
+        if legal_moves.is_empty() {
+            break if is_in_check {
+                (GameResult::win_for(stm.other()), TerminalReason::Checkmate)
+            } else {
+                (GameResult::Draw, TerminalReason::Stalemate)
+            };
+        }
+        if adjudication.insufficient_material
+            && board.are_both_sides_insufficient_material::<false>()
+        {
+            break (GameResult::Draw, TerminalReason::InsufficientMaterial);
+        }
+        if adjudication.fifty_move_rule && halfmove_clock >= 100 {
+            break (GameResult::Draw, TerminalReason::FiftyMoveRule);
+        }
+        if adjudication.threefold_repetition {
+            let key = zobrist_hash_of(&position);
+            if seen.iter().filter(|&&seen_key| seen_key == key).count() >= 3 {
+                break (GameResult::Draw, TerminalReason::ThreefoldRepetition);
+            }
+        }
+        if let Some(result) = adjudication
+            .tablebase
+            .and_then(|probe| probe(board, stm))
+            .filter(|result| *result != GameResult::Ongoing)
+        {
+            break (result, TerminalReason::Adjudication);
+        }
+        if moves.len() >= max_plies {
+            break (GameResult::Draw, TerminalReason::Adjudication);
+        }
+
+        let engine: &mut dyn MatchEngine<N> = match stm {
+            Color::White => &mut *white,
+            Color::Black => &mut *black,
+        };
+        let time_control = clock.as_ref().map(ChessClock::time_control);
+        let search_clock = SearchClock {
+            time_left: clock.as_ref().map(|clock| clock.remaining(stm)),
+            opponent_time_left: clock.as_ref().map(|clock| clock.remaining(stm.other())),
+            increment: time_control.map_or(Duration::ZERO, |time_control| time_control.increment),
+            delay: time_control.map_or(ClockDelay::None, |time_control| time_control.delay),
+        };
+        let start = Instant::now();
+        let engine_move = engine.search(&position, &moves, search_clock);
+        let elapsed = start.elapsed();
+
+        if let Some(clock) = &mut clock
+            && clock.record_move(elapsed).is_err()
+        {
+            break (GameResult::win_for(stm.other()), TerminalReason::Timeout);
+        }
+        let Some(engine_move) = engine_move else {
+            break (
+                GameResult::win_for(stm.other()),
+                TerminalReason::Resignation,
+            );
+        };
+        if !legal_moves.as_slice().contains(&engine_move.move_) {
+            break (GameResult::win_for(stm.other()), TerminalReason::OtherLoss);
+        }
+
+        let move_ = engine_move.move_;
+        moves.push(move_);
+        let (next, captured, gives_check) = match position {
+            TypedPosition::White(mut p) => {
+                p.make_move(move_);
+                let next = p.rebrand_stm::<{ Color::Black }>();
+                let context = *next.context();
+                (
+                    TypedPosition::Black(next),
+                    context.captured_piece(),
+                    context.checkers() != 0,
+                )
+            }
+            TypedPosition::Black(mut p) => {
+                p.make_move(move_);
+                let next = p.rebrand_stm::<{ Color::White }>();
+                let context = *next.context();
+                (
+                    TypedPosition::White(next),
+                    context.captured_piece(),
+                    context.checkers() != 0,
+                )
+            }
+        };
+        position = next;
+        seen.push(zobrist_hash_of(&position));
+
+        let event = MatchMoveEvent {
+            round,
+            ply: moves.len() - 1,
+            mover: stm,
+            move_,
+            score_cp: engine_move.score_cp,
+            captured: (captured != Piece::Null).then_some(captured),
+            gives_check,
+            position: &position,
+        };
+        observer.on_move(&event);
+        if let Some(captured) = event.captured {
+            observer.on_capture(&event, captured);
+        }
+        if gives_check {
+            observer.on_check(&event);
+        }
+
+        if let Some(resign) = adjudication.resign {
+            let white_score = engine_move.score_cp.map(|score| match stm {
+                Color::White => score,
+                Color::Black => -score,
+            });
+            let white_lost = white_score.is_some_and(|score| score <= -resign.score_cp);
+            let black_lost = white_score.is_some_and(|score| score >= resign.score_cp);
+            if white_lost_streak.record(stm, white_lost, resign.move_count) {
+                break (GameResult::BlackWins, TerminalReason::Adjudication);
+            }
+            if black_lost_streak.record(stm, black_lost, resign.move_count) {
+                break (GameResult::WhiteWins, TerminalReason::Adjudication);
+            }
+        }
+        if let Some(draw) = adjudication.draw {
+            let holds = engine_move
+                .score_cp
+                .is_some_and(|score| score.abs() <= draw.score_cp);
+            if draw_streak.record(stm, holds, draw.move_count) {
+                break (GameResult::Draw, TerminalReason::Adjudication);
+            }
+        }
+    };
+
+    let mut pgn_object = PgnObject::<N>::new();
+    for (key, value) in [
+        ("Event", config.event.clone()),
+        ("Site", "?".to_string()),
+        ("Date", "????.??.??".to_string()),
+        ("Round", (round + 1).to_string()),
+        ("White", white.name().to_string()),
+        ("Black", black.name().to_string()),
+        ("Result", result.as_pgn_str().to_string()),
+        ("Termination", termination.pgn_termination().to_string()),
+    ] {
+        pgn_object.add_tag(key.to_string(), value);
+    }
+    pgn_object.set_start_position_tags(start_position);
+    for &move_ in &moves {
+        pgn_object.push_main_line_move(move_);
+    }
+    let mut pgn = pgn_object.render(false, PgnRenderingConfig::default());
+    pgn.push(' ');
+    pgn.push_str(result.as_pgn_str());
+
+    let game = MatchGame {
+        white: white.name().to_string(),
+        black: black.name().to_string(),
+        result,
+        termination,
+        moves,
+        pgn,
+    };
+    observer.on_result(round, &game);
+    game
+}
+
+/// Plays `config.games` games between `first` and `second`, alternating colors.
+pub fn play_match<const N: usize>(
+    first: &mut dyn MatchEngine<N>,
+    second: &mut dyn MatchEngine<N>,
+    config: &MatchConfig,
+) -> MatchReport {
+    play_match_observed(first, second, config, &mut ())
+}
+
+/// Like [`play_match`], but reports every game's moves and result to `observer`.
+pub fn play_match_observed<const N: usize>(
+    first: &mut dyn MatchEngine<N>,
+    second: &mut dyn MatchEngine<N>,
+    config: &MatchConfig,
+    observer: &mut dyn MatchObserver<N>,
+) -> MatchReport {
+    let mut report = MatchReport::default();
+    let start_position = TypedPosition::<N>::White(Position::<N, { Color::White }>::initial());
+
+    for round in 0..config.games {
+        let first_is_white = round % 2 == 0;
+        let game = if first_is_white {
+            play_game_observed(first, second, &start_position, config, round, observer)
+        } else {
+            play_game_observed(second, first, &start_position, config, round, observer)
+        };
+
+        match (game.result, first_is_white) {
+            (GameResult::Draw, _) => report.draws += 1,
+            (GameResult::WhiteWins, true) | (GameResult::BlackWins, false) => report.wins += 1,
+            (GameResult::WhiteWins, false) | (GameResult::BlackWins, true) => report.losses += 1,
+            (GameResult::Ongoing, _) => unreachable!("match games always finish"),
+        }
+        report.games.push(game);
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const N: usize = 64;
+
+    fn initial() -> TypedPosition<N> {
+        TypedPosition::White(Position::<N, { Color::White }>::initial())
+    }
+
+    /// Plays scripted UCI moves, then the first legal move, always reporting `score_cp`.
+    struct ScriptedEngine {
+        name: &'static str,
+        script: Vec<&'static str>,
+        score_cp: Option<i32>,
+    }
+
+    impl MatchEngine<N> for ScriptedEngine {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn search(
+            &mut self,
+            position: &TypedPosition<N>,
+            moves: &[Move],
+            _clock: SearchClock,
+        ) -> Option<EngineMove> {
+            let mut legal_moves = MoveList::new();
+            match position {
+                TypedPosition::White(p) => p.generate_moves(&mut legal_moves),
+                TypedPosition::Black(p) => p.generate_moves(&mut legal_moves),
+            }
+            let move_ = match self.script.get(moves.len() / 2) {
+                Some(uci) => *legal_moves
+                    .as_slice()
+                    .iter()
+                    .find(|move_| move_.uci() == *uci)?,
+                None => *legal_moves.as_slice().first()?,
+            };
+            Some(EngineMove {
+                move_,
+                score_cp: self.score_cp,
+            })
+        }
+    }
+
+    fn engine(
+        name: &'static str,
+        script: &[&'static str],
+        score_cp: Option<i32>,
+    ) -> ScriptedEngine {
+        ScriptedEngine {
+            name,
+            script: script.to_vec(),
+            score_cp,
+        }
+    }
+
+    #[test]
+    fn test_checkmate_and_pgn_output() {
+        let mut white = engine("Scholar", &["e2e4", "d1h5", "f1c4", "h5f7"], None);
+        let mut black = engine("Victim", &["e7e5", "b8c6", "g8f6"], None);
+        let game = play_game::<N>(
+            &mut white,
+            &mut black,
+            &initial(),
+            &MatchConfig::default(),
+            0,
+        );
+
+        assert_eq!(game.result, GameResult::WhiteWins);
+        assert_eq!(game.termination, TerminalReason::Checkmate);
+        assert_eq!(game.moves.len(), 7);
+        assert!(game.pgn.contains("[White \"Scholar\"]"));
+        assert!(game.pgn.contains("[Result \"1-0\"]"));
+        assert!(game.pgn.contains("[Termination \"normal\"]"));
+        assert!(!game.pgn.contains("[FEN "));
+        assert!(game.pgn.ends_with("4. Qxf7# 1-0"));
+    }
+
+    #[test]
+    fn test_game_from_start_position() {
+        let fen = "6k1/p4ppp/8/8/8/8/5PPP/3R2K1 b - - 0 1";
+        let start_position = TypedPosition::<N>::from_fen(fen).unwrap();
+        let mut white = engine("Rook", &["d1d8"], None);
+        let mut black = engine("Pawns", &["a7a6"], None);
+        let game = play_game::<N>(
+            &mut white,
+            &mut black,
+            &start_position,
+            &MatchConfig::default(),
+            0,
+        );
+
+        assert_eq!(game.result, GameResult::WhiteWins);
+        assert_eq!(game.termination, TerminalReason::Checkmate);
+        assert!(game.pgn.contains("[SetUp \"1\"]"));
+        assert!(game.pgn.contains(&format!("[FEN \"{fen}\"]")));
+        assert!(game.pgn.ends_with("Rd8# 1-0"));
+    }
+
+    #[test]
+    fn test_observer_hears_moves_captures_checks_and_results() {
+        #[derive(Default)]
+        struct Log(Vec<String>);
+        impl MatchObserver<N> for Log {
+            fn on_move(&mut self, event: &MatchMoveEvent<'_, N>) {
+                self.0.push(format!("{} {}", event.ply, event.move_));
+            }
+
+            fn on_capture(&mut self, _event: &MatchMoveEvent<'_, N>, captured: Piece) {
+                self.0.push(format!("capture {captured:?}"));
+            }
+
+            fn on_check(&mut self, event: &MatchMoveEvent<'_, N>) {
+                self.0.push(format!("check by {:?}", event.mover));
+            }
+
+            fn on_result(&mut self, round: usize, game: &MatchGame) {
+                self.0.push(format!("round {round}: {}", game.result));
+            }
+        }
+
+        let mut white = engine("Scholar", &["e2e4", "d1h5", "f1c4", "h5f7"], None);
+        let mut black = engine("Victim", &["e7e5", "b8c6", "g8f6"], None);
+        let mut log = Log::default();
+        let game = play_game_observed::<N>(
+            &mut white,
+            &mut black,
+            &initial(),
+            &MatchConfig::default(),
+            3,
+            &mut log,
+        );
+        assert_eq!(game.termination, TerminalReason::Checkmate);
+        assert_eq!(
+            log.0[5..],
+            [
+                "5 g8f6",
+                "6 h5f7",
+                "capture Pawn",
+                "check by White",
+                "round 3: 1-0"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_match_alternates_colors_and_adjudicates() {
+        let config = MatchConfig {
+            games: 2,
+            adjudication: Adjudication {
+                resign: Some(ScoreAdjudication {
+                    score_cp: 500,
+                    move_count: 2,
+                }),
+                ..Adjudication::default()
+            },
+            ..MatchConfig::default()
+        };
+        // Both engines claim to be winning by 600, so the resign rule never agrees.
+        let mut first = engine("A", &[], Some(600));
+        let mut second = engine("B", &[], Some(600));
+        let report = play_match::<N>(&mut first, &mut second, &config);
+        assert_eq!(report.games.len(), 2);
+        assert_eq!(report.games[0].white, "A");
+        assert_eq!(report.games[1].white, "B");
+
+        // A reports a lost position and B agrees.
+        let mut first = engine("A", &[], Some(-600));
+        let mut second = engine("B", &[], Some(600));
+        let report = play_match::<N>(&mut first, &mut second, &config);
+        assert_eq!((report.wins, report.draws, report.losses), (0, 0, 2));
+        assert!(
+            report
+                .games
+                .iter()
+                .all(|game| game.termination == TerminalReason::Adjudication)
+        );
+    }
+
+    #[test]
+    fn test_time_forfeit() {
+        struct SlowEngine;
+        impl MatchEngine<N> for SlowEngine {
+            fn name(&self) -> &str {
+                "Slow"
+            }
+
+            fn search(
+                &mut self,
+                _position: &TypedPosition<N>,
+                _moves: &[Move],
+                _clock: SearchClock,
+            ) -> Option<EngineMove> {
+                std::thread::sleep(Duration::from_millis(20));
+                None
+            }
+        }
+
+        let config = MatchConfig {
+            time_control: Some(TimeControl::fischer(
+                Duration::from_millis(1),
+                Duration::ZERO,
+            )),
+            ..MatchConfig::default()
+        };
+        let mut black = engine("B", &[], None);
+        let game = play_game::<N>(&mut SlowEngine, &mut black, &initial(), &config, 0);
+        assert_eq!(game.result, GameResult::BlackWins);
+        assert_eq!(game.termination, TerminalReason::Timeout);
+        assert!(game.pgn.contains("[Termination \"time forfeit\"]"));
+    }
+
+    #[test]
+    fn test_tablebase_adjudication_ignores_ongoing() {
+        fn unsure(_board: &Board, _stm: Color) -> Option<GameResult> {
+            Some(GameResult::Ongoing)
+        }
+        fn drawn(_board: &Board, _stm: Color) -> Option<GameResult> {
+            Some(GameResult::Draw)
+        }
+
+        let mut config = MatchConfig {
+            adjudication: Adjudication {
+                tablebase: Some(unsure),
+                max_plies: Some(4),
+                ..Adjudication::default()
+            },
+            ..MatchConfig::default()
+        };
+        let mut first = engine("A", &[], None);
+        let mut second = engine("B", &[], None);
+        let game = play_game::<N>(&mut first, &mut second, &initial(), &config, 0);
+        assert_eq!(game.result, GameResult::Draw);
+        assert_eq!(game.moves.len(), 4);
+
+        config.adjudication.tablebase = Some(drawn);
+        let game = play_game::<N>(&mut first, &mut second, &initial(), &config, 0);
+        assert_eq!(game.termination, TerminalReason::Adjudication);
+        assert!(game.moves.is_empty());
+    }
+}
@@ -0,0 +1,234 @@
+//! Elo estimates and sequential probability ratio tests (SPRT) over match results.
+//!
+//! Elo uses the logistic model (`score = 1 / (1 + 10^(-elo / 400))`). The SPRT log-likelihood
+//! ratio uses the normal approximation common to engine-testing frameworks, either per game
+//! (trinomial W/D/L) or per game pair (pentanomial).
+
+use crate::{logic::game_state::GameResult, tournament::MatchReport};
+
+/// Two-sided 95% normal quantile.
+const Z_95: f64 = 1.959963984540054;
+
+/// Expected score for an Elo difference.
+pub fn elo_to_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+/// Elo difference for an expected score in `(0, 1)`.
+pub fn score_to_elo(score: f64) -> f64 {
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+/// Elo difference with a 95% confidence interval.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EloEstimate {
+    /// Point estimate.
+    pub elo: f64,
+    /// Lower bound of the confidence interval.
+    pub lower: f64,
+    /// Upper bound of the confidence interval.
+    pub upper: f64,
+}
+
+/// Mean and per-sample variance of scores given `(score, count)` buckets.
+fn score_stats(buckets: &[(f64, u32)]) -> Option<(f64, f64, f64)> {
+    let n: f64 = buckets.iter().map(|&(_, count)| count as f64).sum();
+    if n == 0.0 {
+        return None;
+    }
+    let mean = buckets
+        .iter()
+        .map(|&(score, count)| score * count as f64)
+        .sum::<f64>()
+        / n;
+    let variance = buckets
+        .iter()
+        .map(|&(score, count)| (score - mean).powi(2) * count as f64)
+        .sum::<f64>()
+        / n;
+    Some((n, mean, variance))
+}
+
+/// Elo estimate from wins, draws, and losses.
+///
+/// Returns `None` without games or when the score is exactly 0% or 100%.
+pub fn elo_estimate(wins: u32, draws: u32, losses: u32) -> Option<EloEstimate> {
+    let (n, score, variance) = score_stats(&[(1.0, wins), (0.5, draws), (0.0, losses)])?;
+    if score <= 0.0 || score >= 1.0 {
+        return None;
+    }
+    let margin = Z_95 * (variance / n).sqrt();
+    Some(EloEstimate {
+        elo: score_to_elo(score),
+        lower: score_to_elo((score - margin).max(f64::MIN_POSITIVE)),
+        upper: score_to_elo((score + margin).min(1.0 - f64::EPSILON)),
+    })
+}
+
+/// Hypotheses and error rates of an SPRT.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SprtParams {
+    /// Elo difference under the null hypothesis.
+    pub elo0: f64,
+    /// Elo difference under the alternative hypothesis.
+    pub elo1: f64,
+    /// False-positive rate.
+    pub alpha: f64,
+    /// False-negative rate.
+    pub beta: f64,
+}
+
+impl Default for SprtParams {
+    fn default() -> Self {
+        SprtParams {
+            elo0: 0.0,
+            elo1: 5.0,
+            alpha: 0.05,
+            beta: 0.05,
+        }
+    }
+}
+
+impl SprtParams {
+    /// Log-likelihood ratio below which H0 is accepted.
+    pub fn lower_bound(&self) -> f64 {
+        (self.beta / (1.0 - self.alpha)).ln()
+    }
+
+    /// Log-likelihood ratio above which H1 is accepted.
+    pub fn upper_bound(&self) -> f64 {
+        ((1.0 - self.beta) / self.alpha).ln()
+    }
+}
+
+/// What an SPRT concludes so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SprtDecision {
+    /// The Elo difference is at most `elo0`; stop.
+    AcceptH0,
+    /// The Elo difference is at least `elo1`; stop.
+    AcceptH1,
+    /// Not enough evidence yet.
+    Continue,
+}
+
+/// Log-likelihood ratio and decision of an SPRT.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SprtResult {
+    /// Log-likelihood ratio of H1 against H0.
+    pub llr: f64,
+    /// See [`SprtParams::lower_bound`].
+    pub lower_bound: f64,
+    /// See [`SprtParams::upper_bound`].
+    pub upper_bound: f64,
+    /// Decision for the current `llr`.
+    pub decision: SprtDecision,
+}
+
+fn sprt_from_buckets(buckets: &[(f64, u32)], params: SprtParams) -> SprtResult {
+    let llr = match score_stats(buckets) {
+        Some((n, score, variance)) if variance > 0.0 => {
+            let score0 = elo_to_score(params.elo0);
+            let score1 = elo_to_score(params.elo1);
+            n * (score1 - score0) * (2.0 * score - score0 - score1) / (2.0 * variance)
+        }
+        _ => 0.0,
+    };
+    let lower_bound = params.lower_bound();
+    let upper_bound = params.upper_bound();
+    let decision = if llr <= lower_bound {
+        SprtDecision::AcceptH0
+    } else if llr >= upper_bound {
+        SprtDecision::AcceptH1
+    } else {
+        SprtDecision::Continue
+    };
+    SprtResult {
+        llr,
+        lower_bound,
+        upper_bound,
+        decision,
+    }
+}
+
+/// Trinomial SPRT from individual game results.
+pub fn sprt_trinomial(wins: u32, draws: u32, losses: u32, params: SprtParams) -> SprtResult {
+    sprt_from_buckets(&[(1.0, wins), (0.5, draws), (0.0, losses)], params)
+}
+
+/// Pentanomial SPRT from game-pair totals.
+///
+/// `pairs[i]` counts pairs in which the tested engine scored `i / 2` points (`0` to `2`).
+pub fn sprt_pentanomial(pairs: [u32; 5], params: SprtParams) -> SprtResult {
+    let buckets: [(f64, u32); 5] = std::array::from_fn(|i| (i as f64 / 4.0, pairs[i]));
+    sprt_from_buckets(&buckets, params)
+}
+
+impl MatchReport {
+    /// Elo estimate of the first engine against the second.
+    pub fn elo(&self) -> Option<EloEstimate> {
+        elo_estimate(self.wins, self.draws, self.losses)
+    }
+
+    /// Game-pair totals for [`sprt_pentanomial`], pairing games `2k` and `2k + 1` (one game with
+    /// each color). An unpaired last game is ignored.
+    pub fn pentanomial(&self) -> [u32; 5] {
+        let mut pairs = [0; 5];
+        for (index, pair) in self.games.chunks_exact(2).enumerate() {
+            let mut half_points = 0;
+            for (offset, game) in pair.iter().enumerate() {
+                let first_is_white = (2 * index + offset) % 2 == 0;
+                half_points += match (game.result, first_is_white) {
+                    (GameResult::Draw, _) => 1,
+                    (GameResult::WhiteWins, true) | (GameResult::BlackWins, false) => 2,
+                    _ => 0,
+                };
+            }
+            pairs[half_points] += 1;
+        }
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elo_conversions() {
+        assert_eq!(score_to_elo(0.5), 0.0);
+        assert!((score_to_elo(0.75) - 190.848).abs() < 1e-3);
+        assert!((elo_to_score(score_to_elo(0.3)) - 0.3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_elo_estimate() {
+        let estimate = elo_estimate(60, 20, 20).unwrap();
+        assert!((estimate.elo - score_to_elo(0.7)).abs() < 1e-9);
+        assert!(estimate.lower < estimate.elo && estimate.elo < estimate.upper);
+        assert_eq!(elo_estimate(10, 0, 0), None);
+        assert_eq!(elo_estimate(0, 0, 0), None);
+    }
+
+    #[test]
+    fn test_sprt_decisions() {
+        let params = SprtParams::default();
+        assert_eq!(
+            sprt_trinomial(20_000, 20_000, 20_000, params).decision,
+            SprtDecision::AcceptH0
+        );
+        assert_eq!(
+            sprt_trinomial(1300, 1000, 1000, params).decision,
+            SprtDecision::AcceptH1
+        );
+        assert_eq!(
+            sprt_trinomial(11, 10, 10, params).decision,
+            SprtDecision::Continue
+        );
+        assert_eq!(
+            sprt_pentanomial([100, 400, 800, 500, 200], params).decision,
+            SprtDecision::AcceptH1
+        );
+        assert_eq!(sprt_pentanomial([0; 5], params).llr, 0.0);
+    }
+}
@@ -35,6 +35,7 @@ define_perft_benches! {
     bench_perft_position_3 => (PerftCase::Position3, 6);
     bench_perft_position_4 => (PerftCase::Position4, 5);
     bench_perft_position_5 => (PerftCase::Position5, 4);
+    bench_perft_promotions => (PerftCase::Promotions, 5);
 }
 
 criterion_group! {
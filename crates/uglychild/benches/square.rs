@@ -0,0 +1,53 @@
+//! Square offset benchmarks. The unchecked offsets assert their bounds unless the
+//! `unchecked-square-math` feature is on, so run both configurations to compare:
+//!
+//! ```text
+//! cargo bench --bench square
+//! cargo bench --bench square --features unchecked-square-math
+//! ```
+
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use uglychild::{types::Square, utilities::IterableEnum};
+
+/// Name of the unchecked benchmark, recording which bounds checks it runs with.
+const UNCHECKED: &str = if cfg!(feature = "unchecked-square-math") {
+    "unchecked (unchecked-square-math)"
+} else {
+    "unchecked (asserted)"
+};
+
+/// Walks diagonally up-right from every square to the board edge, through the `Option`-returning
+/// offset and through the unchecked one guarded by an explicit loop condition.
+fn bench_square_offsets(c: &mut Criterion) {
+    let mut group = c.benchmark_group("square_offsets");
+    group.bench_function("checked", |b| {
+        b.iter(|| {
+            let mut steps = 0u32;
+            for square in Square::ALL {
+                let mut current = black_box(square);
+                while let Some(next) = current.up_right() {
+                    current = next;
+                    steps += 1;
+                }
+            }
+            black_box(steps)
+        })
+    });
+    group.bench_function(UNCHECKED, |b| {
+        b.iter(|| {
+            let mut steps = 0u32;
+            for square in Square::ALL {
+                let mut current = black_box(square);
+                while current.rank_index() < 7 && current.file_index() < 7 {
+                    current = unsafe { current.up_right_unchecked() };
+                    steps += 1;
+                }
+            }
+            black_box(steps)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_square_offsets);
+criterion_main!(benches);
@@ -0,0 +1,55 @@
+//! Validates or normalizes FEN strings, given as arguments or one per line on stdin.
+//!
+//! ```text
+//! cargo run --example fen -- validate "<fen>"...
+//! cargo run --example fen -- normalize < positions.txt
+//! ```
+//!
+//! `normalize` parses each FEN and writes it back out, so equivalent spellings (extra spaces,
+//! castling rights out of order) come out identical.
+
+use std::{
+    env,
+    io::{self, BufRead},
+    process::ExitCode,
+};
+
+use uglychild::types::TypedPosition;
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    let command = args.next();
+    let normalize = match command.as_deref() {
+        Some("validate") => false,
+        Some("normalize") => true,
+        _ => {
+            eprintln!("usage: fen (validate | normalize) [<fen>...]");
+            return ExitCode::FAILURE;
+        }
+    };
+    let fens: Vec<String> = match args.len() {
+        0 => io::stdin()
+            .lock()
+            .lines()
+            .map_while(Result::ok)
+            .filter(|line| !line.trim().is_empty())
+            .collect(),
+        _ => args.collect(),
+    };
+
+    let mut invalid = 0;
+    for fen in &fens {
+        match TypedPosition::<1>::from_fen(fen.trim()) {
+            Ok(position) if normalize => println!("{}", position.to_fen()),
+            Ok(_) => println!("ok: {}", fen.trim()),
+            Err(err) => {
+                invalid += 1;
+                println!("invalid: {} ({err})", fen.trim());
+            }
+        }
+    }
+    match invalid {
+        0 => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    }
+}
@@ -0,0 +1,69 @@
+//! Divide perft from a FEN, in the output format of `go perft` in UCI engines.
+//!
+//! ```text
+//! cargo run --release --example perft -- "<fen>" <depth>
+//! ```
+
+use std::{env, process::ExitCode, time::Instant};
+
+use uglychild::types::{Move, MoveList, TypedPosition};
+
+/// Context stack capacity; bounds the supported depth.
+const N: usize = 64;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let [fen, depth] = args.as_slice() else {
+        eprintln!("usage: perft <fen> <depth>");
+        return ExitCode::FAILURE;
+    };
+    let position = match TypedPosition::<N>::from_fen(fen) {
+        Ok(position) => position,
+        Err(err) => {
+            eprintln!("invalid FEN: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let depth = match depth.parse::<u8>() {
+        Ok(depth) if (1..N as u8).contains(&depth) => depth,
+        _ => {
+            eprintln!("depth must be between 1 and {}", N - 1);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let start = Instant::now();
+    let mut moves = MoveList::new();
+    let divide: Vec<(Move, u64)> = match position {
+        TypedPosition::White(position) => {
+            position.generate_moves(&mut moves);
+            moves
+                .as_slice()
+                .iter()
+                .map(|&move_| (move_, position.make_move_copy(move_).0.perft(depth - 1)))
+                .collect()
+        }
+        TypedPosition::Black(position) => {
+            position.generate_moves(&mut moves);
+            moves
+                .as_slice()
+                .iter()
+                .map(|&move_| (move_, position.make_move_copy(move_).0.perft(depth - 1)))
+                .collect()
+        }
+    };
+    let elapsed = start.elapsed();
+
+    for (move_, nodes) in &divide {
+        println!("{move_}: {nodes}");
+    }
+    let total: u64 = divide.iter().map(|(_, nodes)| nodes).sum();
+    println!();
+    println!("Nodes searched: {total}");
+    eprintln!(
+        "{:.3}s, {:.0} nodes/s",
+        elapsed.as_secs_f64(),
+        total as f64 / elapsed.as_secs_f64().max(f64::EPSILON)
+    );
+    ExitCode::SUCCESS
+}
@@ -1,6 +1,7 @@
 use uglychild::logic::fen::INITIAL_FEN;
 use uglychild::types::{
-    Color, Position, PositionWithoutZobrist, WithoutZobrist, ZobristPolicy,
+    Color, Position, PositionWithZobrist, PositionWithoutZobrist, WithZobrist, WithoutZobrist,
+    ZobristPolicy,
 };
 
 #[derive(Clone, Copy, Debug)]
@@ -10,6 +11,7 @@ pub enum PerftCase {
     Position3,
     Position4,
     Position5,
+    Promotions,
 }
 
 impl PerftCase {
@@ -20,6 +22,7 @@ impl PerftCase {
             PerftCase::Position3 => "position3",
             PerftCase::Position4 => "position4",
             PerftCase::Position5 => "position5",
+            PerftCase::Promotions => "promotions",
         }
     }
 
@@ -56,11 +59,16 @@ impl PerftCase {
                     .unwrap(),
                 )
             }
+            // Seven pawns one step from promoting; nearly every node expands promotions.
+            PerftCase::Promotions => black(Position::<N, { Color::Black }, Z>::from_fen(
+                "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",
+            )
+            .unwrap()),
         }
     }
 
     #[inline]
-    /// Perft fixtures intentionally expose only the no-zobrist constructor path.
+    /// Plain perft fixtures use the no-zobrist constructor path.
     pub fn with_position_without_zobrist<const N: usize, R>(
         self,
         white: impl FnOnce(PositionWithoutZobrist<N, { Color::White }>) -> R,
@@ -69,6 +77,17 @@ impl PerftCase {
         self.with_position_policy::<N, WithoutZobrist, R>(white, black)
     }
 
+    #[inline]
+    /// Hashed perft fixtures need incremental Zobrist keys.
+    #[allow(dead_code)]
+    pub fn with_position_with_zobrist<const N: usize, R>(
+        self,
+        white: impl FnOnce(PositionWithZobrist<N, { Color::White }>) -> R,
+        black: impl FnOnce(PositionWithZobrist<N, { Color::Black }>) -> R,
+    ) -> R {
+        self.with_position_policy::<N, WithZobrist, R>(white, black)
+    }
+
     const fn nodes_depth_map(self) -> &'static [u64; 8] {
         match self {
             PerftCase::Initial => &[
@@ -94,6 +113,7 @@ impl PerftCase {
             PerftCase::Position3 => &[1, 14, 191, 2_812, 43_238, 674_624, 11_030_083, 178_633_661],
             PerftCase::Position4 => &[1, 6, 264, 9_467, 422_333, 15_833_292, 706_045_033, 0],
             PerftCase::Position5 => &[1, 44, 1_486, 62_379, 2_103_487, 89_941_194, 0, 0],
+            PerftCase::Promotions => &[1, 24, 496, 9_483, 182_838, 3_605_103, 71_179_139, 0],
         }
     }
 
@@ -4,12 +4,14 @@
 //! The crate is organized into:
 //! - [`types`] for core domain models (`Position`, `Board`, `Move`, `Square`, etc.)
 //! - [`logic`] for parsing, SAN/FEN helpers, legality checks, and other algorithms.
+//! - [`utilities`] for lookup-table builders, const-friendly arrays, and bit iterators.
 //!
 //! Most consumers will interact with [`types::Position`] plus move generation APIs.
 #![feature(const_trait_impl)]
 #![feature(const_convert)]
 #![feature(const_iter)]
 #![feature(const_default)]
+#![feature(const_destruct)]
 #![feature(const_precise_live_drops)]
 #![feature(const_index)]
 #![feature(const_slice_make_iter)]
@@ -25,4 +27,7 @@ pub mod logic;
 /// Core chess data structures and low-level operations.
 pub mod types;
 
-mod utilities;
+/// Lookup-table builders and other helpers shared with downstream engine code.
+pub mod utilities;
+
+pub use types::{Color, ColoredPiece, Piece};
@@ -0,0 +1,194 @@
+//! Piece-placement delta hooks for incrementally updated evaluation state (e.g. NNUE accumulators).
+//!
+//! [`crate::types::Position::make_move_with`] and [`crate::types::Position::unmake_move_with`]
+//! behave exactly like their plain counterparts and additionally report every piece removed from
+//! or added to a square, so a listener can mirror the board without re-reading it.
+//! [`crate::types::PieceLists`] and [`crate::types::AttacksByColor`] are such listeners.
+
+use crate::{
+    logic::make_move::{
+        castling_rook_from_square,
+        castling_rook_to_square,
+        en_passant_captured_square,
+    },
+    types::{Color, Move, MoveFlag, Piece, Position, Square, ZobristPolicy},
+};
+
+/// Listener for piece-placement deltas produced by a move.
+///
+/// For each move, all removals are reported before any additions. A moved piece is reported as
+/// a removal on its origin and an addition on its destination; a promotion removes a pawn and
+/// adds the promoted piece.
+pub trait AccumulatorDelta {
+    /// `color`'s `piece` was placed on `square`.
+    fn on_piece_added(&mut self, color: Color, piece: Piece, square: Square);
+    /// `color`'s `piece` was removed from `square`.
+    fn on_piece_removed(&mut self, color: Color, piece: Piece, square: Square);
+}
+
+/// Everything needed to describe a move's board deltas in either direction.
+struct MoveDeltas {
+    mover: Color,
+    /// Piece on the origin square before the move.
+    moved_piece: Piece,
+    /// Piece on the destination square after the move.
+    placed_piece: Piece,
+    /// Captured piece and the square it stood on.
+    capture: Option<(Piece, Square)>,
+    /// Rook origin and destination for castling.
+    rook_step: Option<(Square, Square)>,
+}
+
+impl MoveDeltas {
+    fn new(mover: Color, move_: Move, moved_piece: Piece, captured_piece: Piece) -> MoveDeltas {
+        let to = move_.to();
+        let mut placed_piece = moved_piece;
+        let mut capture_square = to;
+        let mut rook_step = None;
+
+        match move_.flag() {
+            MoveFlag::NormalMove => {}
+            MoveFlag::Promotion => placed_piece = move_.promotion(),
+            MoveFlag::EnPassant => {
+                capture_square = en_passant_captured_square(to, mover);
+            }
+            MoveFlag::Castling => {
+                let flank = to.file().flank();
+                rook_step = Some((
+                    castling_rook_from_square(flank, mover),
+                    castling_rook_to_square(flank, mover),
+                ));
+            }
+        }
+
+        MoveDeltas {
+            mover,
+            moved_piece,
+            placed_piece,
+            capture: (captured_piece != Piece::Null).then_some((captured_piece, capture_square)),
+            rook_step,
+        }
+    }
+
+    fn emit_make(&self, move_: Move, listener: &mut impl AccumulatorDelta) {
+        listener.on_piece_removed(self.mover, self.moved_piece, move_.from());
+        if let Some((piece, square)) = self.capture {
+            listener.on_piece_removed(self.mover.other(), piece, square);
+        }
+        if let Some((rook_from, _)) = self.rook_step {
+            listener.on_piece_removed(self.mover, Piece::Rook, rook_from);
+        }
+        listener.on_piece_added(self.mover, self.placed_piece, move_.to());
+        if let Some((_, rook_to)) = self.rook_step {
+            listener.on_piece_added(self.mover, Piece::Rook, rook_to);
+        }
+    }
+
+    fn emit_unmake(&self, move_: Move, listener: &mut impl AccumulatorDelta) {
+        listener.on_piece_removed(self.mover, self.placed_piece, move_.to());
+        if let Some((_, rook_to)) = self.rook_step {
+            listener.on_piece_removed(self.mover, Piece::Rook, rook_to);
+        }
+        listener.on_piece_added(self.mover, self.moved_piece, move_.from());
+        if let Some((piece, square)) = self.capture {
+            listener.on_piece_added(self.mover.other(), piece, square);
+        }
+        if let Some((rook_from, _)) = self.rook_step {
+            listener.on_piece_added(self.mover, Piece::Rook, rook_from);
+        }
+    }
+}
+
+impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
+    /// [`Position::make_move`] that also reports the move's piece deltas to `listener`.
+    pub fn make_move_with(&mut self, move_: Move, listener: &mut impl AccumulatorDelta) {
+        let captured_piece = match move_.flag() {
+            MoveFlag::EnPassant => Piece::Pawn,
+            MoveFlag::Castling => Piece::Null,
+            MoveFlag::NormalMove | MoveFlag::Promotion => self.board.piece_at(move_.to()),
+        };
+        let deltas = MoveDeltas::new(
+            STM,
+            move_,
+            self.board.piece_at(move_.from()),
+            captured_piece,
+        );
+        self.make_move(move_);
+        deltas.emit_make(move_, listener);
+    }
+
+    /// [`Position::unmake_move`] that also reports the reverted piece deltas to `listener`.
+    ///
+    /// Additions and removals mirror those reported by [`Position::make_move_with`].
+    pub fn unmake_move_with(&mut self, move_: Move, listener: &mut impl AccumulatorDelta) {
+        let moved_piece = match move_.flag() {
+            MoveFlag::Promotion => Piece::Pawn,
+            _ => self.board.piece_at(move_.to()),
+        };
+        let deltas = MoveDeltas::new(
+            STM.other(),
+            move_,
+            moved_piece,
+            self.context().captured_piece,
+        );
+        self.unmake_move(move_);
+        deltas.emit_unmake(move_, listener);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Board, MoveList, PositionWithoutZobrist};
+
+    /// Mirrors a board purely from reported deltas.
+    struct MirrorBoard(Board);
+
+    impl AccumulatorDelta for MirrorBoard {
+        fn on_piece_added(&mut self, color: Color, piece: Piece, square: Square) {
+            assert!(!self.0.is_occupied_at(square));
+            self.0.put_piece_and_color(color, piece, square);
+        }
+
+        fn on_piece_removed(&mut self, color: Color, piece: Piece, square: Square) {
+            assert_eq!(self.0.piece_at(square), piece);
+            assert_eq!(self.0.color_at(square), color);
+            self.0.remove_piece_and_color(color, piece, square);
+        }
+    }
+
+    fn assert_mirror_tracks_moves<const STM: Color>(fen: &str) {
+        let mut position = PositionWithoutZobrist::<2, STM>::from_fen(fen).unwrap();
+        let mut mirror = MirrorBoard(position.board.clone());
+        let mut moves = MoveList::new();
+        position.generate_moves(&mut moves);
+
+        for &move_ in moves.as_slice() {
+            position.make_move_with(move_, &mut mirror);
+            assert_eq!(mirror.0, position.board, "after make {}", move_.uci());
+            match STM {
+                Color::White => {
+                    let child = unsafe { position.rebrand_stm_mut::<{ Color::Black }>() };
+                    child.unmake_move_with(move_, &mut mirror);
+                }
+                Color::Black => {
+                    let child = unsafe { position.rebrand_stm_mut::<{ Color::White }>() };
+                    child.unmake_move_with(move_, &mut mirror);
+                }
+            }
+            assert_eq!(mirror.0, position.board, "after unmake {}", move_.uci());
+        }
+    }
+
+    #[test]
+    fn test_deltas_mirror_castling_and_captures() {
+        assert_mirror_tracks_moves::<{ Color::White }>(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        );
+    }
+
+    #[test]
+    fn test_deltas_mirror_promotions_and_en_passant() {
+        assert_mirror_tracks_moves::<{ Color::Black }>("1n2k3/8/8/8/3pP3/8/6p1/4K2R b K e3 0 1");
+    }
+}
@@ -2,12 +2,10 @@
 
 use std::{
     boxed::Box,
-    fs,
-    io,
-    io::{Read, Write},
-    path::PathBuf,
+    error::Error,
+    fmt,
     ptr::NonNull,
-    sync::LazyLock,
+    sync::{LazyLock, OnceLock},
 };
 
 use crate::{
@@ -115,33 +113,6 @@ impl MagicInfo {
         // bounds for that square's subset (verified during generation).
         unsafe { *self.attacks.as_ptr().add(key) }
     }
-
-    /// Serialize MagicInfo to bytes (21 bytes total).
-    /// Stores offset instead of pointer for portability.
-    fn as_bytes(&self, table_base: NonNull<Bitboard>) -> [u8; 21] {
-        let mut bytes = [0u8; 21];
-        bytes[0..8].copy_from_slice(&self.relevant_mask.to_le_bytes());
-        bytes[8..16].copy_from_slice(&self.magic_number.to_le_bytes());
-        bytes[16] = self.right_shift_amount;
-
-        // Store offset from table base instead of raw pointer
-        let offset = unsafe { self.attacks.as_ptr().offset_from(table_base.as_ptr()) as u32 };
-        bytes[17..21].copy_from_slice(&offset.to_le_bytes());
-
-        bytes
-    }
-
-    /// Deserialize MagicInfo from bytes, converting offset to pointer.
-    fn from_bytes(bytes: &[u8; 21], table_base: NonNull<Bitboard>) -> Self {
-        let offset = u32::from_le_bytes(bytes[17..21].try_into().unwrap()) as isize;
-
-        Self {
-            relevant_mask: u64::from_le_bytes(bytes[0..8].try_into().unwrap()),
-            magic_number: u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
-            right_shift_amount: bytes[16],
-            attacks: unsafe { NonNull::new_unchecked(table_base.as_ptr().offset(offset)) },
-        }
-    }
 }
 
 /// Size of the attack table for rooks.
@@ -169,6 +140,8 @@ pub(crate) struct MagicAttacks {
     /// Combined attacks table for both pieces.
     /// Rooks: [0..ROOK_ATTACK_TABLE_SIZE)
     /// Bishops: [ROOK_ATTACK_TABLE_SIZE..COMBINED_TABLE_SIZE)
+    /// Only read through the `MagicInfo` pointers; held here to keep the allocation alive.
+    #[allow(dead_code)]
     attacks: Box<[Bitboard; COMBINED_TABLE_SIZE]>,
 }
 
@@ -196,95 +169,67 @@ impl MagicAttacks {
         unsafe { magic_info.get_attacks(occupied_mask) }
     }
 
-    /// Load from file or generate if not present.
-    pub fn load_or_generate(
-        filename: PathBuf,
-        generate: impl FnOnce() -> Self,
-    ) -> io::Result<Self> {
-        match Self::load_from_file(&filename) {
-            Ok(lookup) => Ok(lookup),
-            Err(_) => {
-                let lookup = generate();
-                lookup.save_to_file(&filename)?;
-                Ok(lookup)
-            }
-        }
+    /// Search for magic numbers from the given PRNG seeds and build the tables.
+    pub fn generate_with_seeds(rook_seed: u64, bishop_seed: u64) -> Self {
+        Self::build(Prng::new(rook_seed), Prng::new(bishop_seed), None)
+            .expect("magic number search cannot fail")
     }
 
-    /// Save to file in a portable format (offsets, not pointers).
-    pub fn save_to_file(&self, filename: &PathBuf) -> io::Result<()> {
-        let mut file = fs::File::create(filename)?;
-        let table_base = NonNull::new(self.attacks.as_ptr() as *mut Bitboard).unwrap();
-
-        // Write header: number of squares (64) and table size info for validation
-        file.write_all(&[64u8])?;
-        file.write_all(&(COMBINED_TABLE_SIZE as u64).to_le_bytes())?;
-
-        // Write rook magic info (64 entries)
-        for magic_info in &self.rook_magic_info_lookup {
-            file.write_all(&magic_info.as_bytes(table_base))?;
-        }
-
-        // Write bishop magic info (64 entries)
-        for magic_info in &self.bishop_magic_info_lookup {
-            file.write_all(&magic_info.as_bytes(table_base))?;
-        }
-
-        // Write the combined attack table
-        for attack in self.attacks.iter() {
-            file.write_all(&attack.to_le_bytes())?;
-        }
-
-        Ok(())
+    /// Build the tables from known magic numbers, without searching.
+    pub fn from_magic_numbers(magic_numbers: &MagicNumbers) -> Result<Self, MagicError> {
+        Self::build(
+            Prng::new(DEFAULT_ROOK_MAGIC_SEED),
+            Prng::new(DEFAULT_BISHOP_MAGIC_SEED),
+            Some(magic_numbers),
+        )
     }
 
-    /// Load from file, converting stored offsets back to pointers.
-    pub fn load_from_file(filename: &PathBuf) -> io::Result<Self> {
-        let mut file = fs::File::open(filename)?;
-
-        // Read and validate header
-        let mut header = [0u8; 9];
-        file.read_exact(&mut header)?;
-        if header[0] != 64 {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "magic lookup file: expected 64 in header",
-            ));
-        }
-        let stored_table_size = u64::from_le_bytes(header[1..9].try_into().unwrap()) as usize;
-        if stored_table_size != COMBINED_TABLE_SIZE {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!(
-                    "magic lookup file: table size mismatch (expected {}, got {})",
-                    COMBINED_TABLE_SIZE, stored_table_size
-                ),
-            ));
+    /// The magic numbers in use, one per square for each piece.
+    pub fn magic_numbers(&self) -> MagicNumbers {
+        MagicNumbers {
+            rook: self
+                .rook_magic_info_lookup
+                .0
+                .map(|magic_info| magic_info.magic_number),
+            bishop: self
+                .bishop_magic_info_lookup
+                .0
+                .map(|magic_info| magic_info.magic_number),
         }
+    }
 
-        // Allocate the attacks table first (we need its base address for pointer reconstruction)
+    /// Fill both rook and bishop tables in a single pass, searching for magic numbers unless
+    /// `magic_numbers` supplies them.
+    fn build(
+        rook_rng: Prng,
+        bishop_rng: Prng,
+        magic_numbers: Option<&MagicNumbers>,
+    ) -> Result<Self, MagicError> {
         let mut attacks = Box::new([0u64; COMBINED_TABLE_SIZE]);
-        let table_base = NonNull::new(attacks.as_mut_ptr()).unwrap();
 
-        // Read magic info, converting offsets to pointers
+        // Initialize rooks (offset starts at 0)
+        let mut rook_initializer = PieceMagicInitializer::new(&mut attacks, 0, rook_rng);
+
         let mut rook_magic_info = Array([MagicInfo::default(); 64]);
         for square in Square::ALL {
-            let mut magic_info_bytes = [0u8; 21];
-            file.read_exact(&mut magic_info_bytes)?;
-            rook_magic_info[square as usize] = MagicInfo::from_bytes(&magic_info_bytes, table_base);
+            rook_magic_info[square as usize] = rook_initializer
+                .generate_square_magic::<{ Piece::Rook }>(
+                    square,
+                    magic_numbers.map(|magic_numbers| magic_numbers.rook[square as usize]),
+                )?;
         }
 
+        // Initialize bishops (offset starts where rooks ended)
+        let mut bishop_initializer =
+            PieceMagicInitializer::new(&mut attacks, BISHOP_TABLE_OFFSET, bishop_rng);
+
         let mut bishop_magic_info = Array([MagicInfo::default(); 64]);
         for square in Square::ALL {
-            let mut magic_info_bytes = [0u8; 21];
-            file.read_exact(&mut magic_info_bytes)?;
-            bishop_magic_info[square as usize] =
-                MagicInfo::from_bytes(&magic_info_bytes, table_base);
-        }
-
-        // Read the attack table
-        for attack in attacks.iter_mut() {
-            *attack = read_u64(&mut file)?;
+            bishop_magic_info[square as usize] = bishop_initializer
+                .generate_square_magic::<{ Piece::Bishop }>(
+                    square,
+                    magic_numbers.map(|magic_numbers| magic_numbers.bishop[square as usize]),
+                )?;
         }
 
         Ok(MagicAttacks {
@@ -293,62 +238,268 @@ impl MagicAttacks {
             attacks,
         })
     }
+}
 
-    /// Generate both rook and bishop magic tables in a single pass.
-    pub fn generate() -> Self {
-        let mut attacks = Box::new([0u64; COMBINED_TABLE_SIZE]);
+/// Magic multipliers for every square, as used by the rook and bishop attack tables.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MagicNumbers {
+    /// Rook magic numbers, indexed by square.
+    pub rook: [Bitboard; 64],
+    /// Bishop magic numbers, indexed by square.
+    pub bishop: [Bitboard; 64],
+}
 
-        // Initialize rooks (offset starts at 0)
-        let mut rook_initializer =
-            PieceMagicInitializer::new(&mut attacks, 0, Prng::new(3141592653589793238));
+impl MagicNumbers {
+    /// Search for collision-free magic numbers, seeding the PRNG per piece.
+    ///
+    /// The search is deterministic: the same seeds yield the same numbers on every platform.
+    pub fn search(rook_seed: u64, bishop_seed: u64) -> MagicNumbers {
+        MagicAttacks::generate_with_seeds(rook_seed, bishop_seed).magic_numbers()
+    }
 
-        let mut rook_magic_info = Array([MagicInfo::default(); 64]);
-        for square in Square::ALL {
-            rook_magic_info[square as usize] =
-                rook_initializer.generate_square_magic::<{ Piece::Rook }>(square);
-        }
+    /// Checks that every magic number maps its square's blocker patterns without collisions.
+    pub fn validate(&self) -> Result<(), MagicError> {
+        MagicAttacks::from_magic_numbers(self).map(|_| ())
+    }
+}
 
-        // Initialize bishops (offset starts where rooks ended)
-        let mut bishop_initializer = PieceMagicInitializer::new(
-            &mut attacks,
-            BISHOP_TABLE_OFFSET,
-            Prng::new(2718281828459045),
-        );
+/// Seed from which [`DEFAULT_MAGIC_NUMBERS`] rook numbers were searched.
+pub const DEFAULT_ROOK_MAGIC_SEED: u64 = 3141592653589793238;
+
+/// Seed from which [`DEFAULT_MAGIC_NUMBERS`] bishop numbers were searched.
+pub const DEFAULT_BISHOP_MAGIC_SEED: u64 = 2718281828459045;
+
+/// Known-good magic numbers, equal to
+/// `MagicNumbers::search(DEFAULT_ROOK_MAGIC_SEED, DEFAULT_BISHOP_MAGIC_SEED)`.
+pub const DEFAULT_MAGIC_NUMBERS: MagicNumbers = MagicNumbers {
+    rook: [
+        0x040140902400c102,
+        0x0120080082311024,
+        0x1001006208040001,
+        0x0041001028000215,
+        0x0122004008200c12,
+        0x400041001008a001,
+        0x030900c000801021,
+        0x0000208000490411,
+        0x0820010240840200,
+        0x0081001a00040100,
+        0x002a800400020080,
+        0x2010800800340080,
+        0x8001000c22500100,
+        0x2400804032012200,
+        0x0800400091086100,
+        0x9341021821418200,
+        0x30a0008041020004,
+        0x0020111008540052,
+        0x5406001028560044,
+        0x00880011004d0008,
+        0x000203e820420010,
+        0x4520008410018020,
+        0x8090082002504000,
+        0x0100304000818001,
+        0x8020008042000411,
+        0x284008428c003001,
+        0x4200808400800200,
+        0x0004004482800800,
+        0x90222a0012002040,
+        0x0220020050100400,
+        0x2820600044c01000,
+        0x8040084020800480,
+        0x8340014a00038401,
+        0xc281001100040600,
+        0x8080120080800400,
+        0x1000080100110004,
+        0x081a002200144008,
+        0x0608100180200081,
+        0x4400200c80400080,
+        0x6201c00080248000,
+        0x00000a001b108c44,
+        0xa040040002080310,
+        0x0010808046000c00,
+        0x0001010005900800,
+        0x0010a20012010840,
+        0x0120028010006082,
+        0x2004808020144000,
+        0x0000828002204001,
+        0x0401000062820100,
+        0x2000800100801200,
+        0x0c4e001482000890,
+        0x040080040280a800,
+        0x0040800800900081,
+        0x9044802000300385,
+        0x80104010022002c0,
+        0x0020800840007080,
+        0x3200004064020083,
+        0x0300044186000300,
+        0x81000400080e0500,
+        0x0a000c1200203008,
+        0x0d80100184080080,
+        0x8100100820004101,
+        0x00c0004020091000,
+        0xc080004002201282,
+    ],
+    bishop: [
+        0x0040040c00a02100,
+        0x0012401002020042,
+        0x2010200410420210,
+        0x01204400200d2400,
+        0x020210a004840c20,
+        0x0180081204420844,
+        0x0068002208440c00,
+        0x0042c14808280200,
+        0x1020054421004080,
+        0x0022204202004a08,
+        0x020a150408820202,
+        0x8600001410c40201,
+        0x8048009084042010,
+        0x0004223201101061,
+        0x8800840442124020,
+        0x000404010450a810,
+        0x108a088202000280,
+        0x0022840c00800408,
+        0x0105462082012100,
+        0x480840281200c044,
+        0x42a0008401200400,
+        0x0402208020805000,
+        0x4802182c02006402,
+        0x9088024820200400,
+        0x4042810100220d88,
+        0x1002880100120880,
+        0x90100502009110c0,
+        0x0854040400041010,
+        0x00020c0400080211,
+        0x0002020608510803,
+        0x1804010c0008104e,
+        0x400220040120a860,
+        0x0002068002009080,
+        0x02c8010029440210,
+        0x2069020062480400,
+        0x09208480a4006000,
+        0x2244024084010002,
+        0x0204411410030204,
+        0x008804480e100260,
+        0x00100840b0200102,
+        0x0000883144040110,
+        0x0402048900901422,
+        0x42050001e0a01000,
+        0x0810800408a00200,
+        0x001800858a004286,
+        0x2034000228005100,
+        0x4004221208220405,
+        0x0086021004100400,
+        0x01006200a2011021,
+        0xc00004840109c000,
+        0x000a0110080c0040,
+        0x0800020210440004,
+        0x00d0422182002000,
+        0x2000090444018400,
+        0x040c200482808108,
+        0x0004200429020414,
+        0xc0c9010051044020,
+        0xa0440202822004a5,
+        0x88228620a0003880,
+        0x0102021001004188,
+        0x280c0c0580000080,
+        0x4a1040a20044312c,
+        0x02a0280201802571,
+        0x1810112308220440,
+    ],
+};
 
-        let mut bishop_magic_info = Array([MagicInfo::default(); 64]);
-        for square in Square::ALL {
-            bishop_magic_info[square as usize] =
-                bishop_initializer.generate_square_magic::<{ Piece::Bishop }>(square);
-        }
+/// Errors from installing or verifying magic numbers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MagicError {
+    /// The magic number for `piece` on `square` maps two blocker patterns with different attacks
+    /// to the same slot.
+    InvalidMagicNumber {
+        /// Rook or bishop.
+        piece: Piece,
+        /// Square whose magic number collides.
+        square: Square,
+    },
+    /// The table lookup for `piece` on `square` disagrees with ray-scanned attacks.
+    AttackMismatch {
+        /// Rook or bishop.
+        piece: Piece,
+        /// Square of the sliding piece.
+        square: Square,
+        /// Occupancy for which the lookup is wrong.
+        occupied_mask: Bitboard,
+    },
+    /// [`set_magic_numbers`] was called after the attack tables were built.
+    AlreadyInitialized,
+}
 
-        MagicAttacks {
-            rook_magic_info_lookup: rook_magic_info,
-            bishop_magic_info_lookup: bishop_magic_info,
-            attacks,
+impl fmt::Display for MagicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MagicError::InvalidMagicNumber { piece, square } => {
+                write!(f, "colliding {piece:?} magic number on {square}")
+            }
+            MagicError::AttackMismatch {
+                piece,
+                square,
+                occupied_mask,
+            } => write!(
+                f,
+                "wrong {piece:?} attacks on {square} for occupancy {occupied_mask:#018x}"
+            ),
+            MagicError::AlreadyInitialized => write!(f, "magic attack tables already built"),
         }
     }
 }
 
+impl Error for MagicError {}
+
+/// Magic numbers the attack tables are built from; set at most once.
+static MAGIC_NUMBERS: OnceLock<MagicNumbers> = OnceLock::new();
+
 /// Single lazy-initialized combined magic attacks table.
 pub(crate) static MAGIC_ATTACKS: LazyLock<MagicAttacks> = LazyLock::new(|| {
-    MagicAttacks::load_or_generate(
-        magic_table_path("magic_attacks_lookup.bin"),
-        MagicAttacks::generate,
-    )
-    .expect("magic table load or generate")
+    let magic_numbers = MAGIC_NUMBERS.get_or_init(|| DEFAULT_MAGIC_NUMBERS);
+    MagicAttacks::from_magic_numbers(magic_numbers).expect("magic numbers validated")
 });
 
-fn magic_table_path(file_name: &str) -> PathBuf {
-    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("../../data/magic")
-        .join(file_name)
+/// Builds the attack tables from `magic_numbers` instead of [`DEFAULT_MAGIC_NUMBERS`].
+///
+/// Must be called before the first sliding-piece attack lookup; afterwards it fails with
+/// [`MagicError::AlreadyInitialized`].
+pub fn set_magic_numbers(magic_numbers: MagicNumbers) -> Result<(), MagicError> {
+    magic_numbers.validate()?;
+    MAGIC_NUMBERS
+        .set(magic_numbers)
+        .map_err(|_| MagicError::AlreadyInitialized)
 }
 
-/// Read a u64 from a file in little-endian format
-fn read_u64(file: &mut fs::File) -> io::Result<u64> {
-    let mut bytes = [0u8; 8];
-    file.read_exact(&mut bytes)?;
-    Ok(u64::from_le_bytes(bytes))
+/// The magic numbers the attack tables are built from, e.g. to embed found numbers as constants.
+pub fn magic_numbers() -> MagicNumbers {
+    MAGIC_ATTACKS.magic_numbers()
+}
+
+/// Checks every rook and bishop table entry against ray-scanned attacks.
+pub fn verify_magics() -> Result<(), MagicError> {
+    fn verify_piece<const P: Piece>(
+        magic_attacks_for: impl Fn(Square, Bitboard) -> Bitboard,
+    ) -> Result<(), MagicError> {
+        for square in Square::ALL {
+            let relevant_mask = sliding_piece_relevant_mask::<{ P }>(square);
+            for occupied_mask in relevant_mask.iter_bit_combinations() {
+                if magic_attacks_for(square, occupied_mask)
+                    != manual_sliding_piece_attacks::<{ P }>(square, occupied_mask)
+                {
+                    return Err(MagicError::AttackMismatch {
+                        piece: P,
+                        square,
+                        occupied_mask,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    verify_piece::<{ Piece::Rook }>(magic_single_rook_attacks)?;
+    verify_piece::<{ Piece::Bishop }>(magic_single_bishop_attacks)
 }
 
 /// DRY magic initializer that handles both pieces using the combined table.
@@ -378,8 +529,13 @@ impl<'a> PieceMagicInitializer<'a> {
         }
     }
 
-    /// Generate magic info for a single square.
-    fn generate_square_magic<const P: Piece>(&mut self, square: Square) -> MagicInfo {
+    /// Generate magic info for a single square, searching for a magic number unless one is
+    /// given.
+    fn generate_square_magic<const P: Piece>(
+        &mut self,
+        square: Square,
+        magic_number: Option<Bitboard>,
+    ) -> Result<MagicInfo, MagicError> {
         let relevant_mask = sliding_piece_relevant_mask::<{ P }>(square);
         let num_relevant_bits = relevant_mask.count_ones() as u8;
         let right_shift_amount = 64 - num_relevant_bits;
@@ -387,8 +543,14 @@ impl<'a> PieceMagicInitializer<'a> {
 
         let mappings =
             self.build_mappings::<{ P }>(square, relevant_mask, num_blocker_combinations);
-        let (magic_number, attacks_lookup) =
-            self.find_valid_magic_number(right_shift_amount, &mappings);
+        let (magic_number, attacks_lookup) = match magic_number {
+            Some(magic_number) => {
+                Self::test_magic_number(magic_number, right_shift_amount, &mappings)
+                    .map(|attacks_lookup| (magic_number, attacks_lookup))
+                    .ok_or(MagicError::InvalidMagicNumber { piece: P, square })?
+            }
+            None => self.find_valid_magic_number(right_shift_amount, &mappings),
+        };
 
         // Calculate the pointer to this square's attack subset
         let attacks_ptr = unsafe { self.table_base.add(self.current_offset) };
@@ -404,12 +566,12 @@ impl<'a> PieceMagicInitializer<'a> {
 
         self.current_offset += num_blocker_combinations;
 
-        MagicInfo {
+        Ok(MagicInfo {
             relevant_mask,
             magic_number,
             right_shift_amount,
             attacks: attacks_ptr,
-        }
+        })
     }
 
     /// Build mapping from occupancy patterns to attack masks
@@ -483,9 +645,17 @@ mod tests {
     use crate::{
         logic::attacks::{
             magic::{
+                DEFAULT_BISHOP_MAGIC_SEED,
+                DEFAULT_MAGIC_NUMBERS,
+                DEFAULT_ROOK_MAGIC_SEED,
+                MagicError,
+                MagicNumbers,
+                magic_numbers,
                 magic_single_bishop_attacks,
                 magic_single_rook_attacks,
+                set_magic_numbers,
                 sliding_piece_relevant_mask,
+                verify_magics,
             },
             manual::{manual_single_bishop_attacks, manual_single_rook_attacks},
         },
@@ -519,4 +689,39 @@ mod tests {
             magic_single_bishop_attacks,
         );
     }
+
+    #[test]
+    fn test_default_magic_numbers_are_reproducible() {
+        assert_eq!(
+            MagicNumbers::search(DEFAULT_ROOK_MAGIC_SEED, DEFAULT_BISHOP_MAGIC_SEED),
+            DEFAULT_MAGIC_NUMBERS
+        );
+        assert_eq!(DEFAULT_MAGIC_NUMBERS.validate(), Ok(()));
+        assert_eq!(verify_magics(), Ok(()));
+        assert_eq!(magic_numbers(), DEFAULT_MAGIC_NUMBERS);
+        assert_eq!(
+            set_magic_numbers(DEFAULT_MAGIC_NUMBERS),
+            Err(MagicError::AlreadyInitialized)
+        );
+    }
+
+    #[test]
+    fn test_colliding_magic_number_is_rejected() {
+        let mut magic_numbers = DEFAULT_MAGIC_NUMBERS;
+        magic_numbers.bishop[Square::C1 as usize] = 1;
+        assert_eq!(
+            magic_numbers.validate(),
+            Err(MagicError::InvalidMagicNumber {
+                piece: Piece::Bishop,
+                square: Square::C1,
+            })
+        );
+        assert_eq!(
+            set_magic_numbers(magic_numbers),
+            Err(MagicError::InvalidMagicNumber {
+                piece: Piece::Bishop,
+                square: Square::C1,
+            })
+        );
+    }
 }
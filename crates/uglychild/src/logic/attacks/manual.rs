@@ -2,7 +2,7 @@
 
 use std::cmp;
 
-use crate::types::{Bitboard, Color, File, Piece, Square};
+use crate::types::{Bitboard, BitboardUtils, Color, File, Piece, QueenLikeMoveDirection, Square};
 
 #[inline]
 const fn walk_ray_limited(
@@ -56,27 +56,27 @@ pub const fn multi_knight_attacks(knights_mask: Bitboard) -> Bitboard {
 
 /// Returns a bitboard with all squares attacked by kings indicated by the bits in `kings_mask`
 pub const fn multi_king_attacks(kings_mask: Bitboard) -> Bitboard {
-    (kings_mask << 9 & !File::H.mask())
-        | (kings_mask << 8)
-        | (kings_mask << 7 & !File::A.mask())
-        | (kings_mask >> 9 & !File::A.mask())
-        | (kings_mask >> 8)
-        | (kings_mask >> 7 & !File::H.mask())
-        | (kings_mask << 1 & !File::H.mask())
-        | (kings_mask >> 1 & !File::A.mask())
+    kings_mask.shift(QueenLikeMoveDirection::UpLeft)
+        | kings_mask.shift(QueenLikeMoveDirection::Up)
+        | kings_mask.shift(QueenLikeMoveDirection::UpRight)
+        | kings_mask.shift(QueenLikeMoveDirection::DownLeft)
+        | kings_mask.shift(QueenLikeMoveDirection::Down)
+        | kings_mask.shift(QueenLikeMoveDirection::DownRight)
+        | kings_mask.shift(QueenLikeMoveDirection::Left)
+        | kings_mask.shift(QueenLikeMoveDirection::Right)
 }
 
 pub const fn multi_pawn_attacks_left(pawns_mask: Bitboard, by_color: Color) -> Bitboard {
     match by_color {
-        Color::White => pawns_mask << 9 & !File::H.mask(),
-        Color::Black => pawns_mask >> 9 & !File::A.mask(),
+        Color::White => pawns_mask.shift(QueenLikeMoveDirection::UpLeft),
+        Color::Black => pawns_mask.shift(QueenLikeMoveDirection::DownRight),
     }
 }
 
 pub const fn multi_pawn_attacks_right(pawns_mask: Bitboard, by_color: Color) -> Bitboard {
     match by_color {
-        Color::White => pawns_mask << 7 & !File::A.mask(),
-        Color::Black => pawns_mask >> 7 & !File::H.mask(),
+        Color::White => pawns_mask.shift(QueenLikeMoveDirection::UpRight),
+        Color::Black => pawns_mask.shift(QueenLikeMoveDirection::DownLeft),
     }
 }
 
@@ -58,19 +58,101 @@ pub fn single_queen_attacks(src_square: Square, occupied_mask: Bitboard) -> Bitb
 }
 
 /// Returns an attack mask encoding all squares attacked by `piece` on `src_square`,
-/// with `occupied_mask` as the mask of occupied squares
+/// with `occupied_mask` as the mask of occupied squares, or `None` if `piece` is not a
+/// bishop, rook or queen
 pub fn sliding_piece_attacks(
     src_square: Square,
     occupied_mask: Bitboard,
     piece: Piece,
+) -> Option<Bitboard> {
+    match piece {
+        Piece::Bishop => Some(single_bishop_attacks(src_square, occupied_mask)),
+        Piece::Rook => Some(single_rook_attacks(src_square, occupied_mask)),
+        Piece::Queen => Some(single_queen_attacks(src_square, occupied_mask)),
+        _ => None,
+    }
+}
+
+/// Like [`sliding_piece_attacks`], without checking that `piece` slides.
+///
+/// # Safety
+/// `piece` must be a bishop, rook or queen. Violating this is undefined behavior.
+pub unsafe fn sliding_piece_attacks_unchecked(
+    src_square: Square,
+    occupied_mask: Bitboard,
+    piece: Piece,
 ) -> Bitboard {
+    debug_assert!(piece.is_sliding_piece(), "Not a sliding piece");
     match piece {
         Piece::Bishop => single_bishop_attacks(src_square, occupied_mask),
         Piece::Rook => single_rook_attacks(src_square, occupied_mask),
         Piece::Queen => single_queen_attacks(src_square, occupied_mask),
-        _ => panic!("Not a sliding piece!"),
+        _ => unsafe { std::hint::unreachable_unchecked() },
+    }
+}
+
+/// Returns an attack mask encoding all squares attacked by a `color` `piece` placed on
+/// `src_square`, with `occupied_mask` as the mask of occupied squares
+///
+/// Covers every piece type; `color` only matters for pawns, and [`Piece::Null`] attacks nothing.
+/// The square need not hold the piece, so this also answers "what would a knight on f5 attack".
+pub fn attacks_from(
+    piece: Piece,
+    color: Color,
+    src_square: Square,
+    occupied_mask: Bitboard,
+) -> Bitboard {
+    match piece {
+        Piece::Null => 0,
+        Piece::Pawn => multi_pawn_attacks(src_square.mask(), color),
+        Piece::Knight => single_knight_attacks(src_square),
+        Piece::King => single_king_attacks(src_square),
+        Piece::Bishop => single_bishop_attacks(src_square, occupied_mask),
+        Piece::Rook => single_rook_attacks(src_square, occupied_mask),
+        Piece::Queen => single_queen_attacks(src_square, occupied_mask),
     }
 }
 
 // Re-export for backward compatibility
 pub use magic::sliding_piece_relevant_mask;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::IterableEnum;
+
+    #[test]
+    fn test_attacks_from_matches_per_piece_functions() {
+        let occupied = Square::D4.mask() | Square::F6.mask() | Square::B7.mask();
+        for square in Square::ALL {
+            for color in [Color::White, Color::Black] {
+                assert_eq!(
+                    attacks_from(Piece::Pawn, color, square, occupied),
+                    multi_pawn_attacks(square.mask(), color)
+                );
+            }
+            assert_eq!(
+                attacks_from(Piece::Knight, Color::White, square, occupied),
+                single_knight_attacks(square)
+            );
+            assert_eq!(
+                attacks_from(Piece::King, Color::Black, square, occupied),
+                single_king_attacks(square)
+            );
+            assert_eq!(
+                attacks_from(Piece::Queen, Color::White, square, occupied),
+                single_queen_attacks(square, occupied)
+            );
+            assert_eq!(attacks_from(Piece::Null, Color::White, square, occupied), 0);
+        }
+        assert_eq!(sliding_piece_attacks(Square::E4, 0, Piece::Knight), None);
+        assert_eq!(
+            sliding_piece_attacks(Square::E4, 0, Piece::Rook),
+            Some(single_rook_attacks(Square::E4, 0))
+        );
+        assert_eq!(
+            attacks_from(Piece::Pawn, Color::Black, Square::E4, 0),
+            Square::D3.mask() | Square::F3.mask()
+        );
+    }
+}
@@ -1,28 +1,57 @@
 //! Precomputed attack tables for non-sliding pieces.
+//!
+//! The tables are built at compile time by `const fn`s and exposed as `const` arrays (indexed by
+//! `square as usize`), so downstream const code can derive its own tables from them. Runtime
+//! lookups go through `static` copies to avoid inlining the arrays at every use.
 
 use crate::{
     logic::attacks::manual,
-    types::{Bitboard, Square},
+    types::{Bitboard, Color, Square},
     utilities::{Array, IterableEnum},
 };
 
-static SINGLE_KING_ATTACKS: Array<Bitboard, 64> = Array({
+/// Builds the king attack table.
+pub const fn build_king_attacks() -> [Bitboard; 64] {
     let mut arr = [0 as Bitboard; 64];
     for square in Square::ALL {
         arr[square as usize] = manual::multi_king_attacks(square.mask());
     }
     arr
-});
+}
 
-static SINGLE_KNIGHT_ATTACKS: Array<Bitboard, 64> = Array({
+/// Builds the knight attack table.
+pub const fn build_knight_attacks() -> [Bitboard; 64] {
     let mut arr = [0 as Bitboard; 64];
     for square in Square::ALL {
         arr[square as usize] = manual::multi_knight_attacks(square.mask());
     }
     arr
-});
+}
 
-/// Returns a precomputed bitboard with all squares attacked by a knight on `src_square`
+/// Builds the pawn attack tables, indexed by `color as usize` then `square as usize`.
+pub const fn build_pawn_attacks() -> [[Bitboard; 64]; 2] {
+    let mut arr = [[0 as Bitboard; 64]; 2];
+    for square in Square::ALL {
+        arr[Color::White as usize][square as usize] =
+            manual::multi_pawn_attacks(square.mask(), Color::White);
+        arr[Color::Black as usize][square as usize] =
+            manual::multi_pawn_attacks(square.mask(), Color::Black);
+    }
+    arr
+}
+
+/// Squares attacked by a king on each square.
+pub const KING_ATTACKS: [Bitboard; 64] = build_king_attacks();
+/// Squares attacked by a knight on each square.
+pub const KNIGHT_ATTACKS: [Bitboard; 64] = build_knight_attacks();
+/// Squares attacked by a pawn of each color on each square.
+pub const PAWN_ATTACKS: [[Bitboard; 64]; 2] = build_pawn_attacks();
+
+static SINGLE_KING_ATTACKS: Array<Bitboard, 64> = Array(KING_ATTACKS);
+
+static SINGLE_KNIGHT_ATTACKS: Array<Bitboard, 64> = Array(KNIGHT_ATTACKS);
+
+/// Returns a precomputed bitboard with all squares attacked by a king on `src_square`
 pub const fn precomputed_single_king_attacks(src_square: Square) -> Bitboard {
     SINGLE_KING_ATTACKS[src_square as usize]
 }
@@ -46,6 +75,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tables_usable_in_const_context() {
+        // Squares from which a knight attacks at least one corner, derived at compile time.
+        const CORNER_KNIGHT_SQUARES: Bitboard = {
+            let corners =
+                Square::A1.mask() | Square::H1.mask() | Square::A8.mask() | Square::H8.mask();
+            let mut mask = 0;
+            for square in Square::ALL {
+                if KNIGHT_ATTACKS[square as usize] & corners != 0 {
+                    mask |= square.mask();
+                }
+            }
+            mask
+        };
+        assert_eq!(CORNER_KNIGHT_SQUARES.count_ones(), 8);
+        assert_eq!(
+            PAWN_ATTACKS[Color::White as usize][Square::E4 as usize],
+            Square::D5.mask() | Square::F5.mask()
+        );
+        assert_eq!(
+            PAWN_ATTACKS[Color::Black as usize][Square::A7 as usize],
+            Square::B6.mask()
+        );
+    }
+
     #[test]
     fn test_single_knight_attacks() {
         for square in Square::ALL {
@@ -1,7 +1,36 @@
 //! Castling legality checks for kingside and queenside.
 
+use std::fmt;
+
 use crate::types::{Color, Flank, Piece, Position, ZobristPolicy};
 
+/// Why a side cannot castle, from [`Position::castling_legality`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CastlingObstacle {
+    /// The king or rook has moved, so the castling right is gone.
+    NoRights,
+    /// A piece stands between the king and the rook.
+    PathBlocked,
+    /// The king is in check.
+    InCheck,
+    /// The king would pass through an attacked square.
+    TransitAttacked,
+    /// The king would land on an attacked square.
+    DestinationAttacked,
+}
+
+impl fmt::Display for CastlingObstacle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CastlingObstacle::NoRights => write!(f, "castling rights have been lost"),
+            CastlingObstacle::PathBlocked => write!(f, "pieces stand between king and rook"),
+            CastlingObstacle::InCheck => write!(f, "the king is in check"),
+            CastlingObstacle::TransitAttacked => write!(f, "the king would pass through check"),
+            CastlingObstacle::DestinationAttacked => write!(f, "the king would land in check"),
+        }
+    }
+}
+
 impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
     /// Returns `true` if the side to move retains castling rights on `flank`.
     pub fn has_castling_rights(&self, flank: Flank) -> bool {
@@ -28,4 +57,104 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
             && self.has_castling_space(flank)
             && self.can_castle_without_check(flank)
     }
+
+    /// Explains whether `color` could castle on `flank` if it were to move, for either side.
+    ///
+    /// Returns the first obstacle found, in the order of [`CastlingObstacle`]'s variants. For the
+    /// side to move, `Ok(())` exactly when castling is among the legal moves.
+    pub fn castling_legality(&self, color: Color, flank: Flank) -> Result<(), CastlingObstacle> {
+        let occupied = self.board.piece_mask::<{ Piece::ALL_PIECES }>();
+        let destination = flank.king_castled_square(color);
+        if !self.context().castling_rights.has(flank, color) {
+            Err(CastlingObstacle::NoRights)
+        } else if flank.castling_gap_mask(color) & occupied != 0 {
+            Err(CastlingObstacle::PathBlocked)
+        } else if self
+            .board
+            .is_square_attacked(self.king_square(color), color.other())
+        {
+            Err(CastlingObstacle::InCheck)
+        } else if self.board.is_mask_attacked(
+            flank.king_path_mask(color) & !destination.mask(),
+            color.other(),
+        ) {
+            Err(CastlingObstacle::TransitAttacked)
+        } else if self.board.is_square_attacked(destination, color.other()) {
+            Err(CastlingObstacle::DestinationAttacked)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Move, MoveFlag, MoveList};
+
+    fn legality(fen: &str, flank: Flank) -> Result<(), CastlingObstacle> {
+        let position = Position::<1, { Color::White }>::from_fen(fen).unwrap();
+        let result = position.castling_legality(Color::White, flank);
+
+        let mut moves = MoveList::new();
+        position.generate_moves(&mut moves);
+        let castling = Move::new_non_promotion(
+            Color::White.king_initial_square(),
+            flank.king_castled_square(Color::White),
+            MoveFlag::Castling,
+        );
+        assert_eq!(
+            result.is_ok(),
+            moves.as_slice().contains(&castling),
+            "{fen}"
+        );
+        result
+    }
+
+    #[test]
+    fn test_castling_legality_reasons() {
+        use CastlingObstacle::*;
+
+        assert_eq!(
+            legality("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", Flank::Kingside),
+            Ok(())
+        );
+        assert_eq!(
+            legality("r3k2r/8/8/8/8/8/8/R3K2R w Qkq - 0 1", Flank::Kingside),
+            Err(NoRights)
+        );
+        assert_eq!(
+            legality("r3k2r/8/8/8/8/8/8/RN2K2R w KQkq - 0 1", Flank::Queenside),
+            Err(PathBlocked)
+        );
+        assert_eq!(
+            legality("4k3/8/8/8/8/8/4r3/R3K2R w KQ - 0 1", Flank::Kingside),
+            Err(InCheck)
+        );
+        assert_eq!(
+            legality("4k3/8/8/8/8/8/5r2/R3K2R w KQ - 0 1", Flank::Kingside),
+            Err(TransitAttacked)
+        );
+        assert_eq!(
+            legality("4k3/8/8/8/8/8/5r2/R3K2R w KQ - 0 1", Flank::Queenside),
+            Ok(())
+        );
+        assert_eq!(
+            legality("4k3/8/8/8/8/8/6r1/R3K2R w KQ - 0 1", Flank::Kingside),
+            Err(DestinationAttacked)
+        );
+
+        // Black's castling can be examined while White is to move.
+        let position =
+            Position::<1, { Color::White }>::from_fen("r3k2r/8/8/8/8/8/1R6/4K3 w kq - 0 1")
+                .unwrap();
+        assert_eq!(
+            position.castling_legality(Color::Black, Flank::Kingside),
+            Ok(())
+        );
+        assert_eq!(
+            position.castling_legality(Color::Black, Flank::Queenside),
+            Ok(())
+        );
+    }
 }
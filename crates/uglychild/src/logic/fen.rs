@@ -4,28 +4,37 @@
 //! - [`crate::logic::fen::parse_fen_to_position_with_policy`]: parse with explicit hashing policy.
 //! - [`crate::logic::fen::parse_fen_to_position`]: parse with default [`crate::types::WithZobrist`] policy.
 //! - [`crate::types::Position::from_fen`]: convenience method on a concrete `Position` type.
+//! - [`crate::types::Position::to_fen`] / [`crate::types::TypedPosition::to_fen`]: the reverse.
+//! - [`crate::types::Position::from_fen_const`] and [`crate::position!`]: parse at compile time,
+//!   for fixtures and constants.
+//! - [`FenFields::parse`]: split and syntax-check the six fields without building a position.
 
-use crate::types::{
-    Board,
-    CastlingRights,
-    Color,
-    ColoredPiece,
-    ConstDoublePawnPushFile,
-    DoublePawnPushFile,
-    File,
-    Position,
-    PositionContext,
-    Square,
-    TypedPosition,
-    WithZobrist,
-    ZobristPolicy,
+use std::{error::Error, fmt};
+
+use crate::{
+    logic::zobrist_hash::calc_position_zobrist_hash,
+    types::{
+        Board,
+        CastlingRights,
+        Color,
+        ColoredPiece,
+        ConstDoublePawnPushFile,
+        DoublePawnPushFile,
+        Piece,
+        Position,
+        PositionContext,
+        Square,
+        TypedPosition,
+        WithZobrist,
+        ZobristPolicy,
+    },
 };
 
 /// The FEN string representing the starting position of a standard chess game.
 pub const INITIAL_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 
 /// An error that occurs when parsing a FEN string.
-#[derive(Eq, PartialEq, Debug)]
+#[derive(Clone, Eq, PartialEq, Debug)]
 pub enum FenParseError {
     /// FEN does not contain exactly six whitespace-separated fields.
     InvalidFieldCount(usize),
@@ -37,7 +46,7 @@ pub enum FenParseError {
     InvalidSideToMove(String),
     /// Castling-rights field is malformed.
     InvalidCastlingRights(String),
-    /// En-passant target field is malformed.
+    /// En-passant target field is malformed, or not on the rank the side to move captures onto.
     InvalidEnPassantTarget(String),
     /// Halfmove clock is invalid (non-numeric or out of range).
     InvalidHalfmoveClock(String),
@@ -47,10 +56,124 @@ pub enum FenParseError {
     InvalidPosition(String),
 }
 
+impl fmt::Display for FenParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FenParseError::InvalidFieldCount(count) => {
+                write!(f, "expected 6 FEN fields, found {count}")
+            }
+            FenParseError::InvalidRankCount(count) => {
+                write!(f, "expected 8 ranks, found {count}")
+            }
+            FenParseError::InvalidBoardRow(row) => write!(f, "invalid board row `{row}`"),
+            FenParseError::InvalidSideToMove(side) => write!(f, "invalid side to move `{side}`"),
+            FenParseError::InvalidCastlingRights(rights) => {
+                write!(f, "invalid castling rights `{rights}`")
+            }
+            FenParseError::InvalidEnPassantTarget(target) => {
+                write!(f, "invalid en passant target `{target}`")
+            }
+            FenParseError::InvalidHalfmoveClock(clock) => {
+                write!(f, "invalid halfmove clock `{clock}`")
+            }
+            FenParseError::InvalidFullmoveNumber(number) => {
+                write!(f, "invalid fullmove number `{number}`")
+            }
+            FenParseError::InvalidPosition(fen) => write!(f, "illegal position `{fen}`"),
+        }
+    }
+}
+
+impl Error for FenParseError {}
+
+/// The six fields of a FEN string as slices of it, found without allocating or building a
+/// [`Position`].
+///
+/// [`FenFields::parse`] checks each field's syntax, but not the position itself (king counts,
+/// checks, castling rights against piece placement); [`Position::from_fen`] does that.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FenFields<'a> {
+    /// Piece placement, rank 8 first (`rnbqkbnr/pppppppp/...`).
+    pub board: &'a str,
+    /// `w` or `b`.
+    pub side_to_move: &'a str,
+    /// `KQkq`, a subset of it, or `-`.
+    pub castling_rights: &'a str,
+    /// En passant target square, or `-`.
+    pub en_passant_target: &'a str,
+    /// Halfmove clock for the fifty-move rule.
+    pub halfmove_clock: &'a str,
+    /// Fullmove number, starting at `1`.
+    pub fullmove_number: &'a str,
+}
+
+impl<'a> FenFields<'a> {
+    /// Splits `fen` into its fields and checks their syntax.
+    ///
+    /// Allocates only to build the error when `fen` is malformed.
+    pub fn parse(fen: &'a str) -> Result<FenFields<'a>, FenParseError> {
+        let fields = FenFields::split(fen)?;
+        validate_fen_board(fields.board)?;
+        let side_to_move = parse_side_to_move(fields.side_to_move)?;
+        parse_castling_rights(fields.castling_rights)?;
+        parse_en_passant_target(fields.en_passant_target, side_to_move)?;
+        parse_fen_halfmove_clock(fields.halfmove_clock)?;
+        parse_fen_fullmove_number(fields.fullmove_number)?;
+        Ok(fields)
+    }
+
+    /// Splits `fen` on ASCII whitespace, requiring exactly six fields.
+    fn split(fen: &'a str) -> Result<FenFields<'a>, FenParseError> {
+        let mut parts = fen.split_ascii_whitespace();
+        let mut fields = [""; 6];
+        for field in &mut fields {
+            *field = parts.next().ok_or_else(|| {
+                FenParseError::InvalidFieldCount(fen.split_ascii_whitespace().count())
+            })?;
+        }
+        if parts.next().is_some() {
+            return Err(FenParseError::InvalidFieldCount(
+                fen.split_ascii_whitespace().count(),
+            ));
+        }
+        let [
+            board,
+            side_to_move,
+            castling_rights,
+            en_passant_target,
+            halfmove_clock,
+            fullmove_number,
+        ] = fields;
+        Ok(FenFields {
+            board,
+            side_to_move,
+            castling_rights,
+            en_passant_target,
+            halfmove_clock,
+            fullmove_number,
+        })
+    }
+
+    /// The side to move.
+    pub fn side_to_move(&self) -> Color {
+        parse_side_to_move(self.side_to_move).expect("validated by FenFields::parse")
+    }
+
+    /// The halfmove clock.
+    pub fn halfmove_clock(&self) -> u8 {
+        parse_fen_halfmove_clock(self.halfmove_clock).expect("validated by FenFields::parse")
+    }
+
+    /// The fullmove number.
+    pub fn fullmove_number(&self) -> u16 {
+        parse_fen_fullmove_number(self.fullmove_number).expect("validated by FenFields::parse")
+    }
+}
+
 fn parse_side_to_move(fen_side_to_move: &str) -> Result<Color, FenParseError> {
-    match fen_side_to_move {
-        "w" => Ok(Color::White),
-        "b" => Ok(Color::Black),
+    let mut chars = fen_side_to_move.chars();
+    match (chars.next().and_then(Color::from_char), chars.next()) {
+        (Some(color), None) => Ok(color),
         _ => Err(FenParseError::InvalidSideToMove(
             fen_side_to_move.to_string(),
         )),
@@ -81,26 +204,19 @@ fn parse_castling_rights(fen_castling_rights: &str) -> Result<CastlingRights, Fe
 
 fn parse_en_passant_target(
     fen_en_passant_target: &str,
+    side_to_move: Color,
 ) -> Result<DoublePawnPushFile, FenParseError> {
     if fen_en_passant_target == "-" {
         Ok(-1)
     } else {
-        if fen_en_passant_target.len() != 2 {
-            return Err(FenParseError::InvalidEnPassantTarget(
-                fen_en_passant_target.to_string(),
-            ));
-        }
-        let file = fen_en_passant_target.chars().next().unwrap();
-        let rank = fen_en_passant_target.chars().nth(1).unwrap();
-        if !('a'..='h').contains(&file) || !('1'..='8').contains(&rank) {
-            return Err(FenParseError::InvalidEnPassantTarget(
+        match fen_en_passant_target.parse::<Square>() {
+            Ok(square) if square.rank() == side_to_move.en_passant_target_rank() => {
+                Ok(DoublePawnPushFile::from_file(Some(square.file())))
+            }
+            _ => Err(FenParseError::InvalidEnPassantTarget(
                 fen_en_passant_target.to_string(),
-            ));
+            )),
         }
-        Ok(DoublePawnPushFile::from_file(Some({
-            let value = file as u8 - b'a';
-            unsafe { File::try_from(value).unwrap_unchecked() }
-        })))
     }
 }
 
@@ -137,11 +253,11 @@ fn parse_fen_board_row(
                 return Err(FenParseError::InvalidBoardRow(row.to_string()));
             }
         } else if c.is_ascii_alphabetic() {
-            match ColoredPiece::from_ascii(c) {
-                ColoredPiece::NoPiece => {
+            match ColoredPiece::from_fen_char(c) {
+                None => {
                     return Err(FenParseError::InvalidBoardRow(row.to_string()));
                 }
-                cp => {
+                Some(cp) => {
                     let dst =
                         unsafe { Square::try_from(row_from_top * 8 + file).unwrap_unchecked() };
                     board.put_piece_and_color(cp.color(), cp.piece(), dst);
@@ -161,6 +277,28 @@ fn parse_fen_board_row(
     }
 }
 
+/// Checks the piece placement field like [`parse_fen_board`], without building a [`Board`].
+fn validate_fen_board(fen_board: &str) -> Result<(), FenParseError> {
+    let row_count = fen_board.split('/').count();
+    if row_count != 8 {
+        return Err(FenParseError::InvalidRankCount(row_count));
+    }
+    for row in fen_board.split('/') {
+        let mut file = 0;
+        for c in row.chars() {
+            file += match c.to_digit(10) {
+                Some(empty) => empty,
+                _ if ColoredPiece::from_fen_char(c).is_some() => 1,
+                _ => return Err(FenParseError::InvalidBoardRow(row.to_string())),
+            };
+        }
+        if file != 8 {
+            return Err(FenParseError::InvalidBoardRow(row.to_string()));
+        }
+    }
+    Ok(())
+}
+
 fn parse_fen_board(fen_board: &str) -> Result<Board, FenParseError> {
     let fen_board_rows: Vec<&str> = fen_board.split('/').collect();
 
@@ -181,75 +319,66 @@ fn parse_fen_board(fen_board: &str) -> Result<Board, FenParseError> {
 pub(crate) fn parse_fen_to_typed_position<const N: usize, Z: ZobristPolicy>(
     fen: &str,
 ) -> Result<TypedPosition<N, Z>, FenParseError> {
-    let fen_parts: Vec<&str> = fen.split_ascii_whitespace().collect();
-    if fen_parts.len() != 6 {
-        return Err(FenParseError::InvalidFieldCount(fen_parts.len()));
-    }
-
-    match fen_parts[..] {
-        [
-            fen_board,
-            fen_side_to_move,
-            fen_castling_rights,
-            fen_en_passant_target,
-            fen_halfmove_clock,
-            fen_fullmove_number,
-        ] => {
-            let side_to_move = parse_side_to_move(fen_side_to_move)?;
-            let castling_rights = parse_castling_rights(fen_castling_rights)?;
-            let double_pawn_push_file = parse_en_passant_target(fen_en_passant_target)?;
-            let halfmove_clock = parse_fen_halfmove_clock(fen_halfmove_clock)?;
-            let fullmove_number = parse_fen_fullmove_number(fen_fullmove_number)?;
-            let board = parse_fen_board(fen_board)?;
-
-            let halfmove =
-                (fullmove_number - 1) * 2 + if side_to_move == Color::Black { 1 } else { 0 };
-            let mut context = PositionContext::<Z::HashState>::blank();
-            context.castling_rights = castling_rights;
-            context.double_pawn_push_file = double_pawn_push_file;
-            context.halfmove_clock = halfmove_clock;
-            context.zobrist_hash = Z::initial_hash(
-                &board,
-                context.castling_rights,
-                context.double_pawn_push_file,
-                side_to_move,
-            );
-
-            let mut contexts = [PositionContext::<Z::HashState>::blank(); N];
-            contexts[0] = context;
-
-            match side_to_move {
-                Color::White => {
-                    let mut state = Position::<N, { Color::White }, Z> {
-                        board,
-                        halfmove,
-                        contexts,
-                        num_contexts: 1,
-                    };
-                    if state.is_unequivocally_valid() {
-                        state.update_pins_and_checks();
-                        Ok(TypedPosition::White(state))
-                    } else {
-                        Err(FenParseError::InvalidPosition(fen.to_string()))
-                    }
-                }
-                Color::Black => {
-                    let mut state = Position::<N, { Color::Black }, Z> {
-                        board,
-                        halfmove,
-                        contexts,
-                        num_contexts: 1,
-                    };
-                    if state.is_unequivocally_valid() {
-                        state.update_pins_and_checks();
-                        Ok(TypedPosition::Black(state))
-                    } else {
-                        Err(FenParseError::InvalidPosition(fen.to_string()))
-                    }
-                }
+    let FenFields {
+        board: fen_board,
+        side_to_move: fen_side_to_move,
+        castling_rights: fen_castling_rights,
+        en_passant_target: fen_en_passant_target,
+        halfmove_clock: fen_halfmove_clock,
+        fullmove_number: fen_fullmove_number,
+    } = FenFields::split(fen)?;
+
+    let side_to_move = parse_side_to_move(fen_side_to_move)?;
+    let castling_rights = parse_castling_rights(fen_castling_rights)?;
+    let double_pawn_push_file = parse_en_passant_target(fen_en_passant_target, side_to_move)?;
+    let halfmove_clock = parse_fen_halfmove_clock(fen_halfmove_clock)?;
+    let fullmove_number = parse_fen_fullmove_number(fen_fullmove_number)?;
+    let board = parse_fen_board(fen_board)?;
+
+    let halfmove = (fullmove_number - 1) * 2 + if side_to_move == Color::Black { 1 } else { 0 };
+    let mut context = PositionContext::<Z::HashState>::blank();
+    context.castling_rights = castling_rights;
+    context.double_pawn_push_file = double_pawn_push_file;
+    context.halfmove_clock = halfmove_clock;
+    context.zobrist_hash = Z::initial_hash(
+        &board,
+        context.castling_rights,
+        context.double_pawn_push_file,
+        side_to_move,
+    );
+
+    let mut contexts = [PositionContext::<Z::HashState>::blank(); N];
+    contexts[0] = context;
+
+    match side_to_move {
+        Color::White => {
+            let mut state = Position::<N, { Color::White }, Z> {
+                board,
+                halfmove,
+                contexts,
+                num_contexts: 1,
+            };
+            if state.is_unequivocally_valid() {
+                state.update_pins_and_checks();
+                Ok(TypedPosition::White(state))
+            } else {
+                Err(FenParseError::InvalidPosition(fen.to_string()))
+            }
+        }
+        Color::Black => {
+            let mut state = Position::<N, { Color::Black }, Z> {
+                board,
+                halfmove,
+                contexts,
+                num_contexts: 1,
+            };
+            if state.is_unequivocally_valid() {
+                state.update_pins_and_checks();
+                Ok(TypedPosition::Black(state))
+            } else {
+                Err(FenParseError::InvalidPosition(fen.to_string()))
             }
         }
-        _ => Err(FenParseError::InvalidFieldCount(fen_parts.len())),
     }
 }
 
@@ -262,8 +391,12 @@ pub fn parse_fen_to_position_with_policy<const N: usize, const STM: Color, Z: Zo
     match parse_fen_to_typed_position::<N, Z>(fen)? {
         TypedPosition::White(pos) if STM == Color::White => Ok(pos.rebrand_stm::<STM>()),
         TypedPosition::Black(pos) if STM == Color::Black => Ok(pos.rebrand_stm::<STM>()),
-        TypedPosition::White(_) => Err(FenParseError::InvalidSideToMove("w".to_string())),
-        TypedPosition::Black(_) => Err(FenParseError::InvalidSideToMove("b".to_string())),
+        TypedPosition::White(_) => Err(FenParseError::InvalidSideToMove(
+            Color::White.as_char().to_string(),
+        )),
+        TypedPosition::Black(_) => Err(FenParseError::InvalidSideToMove(
+            Color::Black.as_char().to_string(),
+        )),
     }
 }
 
@@ -276,6 +409,252 @@ pub fn parse_fen_to_position<const N: usize, const STM: Color>(
     parse_fen_to_position_with_policy::<N, STM, WithZobrist>(fen)
 }
 
+/// Skips one or more spaces between FEN fields.
+const fn expect_fen_separator(fen: &[u8], i: &mut usize) {
+    if *i >= fen.len() || fen[*i] != b' ' {
+        panic!("FEN fields must be separated by spaces");
+    }
+    while *i < fen.len() && fen[*i] == b' ' {
+        *i += 1;
+    }
+}
+
+/// Reads a decimal number of at most `u16::MAX`.
+const fn parse_fen_number_const(fen: &[u8], i: &mut usize) -> u16 {
+    let start = *i;
+    let mut value: u32 = 0;
+    while *i < fen.len() && fen[*i].is_ascii_digit() {
+        value = value * 10 + (fen[*i] - b'0') as u32;
+        if value > u16::MAX as u32 {
+            panic!("FEN number out of range");
+        }
+        *i += 1;
+    }
+    if *i == start {
+        panic!("expected a number in FEN");
+    }
+    value as u16
+}
+
+const fn parse_fen_board_const(fen: &[u8], i: &mut usize) -> Board {
+    let mut board = Board::blank();
+    let mut row_from_top = 0u8;
+    let mut file = 0u8;
+    while *i < fen.len() && fen[*i] != b' ' {
+        let c = fen[*i];
+        if c == b'/' {
+            if file != 8 || row_from_top == 7 {
+                panic!("invalid FEN board row");
+            }
+            row_from_top += 1;
+            file = 0;
+        } else if c >= b'1' && c <= b'8' {
+            file += c - b'0';
+            if file > 8 {
+                panic!("invalid FEN board row");
+            }
+        } else {
+            let Some(cp) = ColoredPiece::from_fen_char(c as char) else {
+                panic!("invalid FEN piece letter");
+            };
+            if file >= 8 {
+                panic!("invalid FEN board row");
+            }
+            let dst = unsafe { Square::try_from(row_from_top * 8 + file).unwrap_unchecked() };
+            board.put_piece_and_color(cp.color(), cp.piece(), dst);
+            file += 1;
+        }
+        *i += 1;
+    }
+    if row_from_top != 7 || file != 8 {
+        panic!("FEN board must have eight ranks of eight files");
+    }
+    board
+}
+
+/// Parses a FEN string in a const context, panicking (a compile error in `const` items) on
+/// malformed input.
+///
+/// Checks what [`parse_fen_to_position`] checks: the syntax, the side to move, the board (one
+/// king per side, consistent occupancy), castling rights matching the kings and rooks, the en
+/// passant target matching the side to move and a pawn that just double-pushed, and the side not
+/// to move not being in check. Uses [`WithZobrist`] hashing. See [`Position::from_fen_const`] and
+/// [`crate::position!`].
+pub const fn parse_fen_const<const N: usize, const STM: Color>(
+    fen: &str,
+) -> Position<N, STM, WithZobrist> {
+    let fen = fen.as_bytes();
+    let mut i = 0;
+
+    let board = parse_fen_board_const(fen, &mut i);
+
+    expect_fen_separator(fen, &mut i);
+    let side_to_move = match fen.get(i) {
+        Some(&c) => match Color::from_char(c as char) {
+            Some(color) => color,
+            None => panic!("invalid FEN side to move"),
+        },
+        None => panic!("invalid FEN side to move"),
+    };
+    if side_to_move != STM {
+        panic!("FEN side to move does not match the position type");
+    }
+    i += 1;
+
+    expect_fen_separator(fen, &mut i);
+    let mut castling_bits = 0u8;
+    if i < fen.len() && fen[i] == b'-' {
+        i += 1;
+    } else {
+        while i < fen.len() && fen[i] != b' ' {
+            castling_bits |= match fen[i] {
+                b'K' => 0b1000,
+                b'Q' => 0b0100,
+                b'k' => 0b0010,
+                b'q' => 0b0001,
+                _ => panic!("invalid FEN castling rights"),
+            };
+            i += 1;
+        }
+    }
+
+    expect_fen_separator(fen, &mut i);
+    let double_pawn_push_file: DoublePawnPushFile = match (fen.get(i), fen.get(i + 1)) {
+        (Some(b'-'), _) => {
+            i += 1;
+            -1
+        }
+        (Some(&file @ b'a'..=b'h'), Some(&rank))
+            if rank == side_to_move.en_passant_target_rank().as_char() as u8 =>
+        {
+            i += 2;
+            (file - b'a') as DoublePawnPushFile
+        }
+        _ => panic!("invalid FEN en passant target"),
+    };
+
+    expect_fen_separator(fen, &mut i);
+    let halfmove_clock = parse_fen_number_const(fen, &mut i);
+    if halfmove_clock > 100 {
+        panic!("invalid FEN halfmove clock");
+    }
+
+    expect_fen_separator(fen, &mut i);
+    let fullmove_number = parse_fen_number_const(fen, &mut i);
+    if fullmove_number == 0 {
+        panic!("invalid FEN fullmove number");
+    }
+    if i != fen.len() {
+        panic!("unexpected text after FEN");
+    }
+
+    if !board.is_unequivocally_valid() {
+        panic!("illegal FEN board");
+    }
+    let castling_rights = CastlingRights::from_bits(castling_bits);
+    let context = PositionContext {
+        halfmove_clock: halfmove_clock as u8,
+        double_pawn_push_file,
+        castling_rights,
+        captured_piece: Piece::Null,
+        zobrist_hash: calc_position_zobrist_hash(
+            &board,
+            castling_rights,
+            double_pawn_push_file,
+            side_to_move,
+        ),
+        pinned: 0,
+        checkers: 0,
+    };
+    let mut contexts = [PositionContext {
+        zobrist_hash: 0,
+        ..context
+    }; N];
+    contexts[0] = context;
+
+    let mut position = Position {
+        board,
+        halfmove: (fullmove_number - 1) * 2 + side_to_move as u16,
+        contexts,
+        num_contexts: 1,
+    };
+    if !position.has_valid_castling_rights() {
+        panic!("FEN castling rights do not match the kings and rooks");
+    }
+    if !position.has_valid_double_pawn_push() {
+        panic!("FEN en passant target has no pawn that just double-pushed");
+    }
+    if position.is_opposite_side_in_check() {
+        panic!("FEN side not to move is in check");
+    }
+    position.update_pins_and_checks();
+    position
+}
+
+/// Builds a [`Position`] from a FEN string literal at compile time.
+///
+/// The stack capacity and side to move come from the expected type, so the macro can
+/// initialize `const` and `static` items directly. Malformed FEN fails the build; see
+/// [`parse_fen_const`] for what is checked.
+///
+/// ```
+/// use uglychild::{Color, position, types::Position};
+///
+/// static AFTER_E4: Position<8, { Color::Black }> =
+///     position!("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+/// assert_eq!(AFTER_E4.get_fullmove(), 1);
+/// ```
+#[macro_export]
+macro_rules! position {
+    ($fen:expr) => {
+        const { $crate::logic::fen::parse_fen_const($fen) }
+    };
+}
+
+fn write_fen_board(board: &Board, fen: &mut String) {
+    for row_from_top in 0..8u8 {
+        if row_from_top > 0 {
+            fen.push('/');
+        }
+        let mut empty = 0;
+        for file in 0..8u8 {
+            let square = unsafe { Square::try_from(row_from_top * 8 + file).unwrap_unchecked() };
+            if board.is_occupied_at(square) {
+                if empty > 0 {
+                    fen.push(char::from(b'0' + empty));
+                    empty = 0;
+                }
+                let piece = ColoredPiece::new(board.color_at(square), board.piece_at(square));
+                fen.push(piece.to_fen_char());
+            } else {
+                empty += 1;
+            }
+        }
+        if empty > 0 {
+            fen.push(char::from(b'0' + empty));
+        }
+    }
+}
+
+fn write_fen_castling_rights(castling_rights: CastlingRights, fen: &mut String) {
+    let len_before = fen.len();
+    for (bit, c) in [(0b1000, 'K'), (0b0100, 'Q'), (0b0010, 'k'), (0b0001, 'q')] {
+        if castling_rights.intersects(bit) {
+            fen.push(c);
+        }
+    }
+    if fen.len() == len_before {
+        fen.push('-');
+    }
+}
+
+impl<const N: usize, const STM: Color> Position<N, STM, WithZobrist> {
+    /// Parses `fen` into `Self` in a const context; see [`parse_fen_const`].
+    pub const fn from_fen_const(fen: &str) -> Self {
+        parse_fen_const(fen)
+    }
+}
+
 impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
     /// Parses `fen` into `Self`.
     ///
@@ -283,6 +662,43 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
     pub fn from_fen(fen: &str) -> Result<Self, FenParseError> {
         parse_fen_to_position_with_policy::<N, STM, Z>(fen)
     }
+
+    /// Writes the position as a FEN string.
+    ///
+    /// The en-passant field names the skipped square after any double pawn push, whether or
+    /// not a capture is possible, as [`Self::from_fen`] expects.
+    pub fn to_fen(&self) -> String {
+        let context = self.context();
+        let mut fen = String::with_capacity(90);
+        write_fen_board(&self.board, &mut fen);
+        fen.push(' ');
+        fen.push(STM.as_char());
+        fen.push(' ');
+        write_fen_castling_rights(context.castling_rights, &mut fen);
+        match context.double_pawn_push_file.has_file() {
+            true => {
+                let target = context.double_pawn_push_file.ep_dst_square(STM);
+                fen.push_str(&format!(" {target}"));
+            }
+            false => fen.push_str(" -"),
+        }
+        fen.push_str(&format!(
+            " {} {}",
+            context.halfmove_clock,
+            self.get_fullmove()
+        ));
+        fen
+    }
+}
+
+impl<const N: usize, Z: ZobristPolicy> TypedPosition<N, Z> {
+    /// Writes the position as a FEN string; see [`Position::to_fen`].
+    pub fn to_fen(&self) -> String {
+        match self {
+            TypedPosition::White(position) => position.to_fen(),
+            TypedPosition::Black(position) => position.to_fen(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -290,6 +706,49 @@ mod tests {
     use super::*;
     use crate::types::TypedPosition;
 
+    #[test]
+    fn test_fen_parse_error_display() {
+        let err = TypedPosition::<1>::from_fen("8/8/8/8/8/8/8/8 x - - 0 1").unwrap_err();
+        assert_eq!(err, FenParseError::InvalidSideToMove("x".to_string()));
+        assert_eq!(err.to_string(), "invalid side to move `x`");
+        let boxed: Box<dyn Error> = Box::new(err);
+        assert!(boxed.source().is_none());
+    }
+
+    #[test]
+    fn test_fen_fields() {
+        let fields = FenFields::parse("r3k2r/8/8/3pP3/8/8/8/R3K2R  w Kq d6 3 41\n").unwrap();
+        assert_eq!(fields.board, "r3k2r/8/8/3pP3/8/8/8/R3K2R");
+        assert_eq!(fields.castling_rights, "Kq");
+        assert_eq!(fields.en_passant_target, "d6");
+        assert_eq!(fields.side_to_move(), Color::White);
+        assert_eq!(fields.halfmove_clock(), 3);
+        assert_eq!(fields.fullmove_number(), 41);
+
+        // Only the syntax is checked: a board without kings is accepted.
+        assert!(FenFields::parse("8/8/8/8/8/8/8/8 b - - 0 1").is_ok());
+        assert_eq!(
+            FenFields::parse("8/8/8/8/8/8/8/8 b - - 0"),
+            Err(FenParseError::InvalidFieldCount(5))
+        );
+        assert_eq!(
+            FenFields::parse("8/8/8/8/8/8/8/8 b - - 0 1 x"),
+            Err(FenParseError::InvalidFieldCount(7))
+        );
+        assert_eq!(
+            FenFields::parse("8/8/8/8/8/8/8/7 b - - 0 1"),
+            Err(FenParseError::InvalidBoardRow("7".to_string()))
+        );
+        assert_eq!(
+            FenFields::parse("8/8/8/8/8/8/8/8 b X - 0 1"),
+            Err(FenParseError::InvalidCastlingRights("X".to_string()))
+        );
+        assert_eq!(
+            FenFields::parse("8/8/8/8/8/8/8/8 b - - 0 0"),
+            Err(FenParseError::InvalidFullmoveNumber("0".to_string()))
+        );
+    }
+
     #[test]
     fn test_from_fen() {
         let fen = "8/1P1n1B2/5P2/4pkNp/1PQ4K/p2p2P1/8/3R1N2 w - - 0 1";
@@ -315,5 +774,67 @@ mod tests {
         let fen = "nb4K1/2N4p/8/3P1rk1/1r2P3/5p2/3P1Q2/B2R1b2 b - - 0 1";
         let state_result = TypedPosition::<1>::from_fen(fen);
         assert!(state_result.is_ok());
+
+        // The en passant target must be on the rank the side to move captures onto.
+        let fen = "4k3/8/8/4p3/8/8/8/4K3 w - e3 0 1";
+        assert_eq!(
+            TypedPosition::<1>::from_fen(fen).err(),
+            Some(FenParseError::InvalidEnPassantTarget("e3".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_const_parsing_matches_runtime() {
+        const INITIAL: Position<4, { Color::White }> = crate::position!(INITIAL_FEN);
+        static SICILIAN: Position<4, { Color::White }> =
+            crate::position!("rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2");
+        assert_eq!(INITIAL, Position::<4, { Color::White }>::initial());
+        assert_eq!(SICILIAN, Position::from_fen(&SICILIAN.to_fen()).unwrap());
+
+        // Checkers are computed too.
+        let fen = "4k3/8/8/1B6/8/8/7q/4K3 b - - 12 40";
+        let position = Position::<1, { Color::Black }>::from_fen_const(fen);
+        assert_ne!(position.context().checkers, 0);
+        assert_eq!(position, Position::from_fen(fen).unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "FEN side to move does not match the position type")]
+    fn test_const_parsing_rejects_wrong_side_to_move() {
+        Position::<1, { Color::White }>::from_fen_const("4k3/8/8/8/8/8/8/4K3 b - - 0 1");
+    }
+
+    #[test]
+    #[should_panic(expected = "FEN castling rights do not match the kings and rooks")]
+    fn test_const_parsing_rejects_castling_without_rook() {
+        Position::<1, { Color::White }>::from_fen_const("4k3/8/8/8/8/8/8/4K3 w K - 0 1");
+    }
+
+    #[test]
+    #[should_panic(expected = "FEN side not to move is in check")]
+    fn test_const_parsing_rejects_side_not_to_move_in_check() {
+        Position::<1, { Color::White }>::from_fen_const("4k2R/8/8/8/8/8/8/4K3 w - - 0 1");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid FEN en passant target")]
+    fn test_const_parsing_rejects_en_passant_rank_of_wrong_side() {
+        Position::<1, { Color::White }>::from_fen_const("4k3/8/8/4p3/8/8/8/4K3 w - e3 0 1");
+    }
+
+    #[test]
+    fn test_to_fen_round_trips() {
+        assert_eq!(
+            Position::<1, { Color::White }>::initial().to_fen(),
+            INITIAL_FEN
+        );
+        for fen in [
+            "1k2N1K1/4Q3/6p1/2B2B2/p1PPb3/2P2Nb1/2r5/n7 b - - 35 18",
+            "r3k3/P3P3/1B3q2/N3P2P/R6N/8/np2b2p/1K3n2 w q - 100 96",
+            "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w Kq e6 0 2",
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+        ] {
+            assert_eq!(TypedPosition::<1>::from_fen(fen).unwrap().to_fen(), fen);
+        }
     }
 }
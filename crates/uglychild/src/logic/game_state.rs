@@ -1,7 +1,80 @@
-//! Game state tracking: ongoing vs terminal positions with end reasons.
+//! Game state tracking: ongoing vs terminal positions with end reasons and results.
+
+use std::fmt::{Display, Formatter};
 
 use crate::types::{Color, Move, MoveList, Position, ZobristPolicy};
 
+/// Outcome of a game, shared by position termination, PGN, and match play.
+#[derive(Debug, Clone, Copy, Default, Eq, Hash)]
+#[derive_const(PartialEq)]
+pub enum GameResult {
+    /// White won (`1-0`).
+    WhiteWins,
+    /// Black won (`0-1`).
+    BlackWins,
+    /// Drawn (`1/2-1/2`).
+    Draw,
+    /// Not decided: still in progress, abandoned, or unknown (`*`).
+    #[default]
+    Ongoing,
+}
+
+impl GameResult {
+    /// The result of `color` winning.
+    pub const fn win_for(color: Color) -> GameResult {
+        match color {
+            Color::White => GameResult::WhiteWins,
+            Color::Black => GameResult::BlackWins,
+        }
+    }
+
+    /// Converts a winner (`None` for a draw) into a result.
+    pub const fn from_winner(winner: Option<Color>) -> GameResult {
+        match winner {
+            Some(color) => GameResult::win_for(color),
+            None => GameResult::Draw,
+        }
+    }
+
+    /// The winning side, if the game was won.
+    pub const fn winner(self) -> Option<Color> {
+        match self {
+            GameResult::WhiteWins => Some(Color::White),
+            GameResult::BlackWins => Some(Color::Black),
+            GameResult::Draw | GameResult::Ongoing => None,
+        }
+    }
+
+    /// Parses a PGN result string (`1-0`, `0-1`, `1/2-1/2`, `*`), ignoring surrounding
+    /// whitespace.
+    pub fn from_pgn_str(value: &str) -> Option<GameResult> {
+        match value.trim() {
+            "1-0" => Some(GameResult::WhiteWins),
+            "0-1" => Some(GameResult::BlackWins),
+            "1/2-1/2" => Some(GameResult::Draw),
+            "*" => Some(GameResult::Ongoing),
+            _ => None,
+        }
+    }
+
+    /// The PGN result string.
+    pub const fn as_pgn_str(self) -> &'static str {
+        match self {
+            GameResult::WhiteWins => "1-0",
+            GameResult::BlackWins => "0-1",
+            GameResult::Draw => "1/2-1/2",
+            GameResult::Ongoing => "*",
+        }
+    }
+}
+
+/// Formats as the PGN result string.
+impl Display for GameResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_pgn_str())
+    }
+}
+
 /// Reasons why a chess game can end (win, loss, or draw).
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum TerminalReason {
@@ -13,18 +86,59 @@ pub enum TerminalReason {
     InsufficientMaterial,
     /// 100 half-moves without capture or pawn move.
     FiftyMoveRule,
-    /// Position repeated three times (not yet tracked in uglychild).
+    /// Position repeated three times. A draw must be claimed, so classification never reports
+    /// it; see [`Position::is_threefold_repetition`].
     ThreefoldRepetition,
     /// Other draw by agreement or rule.
     OtherDraw,
-    /// Terminal win (e.g., resignation, timeout with material).
+    /// The side to move resigned.
+    Resignation,
+    /// The side to move ran out of time.
+    Timeout,
+    /// Other win for the side to move.
     Win,
-    /// Terminal loss (e.g., resignation, timeout with material).
+    /// Other loss for the side to move (e.g., forfeit for an illegal move).
     OtherLoss,
+    /// Decided by an arbiter or adjudication rule rather than on the board; the result is
+    /// recorded separately.
+    Adjudication,
     /// Unclassified terminal state.
     Unknown,
 }
 
+impl TerminalReason {
+    /// The game result when the game ends for this reason with `side_to_move` to move.
+    ///
+    /// [`TerminalReason::Adjudication`] and [`TerminalReason::Unknown`] yield
+    /// [`GameResult::Ongoing`], since the reason alone does not decide the game.
+    pub const fn result(self, side_to_move: Color) -> GameResult {
+        match self {
+            TerminalReason::Checkmate
+            | TerminalReason::Resignation
+            | TerminalReason::Timeout
+            | TerminalReason::OtherLoss => GameResult::win_for(side_to_move.other()),
+            TerminalReason::Win => GameResult::win_for(side_to_move),
+            TerminalReason::Stalemate
+            | TerminalReason::InsufficientMaterial
+            | TerminalReason::FiftyMoveRule
+            | TerminalReason::ThreefoldRepetition
+            | TerminalReason::OtherDraw => GameResult::Draw,
+            TerminalReason::Adjudication | TerminalReason::Unknown => GameResult::Ongoing,
+        }
+    }
+
+    /// The value of the PGN `Termination` tag for a game that ended for this reason.
+    pub const fn pgn_termination(self) -> &'static str {
+        match self {
+            TerminalReason::Timeout => "time forfeit",
+            TerminalReason::OtherLoss => "rules infraction",
+            TerminalReason::Adjudication => "adjudication",
+            TerminalReason::Unknown => "unterminated",
+            _ => "normal",
+        }
+    }
+}
+
 /// A position where the game is still in progress.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Ongoing<P>(P);
@@ -139,9 +253,7 @@ fn classify_terminal<const N: usize, const STM: Color, Z: ZobristPolicy>(
         return Some(TerminalReason::InsufficientMaterial);
     }
 
-    let mut replies = MoveList::new();
-    position.generate_moves(&mut replies);
-    if replies.is_empty() {
+    if !position.has_legal_moves() {
         if position.is_current_side_in_check() {
             Some(TerminalReason::Checkmate)
         } else {
@@ -152,6 +264,25 @@ fn classify_terminal<const N: usize, const STM: Color, Z: ZobristPolicy>(
     }
 }
 
+impl<const N: usize, const STM: Color, Z: ZobristPolicy> Terminal<Position<N, STM, Z>> {
+    /// The game result implied by the terminal reason.
+    #[inline]
+    pub fn result(&self) -> GameResult {
+        self.reason.result(STM)
+    }
+}
+
+impl<const N: usize, const STM: Color, Z: ZobristPolicy> GameState<Position<N, STM, Z>> {
+    /// The game result; [`GameResult::Ongoing`] while the game continues.
+    #[inline]
+    pub fn result(&self) -> GameResult {
+        match self {
+            GameState::Ongoing(_) => GameResult::Ongoing,
+            GameState::Terminal(terminal) => terminal.result(),
+        }
+    }
+}
+
 impl<const N: usize, const STM: Color, Z: ZobristPolicy> Ongoing<Position<N, STM, Z>> {
     /// Generates legal moves for the current position.
     #[inline]
@@ -177,3 +308,55 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Ongoing<Position<N, STM
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_result_pgn_round_trip() {
+        for result in [
+            GameResult::WhiteWins,
+            GameResult::BlackWins,
+            GameResult::Draw,
+            GameResult::Ongoing,
+        ] {
+            assert_eq!(GameResult::from_pgn_str(result.as_pgn_str()), Some(result));
+        }
+        assert_eq!(
+            GameResult::from_pgn_str(" 1-0 "),
+            Some(GameResult::WhiteWins)
+        );
+        assert_eq!(GameResult::from_pgn_str("?"), None);
+        assert_eq!(GameResult::Draw.to_string(), "1/2-1/2");
+    }
+
+    #[test]
+    fn test_terminal_result() {
+        // Fool's mate: White is mated.
+        let position = Position::<2, { Color::White }>::from_fen(
+            "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3",
+        )
+        .unwrap();
+        let state = match classify_terminal(&position) {
+            Some(reason) => GameState::from_terminal(position, reason),
+            None => GameState::from_ongoing(position),
+        };
+        assert_eq!(state.result(), GameResult::BlackWins);
+
+        assert_eq!(
+            TerminalReason::Resignation.result(Color::Black),
+            GameResult::WhiteWins
+        );
+        assert_eq!(
+            TerminalReason::Stalemate.result(Color::White),
+            GameResult::Draw
+        );
+        assert_eq!(
+            TerminalReason::Unknown.result(Color::White),
+            GameResult::Ongoing
+        );
+        assert_eq!(TerminalReason::Timeout.pgn_termination(), "time forfeit");
+        assert_eq!(TerminalReason::Checkmate.pgn_termination(), "normal");
+    }
+}
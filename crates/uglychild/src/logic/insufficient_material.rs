@@ -1,8 +1,25 @@
 use crate::{
-    types::{Board, Color, Piece},
+    types::{Board, Color, Piece, material_key_field},
     utilities::IterableEnum,
 };
 
+/// [`Board::material_key`] bits of both sides' pawns, rooks and queens.
+const MATING_MATERIAL: u64 = material_key_field(Color::White, Piece::Pawn)
+    | material_key_field(Color::White, Piece::Rook)
+    | material_key_field(Color::White, Piece::Queen)
+    | material_key_field(Color::Black, Piece::Pawn)
+    | material_key_field(Color::Black, Piece::Rook)
+    | material_key_field(Color::Black, Piece::Queen);
+
+/// [`Board::material_key`] bits of all of `color`'s pieces other than the king.
+const fn side_material(color: Color) -> u64 {
+    let mut mask = 0;
+    for piece in Piece::NON_KING_PIECES {
+        mask |= material_key_field(color, piece);
+    }
+    mask
+}
+
 impl Board {
     /// Returns true if there is insufficient material on both sides to checkmate.
     /// This is the case if both sides have any one of the following, and there are no pawns on the board:
@@ -11,29 +28,22 @@ impl Board {
     /// A king and knight
     /// A king and two knights, only if the other side is a lone king
     pub const fn are_both_sides_insufficient_material<const USCF: bool>(&self) -> bool {
-        if self.piece_mask::<{ Piece::Pawn }>()
-            | self.piece_mask::<{ Piece::Rook }>()
-            | self.piece_mask::<{ Piece::Queen }>()
-            != 0
-        {
+        let key = self.material_key();
+        if key & MATING_MATERIAL != 0 {
             return false;
         }
 
         for color in Color::ALL {
-            let bishops = self.piece_mask::<{ Piece::Bishop }>() & self.color_mask_at(color);
-            let num_bishops = bishops.count_ones();
+            let num_bishops = self.piece_count(color, Piece::Bishop);
             if num_bishops > 1 {
                 return false;
             }
 
-            let knights = self.piece_mask::<{ Piece::Knight }>() & self.color_mask_at(color);
-            let num_knights = knights.count_ones();
+            let num_knights = self.piece_count(color, Piece::Knight);
 
             if USCF && num_knights == 2 && num_bishops == 0 {
                 // king and two knights
-                let opponent_mask = self.color_mask_at(color.other());
-                let all_occupancy_mask = self.piece_mask::<{ Piece::ALL_PIECES }>();
-                let opponent_is_lone_king = (opponent_mask & all_occupancy_mask).count_ones() == 1;
+                let opponent_is_lone_king = key & side_material(color.other()) == 0;
                 return opponent_is_lone_king;
             }
             if num_knights + num_bishops > 1 {
@@ -1,6 +1,10 @@
-//! Contains [`crate::types::Position::make_move`] and [`crate::types::Position::unmake_move`].
+//! Contains [`crate::types::Position::make_move`] and [`crate::types::Position::unmake_move`],
+//! their by-value forms [`crate::types::Position::make_move_copy`] /
+//! [`crate::types::Position::make_move_owned`] / [`crate::types::Position::unmake_move_owned`],
+//! plus the board-only [`crate::types::Board::apply_move`] / [`crate::types::Board::undo_move`].
 
 use crate::types::{
+    Board,
     Color,
     ConstDoublePawnPushFile,
     DoublePawnPushFile,
@@ -11,7 +15,6 @@ use crate::types::{
     Piece,
     Position,
     PositionContext,
-    Rank,
     Square,
     ZobristPolicy,
 };
@@ -22,7 +25,11 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
     /// This pushes a new [`PositionContext`], updates board/hash/counters, then recomputes
     /// pins and checkers for the opponent (`STM.other()`).
     pub fn make_move(&mut self, move_: Move) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("make_move", %move_).entered();
         debug_assert!(self.num_contexts < N);
+        #[cfg(feature = "strict-debug")]
+        let strict_before = (self.to_fen(), self.num_contexts, self.halfmove);
 
         let from = move_.from();
         let to = move_.to();
@@ -56,15 +63,12 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
 
         match flag {
             MoveFlag::Promotion => {
-                self.remove_piece_at(Piece::Pawn, to);
-                self.put_piece_at(move_.promotion(), to);
+                self.remove_piece_and_color(STM, Piece::Pawn, to);
+                self.put_piece_and_color(STM, move_.promotion(), to);
                 self.mut_context().halfmove_clock = 0;
             }
             MoveFlag::EnPassant => {
-                let capture_square = unsafe {
-                    Square::try_from((to as u8).wrapping_add_signed(en_passant_capture_offset(STM)))
-                        .unwrap_unchecked()
-                };
+                let capture_square = en_passant_captured_square(to, STM);
                 self.remove_piece_and_color(STM.other(), Piece::Pawn, capture_square);
                 let context = self.mut_context();
                 context.captured_piece = Piece::Pawn;
@@ -91,10 +95,22 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
 
         self.halfmove += 1;
         self.update_pins_and_checks_for_stm(STM.other());
+
+        #[cfg(feature = "strict-debug")]
+        self.assert_strict_consistency(
+            STM.other(),
+            strict_before.1 + 1,
+            strict_before.2 + 1,
+            || format!("make_move({move_}) from {}", strict_before.0),
+        );
     }
 
     /// Undoes `move_` in place, restoring the previous context and board state.
     pub fn unmake_move(&mut self, move_: Move) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("unmake_move", %move_).entered();
+        #[cfg(feature = "strict-debug")]
+        let strict_before = (self.to_fen(), self.num_contexts, self.halfmove);
         let from = move_.from();
         let to = move_.to();
         let flag = move_.flag();
@@ -112,16 +128,11 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
             MoveFlag::NormalMove => {}
             MoveFlag::Promotion => {
                 let promoted = self.board.piece_at(from);
-                self.remove_piece_at(promoted, from);
-                self.put_piece_at(Piece::Pawn, from);
+                self.remove_piece_and_color(side_just_moved, promoted, from);
+                self.put_piece_and_color(side_just_moved, Piece::Pawn, from);
             }
             MoveFlag::EnPassant => {
-                let capture_square = unsafe {
-                    Square::try_from(
-                        (to as u8).wrapping_add_signed(en_passant_capture_offset(side_just_moved)),
-                    )
-                    .unwrap_unchecked()
-                };
+                let capture_square = en_passant_captured_square(to, side_just_moved);
                 self.move_piece_and_color(STM, Piece::Pawn, to, capture_square);
             }
             MoveFlag::Castling => {
@@ -136,26 +147,183 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
 
         self.halfmove -= 1;
         self.decrement_context_stack_for_unmake();
+
+        #[cfg(feature = "strict-debug")]
+        self.assert_strict_consistency(
+            STM.other(),
+            strict_before.1 - 1,
+            strict_before.2 - 1,
+            || format!("unmake_move({move_}) from {}", strict_before.0),
+        );
+    }
+
+    /// Passes the turn without moving a piece (a null move), as used by null-move pruning.
+    ///
+    /// Pushes a context like [`Self::make_move`]: the en passant file is cleared, the halfmove
+    /// clock advances, and pins and checkers are recomputed for `STM.other()`. The side to move
+    /// must not be in check.
+    pub fn make_null_move(&mut self) {
+        debug_assert!(!self.is_current_side_in_check());
+        let old_context = *self.context();
+        let mut new_context = PositionContext::<Z::HashState>::blank();
+        new_context.halfmove_clock = old_context.halfmove_clock + 1;
+        new_context.castling_rights = old_context.castling_rights;
+        new_context.double_pawn_push_file = old_context.double_pawn_push_file;
+        new_context.zobrist_hash = old_context.zobrist_hash;
+        self.push_context(new_context);
+
+        self.set_double_pawn_push_file(DoublePawnPushFile::NONE);
+        self.flip_side_to_move_hash();
+        self.halfmove += 1;
+        self.update_pins_and_checks_for_stm(STM.other());
+    }
+
+    /// Undoes [`Self::make_null_move`]; call it on the position typed for the side that did
+    /// not move.
+    pub fn unmake_null_move(&mut self) {
+        self.halfmove -= 1;
+        self.decrement_context_stack_for_unmake();
+    }
+}
+
+/// Receipt for a move played by [`Position::make_move_copy`] or [`Position::make_move_owned`];
+/// pass it to [`Position::unmake_move_owned`] to take the move back.
+///
+/// Only the move is stored: everything else needed to restore the position lives on the
+/// resulting position's context stack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UndoToken {
+    move_: Move,
+}
+
+impl UndoToken {
+    /// The move that was played.
+    pub const fn move_(&self) -> Move {
+        self.move_
     }
 }
 
-const fn en_passant_capture_offset(stm: Color) -> i8 {
-    match stm {
-        Color::White => 8,
-        Color::Black => -8,
+impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
+    /// Copy-make: returns the position after `move_`, leaving `self` untouched.
+    ///
+    /// The copy keeps the full history, so the returned token can also take the move back.
+    /// When the history is not needed, [`Position::clone_current`] followed by
+    /// [`Self::make_move_owned`] copies less.
+    pub fn make_move_copy(&self, move_: Move) -> (Position<N, { STM.other() }, Z>, UndoToken) {
+        self.clone().make_move_owned(move_)
+    }
+
+    /// Plays `move_` on `self` by value and returns the resulting position, typed for the new
+    /// side to move, with a token for [`Self::unmake_move_owned`].
+    pub fn make_move_owned(mut self, move_: Move) -> (Position<N, { STM.other() }, Z>, UndoToken) {
+        self.make_move(move_);
+        (self.rebrand_stm(), UndoToken { move_ })
+    }
+
+    /// Takes back the move recorded in `token`, which must be the last move played to reach
+    /// `self`.
+    pub fn unmake_move_owned(mut self, token: UndoToken) -> Position<N, { STM.other() }, Z> {
+        self.unmake_move(token.move_);
+        self.rebrand_stm()
     }
 }
 
-const fn castling_rook_from_square(flank: Flank, color: Color) -> Square {
-    let rank = Rank::One.from_perspective(color);
+impl Board {
+    /// Applies `move_` to the piece placement only and returns the captured piece
+    /// ([`Piece::Null`] if none).
+    ///
+    /// This is the lightweight layer under [`Position::make_move`]: no legality checks, clocks,
+    /// castling rights, hashes, or pins. The mover is the color on `move_.from()`; castling and
+    /// en passant are taken from the move flag. Pass the returned piece to [`Self::undo_move`].
+    pub fn apply_move(&mut self, move_: Move) -> Piece {
+        let from = move_.from();
+        let to = move_.to();
+        let color = self.color_at(from);
+
+        let mut captured_piece = self.piece_at(to);
+        if captured_piece != Piece::Null {
+            self.remove_piece_and_color(color.other(), captured_piece, to);
+        }
+        let piece = self.piece_at(from);
+        self.move_piece_and_color(color, piece, from, to);
+
+        match move_.flag() {
+            MoveFlag::NormalMove => {}
+            MoveFlag::Promotion => {
+                self.remove_piece_and_color(color, Piece::Pawn, to);
+                self.put_piece_and_color(color, move_.promotion(), to);
+            }
+            MoveFlag::EnPassant => {
+                let capture_square = en_passant_captured_square(to, color);
+                self.remove_piece_and_color(color.other(), Piece::Pawn, capture_square);
+                captured_piece = Piece::Pawn;
+            }
+            MoveFlag::Castling => {
+                let flank = to.file().flank();
+                let rook_from = castling_rook_from_square(flank, color);
+                let rook_to = castling_rook_to_square(flank, color);
+                self.move_piece_and_color(color, Piece::Rook, rook_from, rook_to);
+            }
+        }
+
+        captured_piece
+    }
+
+    /// Reverts [`Self::apply_move`], given the piece it returned.
+    pub fn undo_move(&mut self, move_: Move, captured_piece: Piece) {
+        let from = move_.from();
+        let to = move_.to();
+        let color = self.color_at(to);
+
+        let piece = self.piece_at(to);
+        self.move_piece_and_color(color, piece, to, from);
+
+        match move_.flag() {
+            MoveFlag::NormalMove => {
+                if captured_piece != Piece::Null {
+                    self.put_piece_and_color(color.other(), captured_piece, to);
+                }
+            }
+            MoveFlag::Promotion => {
+                self.remove_piece_and_color(color, piece, from);
+                self.put_piece_and_color(color, Piece::Pawn, from);
+                if captured_piece != Piece::Null {
+                    self.put_piece_and_color(color.other(), captured_piece, to);
+                }
+            }
+            MoveFlag::EnPassant => {
+                let capture_square = en_passant_captured_square(to, color);
+                self.put_piece_and_color(color.other(), Piece::Pawn, capture_square);
+            }
+            MoveFlag::Castling => {
+                let flank = to.file().flank();
+                let rook_from = castling_rook_from_square(flank, color);
+                let rook_to = castling_rook_to_square(flank, color);
+                self.move_piece_and_color(color, Piece::Rook, rook_to, rook_from);
+            }
+        }
+    }
+}
+
+/// Square of the pawn captured en passant by `color`'s pawn landing on `to`, one step behind it.
+pub(crate) const fn en_passant_captured_square(to: Square, color: Color) -> Square {
+    // SAFETY: `to` is an en passant target square, so the step back stays on the board.
+    unsafe {
+        Square::try_from((to as u8).wrapping_add_signed(-color.pawn_push_delta()))
+            .unwrap_unchecked()
+    }
+}
+
+pub(crate) const fn castling_rook_from_square(flank: Flank, color: Color) -> Square {
+    let rank = color.back_rank();
     match flank {
         Flank::Kingside => Square::from_rank_and_file(rank, File::H),
         Flank::Queenside => Square::from_rank_and_file(rank, File::A),
     }
 }
 
-const fn castling_rook_to_square(flank: Flank, color: Color) -> Square {
-    let rank = Rank::One.from_perspective(color);
+pub(crate) const fn castling_rook_to_square(flank: Flank, color: Color) -> Square {
+    let rank = color.back_rank();
     match flank {
         Flank::Kingside => Square::from_rank_and_file(rank, File::F),
         Flank::Queenside => Square::from_rank_and_file(rank, File::D),
@@ -215,6 +383,27 @@ mod tests {
         assert_hash_consistency_after_plies(&mut pos, 5);
     }
 
+    #[test]
+    fn copy_make_and_owned_make_unmake_round_trip() {
+        let position = PositionWithZobrist::<4, { Color::White }>::initial();
+        let mut moves = MoveList::new();
+        position.generate_moves(&mut moves);
+        let move_ = *moves.as_slice().first().expect("at least one legal move");
+
+        let (child, token) = position.make_move_copy(move_);
+        assert_eq!(token.move_(), move_);
+        assert_eq!(child.num_contexts(), 2);
+        assert!(child.is_zobrist_consistent());
+
+        let mut in_place = position.clone();
+        in_place.make_move(move_);
+        assert_eq!(child.board, in_place.board);
+        assert_eq!(child.context(), in_place.context());
+
+        let (child, token) = position.clone().make_move_owned(move_);
+        assert_eq!(child.unmake_move_owned(token), position);
+    }
+
     #[test]
     fn without_zobrist_make_unmake_round_trip() {
         let mut pos = PositionWithoutZobrist::<8, { Color::White }>::initial();
@@ -231,4 +420,69 @@ mod tests {
         assert_eq!(pos, baseline);
         assert!(pos.is_zobrist_consistent());
     }
+
+    #[test]
+    fn board_apply_move_matches_position_make_move() {
+        let mut position = PositionWithoutZobrist::<2, { Color::White }>::from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        )
+        .unwrap();
+        let mut moves = MoveList::new();
+        position.generate_moves(&mut moves);
+
+        for &move_ in moves.as_slice() {
+            let mut board = position.board.clone();
+            let captured = board.apply_move(move_);
+            assert_eq!(
+                captured != Piece::Null,
+                move_.is_capture_on_board(&position.board)
+            );
+
+            position.make_move(move_);
+            assert_eq!(board, position.board);
+            let child = unsafe { position.rebrand_stm_mut::<{ Color::Black }>() };
+            child.unmake_move(move_);
+
+            board.undo_move(move_, captured);
+            assert_eq!(board, position.board);
+        }
+    }
+
+    #[test]
+    fn null_move_flips_side_and_clears_en_passant() {
+        let mut pos = PositionWithZobrist::<2, { Color::White }>::from_fen(
+            "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+        )
+        .unwrap();
+        let baseline = pos.clone();
+
+        pos.make_null_move();
+        let child = unsafe { pos.rebrand_stm_mut::<{ Color::Black }>() };
+        let expected = PositionWithZobrist::<2, { Color::Black }>::from_fen(
+            "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR b KQkq - 1 3",
+        )
+        .unwrap();
+        assert!(child.is_zobrist_consistent());
+        assert_eq!(child.board, expected.board);
+        assert_eq!(child.halfmove, expected.halfmove);
+        assert_eq!(child.context(), expected.context());
+        child.unmake_null_move();
+        assert_eq!(pos, baseline);
+    }
+
+    #[cfg(feature = "strict-debug")]
+    #[test]
+    #[should_panic(
+        expected = "make_move(e2e4) from rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 \
+                    left an inconsistent position: zobrist hash does not match the board"
+    )]
+    fn strict_debug_reports_corrupted_hash() {
+        let mut pos = PositionWithZobrist::<4, { Color::White }>::initial();
+        pos.mut_context().zobrist_hash ^= 1;
+        pos.make_move(Move::new_non_promotion(
+            Square::E2,
+            Square::E4,
+            MoveFlag::NormalMove,
+        ));
+    }
 }
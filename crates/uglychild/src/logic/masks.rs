@@ -0,0 +1,303 @@
+//! Geometric bitboard masks: segments, lines, and rays between squares, plus board regions
+//! (square colors, center, flanks, king zones, pawn spans) and the castling path masks.
+//!
+//! These are the lookups used internally for pins and check evasion, exposed for downstream
+//! engine and GUI code (pins, skewers, x-rays, move validation, evaluation terms).
+
+use crate::{
+    types::{Bitboard, BitboardUtils, Color, File, Flank, QueenLikeMoveDirection, Rank, Square},
+    utilities::{IterableEnum, SquareMapping},
+};
+
+/// Light squares (h1, a8, and every square of their color).
+pub const LIGHT_SQUARES: Bitboard = {
+    let mut mask = 0;
+    for square in <Square as IterableEnum<64>>::ALL {
+        if (square.rank_index() + square.file_index()) % 2 == 1 {
+            mask |= square.mask();
+        }
+    }
+    mask
+};
+/// Dark squares (a1, h8, and every square of their color).
+pub const DARK_SQUARES: Bitboard = !LIGHT_SQUARES;
+/// The four central squares d4, e4, d5, e5.
+pub const CENTER: Bitboard =
+    Square::D4.mask() | Square::E4.mask() | Square::D5.mask() | Square::E5.mask();
+/// The sixteen squares c3 through f6.
+pub const EXTENDED_CENTER: Bitboard = {
+    let files = File::C.mask() | File::D.mask() | File::E.mask() | File::F.mask();
+    let ranks = Rank::Three.mask() | Rank::Four.mask() | Rank::Five.mask() | Rank::Six.mask();
+    files & ranks
+};
+/// Files e through h.
+pub const KINGSIDE: Bitboard = Flank::Kingside.half_board_mask();
+/// Files a through d.
+pub const QUEENSIDE: Bitboard = Flank::Queenside.half_board_mask();
+
+/// Squares that must be empty for White to castle kingside (f1, g1).
+pub const WHITE_KINGSIDE_CASTLING_GAP: Bitboard = Flank::Kingside.castling_gap_mask(Color::White);
+/// Squares that must be empty for White to castle queenside (b1, c1, d1).
+pub const WHITE_QUEENSIDE_CASTLING_GAP: Bitboard = Flank::Queenside.castling_gap_mask(Color::White);
+/// Squares that must be empty for Black to castle kingside (f8, g8).
+pub const BLACK_KINGSIDE_CASTLING_GAP: Bitboard = Flank::Kingside.castling_gap_mask(Color::Black);
+/// Squares that must be empty for Black to castle queenside (b8, c8, d8).
+pub const BLACK_QUEENSIDE_CASTLING_GAP: Bitboard = Flank::Queenside.castling_gap_mask(Color::Black);
+
+/// Squares that must not be attacked for White to castle kingside (f1, g1), besides e1.
+pub const WHITE_KINGSIDE_CASTLING_KING_PATH: Bitboard =
+    Flank::Kingside.king_path_mask(Color::White);
+/// Squares that must not be attacked for White to castle queenside (d1, c1), besides e1.
+///
+/// Unlike [`WHITE_QUEENSIDE_CASTLING_GAP`], this excludes b1, which only the rook crosses.
+pub const WHITE_QUEENSIDE_CASTLING_KING_PATH: Bitboard =
+    Flank::Queenside.king_path_mask(Color::White);
+/// Squares that must not be attacked for Black to castle kingside (f8, g8), besides e8.
+pub const BLACK_KINGSIDE_CASTLING_KING_PATH: Bitboard =
+    Flank::Kingside.king_path_mask(Color::Black);
+/// Squares that must not be attacked for Black to castle queenside (d8, c8), besides e8.
+///
+/// Unlike [`BLACK_QUEENSIDE_CASTLING_GAP`], this excludes b8, which only the rook crosses.
+pub const BLACK_QUEENSIDE_CASTLING_KING_PATH: Bitboard =
+    Flank::Queenside.king_path_mask(Color::Black);
+
+/// Squares strictly between `a` and `b` (endpoints excluded).
+///
+/// Zero unless the squares share a rank, file, or diagonal.
+#[inline]
+pub const fn between(a: Square, b: Square) -> Bitboard {
+    Bitboard::between(a, b)
+}
+
+/// Full edge-to-edge line through `a` and `b`, including both squares.
+///
+/// Zero unless `a != b` and the squares share a rank, file, or diagonal.
+#[inline]
+pub const fn line(a: Square, b: Square) -> Bitboard {
+    Bitboard::edge_to_edge_ray(a, b)
+}
+
+/// Squares from `square` (excluded) to the board edge in `direction`.
+#[inline]
+pub const fn ray(square: Square, direction: QueenLikeMoveDirection) -> Bitboard {
+    RAYS[square as usize][direction as usize]
+}
+
+/// Whether `c` lies on the line through `a` and `b`.
+///
+/// Returns `false` when `a == b` or `a` and `b` are not on a common line.
+#[inline]
+pub const fn aligned(a: Square, b: Square, c: Square) -> bool {
+    line(a, b) & c.mask() != 0
+}
+
+/// Squares of the same color as `square`.
+#[inline]
+pub const fn same_color_squares(square: Square) -> Bitboard {
+    if square.mask() & LIGHT_SQUARES != 0 {
+        LIGHT_SQUARES
+    } else {
+        DARK_SQUARES
+    }
+}
+
+/// Files e–h for [`Flank::Kingside`], a–d for [`Flank::Queenside`].
+#[inline]
+pub const fn flank(flank: Flank) -> Bitboard {
+    flank.half_board_mask()
+}
+
+/// Squares that must be empty for `color` to castle on `flank`; one of the
+/// `*_CASTLING_GAP` constants.
+#[inline]
+pub const fn castling_gap(color: Color, flank: Flank) -> Bitboard {
+    flank.castling_gap_mask(color)
+}
+
+/// Squares the king crosses or lands on when `color` castles on `flank`, which must not be
+/// attacked; one of the `*_CASTLING_KING_PATH` constants.
+///
+/// The king's own square is excluded: castling out of check is ruled out separately.
+#[inline]
+pub const fn castling_king_path(color: Color, flank: Flank) -> Bitboard {
+    flank.king_path_mask(color)
+}
+
+/// `square` and the (up to eight) squares around it.
+#[inline]
+pub const fn king_zone(square: Square) -> Bitboard {
+    KING_ZONES.get(square)
+}
+
+/// Squares in front of `square` on its file, from `color`'s point of view (`square` excluded).
+#[inline]
+pub const fn forward_span(color: Color, square: Square) -> Bitboard {
+    match color {
+        Color::White => ray(square, QueenLikeMoveDirection::Up),
+        Color::Black => ray(square, QueenLikeMoveDirection::Down),
+    }
+}
+
+/// The files directly left and right of `file` (one file at the edges).
+#[inline]
+pub const fn adjacent_files(file: File) -> Bitboard {
+    let left = match file.offset(-1) {
+        Some(left) => left.mask(),
+        None => 0,
+    };
+    let right = match file.offset(1) {
+        Some(right) => right.mask(),
+        None => 0,
+    };
+    left | right
+}
+
+const fn calc_ray(square: Square, direction: QueenLikeMoveDirection) -> Bitboard {
+    let mut mask = 0;
+    let mut current = square;
+    while let Some(next) = current.neighbor_in_direction(direction) {
+        mask |= next.mask();
+        current = next;
+    }
+    mask
+}
+
+static RAYS: [[Bitboard; 8]; 64] = {
+    let mut arr = [[0; 8]; 64];
+    for square in <Square as IterableEnum<64>>::ALL {
+        for direction in <QueenLikeMoveDirection as IterableEnum<8>>::ALL {
+            arr[square as usize][direction as usize] = calc_ray(square, direction);
+        }
+    }
+    arr
+};
+
+const fn calc_king_zone(square: Square) -> Bitboard {
+    let mut zone = square.mask();
+    for direction in <QueenLikeMoveDirection as IterableEnum<8>>::ALL {
+        if let Some(neighbor) = square.neighbor_in_direction(direction) {
+            zone |= neighbor.mask();
+        }
+    }
+    zone
+}
+
+static KING_ZONES: SquareMapping<Bitboard> = SquareMapping::init(calc_king_zone);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ray() {
+        assert_eq!(
+            ray(Square::A1, QueenLikeMoveDirection::Up),
+            File::A.mask() & !Square::A1.mask()
+        );
+        assert_eq!(
+            ray(Square::D1, QueenLikeMoveDirection::Left),
+            Square::A1.mask() | Square::B1.mask() | Square::C1.mask()
+        );
+        assert_eq!(ray(Square::H8, QueenLikeMoveDirection::UpRight), 0);
+        assert_eq!(
+            ray(Square::A1, QueenLikeMoveDirection::UpRight) | Square::A1.mask(),
+            line(Square::A1, Square::H8)
+        );
+    }
+
+    #[test]
+    fn test_ray_pairs_form_lines() {
+        for square in Square::ALL {
+            for direction in QueenLikeMoveDirection::ALL {
+                let forward = ray(square, direction);
+                let backward = ray(square, direction.opposite());
+                if let Some(next) = square.neighbor_in_direction(direction) {
+                    assert_eq!(forward | backward | square.mask(), line(square, next));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_aligned_and_between() {
+        assert!(aligned(Square::A1, Square::C3, Square::H8));
+        assert!(aligned(Square::E1, Square::E8, Square::E4));
+        assert!(!aligned(Square::E1, Square::E8, Square::D4));
+        assert!(!aligned(Square::E1, Square::E1, Square::E4));
+        assert_eq!(
+            between(Square::A1, Square::D1),
+            Rank::One.mask() & (Square::B1.mask() | Square::C1.mask())
+        );
+        assert_eq!(between(Square::A1, Square::B3), 0);
+    }
+
+    #[test]
+    fn test_board_regions() {
+        assert_eq!(LIGHT_SQUARES.count_ones(), 32);
+        assert_ne!(Square::H1.mask() & LIGHT_SQUARES, 0);
+        assert_ne!(Square::A1.mask() & DARK_SQUARES, 0);
+        assert_ne!(Square::D1.mask() & LIGHT_SQUARES, 0);
+        assert_eq!(same_color_squares(Square::E4), LIGHT_SQUARES);
+        assert_eq!(same_color_squares(Square::D4), DARK_SQUARES);
+
+        assert_eq!(CENTER & EXTENDED_CENTER, CENTER);
+        assert_eq!(EXTENDED_CENTER.count_ones(), 16);
+        assert_ne!(EXTENDED_CENTER & Square::C3.mask(), 0);
+        assert_ne!(EXTENDED_CENTER & Square::F6.mask(), 0);
+        assert_eq!(EXTENDED_CENTER & Square::B3.mask(), 0);
+        assert_eq!(KINGSIDE | QUEENSIDE, !0);
+        assert_eq!(flank(Flank::Kingside), KINGSIDE);
+    }
+
+    #[test]
+    fn test_castling_masks() {
+        assert_eq!(
+            WHITE_KINGSIDE_CASTLING_GAP,
+            Square::F1.mask() | Square::G1.mask()
+        );
+        assert_eq!(
+            BLACK_QUEENSIDE_CASTLING_GAP,
+            Square::B8.mask() | Square::C8.mask() | Square::D8.mask()
+        );
+        assert_eq!(
+            WHITE_QUEENSIDE_CASTLING_KING_PATH,
+            Square::C1.mask() | Square::D1.mask()
+        );
+        assert_eq!(
+            BLACK_KINGSIDE_CASTLING_KING_PATH,
+            BLACK_KINGSIDE_CASTLING_GAP
+        );
+        for color in Color::ALL {
+            for flank in Flank::ALL {
+                let path = castling_king_path(color, flank);
+                assert_eq!(path & castling_gap(color, flank), path);
+                assert_ne!(path & flank.king_castled_square(color).mask(), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_king_zones_and_pawn_spans() {
+        assert_eq!(king_zone(Square::E4).count_ones(), 9);
+        assert_eq!(
+            king_zone(Square::A1),
+            Square::A1.mask() | Square::A2.mask() | Square::B1.mask() | Square::B2.mask()
+        );
+
+        assert_eq!(
+            forward_span(Color::White, Square::E6),
+            Square::E7.mask() | Square::E8.mask()
+        );
+        assert_eq!(forward_span(Color::Black, Square::E2), Square::E1.mask());
+        assert_eq!(
+            forward_span(Color::White, Square::E2) | forward_span(Color::Black, Square::E2),
+            File::E.mask() & !Square::E2.mask()
+        );
+        assert_eq!(adjacent_files(File::A), File::B.mask());
+        assert_eq!(adjacent_files(File::E), File::D.mask() | File::F.mask());
+        assert_eq!(
+            adjacent_files(File::H) & Rank::One.mask(),
+            Square::G1.mask()
+        );
+    }
+}
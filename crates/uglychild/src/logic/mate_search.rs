@@ -0,0 +1,181 @@
+//! Bounded forced-mate search for puzzle and tactic extraction.
+//!
+//! [`crate::types::Position::mate_in_n`] and [`crate::types::Position::is_lost_within`] perform
+//! an exhaustive make/unmake search that ignores draws by the fifty-move rule or repetition; they
+//! are intended for short horizons (`n <= 4`).
+
+use crate::types::{Color, Move, MoveList, Position, ZobristPolicy};
+
+/// Returns a move that mates within `n` moves of the side to move, if one exists.
+fn find_forced_mate<const N: usize, const STM: Color, Z: ZobristPolicy>(
+    position: &mut Position<N, STM, Z>,
+    n: u8,
+) -> Option<Move> {
+    if n == 0 {
+        return None;
+    }
+    let mut moves = MoveList::new();
+    position.generate_moves(&mut moves);
+
+    for &move_ in moves.as_slice() {
+        position.make_move(move_);
+        let is_forced = match STM {
+            Color::White => {
+                let child = unsafe { position.rebrand_stm_mut::<{ Color::Black }>() };
+                let is_forced = is_defender_lost(child, n - 1);
+                child.unmake_move(move_);
+                is_forced
+            }
+            Color::Black => {
+                let child = unsafe { position.rebrand_stm_mut::<{ Color::White }>() };
+                let is_forced = is_defender_lost(child, n - 1);
+                child.unmake_move(move_);
+                is_forced
+            }
+        };
+        if is_forced {
+            return Some(move_);
+        }
+    }
+    None
+}
+
+/// Returns whether the side to move is mated now or after every reply within `n` attacker moves.
+fn is_defender_lost<const N: usize, const STM: Color, Z: ZobristPolicy>(
+    position: &mut Position<N, STM, Z>,
+    n: u8,
+) -> bool {
+    let mut moves = MoveList::new();
+    position.generate_moves(&mut moves);
+    if moves.is_empty() {
+        return position.is_current_side_in_check();
+    }
+    if n == 0 {
+        return false;
+    }
+
+    for &move_ in moves.as_slice() {
+        position.make_move(move_);
+        let is_refuted = match STM {
+            Color::White => {
+                let child = unsafe { position.rebrand_stm_mut::<{ Color::Black }>() };
+                let is_refuted = find_forced_mate(child, n).is_none();
+                child.unmake_move(move_);
+                is_refuted
+            }
+            Color::Black => {
+                let child = unsafe { position.rebrand_stm_mut::<{ Color::White }>() };
+                let is_refuted = find_forced_mate(child, n).is_none();
+                child.unmake_move(move_);
+                is_refuted
+            }
+        };
+        if is_refuted {
+            return false;
+        }
+    }
+    true
+}
+
+impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
+    /// Appends every move that delivers immediate checkmate to `mates` (does not clear `mates`).
+    ///
+    /// The context stack must have room for one more ply.
+    pub fn find_mates_in_one(&mut self, mates: &mut MoveList) {
+        let mut moves = MoveList::new();
+        self.generate_moves(&mut moves);
+
+        for &move_ in moves.as_slice() {
+            self.make_move(move_);
+            let is_mate = match STM {
+                Color::White => {
+                    let child = unsafe { self.rebrand_stm_mut::<{ Color::Black }>() };
+                    let is_mate = is_defender_lost(child, 0);
+                    child.unmake_move(move_);
+                    is_mate
+                }
+                Color::Black => {
+                    let child = unsafe { self.rebrand_stm_mut::<{ Color::White }>() };
+                    let is_mate = is_defender_lost(child, 0);
+                    child.unmake_move(move_);
+                    is_mate
+                }
+            };
+            if is_mate {
+                mates.push(move_);
+            }
+        }
+    }
+
+    /// Returns a first move that forces checkmate in at most `n` moves of the side to move.
+    ///
+    /// The context stack must have room for `2 * n - 1` more plies. The returned move is not
+    /// necessarily the fastest mate; call with increasing `n` to find the shortest one.
+    pub fn mate_in_n(&mut self, n: u8) -> Option<Move> {
+        find_forced_mate(self, n)
+    }
+
+    /// Returns whether the side to move is checkmated, or is mated within `n` moves of the
+    /// opponent whatever it plays.
+    ///
+    /// The context stack must have room for `2 * n` more plies.
+    pub fn is_lost_within(&mut self, n: u8) -> bool {
+        is_defender_lost(self, n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{Color, MoveList, Position, Square};
+
+    #[test]
+    fn test_find_mates_in_one() {
+        let mut position =
+            Position::<4, { Color::White }>::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        let mut mates = MoveList::new();
+        position.find_mates_in_one(&mut mates);
+        assert_eq!(mates.len(), 1);
+        assert_eq!(mates.as_slice()[0].from(), Square::A1);
+        assert_eq!(mates.as_slice()[0].to(), Square::A8);
+    }
+
+    #[test]
+    fn test_stalemate_is_not_mate() {
+        let mut position =
+            Position::<4, { Color::White }>::from_fen("7k/5Q2/8/8/8/8/8/6K1 w - - 0 1").unwrap();
+        let mut mates = MoveList::new();
+        position.find_mates_in_one(&mut mates);
+        assert!(
+            mates
+                .as_slice()
+                .iter()
+                .all(|move_| move_.to() != Square::G6)
+        );
+    }
+
+    #[test]
+    fn test_mate_in_two() {
+        let mut position =
+            Position::<8, { Color::White }>::from_fen("7k/8/8/8/8/8/R7/1R5K w - - 0 1").unwrap();
+        let baseline = position.clone();
+        assert_eq!(position.mate_in_n(1), None);
+        assert!(position.mate_in_n(2).is_some());
+        assert_eq!(position, baseline);
+    }
+
+    #[test]
+    fn test_is_lost_within() {
+        let mut position =
+            Position::<8, { Color::Black }>::from_fen("7k/1R6/8/8/8/8/8/R5K1 b - - 0 1").unwrap();
+        assert!(!position.is_lost_within(0));
+        assert!(position.is_lost_within(1));
+    }
+
+    #[test]
+    fn test_mate_in_n_for_black() {
+        let mut position =
+            Position::<8, { Color::Black }>::from_fen("r5k1/8/8/8/8/8/5PPP/6K1 b - - 0 1").unwrap();
+        let mate = position.mate_in_n(1).expect("back-rank mate");
+        assert_eq!(mate.to(), Square::A1);
+    }
+}
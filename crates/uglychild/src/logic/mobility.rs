@@ -0,0 +1,116 @@
+//! Per-piece-type mobility bitboards for evaluation and training features.
+
+use crate::{
+    logic::attacks::{
+        multi_pawn_attacks,
+        multi_pawn_moves,
+        single_bishop_attacks,
+        single_king_attacks,
+        single_knight_attacks,
+        single_queen_attacks,
+        single_rook_attacks,
+    },
+    types::{Bitboard, BitboardUtils, Color, Piece, Position, Rank, Square, ZobristPolicy},
+};
+
+impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
+    /// Returns, per piece type in [`Piece::PIECES`] order, the union of squares `color`'s pieces
+    /// of that type can move to, ignoring pins and checks.
+    ///
+    /// Pieces cover their attacked squares not occupied by their own side; pawns cover their
+    /// pushes onto empty squares and their captures of opponent pieces. En passant and castling
+    /// are not included, and the king may step onto attacked squares.
+    pub fn mobility(&self, color: Color) -> [Bitboard; 6] {
+        self.mobility_within_pins(color, 0)
+    }
+
+    /// Like [`Self::mobility`], but pieces pinned to `color`'s king only move along the pin.
+    pub fn pin_aware_mobility(&self, color: Color) -> [Bitboard; 6] {
+        let pinned = self
+            .pins_and_checkers(color)
+            .map_or(0, |(pinned, _)| pinned);
+        self.mobility_within_pins(color, pinned)
+    }
+
+    fn mobility_within_pins(&self, color: Color, pinned: Bitboard) -> [Bitboard; 6] {
+        let board = &self.board;
+        let own = board.color_mask_at(color);
+        let opposite = board.color_mask_at(color.other());
+        let occupied = board.pieces();
+        let double_push_rank = color.relative_rank(Rank::Two).mask();
+        let pin_ray = |from: Square| {
+            if pinned & from.mask() != 0 {
+                Bitboard::edge_to_edge_ray(from, self.king_square(color))
+            } else {
+                !0
+            }
+        };
+
+        let mut mobility = [0; 6];
+        for (index, piece) in Piece::PIECES.into_iter().enumerate() {
+            for from in (board.piece_mask_at(piece) & own).iter_set_bits_as_squares() {
+                let to_mask = match piece {
+                    Piece::Pawn => {
+                        let single_push = multi_pawn_moves(from.mask(), color) & !occupied;
+                        let double_push = if from.mask() & double_push_rank != 0 {
+                            multi_pawn_moves(single_push, color) & !occupied
+                        } else {
+                            0
+                        };
+                        single_push
+                            | double_push
+                            | (multi_pawn_attacks(from.mask(), color) & opposite)
+                    }
+                    Piece::Knight => single_knight_attacks(from) & !own,
+                    Piece::Bishop => single_bishop_attacks(from, occupied) & !own,
+                    Piece::Rook => single_rook_attacks(from, occupied) & !own,
+                    Piece::Queen => single_queen_attacks(from, occupied) & !own,
+                    Piece::King => single_king_attacks(from) & !own,
+                    Piece::Null => 0,
+                };
+                mobility[index] |= to_mask & pin_ray(from);
+            }
+        }
+        mobility
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{Color, Position, Square};
+
+    #[test]
+    fn test_mobility_initial_position() {
+        let position = Position::<1, { Color::White }>::initial();
+        let [pawns, knights, bishops, rooks, queens, kings] = position.mobility(Color::White);
+        assert_eq!(pawns.count_ones(), 16);
+        assert_eq!(
+            knights,
+            Square::A3.mask() | Square::C3.mask() | Square::F3.mask() | Square::H3.mask()
+        );
+        assert_eq!(bishops | rooks | queens | kings, 0);
+        assert_eq!(
+            position.pin_aware_mobility(Color::Black)[0].count_ones(),
+            16
+        );
+    }
+
+    #[test]
+    fn test_pin_aware_mobility_restricts_pinned_pieces() {
+        // The e4 knight is pinned by the e8 rook; the d2 bishop is pinned by the a5 bishop but
+        // may still slide along the pin.
+        let position =
+            Position::<1, { Color::White }>::from_fen("4r2k/8/8/b7/4N3/8/3B4/4K3 w - - 0 1")
+                .unwrap();
+        let free = position.mobility(Color::White);
+        let pinned = position.pin_aware_mobility(Color::White);
+        assert_eq!(free[1].count_ones(), 7);
+        assert_eq!(pinned[1], 0);
+        assert_eq!(free[2].count_ones(), 8);
+        assert_eq!(
+            pinned[2],
+            Square::C3.mask() | Square::B4.mask() | Square::A5.mask()
+        );
+        assert_eq!(free[5], pinned[5]);
+    }
+}
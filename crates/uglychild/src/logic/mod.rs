@@ -3,6 +3,8 @@
 //! This module contains move generation/execution, notation helpers (FEN/SAN),
 //! terminal-state classification, attack tables, and validation utilities.
 
+/// Piece-delta listener hooks for incremental accumulators.
+pub mod accumulator;
 /// Attack generation helpers (manual and magic-bitboard based).
 pub mod attacks;
 /// Castling-rights updates and castling-specific helpers.
@@ -15,12 +17,38 @@ pub mod game_state;
 pub mod insufficient_material;
 /// In-place `make_move`/`unmake_move` transition logic.
 pub mod make_move;
+/// Geometric bitboard masks: between, lines, rays, and alignment.
+pub mod masks;
+/// Bounded forced-mate search (mate-in-one / mate-in-N).
+pub mod mate_search;
+/// Per-piece-type mobility bitboards.
+pub mod mobility;
 /// Legal move generation and counting APIs on [`crate::types::Position`].
 pub mod move_generation;
+/// Inferring the legal move behind a board change (electronic chessboards).
+pub mod move_inference;
+/// Pseudo-legal generation filtered by make-move, as an oracle for the strict generator.
+pub mod move_oracle;
+/// SAN-style partial move patterns (`Nxe5`, `axb8=Q`) matched against legal moves.
+pub mod move_pattern;
+/// Staged move ordering (TT move, captures, killers, quiets) for search.
+pub mod move_picker;
 /// Perft node-count benchmarking helpers.
 pub mod perft;
+/// Differential perft against an external UCI engine (`perft-diff` feature).
+#[cfg(feature = "perft-diff")]
+pub mod perft_diff;
+/// Neural-network input planes encoding of positions.
+pub mod planes;
+/// Repetition counting by stored hash or by FIDE position identity.
+pub mod repetition;
 /// Standard Algebraic Notation rendering.
 pub mod san;
+/// Reference iterative-deepening alpha-beta search (`search` feature).
+#[cfg(feature = "search")]
+pub mod search;
+/// Threat queries for candidate moves (attacked squares, hanging pieces).
+pub mod threats;
 /// Position consistency and legality validation checks.
 pub mod validation;
 /// Zobrist hashing keys and position hash calculation.
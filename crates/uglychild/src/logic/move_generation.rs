@@ -1,10 +1,13 @@
 //! Move generation: small pure helpers for masks, then writers that take explicit
 //! bitboards and closures only where attack or castling needs hidden board state.
+//!
+//! Generation is strictly legal: pins and checkers restrict each piece's destinations up front,
+//! and no move is played to test its legality. [`crate::logic::move_oracle`] holds the slower
+//! make-and-filter generator used to cross-check it.
 
 use crate::{
     logic::attacks::{
         manual::{multi_pawn_attacks_left, multi_pawn_attacks_right},
-        multi_pawn_attacks,
         multi_pawn_moves,
         single_bishop_attacks,
         single_king_attacks,
@@ -44,6 +47,33 @@ fn pin_restrict(from: Square, to_mask: Bitboard, king: Square, pinned_mask: Bitb
     to_mask & pin_mask
 }
 
+/// Which promotions [`Position::generate_moves_with_promotions`] emits for each promoting pawn
+/// step, and in what order.
+#[derive(Debug, Clone, Copy, Eq, Default)]
+#[derive_const(PartialEq)]
+pub enum PromotionPolicy {
+    /// All four promotions, in [`Piece::PROMOTION_PIECES`] order (as [`Position::generate_moves`]).
+    #[default]
+    All,
+    /// All four promotions, ordered queen, knight, rook, bishop.
+    QueenFirst,
+    /// Queen promotions only; underpromotions are pruned.
+    QueenOnly,
+}
+
+impl PromotionPolicy {
+    /// Promotion pieces emitted under this policy, in emission order.
+    pub const fn pieces(self) -> &'static [Piece] {
+        match self {
+            PromotionPolicy::All => &[Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen],
+            PromotionPolicy::QueenFirst => {
+                &[Piece::Queen, Piece::Knight, Piece::Rook, Piece::Bishop]
+            }
+            PromotionPolicy::QueenOnly => &[Piece::Queen],
+        }
+    }
+}
+
 fn generate_pawn_promotions(src_square: Square, dst_square: Square) -> [Move; 4] {
     Piece::PROMOTION_PIECES
         .map(|promotion_piece| Move::new_promotion(src_square, dst_square, promotion_piece))
@@ -101,13 +131,42 @@ const fn split_promotions(to_mask: Bitboard, promo_rank: Bitboard) -> (Bitboard,
 }
 
 trait LegalMoveSink {
+    /// Whether generation can stop early because the sink needs no further moves.
+    fn is_done(&self) -> bool {
+        false
+    }
+
+    /// Squares whose pieces are worth visiting; moves from other squares may be skipped.
+    fn source_mask(&self) -> Bitboard {
+        !0
+    }
+
+    /// Destinations of non-promoting moves worth visiting; moves to other squares may be skipped.
+    fn target_mask(&self) -> Bitboard {
+        !0
+    }
+
     fn normal(&mut self, from: Square, to: Square);
     fn promotions(&mut self, from: Square, to: Square);
     fn en_passant(&mut self, from: Square, to: Square);
     fn castling(&mut self, from: Square, to: Square);
     fn normal_mask(&mut self, from: Square, to_mask: Bitboard);
-    fn promotions_mask(&mut self, from: Square, to_mask: Bitboard);
-    fn emit_pawn_dsts(&mut self, sd: SquareDelta, to_mask: Bitboard, promo_rank: Bitboard);
+
+    /// Emits the pawn moves landing on `to_mask`, each from the square `sd` away, as promotions
+    /// on `promo_rank` and normal moves elsewhere.
+    fn emit_pawn_dsts(&mut self, sd: SquareDelta, to_mask: Bitboard, promo_rank: Bitboard) {
+        let (normal, promotions) = split_promotions(to_mask, promo_rank);
+        for to in normal.iter_set_bits_as_squares() {
+            // SAFETY: `to_mask` holds pawn destinations, which are `sd` away from a pawn.
+            let from = unsafe { to.relative_unchecked(sd) };
+            self.normal(from, to);
+        }
+        for to in promotions.iter_set_bits_as_squares() {
+            // SAFETY: `to_mask` holds pawn destinations, which are `sd` away from a pawn.
+            let from = unsafe { to.relative_unchecked(sd) };
+            self.promotions(from, to);
+        }
+    }
 
     fn emit_non_ep_pawn_captures<const STM: Color>(
         &mut self,
@@ -121,25 +180,22 @@ trait LegalMoveSink {
         let up_right = SquareDelta::UP_RIGHT.for_perspective(STM);
         let down_right = -up_left;
         let down_left = -up_right;
-        let promo_rank = Rank::Eight.from_perspective(STM).mask();
+        let promo_rank = STM.promotion_rank().mask();
 
-        // Free pawns: batch attack generation, no pin reasoning required.
+        // A pawn pinned along a diagonal may still capture along that diagonal, and its capture in
+        // the other direction never lands on a diagonal through the king, so restricting the
+        // pinned pawns' targets to the king's diagonals keeps exactly the legal captures. Pawns
+        // pinned along a rank or file cannot capture at all.
         let free = stm_pawns & !pinned;
-        let left = multi_pawn_attacks_left(free, STM) & opposite_pieces & dst_mask;
-        let right = multi_pawn_attacks_right(free, STM) & opposite_pieces & dst_mask;
+        let diagonally_pinned = stm_pawns & pinned & king_sq.diagonals_mask();
+        let targets = opposite_pieces & dst_mask;
+        let pinned_targets = targets & king_sq.diagonals_mask();
+        let left = (multi_pawn_attacks_left(free, STM) & targets)
+            | (multi_pawn_attacks_left(diagonally_pinned, STM) & pinned_targets);
+        let right = (multi_pawn_attacks_right(free, STM) & targets)
+            | (multi_pawn_attacks_right(diagonally_pinned, STM) & pinned_targets);
         self.emit_pawn_dsts(down_right, left, promo_rank);
         self.emit_pawn_dsts(down_left, right, promo_rank);
-
-        // Pinned pawns (rare): per-source emission so the pin restriction is just an AND.
-        for from in (stm_pawns & pinned).iter_set_bits_as_squares() {
-            let attacks = multi_pawn_attacks(from.mask(), STM)
-                & opposite_pieces
-                & dst_mask
-                & Bitboard::edge_to_edge_ray(from, king_sq);
-            let (normal, promotions) = split_promotions(attacks, promo_rank);
-            self.normal_mask(from, normal);
-            self.promotions_mask(from, promotions);
-        }
     }
 
     fn emit_en_passants<const STM: Color>(
@@ -184,8 +240,8 @@ trait LegalMoveSink {
         let king_file_mask = king_sq.file().mask();
         let movable_pawns = pawns_stm & !(pinned & !king_file_mask);
 
-        let promo_rank = Rank::Eight.from_perspective(STM).mask();
-        let push_again_mask = Rank::Three.from_perspective(STM).mask();
+        let promo_rank = STM.promotion_rank().mask();
+        let push_again_mask = STM.relative_rank(Rank::Three).mask();
         let down = SquareDelta::DOWN.for_perspective(STM);
 
         let single_push_dsts = multi_pawn_moves(movable_pawns, STM) & !occupied;
@@ -198,6 +254,9 @@ trait LegalMoveSink {
 
     fn emit_moves(&mut self, moves: impl Fn(Square) -> Bitboard, pieces: Bitboard) {
         for from in pieces.iter_set_bits_as_squares() {
+            if self.is_done() {
+                return;
+            }
             let to_mask = moves(from);
             self.normal_mask(from, to_mask);
         }
@@ -209,9 +268,10 @@ trait LegalMoveSink {
         king_sq: Square,
         stm_occupancy: Bitboard,
         king_mask: Bitboard,
+        targets: Bitboard,
         king_dst_is_safe: impl Fn(Square, Bitboard) -> bool,
     ) {
-        let king_moves = single_king_attacks(king_sq) & !stm_occupancy;
+        let king_moves = single_king_attacks(king_sq) & !stm_occupancy & targets;
 
         for dst_square in king_moves.iter_set_bits_as_squares() {
             if king_dst_is_safe(dst_square, king_mask | dst_square.mask()) {
@@ -220,11 +280,16 @@ trait LegalMoveSink {
         }
     }
 
-    fn emit_castling_moves<const STM: Color>(&mut self, may_castle: impl Fn(Flank) -> bool) {
+    fn emit_castling_moves<const STM: Color>(
+        &mut self,
+        targets: Bitboard,
+        may_castle: impl Fn(Flank) -> bool,
+    ) {
         let king_src_square = STM.king_initial_square();
         for flank in Flank::ALL {
-            if may_castle(flank) {
-                self.castling(king_src_square, flank.king_castled_square(STM));
+            let king_dst_square = flank.king_castled_square(STM);
+            if king_dst_square.mask() & targets != 0 && may_castle(flank) {
+                self.castling(king_src_square, king_dst_square);
             }
         }
     }
@@ -232,11 +297,15 @@ trait LegalMoveSink {
 
 struct MoveListSink<'a> {
     moves: &'a mut MoveList,
+    promotion_policy: PromotionPolicy,
 }
 
 impl<'a> MoveListSink<'a> {
-    fn new(moves: &'a mut MoveList) -> Self {
-        Self { moves }
+    fn new(moves: &'a mut MoveList, promotion_policy: PromotionPolicy) -> Self {
+        Self {
+            moves,
+            promotion_policy,
+        }
     }
 }
 
@@ -247,7 +316,14 @@ impl LegalMoveSink for MoveListSink<'_> {
     }
 
     fn promotions(&mut self, from: Square, to: Square) {
-        self.moves.push_all(generate_pawn_promotions(from, to));
+        match self.promotion_policy {
+            PromotionPolicy::All => self.moves.push_all(generate_pawn_promotions(from, to)),
+            policy => {
+                for &piece in policy.pieces() {
+                    self.moves.push(Move::new_promotion(from, to, piece));
+                }
+            }
+        }
     }
 
     fn en_passant(&mut self, from: Square, to: Square) {
@@ -265,22 +341,86 @@ impl LegalMoveSink for MoveListSink<'_> {
             self.normal(from, to);
         }
     }
+}
 
-    fn promotions_mask(&mut self, from: Square, to_mask: Bitboard) {
-        for to in to_mask.iter_set_bits_as_squares() {
-            self.promotions(from, to);
+/// Forwards to a [`MoveListSink`] only the moves of a category, optionally from and to given
+/// squares.
+struct FilterSink<'a> {
+    inner: MoveListSink<'a>,
+    from_mask: Bitboard,
+    to_mask: Bitboard,
+    /// Allowed destinations of non-promoting, non-en-passant moves.
+    normal_to_mask: Bitboard,
+    noisy: bool,
+    quiet: bool,
+}
+
+impl<'a> FilterSink<'a> {
+    fn new(
+        moves: &'a mut MoveList,
+        from_mask: Bitboard,
+        to_mask: Bitboard,
+        opponents: Bitboard,
+        noisy: bool,
+        quiet: bool,
+    ) -> Self {
+        let category_mask = match (noisy, quiet) {
+            (true, true) => !0,
+            (true, false) => opponents,
+            (false, true) => !opponents,
+            (false, false) => 0,
+        };
+        Self {
+            inner: MoveListSink::new(moves, PromotionPolicy::All),
+            from_mask,
+            to_mask,
+            normal_to_mask: category_mask & to_mask,
+            noisy,
+            quiet,
         }
     }
 
-    fn emit_pawn_dsts(&mut self, sd: SquareDelta, to_mask: Bitboard, promo_rank: Bitboard) {
-        let (normal, promotions) = split_promotions(to_mask, promo_rank);
-        for to in normal.iter_set_bits_as_squares() {
-            let from = to.relative(sd).expect("Invalid SquareDelta for to_mask");
-            self.normal(from, to);
+    fn allows(&self, from: Square, to: Square) -> bool {
+        from.mask() & self.from_mask != 0 && to.mask() & self.to_mask != 0
+    }
+}
+
+impl LegalMoveSink for FilterSink<'_> {
+    fn source_mask(&self) -> Bitboard {
+        self.from_mask
+    }
+
+    fn target_mask(&self) -> Bitboard {
+        self.normal_to_mask
+    }
+
+    fn normal(&mut self, from: Square, to: Square) {
+        if from.mask() & self.from_mask != 0 && to.mask() & self.normal_to_mask != 0 {
+            self.inner.normal(from, to);
         }
-        for to in promotions.iter_set_bits_as_squares() {
-            let from = to.relative(sd).expect("Invalid SquareDelta for to_mask");
-            self.promotions(from, to);
+    }
+
+    fn promotions(&mut self, from: Square, to: Square) {
+        if self.noisy && self.allows(from, to) {
+            self.inner.promotions(from, to);
+        }
+    }
+
+    fn en_passant(&mut self, from: Square, to: Square) {
+        if self.noisy && self.allows(from, to) {
+            self.inner.en_passant(from, to);
+        }
+    }
+
+    fn castling(&mut self, from: Square, to: Square) {
+        if self.quiet && self.allows(from, to) {
+            self.inner.castling(from, to);
+        }
+    }
+
+    fn normal_mask(&mut self, from: Square, to_mask: Bitboard) {
+        if from.mask() & self.from_mask != 0 {
+            self.inner.normal_mask(from, to_mask & self.normal_to_mask);
         }
     }
 }
@@ -311,31 +451,72 @@ impl LegalMoveSink for MoveCountSink {
         self.count += to_mask.count_ones();
     }
 
-    fn promotions_mask(&mut self, _from: Square, to_mask: Bitboard) {
-        self.count += to_mask.count_ones() * 4;
-    }
-
     fn emit_pawn_dsts(&mut self, _sd: SquareDelta, to_mask: Bitboard, promo_rank: Bitboard) {
         let (normal, promotions) = split_promotions(to_mask, promo_rank);
         self.count += normal.count_ones() + promotions.count_ones() * 4;
     }
 }
 
+/// Records whether any legal move exists, stopping generation at the first one.
+#[derive_const(Default)]
+struct AnyMoveSink {
+    found: bool,
+}
+
+impl LegalMoveSink for AnyMoveSink {
+    fn is_done(&self) -> bool {
+        self.found
+    }
+
+    fn normal(&mut self, _from: Square, _to: Square) {
+        self.found = true;
+    }
+
+    fn promotions(&mut self, _from: Square, _to: Square) {
+        self.found = true;
+    }
+
+    fn en_passant(&mut self, _from: Square, _to: Square) {
+        self.found = true;
+    }
+
+    fn castling(&mut self, _from: Square, _to: Square) {
+        self.found = true;
+    }
+
+    fn normal_mask(&mut self, _from: Square, to_mask: Bitboard) {
+        self.found |= to_mask != 0;
+    }
+
+    fn emit_pawn_dsts(&mut self, _sd: SquareDelta, to_mask: Bitboard, _promo_rank: Bitboard) {
+        self.found |= to_mask != 0;
+    }
+}
+
 impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
     fn visit_legal_moves<S: LegalMoveSink>(&self, sink: &mut S) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("movegen", side_to_move = ?STM).entered();
         let ctx = self.context();
         let board = &self.board;
         let king_sq = self.king_square(STM);
         let stm_pieces = board.color_mask_at(STM);
         let stm_king_mask = stm_pieces & board.piece_mask::<{ Piece::King }>();
+        // Sinks only interested in some pieces (e.g. `moves_from`) or some destinations (e.g.
+        // `generate_captures`) skip the rest entirely.
+        let sources = sink.source_mask();
+        let targets = sink.target_mask();
+        let visit_king = stm_king_mask & sources != 0;
 
         // 1. King moves are always legal candidates, regardless of check status.
-        sink.emit_king_moves(king_sq, stm_pieces, stm_king_mask, |dst, occ| {
-            !board.is_square_attacked_after_move(dst, STM.other(), occ)
-        });
+        if visit_king {
+            sink.emit_king_moves(king_sq, stm_pieces, stm_king_mask, targets, |dst, occ| {
+                !board.is_square_attacked_after_move(dst, STM.other(), occ)
+            });
+        }
 
         // 2. Double check: only the king can move.
-        if ctx.checkers.count_ones() > 1 {
+        if ctx.checkers.count_ones() > 1 || sink.is_done() {
             return;
         }
 
@@ -348,11 +529,21 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
             });
 
         // 4. Emit pawns, knights, sliders, castling.
-        let pawns = stm_pieces & board.piece_mask::<{ Piece::Pawn }>();
+        let pawns = stm_pieces & board.piece_mask::<{ Piece::Pawn }>() & sources;
         let opposite = board.color_mask_at(STM.other());
         let occupied = board.pieces();
 
-        sink.emit_non_ep_pawn_captures::<STM>(pawns, opposite, king_sq, dst_mask, ctx.pinned);
+        let promo_rank = STM.promotion_rank().mask();
+        sink.emit_non_ep_pawn_captures::<STM>(
+            pawns,
+            opposite,
+            king_sq,
+            dst_mask & (targets | promo_rank),
+            ctx.pinned,
+        );
+        if sink.is_done() {
+            return;
+        }
 
         sink.emit_en_passants::<STM>(
             ctx.double_pawn_push_file,
@@ -369,11 +560,21 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
             },
         );
 
-        sink.emit_pawn_pushes::<STM>(occupied, pawns, king_sq, dst_mask, ctx.pinned);
+        sink.emit_pawn_pushes::<STM>(
+            occupied,
+            pawns,
+            king_sq,
+            dst_mask & (targets | promo_rank),
+            ctx.pinned,
+        );
+        if sink.is_done() {
+            return;
+        }
 
+        let dst_mask = dst_mask & targets;
         sink.emit_moves(
             |from| single_knight_attacks(from) & dst_mask,
-            stm_pieces & board.piece_mask::<{ Piece::Knight }>() & !ctx.pinned,
+            stm_pieces & board.piece_mask::<{ Piece::Knight }>() & !ctx.pinned & sources,
         );
 
         let queens = board.piece_mask::<{ Piece::Queen }>();
@@ -387,7 +588,7 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
                     ctx.pinned,
                 )
             },
-            stm_pieces & (board.piece_mask::<{ Piece::Bishop }>() | queens),
+            stm_pieces & (board.piece_mask::<{ Piece::Bishop }>() | queens) & sources,
         );
 
         sink.emit_moves(
@@ -399,20 +600,97 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
                     ctx.pinned,
                 )
             },
-            stm_pieces & (board.piece_mask::<{ Piece::Rook }>() | queens),
+            stm_pieces & (board.piece_mask::<{ Piece::Rook }>() | queens) & sources,
         );
 
-        if allow_castling {
-            sink.emit_castling_moves::<STM>(|flank| self.can_legally_castle(flank));
+        if allow_castling && visit_king {
+            sink.emit_castling_moves::<STM>(targets, |flank| self.can_legally_castle(flank));
         }
     }
 
     /// Fills `moves` with all legal moves (does not clear `moves`; clear or use a fresh list if needed).
     pub fn generate_moves(&self, moves: &mut MoveList) {
-        let mut sink = MoveListSink::new(moves);
+        self.generate_moves_with_promotions(moves, PromotionPolicy::All);
+    }
+
+    /// Like [`Self::generate_moves`], but emits promotions as dictated by `promotion_policy`.
+    pub fn generate_moves_with_promotions(
+        &self,
+        moves: &mut MoveList,
+        promotion_policy: PromotionPolicy,
+    ) {
+        let mut sink = MoveListSink::new(moves, promotion_policy);
         self.visit_legal_moves(&mut sink);
     }
 
+    /// Appends the legal captures (including en passant) and all promotions to `moves`.
+    pub fn generate_captures(&self, moves: &mut MoveList) {
+        self.generate_filtered_moves(moves, !0, !0, true, false);
+    }
+
+    /// Appends the legal moves not produced by [`Self::generate_captures`] to `moves`.
+    pub fn generate_quiets(&self, moves: &mut MoveList) {
+        self.generate_filtered_moves(moves, !0, !0, false, true);
+    }
+
+    pub(crate) fn generate_filtered_moves(
+        &self,
+        moves: &mut MoveList,
+        from_mask: Bitboard,
+        to_mask: Bitboard,
+        noisy: bool,
+        quiet: bool,
+    ) {
+        let opponents = self.board.color_mask_at(STM.other());
+        let mut sink = FilterSink::new(moves, from_mask, to_mask, opponents, noisy, quiet);
+        self.visit_legal_moves(&mut sink);
+    }
+
+    /// Returns the legal moves of the piece on `square` (empty if it holds no piece of the side
+    /// to move). Only that piece is visited, so this is much cheaper than full generation.
+    pub fn moves_from(&self, square: Square) -> Vec<Move> {
+        let mut moves = MoveList::new();
+        self.generate_filtered_moves(&mut moves, square.mask(), !0, true, true);
+        moves.as_slice().to_vec()
+    }
+
+    /// Returns the legal moves landing on `square` (including en passant onto it and castling
+    /// whose king destination it is).
+    ///
+    /// Only pieces that attack `square`, pawns on its file, and the king are visited.
+    pub fn moves_to(&self, square: Square) -> Vec<Move> {
+        let board = &self.board;
+        let occupied = board.pieces();
+        let attackers = board.non_sliding_attacks_on_square(square, STM)
+            | (single_bishop_attacks(square, occupied) & board.diagonal_sliders())
+            | (single_rook_attacks(square, occupied) & board.orthogonal_sliders());
+        let candidates = (attackers
+            | (square.file().mask() & board.piece_mask::<{ Piece::Pawn }>())
+            | board.piece_mask::<{ Piece::King }>())
+            & board.color_mask_at(STM);
+
+        let mut moves = MoveList::new();
+        self.generate_filtered_moves(&mut moves, candidates, square.mask(), true, true);
+        moves.as_slice().to_vec()
+    }
+
+    /// Returns whether the side to move has any legal move, stopping at the first one found.
+    pub fn has_legal_moves(&self) -> bool {
+        let mut sink = AnyMoveSink::default();
+        self.visit_legal_moves(&mut sink);
+        sink.found
+    }
+
+    /// Returns whether the side to move is checkmated.
+    pub fn is_checkmate(&self) -> bool {
+        self.is_current_side_in_check() && !self.has_legal_moves()
+    }
+
+    /// Returns whether the side to move is stalemated.
+    pub fn is_stalemate(&self) -> bool {
+        !self.is_current_side_in_check() && !self.has_legal_moves()
+    }
+
     /// Counts all legal moves without materializing [`Move`] values.
     pub fn count_legal_moves(&self) -> u32 {
         let mut sink = MoveCountSink::default();
@@ -425,7 +703,11 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
 mod tests {
     use std::collections::HashSet;
 
-    use crate::types::{Color, Move, MoveFlag, MoveList, Piece, Position, Square};
+    use super::PromotionPolicy;
+    use crate::{
+        types::{Color, Move, MoveFlag, MoveList, Piece, Position, Square},
+        utilities::IterableEnum,
+    };
 
     fn expected_moves_test_for_position<const M: usize, const STM: Color>(
         pos: &Position<1, STM>,
@@ -508,6 +790,145 @@ mod tests {
         assert_eq!(legal.len(), first_len * 2);
     }
 
+    #[test]
+    fn test_promotion_policies() {
+        let pos =
+            Position::<1, { Color::White }>::from_fen("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let promotions = |policy| {
+            let mut moves = MoveList::new();
+            pos.generate_moves_with_promotions(&mut moves, policy);
+            moves
+                .iter()
+                .filter(|m| m.flag() == MoveFlag::Promotion)
+                .map(|m| (m.to(), m.promotion()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(promotions(PromotionPolicy::All).len(), 8);
+        assert_eq!(
+            promotions(PromotionPolicy::QueenFirst)[4..],
+            [
+                (Square::A8, Piece::Queen),
+                (Square::A8, Piece::Knight),
+                (Square::A8, Piece::Rook),
+                (Square::A8, Piece::Bishop),
+            ]
+        );
+        assert_eq!(
+            promotions(PromotionPolicy::QueenOnly),
+            vec![(Square::B8, Piece::Queen), (Square::A8, Piece::Queen)]
+        );
+    }
+
+    #[test]
+    fn test_captures_and_quiets_partition_moves() {
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1",
+            "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2",
+        ] {
+            let pos = Position::<1, { Color::White }>::from_fen(fen).unwrap();
+            let mut all = MoveList::new();
+            pos.generate_moves(&mut all);
+            let mut split = MoveList::new();
+            pos.generate_captures(&mut split);
+            let num_captures = split.len();
+            pos.generate_quiets(&mut split);
+
+            let all: HashSet<Move> = all.iter().copied().collect();
+            let split_set: HashSet<Move> = split.iter().copied().collect();
+            assert_eq!(split.len(), all.len(), "{fen}");
+            assert_eq!(split_set, all, "{fen}");
+            for m in &split.as_slice()[..num_captures] {
+                assert!(
+                    m.is_capture(&pos) || m.flag() == MoveFlag::Promotion,
+                    "{fen}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_checkmate_and_stalemate_predicates() {
+        let cases = [
+            // Fool's mate.
+            (
+                "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3",
+                true,
+                false,
+            ),
+            ("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1", false, true),
+            ("8/8/8/8/k2Pp3/8/8/2KR4 b - d3 0 2", false, false),
+            (
+                "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+                false,
+                false,
+            ),
+        ];
+        for (fen, is_checkmate, is_stalemate) in cases {
+            let has_moves = !is_checkmate && !is_stalemate;
+            if fen.contains(" w ") {
+                let pos = Position::<1, { Color::White }>::from_fen(fen).unwrap();
+                assert_eq!(pos.has_legal_moves(), has_moves, "{fen}");
+                assert_eq!(pos.is_checkmate(), is_checkmate, "{fen}");
+                assert_eq!(pos.is_stalemate(), is_stalemate, "{fen}");
+            } else {
+                let pos = Position::<1, { Color::Black }>::from_fen(fen).unwrap();
+                assert_eq!(pos.has_legal_moves(), has_moves, "{fen}");
+                assert_eq!(pos.is_checkmate(), is_checkmate, "{fen}");
+                assert_eq!(pos.is_stalemate(), is_stalemate, "{fen}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_moves_from_matches_filtered_generation() {
+        let pos = Position::<1, { Color::White }>::from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        )
+        .unwrap();
+        let mut all = MoveList::new();
+        pos.generate_moves(&mut all);
+
+        for square in Square::ALL {
+            let expected: Vec<Move> = all.iter().copied().filter(|m| m.from() == square).collect();
+            let actual = pos.moves_from(square);
+            assert_eq!(
+                actual.iter().copied().collect::<HashSet<_>>(),
+                expected.iter().copied().collect::<HashSet<_>>(),
+                "{square}"
+            );
+            assert_eq!(actual.len(), expected.len(), "{square}");
+        }
+        // King moves include castling.
+        assert_eq!(pos.moves_from(Square::E1).len(), 4);
+        assert!(pos.moves_from(Square::E8).is_empty());
+    }
+
+    #[test]
+    fn test_moves_to_matches_filtered_generation() {
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "4k3/1P6/8/3pP3/8/8/8/4K2R w K d6 0 2",
+        ] {
+            let pos = Position::<1, { Color::White }>::from_fen(fen).unwrap();
+            let mut all = MoveList::new();
+            pos.generate_moves(&mut all);
+
+            for square in Square::ALL {
+                let expected: HashSet<Move> =
+                    all.iter().copied().filter(|m| m.to() == square).collect();
+                let actual = pos.moves_to(square);
+                assert_eq!(actual.len(), expected.len(), "{fen} {square}");
+                assert_eq!(
+                    actual.into_iter().collect::<HashSet<_>>(),
+                    expected,
+                    "{fen} {square}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_knight_movegen() {
         let is_knight_move_white = |mv: Move, pos: &Position<1, { Color::White }>| {
@@ -692,6 +1113,17 @@ mod tests {
                 Move::new_non_promotion(Square::D5, Square::E6, MoveFlag::NormalMove),
             ],
         );
+
+        // Both pawns are pinned along a diagonal: each may only take the piece on its pin line.
+        expected_moves_test(
+            "4k3/3p1p2/2B1N1Q1/8/8/8/8/7K b - - 0 1",
+            is_non_ep_pawn_capture_white,
+            is_non_ep_pawn_capture_black,
+            [
+                Move::new_non_promotion(Square::D7, Square::C6, MoveFlag::NormalMove),
+                Move::new_non_promotion(Square::F7, Square::G6, MoveFlag::NormalMove),
+            ],
+        );
     }
 
     #[test]
@@ -0,0 +1,85 @@
+//! Inferring the legal move behind a change of board, as reported by electronic chessboards.
+
+use crate::types::{Bitboard, Board, Color, Move, MoveList, Piece, Position, ZobristPolicy};
+
+impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
+    /// Returns the legal move that turns this position's board into `after`, or `None` if no
+    /// single legal move does.
+    ///
+    /// Castling and en passant, which change more than two squares, are matched like any other
+    /// move. Since `after` identifies every piece, at most one move can match.
+    pub fn infer_move(&self, after: &Board) -> Option<Move> {
+        self.legal_moves_with_board()
+            .find(|(_, board)| board == after)
+            .map(|(move_, _)| move_)
+    }
+
+    /// Returns every legal move that leaves exactly the squares in `occupied` occupied, for
+    /// boards that only sense occupancy.
+    ///
+    /// Occupancy cannot tell promotion pieces apart, nor captures by the same piece on different
+    /// squares, so several moves may match; callers resolve them, e.g. by the order pieces were
+    /// lifted. An empty result means no legal move explains the change.
+    pub fn infer_moves_from_occupancy(&self, occupied: Bitboard) -> Vec<Move> {
+        self.legal_moves_with_board()
+            .filter(|(_, board)| board.piece_mask::<{ Piece::ALL_PIECES }>() == occupied)
+            .map(|(move_, _)| move_)
+            .collect()
+    }
+
+    /// Pairs each legal move with the board it leads to.
+    fn legal_moves_with_board(&self) -> impl Iterator<Item = (Move, Board)> {
+        let mut moves = MoveList::new();
+        self.generate_moves(&mut moves);
+        moves.as_slice().to_vec().into_iter().map(|move_| {
+            let mut board = self.board.clone();
+            board.apply_move(move_);
+            (move_, board)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{Color, MoveFlag, MoveList, Position, Square};
+
+    #[test]
+    fn test_infer_move() {
+        let position =
+            Position::<2, { Color::White }>::from_fen("r3k2r/1P6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 2")
+                .unwrap();
+        let mut moves = MoveList::new();
+        position.generate_moves(&mut moves);
+        let board_after = |from, to| {
+            let move_ = *moves
+                .as_slice()
+                .iter()
+                .find(|move_| move_.from() == from && move_.to() == to)
+                .unwrap();
+            let mut board = position.board.clone();
+            board.apply_move(move_);
+            (move_, board)
+        };
+
+        for (from, to, flag) in [
+            (Square::E1, Square::G1, MoveFlag::Castling),
+            (Square::E1, Square::C1, MoveFlag::Castling),
+            (Square::E5, Square::D6, MoveFlag::EnPassant),
+            (Square::A1, Square::A8, MoveFlag::NormalMove),
+        ] {
+            let (move_, board) = board_after(from, to);
+            assert_eq!(move_.flag(), flag);
+            assert_eq!(position.infer_move(&board), Some(move_));
+            assert_eq!(position.infer_moves_from_occupancy(board.pieces()), [move_]);
+        }
+        assert_eq!(position.infer_move(&position.board), None);
+
+        // The four promotions on b8 leave the same squares occupied.
+        let (_, promoted) = board_after(Square::B7, Square::B8);
+        assert_eq!(
+            position.infer_moves_from_occupancy(promoted.pieces()).len(),
+            4
+        );
+        assert!(position.infer_move(&promoted).is_some());
+    }
+}
@@ -0,0 +1,212 @@
+//! Reference move generator for cross-checking [`Position::generate_moves`].
+//!
+//! [`Position::generate_moves`] is strictly legal: it derives every restriction from the pin and
+//! checker masks and never plays a move to see whether it was legal. The generator here takes the
+//! opposite approach, emitting pseudo-legal moves and keeping those after which
+//! [`Position::is_probably_valid`] holds. It is much slower, but simple enough to trust, so it
+//! serves as an oracle in tests and when debugging move generation.
+
+use crate::{
+    logic::attacks::{
+        multi_pawn_attacks,
+        multi_pawn_moves,
+        single_bishop_attacks,
+        single_king_attacks,
+        single_knight_attacks,
+        single_rook_attacks,
+    },
+    types::{
+        Bitboard,
+        BitboardUtils,
+        Color,
+        ConstDoublePawnPushFile,
+        Flank,
+        Move,
+        MoveFlag,
+        MoveList,
+        Piece,
+        Position,
+        Rank,
+        Square,
+        ZobristPolicy,
+    },
+    utilities::IterableEnum,
+};
+
+fn push_moves(moves: &mut MoveList, from: Square, to_mask: Bitboard) {
+    for to in to_mask.iter_set_bits_as_squares() {
+        moves.push(Move::new_non_promotion(from, to, MoveFlag::NormalMove));
+    }
+}
+
+fn push_pawn_moves<const STM: Color>(moves: &mut MoveList, from: Square, to_mask: Bitboard) {
+    let promo_rank = STM.promotion_rank().mask();
+    push_moves(moves, from, to_mask & !promo_rank);
+    for to in (to_mask & promo_rank).iter_set_bits_as_squares() {
+        for promotion in Piece::PROMOTION_PIECES {
+            moves.push(Move::new_promotion(from, to, promotion));
+        }
+    }
+}
+
+impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
+    /// Appends every pseudo-legal move to `moves`: moves that follow the piece movement rules
+    /// but may leave the mover's king in check.
+    ///
+    /// Castling is only emitted when it is fully legal, since whether the king passes through
+    /// an attacked square cannot be told from the position after the move.
+    pub fn generate_pseudo_legal_moves(&self, moves: &mut MoveList) {
+        let board = &self.board;
+        let own = board.color_mask_at(STM);
+        let opposite = board.color_mask_at(STM.other());
+        let occupied = board.pieces();
+        let queens = board.piece_mask::<{ Piece::Queen }>();
+
+        for from in (own & board.piece_mask::<{ Piece::Knight }>()).iter_set_bits_as_squares() {
+            push_moves(moves, from, single_knight_attacks(from) & !own);
+        }
+        for from in
+            (own & (board.piece_mask::<{ Piece::Bishop }>() | queens)).iter_set_bits_as_squares()
+        {
+            push_moves(moves, from, single_bishop_attacks(from, occupied) & !own);
+        }
+        for from in
+            (own & (board.piece_mask::<{ Piece::Rook }>() | queens)).iter_set_bits_as_squares()
+        {
+            push_moves(moves, from, single_rook_attacks(from, occupied) & !own);
+        }
+        for from in (own & board.piece_mask::<{ Piece::King }>()).iter_set_bits_as_squares() {
+            push_moves(moves, from, single_king_attacks(from) & !own);
+        }
+
+        let double_pawn_push_file = self.context().double_pawn_push_file;
+        let ep_dst_mask = if double_pawn_push_file.has_file() {
+            double_pawn_push_file.ep_dst_square(STM).mask()
+        } else {
+            0
+        };
+        let double_push_rank = STM.relative_rank(Rank::Two).mask();
+        for from in (own & board.piece_mask::<{ Piece::Pawn }>()).iter_set_bits_as_squares() {
+            let single_push = multi_pawn_moves(from.mask(), STM) & !occupied;
+            let double_push = if from.mask() & double_push_rank != 0 {
+                multi_pawn_moves(single_push, STM) & !occupied
+            } else {
+                0
+            };
+            let attacks = multi_pawn_attacks(from.mask(), STM);
+            push_pawn_moves::<STM>(
+                moves,
+                from,
+                single_push | double_push | (attacks & opposite),
+            );
+            for to in (attacks & ep_dst_mask).iter_set_bits_as_squares() {
+                moves.push(Move::new_non_promotion(from, to, MoveFlag::EnPassant));
+            }
+        }
+
+        if !self.is_current_side_in_check() {
+            for flank in Flank::ALL {
+                if self.can_legally_castle(flank) {
+                    moves.push(Move::new_non_promotion(
+                        STM.king_initial_square(),
+                        flank.king_castled_square(STM),
+                        MoveFlag::Castling,
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Appends the legal moves to `moves` by playing each pseudo-legal move and keeping those
+    /// that pass [`Self::is_probably_valid`].
+    ///
+    /// This is a debugging oracle for [`Self::generate_moves`]: it yields the same set of moves,
+    /// though not necessarily in the same order, and is far slower.
+    pub fn generate_moves_by_filtering(&self, moves: &mut MoveList) {
+        let mut candidates = MoveList::new();
+        self.generate_pseudo_legal_moves(&mut candidates);
+        for &move_ in candidates.iter() {
+            let mut next = self.clone_current::<2>();
+            next.make_move(move_);
+            let is_legal = match STM {
+                Color::White => next.rebrand_stm::<{ Color::Black }>().is_probably_valid(),
+                Color::Black => next.rebrand_stm::<{ Color::White }>().is_probably_valid(),
+            };
+            if is_legal {
+                moves.push(move_);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{
+        logic::fen::INITIAL_FEN,
+        types::{Color, Move, MoveList, Position, TypedPosition},
+    };
+
+    fn assert_generators_agree<const N: usize, const STM: Color>(
+        position: &mut Position<N, STM>,
+        depth: u8,
+    ) {
+        let mut strict = MoveList::new();
+        position.generate_moves(&mut strict);
+        let mut filtered = MoveList::new();
+        position.generate_moves_by_filtering(&mut filtered);
+
+        let strict_set: HashSet<Move> = strict.iter().copied().collect();
+        let filtered_set: HashSet<Move> = filtered.iter().copied().collect();
+        assert_eq!(
+            strict.len(),
+            strict_set.len(),
+            "duplicate moves in generate_moves"
+        );
+        assert_eq!(
+            strict_set,
+            filtered_set,
+            "generators disagree in {}",
+            position.to_fen()
+        );
+
+        if depth == 0 {
+            return;
+        }
+        for &move_ in strict.as_slice() {
+            position.make_move(move_);
+            match STM {
+                Color::White => {
+                    let child = unsafe { position.rebrand_stm_mut::<{ Color::Black }>() };
+                    assert_generators_agree(child, depth - 1);
+                    child.unmake_move(move_);
+                }
+                Color::Black => {
+                    let child = unsafe { position.rebrand_stm_mut::<{ Color::White }>() };
+                    assert_generators_agree(child, depth - 1);
+                    child.unmake_move(move_);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_strict_generator_matches_filtering_oracle() {
+        let fens = [
+            INITIAL_FEN,
+            // Kiwipete: castling, pins, en passant and promotions in a few plies.
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            // Horizontal en passant pin and rook checks.
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        ];
+        for fen in fens {
+            match TypedPosition::<8>::from_fen(fen).unwrap() {
+                TypedPosition::White(mut position) => assert_generators_agree(&mut position, 2),
+                TypedPosition::Black(mut position) => assert_generators_agree(&mut position, 2),
+            }
+        }
+    }
+}
@@ -0,0 +1,259 @@
+//! Partially specified moves (as written in SAN) matched against legal moves.
+//!
+//! A [`MovePattern`] names the moved piece, the destination, and optionally a source file/rank,
+//! promotion piece, and capture marker, e.g. `Nxe5`, `axb8=Q`, `R1e2`, or `O-O`.
+
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::types::{
+    Board,
+    Color,
+    File,
+    Flank,
+    Move,
+    MoveFlag,
+    Piece,
+    Position,
+    Rank,
+    Square,
+    ZobristPolicy,
+};
+
+/// A move described the way SAN describes it, without a position.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MovePattern {
+    /// Castling toward a flank.
+    Castling(Flank),
+    /// Any other move.
+    Normal {
+        /// The moved piece.
+        piece: Piece,
+        /// Source file, when disambiguated.
+        from_file: Option<File>,
+        /// Source rank, when disambiguated.
+        from_rank: Option<Rank>,
+        /// Destination square.
+        to: Square,
+        /// Promotion piece, or `None` for a non-promotion.
+        promotion: Option<Piece>,
+        /// Whether the move must (`Some(true)`) or must not (`Some(false)`) capture; `None`
+        /// accepts either.
+        capture: Option<bool>,
+    },
+}
+
+/// Why [`MovePattern::find_move`] did not yield exactly one move.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MovePatternMatchError {
+    /// No legal move matches.
+    NoMatch,
+    /// More than one legal move matches; the pattern needs disambiguation.
+    Ambiguous,
+}
+
+impl fmt::Display for MovePatternMatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MovePatternMatchError::NoMatch => write!(f, "no legal move matches"),
+            MovePatternMatchError::Ambiguous => write!(f, "more than one legal move matches"),
+        }
+    }
+}
+
+impl Error for MovePatternMatchError {}
+
+impl MovePattern {
+    /// Returns whether `move_`, played on `board`, fits this pattern.
+    pub fn matches_on_board(&self, move_: Move, board: &Board) -> bool {
+        match *self {
+            MovePattern::Castling(flank) => {
+                move_.flag() == MoveFlag::Castling && move_.to().file().flank() == flank
+            }
+            MovePattern::Normal {
+                piece,
+                from_file,
+                from_rank,
+                to,
+                promotion,
+                capture,
+            } => {
+                let from = move_.from();
+                let move_promotion = match move_.flag() {
+                    MoveFlag::Promotion => Some(move_.promotion()),
+                    _ => None,
+                };
+                move_.to() == to
+                    && move_.flag() != MoveFlag::Castling
+                    && board.piece_at(from) == piece
+                    && move_promotion == promotion
+                    && from_file.is_none_or(|file| from.file() == file)
+                    && from_rank.is_none_or(|rank| from.rank() == rank)
+                    && capture.is_none_or(|capture| capture == move_.is_capture_on_board(board))
+            }
+        }
+    }
+
+    /// Returns the legal moves of `position` that fit this pattern.
+    pub fn matching_moves<const N: usize, const STM: Color, Z: ZobristPolicy>(
+        &self,
+        position: &Position<N, STM, Z>,
+    ) -> Vec<Move> {
+        let candidates = match *self {
+            MovePattern::Castling(_) => position.moves_from(position.king_square(STM)),
+            MovePattern::Normal { to, .. } => position.moves_to(to),
+        };
+        candidates
+            .into_iter()
+            .filter(|&move_| self.matches_on_board(move_, &position.board))
+            .collect()
+    }
+
+    /// Returns the single legal move of `position` that fits this pattern.
+    pub fn find_move<const N: usize, const STM: Color, Z: ZobristPolicy>(
+        &self,
+        position: &Position<N, STM, Z>,
+    ) -> Result<Move, MovePatternMatchError> {
+        match self.matching_moves(position)[..] {
+            [move_] => Ok(move_),
+            [] => Err(MovePatternMatchError::NoMatch),
+            _ => Err(MovePatternMatchError::Ambiguous),
+        }
+    }
+}
+
+/// Parses SAN-style text such as `e4`, `Nbd7`, `exd5`, `axb8=Q`, `O-O-O`, or `0-0`.
+///
+/// Trailing check, mate, and annotation marks (`+`, `#`, `!`, `?`) are ignored. Without an `x`,
+/// the pattern accepts captures and non-captures alike.
+impl FromStr for MovePattern {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().trim_end_matches(['+', '#', '!', '?']);
+        match s {
+            "O-O" | "0-0" => return Ok(MovePattern::Castling(Flank::Kingside)),
+            "O-O-O" | "0-0-0" => return Ok(MovePattern::Castling(Flank::Queenside)),
+            _ => {}
+        }
+
+        let (s, promotion) = match s.split_once('=') {
+            Some((head, promotion)) => {
+                let mut chars = promotion.chars();
+                match (chars.next().and_then(Piece::from_char), chars.next()) {
+                    (Some(piece), None) if Piece::PROMOTION_PIECES.0.contains(&piece) => {
+                        (head, Some(piece))
+                    }
+                    _ => return Err("Invalid promotion piece"),
+                }
+            }
+            None => (s, None),
+        };
+
+        let mut chars: Vec<char> = s.chars().collect();
+        let piece = match chars.first() {
+            Some(&c) if c.is_ascii_uppercase() => {
+                chars.remove(0);
+                match Piece::from_char(c) {
+                    Some(Piece::Pawn) | None => return Err("Invalid piece letter"),
+                    Some(piece) => piece,
+                }
+            }
+            _ => Piece::Pawn,
+        };
+
+        if chars.len() < 2 {
+            return Err("Missing destination square");
+        }
+        let to_chars = chars.split_off(chars.len() - 2);
+        let to = String::from_iter(to_chars).parse::<Square>()?;
+
+        let capture = match chars.last() {
+            Some('x') => {
+                chars.pop();
+                Some(true)
+            }
+            _ => None,
+        };
+
+        let (mut from_file, mut from_rank) = (None, None);
+        for c in chars {
+            match (File::from_char(c), Rank::from_char(c)) {
+                (Some(file), _) if c.is_ascii_lowercase() && from_file.is_none() => {
+                    from_file = Some(file)
+                }
+                (_, Some(rank)) if from_rank.is_none() => from_rank = Some(rank),
+                _ => return Err("Invalid disambiguation"),
+            }
+        }
+
+        if promotion.is_some() && piece != Piece::Pawn {
+            return Err("Only pawns can promote");
+        }
+
+        Ok(MovePattern::Normal {
+            piece,
+            from_file,
+            from_rank,
+            to,
+            promotion,
+            capture,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "Nbxd7+".parse(),
+            Ok(MovePattern::Normal {
+                piece: Piece::Knight,
+                from_file: Some(File::B),
+                from_rank: None,
+                to: Square::D7,
+                promotion: None,
+                capture: Some(true),
+            })
+        );
+        assert_eq!(
+            "axb8=Q".parse(),
+            Ok(MovePattern::Normal {
+                piece: Piece::Pawn,
+                from_file: Some(File::A),
+                from_rank: None,
+                to: Square::B8,
+                promotion: Some(Piece::Queen),
+                capture: Some(true),
+            })
+        );
+        assert_eq!("0-0-0".parse(), Ok(MovePattern::Castling(Flank::Queenside)));
+        assert!("Ze4".parse::<MovePattern>().is_err());
+        assert!("e9".parse::<MovePattern>().is_err());
+        assert!("Nf3=Q".parse::<MovePattern>().is_err());
+    }
+
+    #[test]
+    fn test_find_move() {
+        let position =
+            Position::<1, { Color::White }>::from_fen("r3k3/1P6/8/3p4/8/2N3N1/8/R3K2R w KQq - 0 1")
+                .unwrap();
+        let find = |text: &str| text.parse::<MovePattern>().unwrap().find_move(&position);
+
+        assert_eq!(find("Ne4"), Err(MovePatternMatchError::Ambiguous));
+        let nce4 = find("Nce4").unwrap();
+        assert_eq!((nce4.from(), nce4.to()), (Square::C3, Square::E4));
+        assert_eq!(find("Nxd5").unwrap().from(), Square::C3);
+        assert_eq!(find("Nxe4"), Err(MovePatternMatchError::NoMatch));
+
+        let promotion = find("bxa8=N").unwrap();
+        assert_eq!(promotion.promotion(), Piece::Knight);
+        assert_eq!(find("b8=Q").unwrap().flag(), MoveFlag::Promotion);
+        assert_eq!(find("b8"), Err(MovePatternMatchError::NoMatch));
+
+        assert_eq!(find("O-O").unwrap().to(), Square::G1);
+        assert_eq!(find("Rh8").unwrap().from(), Square::H1);
+    }
+}
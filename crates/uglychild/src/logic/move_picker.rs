@@ -0,0 +1,241 @@
+//! Staged move ordering for alpha-beta search: TT move, captures, killers, then quiets.
+//!
+//! Each stage generates only the moves it yields: the capture stage visits capture and promotion
+//! destinations only, so a cutoff on the TT move or a capture never pays for quiet move
+//! generation.
+
+use crate::types::{Board, Color, Move, MoveFlag, MoveList, Piece, Position, ZobristPolicy};
+
+/// Stage a [`MovePicker`] is in; moves are yielded in this order.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
+pub enum MovePickerStage {
+    /// The transposition-table move, if it is legal.
+    TtMove,
+    /// Captures and promotions, most valuable victim first.
+    Captures,
+    /// Killer moves that are legal quiet moves.
+    Killers,
+    /// Remaining quiet moves, in generation order.
+    Quiets,
+    /// No moves left.
+    Done,
+}
+
+/// Yields the legal moves of a position in stages, each move exactly once.
+///
+/// The TT move and killers are hints: they are verified against the position and skipped if
+/// illegal, so stale entries are harmless.
+pub struct MovePicker<'a, const N: usize, const STM: Color, Z: ZobristPolicy> {
+    position: &'a Position<N, STM, Z>,
+    tt_move: Option<Move>,
+    killers: [Option<Move>; 2],
+    stage: MovePickerStage,
+    skip_quiets: bool,
+    moves: MoveList,
+    scores: [i32; 256],
+    index: usize,
+}
+
+/// Most-valuable-victim, least-valuable-attacker score of a capture or promotion.
+fn mvv_lva(board: &Board, move_: Move) -> i32 {
+    let victim = match move_.flag() {
+        MoveFlag::EnPassant => Piece::Pawn,
+        _ => board.piece_at(move_.to()),
+    };
+    let promotion = match move_.flag() {
+        MoveFlag::Promotion => move_.promotion() as i32,
+        _ => 0,
+    };
+    (victim as i32 + promotion) * 8 - board.piece_at(move_.from()) as i32
+}
+
+impl<'a, const N: usize, const STM: Color, Z: ZobristPolicy> MovePicker<'a, N, STM, Z> {
+    /// Creates a picker over the legal moves of `position`.
+    pub fn new(
+        position: &'a Position<N, STM, Z>,
+        tt_move: Option<Move>,
+        killers: [Option<Move>; 2],
+    ) -> Self {
+        MovePicker {
+            position,
+            tt_move,
+            killers,
+            stage: MovePickerStage::TtMove,
+            skip_quiets: false,
+            moves: MoveList::new(),
+            scores: [0; 256],
+            index: 0,
+        }
+    }
+
+    /// The stage of the most recently yielded move.
+    pub fn stage(&self) -> MovePickerStage {
+        self.stage
+    }
+
+    /// Stops yielding killers and quiet moves (e.g. in quiescence search or once move-count
+    /// pruning kicks in).
+    pub fn skip_quiets(&mut self) {
+        self.skip_quiets = true;
+    }
+
+    /// Whether `move_` is legal and belongs to the requested categories.
+    fn is_legal(&self, move_: Move, noisy: bool, quiet: bool) -> bool {
+        let mut moves = MoveList::new();
+        self.position
+            .generate_filtered_moves(&mut moves, move_.from().mask(), !0, noisy, quiet);
+        moves.iter().any(|&m| m == move_)
+    }
+
+    fn is_hint(&self, move_: Move) -> bool {
+        self.tt_move == Some(move_) || self.killers.contains(&Some(move_))
+    }
+
+    fn enter(&mut self, stage: MovePickerStage) {
+        self.stage = stage;
+        self.moves.clear();
+        self.index = 0;
+        match stage {
+            MovePickerStage::Captures => {
+                self.position.generate_captures(&mut self.moves);
+                for (score, &move_) in self.scores.iter_mut().zip(self.moves.iter()) {
+                    *score = mvv_lva(&self.position.board, move_);
+                }
+            }
+            MovePickerStage::Killers => {
+                for killer in self.killers.into_iter().flatten() {
+                    if Some(killer) != self.tt_move
+                        && !self.moves.iter().any(|&m| m == killer)
+                        && self.is_legal(killer, false, true)
+                    {
+                        self.moves.push(killer);
+                    }
+                }
+            }
+            MovePickerStage::Quiets => self.position.generate_quiets(&mut self.moves),
+            MovePickerStage::TtMove | MovePickerStage::Done => {}
+        }
+    }
+
+    /// Removes and returns the highest-scored remaining capture (selection sort, so only
+    /// the captures actually searched get sorted).
+    fn pick_best_capture(&mut self) -> Option<Move> {
+        let remaining = self.index..self.moves.len();
+        let best = remaining.max_by_key(|&i| self.scores[i])?;
+        self.moves.as_mut_slice().swap(self.index, best);
+        self.scores.swap(self.index, best);
+        self.index += 1;
+        Some(self.moves.as_slice()[self.index - 1])
+    }
+}
+
+impl<const N: usize, const STM: Color, Z: ZobristPolicy> Iterator for MovePicker<'_, N, STM, Z> {
+    type Item = Move;
+
+    fn next(&mut self) -> Option<Move> {
+        loop {
+            match self.stage {
+                MovePickerStage::TtMove => {
+                    if self.index == 0 {
+                        self.index = 1;
+                        if let Some(tt_move) = self.tt_move
+                            && self.is_legal(tt_move, true, true)
+                        {
+                            return Some(tt_move);
+                        }
+                    }
+                    self.enter(MovePickerStage::Captures);
+                }
+                MovePickerStage::Captures => {
+                    while let Some(move_) = self.pick_best_capture() {
+                        if Some(move_) != self.tt_move {
+                            return Some(move_);
+                        }
+                    }
+                    self.enter(match self.skip_quiets {
+                        true => MovePickerStage::Done,
+                        false => MovePickerStage::Killers,
+                    });
+                }
+                MovePickerStage::Killers | MovePickerStage::Quiets if self.skip_quiets => {
+                    self.enter(MovePickerStage::Done);
+                }
+                MovePickerStage::Killers => {
+                    if self.index < self.moves.len() {
+                        self.index += 1;
+                        return Some(self.moves.as_slice()[self.index - 1]);
+                    }
+                    self.enter(MovePickerStage::Quiets);
+                }
+                MovePickerStage::Quiets => {
+                    while self.index < self.moves.len() {
+                        let move_ = self.moves.as_slice()[self.index];
+                        self.index += 1;
+                        if !self.is_hint(move_) {
+                            return Some(move_);
+                        }
+                    }
+                    self.enter(MovePickerStage::Done);
+                }
+                MovePickerStage::Done => return None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::types::Square;
+
+    #[test]
+    fn test_move_picker_yields_each_legal_move_once_in_stages() {
+        let position = Position::<1, { Color::White }>::from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        )
+        .unwrap();
+        let mut legal = MoveList::new();
+        position.generate_moves(&mut legal);
+        let legal: HashSet<Move> = legal.iter().copied().collect();
+
+        let tt_move = Move::new_non_promotion(Square::E1, Square::G1, MoveFlag::Castling);
+        let killer = Move::new_non_promotion(Square::A2, Square::A3, MoveFlag::NormalMove);
+        let illegal = Move::new_non_promotion(Square::A2, Square::A5, MoveFlag::NormalMove);
+        let capture = Move::new_non_promotion(Square::F3, Square::F6, MoveFlag::NormalMove);
+
+        let mut picker = MovePicker::new(&position, Some(tt_move), [Some(killer), Some(illegal)]);
+        let mut yielded = Vec::new();
+        let mut stages = Vec::new();
+        while let Some(move_) = picker.next() {
+            yielded.push(move_);
+            stages.push(picker.stage());
+        }
+
+        assert_eq!(yielded.len(), legal.len());
+        assert_eq!(yielded.iter().copied().collect::<HashSet<_>>(), legal);
+        assert_eq!(yielded[0], tt_move);
+        assert!(stages.is_sorted());
+        assert_eq!(stages[1], MovePickerStage::Captures);
+        // Queen takes knight is ordered before queen takes pawn.
+        let capture_index = yielded.iter().position(|&m| m == capture).unwrap();
+        let killer_index = yielded.iter().position(|&m| m == killer).unwrap();
+        assert_eq!(stages[capture_index], MovePickerStage::Captures);
+        assert!(
+            yielded[1..capture_index]
+                .iter()
+                .all(|m| position.board.piece_at(m.to()) != Piece::Pawn)
+        );
+        assert_eq!(stages[killer_index], MovePickerStage::Killers);
+    }
+
+    #[test]
+    fn test_move_picker_skip_quiets() {
+        let position = Position::<1, { Color::White }>::initial();
+        let mut picker = MovePicker::new(&position, None, [None, None]);
+        picker.skip_quiets();
+        assert_eq!(picker.next(), None);
+        assert_eq!(picker.stage(), MovePickerStage::Done);
+    }
+}
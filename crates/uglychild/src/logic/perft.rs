@@ -1,9 +1,16 @@
 //! Perft (performance test) helpers for move-generation validation.
 //!
 //! [`crate::types::Position::perft`] performs in-place make/unmake traversal and returns
-//! the number of leaf nodes at a given depth.
+//! the number of leaf nodes at a given depth. [`crate::types::Position::perft_hashed`] caches
+//! subtree counts in a transposition table keyed by Zobrist hash and depth.
+//! [`crate::types::Position::perft_stats`] also tallies the kinds of leaf moves.
 
-use crate::types::{Color, MoveList, Position, ZobristPolicy};
+use std::ops::AddAssign;
+
+use crate::{
+    logic::make_move::castling_rook_to_square,
+    types::{Color, MoveFlag, MoveList, Position, WithZobrist, ZobristPolicy},
+};
 
 fn count_nodes<const N: usize, const STM: Color, Z: ZobristPolicy>(
     position: &mut Position<N, STM, Z>,
@@ -45,3 +52,201 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
         count_nodes(self, depth)
     }
 }
+
+/// Leaf move tallies from [`Position::perft_stats`], in the columns of the chessprogramming.org
+/// perft tables.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PerftStats {
+    /// Leaf nodes, as returned by [`Position::perft`].
+    pub nodes: u64,
+    /// Captures, including en passant.
+    pub captures: u64,
+    /// En passant captures.
+    pub en_passants: u64,
+    /// Castling moves.
+    pub castles: u64,
+    /// Promotions, counting each promotion piece separately.
+    pub promotions: u64,
+    /// Moves giving check.
+    pub checks: u64,
+    /// Single checks given by a piece other than the moved one (or the castled rook).
+    pub discovery_checks: u64,
+    /// Checks given by two pieces at once.
+    pub double_checks: u64,
+    /// Moves giving checkmate.
+    pub checkmates: u64,
+}
+
+impl AddAssign for PerftStats {
+    fn add_assign(&mut self, other: PerftStats) {
+        self.nodes += other.nodes;
+        self.captures += other.captures;
+        self.en_passants += other.en_passants;
+        self.castles += other.castles;
+        self.promotions += other.promotions;
+        self.checks += other.checks;
+        self.discovery_checks += other.discovery_checks;
+        self.double_checks += other.double_checks;
+        self.checkmates += other.checkmates;
+    }
+}
+
+fn count_stats<const N: usize, const STM: Color, Z: ZobristPolicy>(
+    position: &mut Position<N, STM, Z>,
+    depth: u8,
+) -> PerftStats {
+    if depth == 0 {
+        return PerftStats {
+            nodes: 1,
+            ..PerftStats::default()
+        };
+    }
+    let mut moves = MoveList::new();
+    position.generate_moves(&mut moves);
+
+    let mut stats = PerftStats::default();
+    for &move_ in moves.as_slice() {
+        let mut leaf = PerftStats::default();
+        if depth == 1 {
+            leaf.nodes = 1;
+            leaf.captures = move_.is_capture(position) as u64;
+            let mut moved_mask = move_.to().mask();
+            match move_.flag() {
+                MoveFlag::NormalMove => {}
+                MoveFlag::Promotion => leaf.promotions = 1,
+                MoveFlag::EnPassant => leaf.en_passants = 1,
+                MoveFlag::Castling => {
+                    leaf.castles = 1;
+                    moved_mask |= castling_rook_to_square(move_.to().file().flank(), STM).mask();
+                }
+            }
+            position.make_move(move_);
+            let checkers = position.context().checkers;
+            if checkers != 0 {
+                leaf.checks = 1;
+                let is_double = checkers.count_ones() > 1;
+                leaf.discovery_checks = (!is_double && checkers & !moved_mask != 0) as u64;
+                leaf.double_checks = is_double as u64;
+            }
+        } else {
+            position.make_move(move_);
+        }
+        match STM {
+            Color::White => {
+                let child = unsafe { position.rebrand_stm_mut::<{ Color::Black }>() };
+                match depth {
+                    1 => leaf.checkmates = (leaf.checks == 1 && !child.has_legal_moves()) as u64,
+                    _ => leaf = count_stats(child, depth - 1),
+                }
+                child.unmake_move(move_);
+            }
+            Color::Black => {
+                let child = unsafe { position.rebrand_stm_mut::<{ Color::White }>() };
+                match depth {
+                    1 => leaf.checkmates = (leaf.checks == 1 && !child.has_legal_moves()) as u64,
+                    _ => leaf = count_stats(child, depth - 1),
+                }
+                child.unmake_move(move_);
+            }
+        }
+        stats += leaf;
+    }
+    stats
+}
+
+impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
+    /// Like [`Self::perft`], but also tallies captures, en passant, castling, promotions,
+    /// checks and checkmates among the moves at the last ply.
+    ///
+    /// Every leaf move is made, so this is much slower than [`Self::perft`]; it is meant for
+    /// localizing move generation bugs against published tables.
+    pub fn perft_stats(&mut self, depth: u8) -> PerftStats {
+        count_stats(self, depth)
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct PerftEntry {
+    key: u64,
+    depth: u8,
+    nodes: u64,
+}
+
+/// Fixed-size, always-replace cache of subtree node counts.
+struct PerftTable {
+    entries: Vec<PerftEntry>,
+    index_mask: usize,
+}
+
+impl PerftTable {
+    fn with_size_mb(hash_mb: usize) -> PerftTable {
+        let max_entries = (hash_mb * 1024 * 1024 / size_of::<PerftEntry>()).max(1);
+        // Round down to a power of two so that indexing is a mask.
+        let num_entries = 1usize << (usize::BITS - 1 - max_entries.leading_zeros());
+        PerftTable {
+            entries: vec![PerftEntry::default(); num_entries],
+            index_mask: num_entries - 1,
+        }
+    }
+
+    fn probe(&self, key: u64, depth: u8) -> Option<u64> {
+        let entry = &self.entries[key as usize & self.index_mask];
+        // `nodes == 0` marks an empty slot; real subtrees at depth >= 2 can also count zero,
+        // which only costs a recomputation.
+        (entry.key == key && entry.depth == depth && entry.nodes != 0).then_some(entry.nodes)
+    }
+
+    fn store(&mut self, key: u64, depth: u8, nodes: u64) {
+        self.entries[key as usize & self.index_mask] = PerftEntry { key, depth, nodes };
+    }
+}
+
+fn count_nodes_hashed<const N: usize, const STM: Color>(
+    position: &mut Position<N, STM, WithZobrist>,
+    depth: u8,
+    table: &mut PerftTable,
+) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    if depth == 1 {
+        return position.count_legal_moves() as u64;
+    }
+    let key = position.context().zobrist_hash();
+    if let Some(nodes) = table.probe(key, depth) {
+        return nodes;
+    }
+
+    let mut moves = MoveList::new();
+    position.generate_moves(&mut moves);
+
+    let mut total = 0u64;
+    for &move_ in moves.as_slice() {
+        position.make_move(move_);
+        match STM {
+            Color::White => {
+                let child = unsafe { position.rebrand_stm_mut::<{ Color::Black }>() };
+                total += count_nodes_hashed(child, depth - 1, table);
+                child.unmake_move(move_);
+            }
+            Color::Black => {
+                let child = unsafe { position.rebrand_stm_mut::<{ Color::White }>() };
+                total += count_nodes_hashed(child, depth - 1, table);
+                child.unmake_move(move_);
+            }
+        }
+    }
+    table.store(key, depth, total);
+    total
+}
+
+impl<const N: usize, const STM: Color> Position<N, STM, WithZobrist> {
+    /// Like [`Position::perft`], but caches subtree counts in a `hash_mb` megabyte table.
+    ///
+    /// Entries are keyed by the Zobrist hash and remaining depth alone, so a
+    /// mismatch against [`Position::perft`] also points at hashing bugs.
+    pub fn perft_hashed(&mut self, depth: u8, hash_mb: usize) -> u64 {
+        let mut table = PerftTable::with_size_mb(hash_mb);
+        count_nodes_hashed(self, depth, &mut table)
+    }
+}
@@ -0,0 +1,308 @@
+//! Differential perft against an external UCI engine, for locating move-generation bugs.
+//!
+//! [`find_perft_divergence`] compares this crate's divide counts with the engine's `go perft`
+//! output and descends into the first move whose subtree counts disagree, until it reaches a
+//! position where the two sides disagree on the legal moves themselves.
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    ffi::OsStr,
+    fmt,
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+use crate::{
+    logic::fen::FenParseError,
+    types::{Color, MoveList, TypedPosition},
+};
+
+/// Context stack capacity of the positions divided here; bounds the supported perft depth.
+const CONTEXTS_CAPACITY: usize = 64;
+
+/// Per-move subtree node counts, keyed by UCI move string.
+pub type PerftDivide = BTreeMap<String, u64>;
+
+/// Errors from a differential perft run.
+#[derive(Debug)]
+pub enum PerftDiffError {
+    /// The root FEN could not be parsed.
+    Fen(FenParseError),
+    /// Talking to the engine process failed.
+    Io(io::Error),
+    /// The engine closed its output before finishing a reply.
+    EngineExited,
+    /// The requested depth does not fit the position's context stack.
+    DepthTooLarge(u8),
+}
+
+impl fmt::Display for PerftDiffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PerftDiffError::Fen(_) => write!(f, "invalid root FEN"),
+            PerftDiffError::Io(_) => write!(f, "engine I/O failed"),
+            PerftDiffError::EngineExited => write!(f, "engine exited mid-reply"),
+            PerftDiffError::DepthTooLarge(depth) => write!(f, "perft depth {depth} is too large"),
+        }
+    }
+}
+
+impl Error for PerftDiffError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PerftDiffError::Fen(err) => Some(err),
+            PerftDiffError::Io(err) => Some(err),
+            PerftDiffError::EngineExited | PerftDiffError::DepthTooLarge(_) => None,
+        }
+    }
+}
+
+impl From<FenParseError> for PerftDiffError {
+    fn from(err: FenParseError) -> Self {
+        PerftDiffError::Fen(err)
+    }
+}
+
+impl From<io::Error> for PerftDiffError {
+    fn from(err: io::Error) -> Self {
+        PerftDiffError::Io(err)
+    }
+}
+
+/// A UCI engine process that supports the `go perft` extension (e.g. Stockfish).
+pub struct UciPerftEngine {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl UciPerftEngine {
+    /// Starts the engine at `path` and completes the `uci` handshake.
+    pub fn spawn(path: impl AsRef<OsStr>) -> Result<Self, PerftDiffError> {
+        let mut child = Command::new(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("piped stdin");
+        let stdout = BufReader::new(child.stdout.take().expect("piped stdout"));
+        let mut engine = UciPerftEngine {
+            child,
+            stdin,
+            stdout,
+        };
+        engine.send("uci")?;
+        while engine.read_line()? != "uciok" {}
+        Ok(engine)
+    }
+
+    /// Runs `go perft depth` on `fen` after playing `moves` (UCI strings) and returns the
+    /// engine's divide counts.
+    pub fn perft_divide(
+        &mut self,
+        fen: &str,
+        moves: &[String],
+        depth: u8,
+    ) -> Result<PerftDivide, PerftDiffError> {
+        match moves {
+            [] => self.send(&format!("position fen {fen}"))?,
+            _ => self.send(&format!("position fen {fen} moves {}", moves.join(" ")))?,
+        }
+        self.send(&format!("go perft {depth}"))?;
+
+        let mut divide = PerftDivide::new();
+        loop {
+            let line = self.read_line()?;
+            if line.starts_with("Nodes searched") {
+                return Ok(divide);
+            }
+            if let Some((move_, nodes)) = parse_divide_line(&line) {
+                divide.insert(move_, nodes);
+            }
+        }
+    }
+
+    fn send(&mut self, command: &str) -> io::Result<()> {
+        writeln!(self.stdin, "{command}")?;
+        self.stdin.flush()
+    }
+
+    fn read_line(&mut self) -> Result<String, PerftDiffError> {
+        let mut line = String::new();
+        match self.stdout.read_line(&mut line)? {
+            0 => Err(PerftDiffError::EngineExited),
+            _ => Ok(line.trim().to_string()),
+        }
+    }
+}
+
+impl Drop for UciPerftEngine {
+    fn drop(&mut self) {
+        let _ = self.send("quit");
+        let _ = self.child.wait();
+    }
+}
+
+/// Parses a divide line such as `e2e4: 20`.
+fn parse_divide_line(line: &str) -> Option<(String, u64)> {
+    let (move_, nodes) = line.split_once(':')?;
+    let move_ = move_.trim();
+    if !matches!(move_.len(), 4 | 5) || !move_.is_ascii() {
+        return None;
+    }
+    Some((move_.to_string(), nodes.trim().parse().ok()?))
+}
+
+/// This crate's divide counts for `fen` after playing `moves` (UCI strings).
+///
+/// Returns `Ok(None)` if one of `moves` is not legal here.
+pub fn perft_divide(
+    fen: &str,
+    moves: &[String],
+    depth: u8,
+) -> Result<Option<PerftDivide>, PerftDiffError> {
+    if moves.len() + depth as usize >= CONTEXTS_CAPACITY {
+        return Err(PerftDiffError::DepthTooLarge(depth));
+    }
+    let mut position = TypedPosition::<CONTEXTS_CAPACITY>::from_fen(fen)?;
+    for uci in moves {
+        let Some(next) = play_uci(position, uci) else {
+            return Ok(None);
+        };
+        position = next;
+    }
+
+    let mut divide = PerftDivide::new();
+    let moves = legal_moves(&position);
+    for &move_ in moves.as_slice() {
+        let nodes = match position.clone() {
+            TypedPosition::White(mut p) => {
+                p.make_move(move_);
+                p.rebrand_stm::<{ Color::Black }>()
+                    .perft(depth.saturating_sub(1))
+            }
+            TypedPosition::Black(mut p) => {
+                p.make_move(move_);
+                p.rebrand_stm::<{ Color::White }>()
+                    .perft(depth.saturating_sub(1))
+            }
+        };
+        divide.insert(move_.uci(), nodes);
+    }
+    Ok(Some(divide))
+}
+
+fn legal_moves(position: &TypedPosition<CONTEXTS_CAPACITY>) -> MoveList {
+    let mut moves = MoveList::new();
+    match position {
+        TypedPosition::White(p) => p.generate_moves(&mut moves),
+        TypedPosition::Black(p) => p.generate_moves(&mut moves),
+    }
+    moves
+}
+
+fn play_uci(
+    position: TypedPosition<CONTEXTS_CAPACITY>,
+    uci: &str,
+) -> Option<TypedPosition<CONTEXTS_CAPACITY>> {
+    let moves = legal_moves(&position);
+    let move_ = *moves.iter().find(|move_| move_.uci() == uci)?;
+    Some(match position {
+        TypedPosition::White(mut p) => {
+            p.make_move(move_);
+            TypedPosition::Black(p.rebrand_stm())
+        }
+        TypedPosition::Black(mut p) => {
+            p.make_move(move_);
+            TypedPosition::White(p.rebrand_stm())
+        }
+    })
+}
+
+/// Where this crate and the engine first disagree.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PerftDivergence {
+    /// Moves (UCI) from the root to the position where the move lists differ.
+    pub path: Vec<String>,
+    /// Legal moves the engine generates but this crate does not.
+    pub missing: Vec<String>,
+    /// Moves this crate generates that the engine does not.
+    pub extra: Vec<String>,
+}
+
+/// Descends from `fen` towards the first divergence between this crate's perft and `engine`'s.
+///
+/// Returns `Ok(None)` if the divide counts agree at `depth`. Otherwise follows the first move
+/// whose subtree counts differ until the move lists themselves differ, and reports that path.
+pub fn find_perft_divergence(
+    engine: &mut UciPerftEngine,
+    fen: &str,
+    depth: u8,
+) -> Result<Option<PerftDivergence>, PerftDiffError> {
+    let mut path = Vec::new();
+    for remaining_depth in (1..=depth).rev() {
+        let ours = perft_divide(fen, &path, remaining_depth)?
+            .expect("path only contains moves legal for this crate");
+        let theirs = engine.perft_divide(fen, &path, remaining_depth)?;
+
+        let missing: Vec<String> = theirs
+            .keys()
+            .filter(|move_| !ours.contains_key(*move_))
+            .cloned()
+            .collect();
+        let extra: Vec<String> = ours
+            .keys()
+            .filter(|move_| !theirs.contains_key(*move_))
+            .cloned()
+            .collect();
+        if !missing.is_empty() || !extra.is_empty() {
+            return Ok(Some(PerftDivergence {
+                path,
+                missing,
+                extra,
+            }));
+        }
+
+        match ours.iter().find(|(move_, nodes)| theirs[*move_] != **nodes) {
+            Some((move_, _)) => path.push(move_.clone()),
+            None => return Ok(None),
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::fen::INITIAL_FEN;
+
+    #[test]
+    fn test_parse_divide_line() {
+        assert_eq!(
+            parse_divide_line("e2e4: 20"),
+            Some(("e2e4".to_string(), 20))
+        );
+        assert_eq!(
+            parse_divide_line("a7a8q: 1"),
+            Some(("a7a8q".to_string(), 1))
+        );
+        assert_eq!(parse_divide_line("info string NNUE enabled"), None);
+        assert_eq!(parse_divide_line("Nodes searched: 400"), None);
+    }
+
+    #[test]
+    fn test_perft_divide() {
+        let divide = perft_divide(INITIAL_FEN, &[], 2).unwrap().unwrap();
+        assert_eq!(divide.len(), 20);
+        assert_eq!(divide.values().sum::<u64>(), 400);
+
+        let divide = perft_divide(INITIAL_FEN, &["e2e4".to_string()], 1)
+            .unwrap()
+            .unwrap();
+        assert_eq!(divide.len(), 20);
+        assert_eq!(
+            perft_divide(INITIAL_FEN, &["e2e5".to_string()], 1).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Neural-network input encoding of a [`crate::types::Position`] as feature planes.
+//!
+//! Every plane is 64 cells in [`crate::types::Square`] order: index `0` is A8, `7` is H8, `56` is A1 and `63`
+//! is H1 (rank 8 to rank 1, file A to file H within a rank). The board is never flipped for the
+//! side to move. In packed form a plane is a [`Bitboard`], where index `i` is bit `63 - i`
+//! (i.e. `square.mask()`).
+//!
+//! Plane layout:
+//!
+//! | Planes    | Contents                                                        |
+//! |-----------|-----------------------------------------------------------------|
+//! | `0..6`    | White pawn, knight, bishop, rook, queen, king                   |
+//! | `6..12`   | Black pawn, knight, bishop, rook, queen, king                   |
+//! | `12`      | Side to move: all ones when Black is to move                    |
+//! | `13..17`  | Castling rights K, Q, k, q: all ones when the right is held     |
+//! | `17`      | En-passant target square (single one, or empty)                 |
+//! | `18`      | Halfmove clock in every cell (`u8` form only)                   |
+
+use crate::{
+    types::{
+        Bitboard,
+        BitboardUtils,
+        Color,
+        ColoredPiece,
+        ConstDoublePawnPushFile,
+        Flank,
+        Piece,
+        Position,
+        ZobristPolicy,
+    },
+    utilities::IterableEnum,
+};
+
+/// Number of binary planes (pieces, side to move, castling, en passant).
+pub const NUM_BINARY_PLANES: usize = 18;
+/// Total number of planes in the `u8` encoding (binary planes plus the halfmove clock).
+pub const NUM_PLANES: usize = NUM_BINARY_PLANES + 1;
+
+/// Index of the piece plane for `color`'s pieces of type `piece`.
+pub const fn piece_plane_index(color: Color, piece: Piece) -> usize {
+    ColoredPiece::new(color, piece).index()
+}
+
+/// Index of the side-to-move plane.
+pub const SIDE_TO_MOVE_PLANE: usize = 12;
+/// Index of the first castling-rights plane (K, Q, k, q follow in that order).
+pub const CASTLING_PLANES_START: usize = 13;
+/// Index of the en-passant target plane.
+pub const EN_PASSANT_PLANE: usize = 17;
+/// Index of the halfmove-clock plane (`u8` encoding only).
+pub const HALFMOVE_CLOCK_PLANE: usize = 18;
+
+/// Bitboard form of the feature planes; see the [module docs](self) for the layout.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PackedPlanes {
+    /// The binary planes, indexed like the `u8` encoding.
+    pub planes: [Bitboard; NUM_BINARY_PLANES],
+    /// Halfmove clock (not representable as a bitboard).
+    pub halfmove_clock: u8,
+}
+
+impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
+    /// Encodes the position as packed bitboard planes; see the [module docs](self) for the layout.
+    pub fn to_packed_planes(&self) -> PackedPlanes {
+        let mut planes = [0; NUM_BINARY_PLANES];
+
+        for color in Color::ALL {
+            let color_mask = self.board.color_mask_at(color);
+            for piece in Piece::PIECES {
+                planes[piece_plane_index(color, piece)] =
+                    self.board.piece_mask_at(piece) & color_mask;
+            }
+        }
+
+        if STM == Color::Black {
+            planes[SIDE_TO_MOVE_PLANE] = Bitboard::MAX;
+        }
+
+        let context = self.context();
+        for (i, (color, flank)) in [
+            (Color::White, Flank::Kingside),
+            (Color::White, Flank::Queenside),
+            (Color::Black, Flank::Kingside),
+            (Color::Black, Flank::Queenside),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            if context.castling_rights.has(flank, color) {
+                planes[CASTLING_PLANES_START + i] = Bitboard::MAX;
+            }
+        }
+
+        if context.double_pawn_push_file.has_file() {
+            planes[EN_PASSANT_PLANE] = context.double_pawn_push_file.ep_dst_square(STM).mask();
+        }
+
+        PackedPlanes {
+            planes,
+            halfmove_clock: context.halfmove_clock,
+        }
+    }
+
+    /// Encodes the position as `NUM_PLANES` planes of 64 bytes each, plane-major.
+    ///
+    /// Cell `plane * 64 + square as usize` holds `0`/`1` for binary planes and the halfmove
+    /// clock for [`HALFMOVE_CLOCK_PLANE`]; see the [module docs](self) for the layout.
+    pub fn to_planes(&self) -> [u8; 64 * NUM_PLANES] {
+        let packed = self.to_packed_planes();
+        let mut out = [0; 64 * NUM_PLANES];
+
+        for (plane, mask) in packed.planes.into_iter().enumerate() {
+            for square in mask.iter_set_bits_as_squares() {
+                out[plane * 64 + square as usize] = 1;
+            }
+        }
+
+        out[HALFMOVE_CLOCK_PLANE * 64..].fill(packed.halfmove_clock);
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Rank, Square};
+
+    #[test]
+    fn test_initial_position_planes() {
+        let position = Position::<1, { Color::White }>::initial();
+        let packed = position.to_packed_planes();
+
+        assert_eq!(
+            packed.planes[piece_plane_index(Color::White, Piece::Pawn)],
+            Rank::Two.mask()
+        );
+        assert_eq!(
+            packed.planes[piece_plane_index(Color::Black, Piece::King)],
+            Square::E8.mask()
+        );
+        assert_eq!(packed.planes[SIDE_TO_MOVE_PLANE], 0);
+        for i in 0..4 {
+            assert_eq!(packed.planes[CASTLING_PLANES_START + i], Bitboard::MAX);
+        }
+        assert_eq!(packed.planes[EN_PASSANT_PLANE], 0);
+
+        let planes = position.to_planes();
+        let black_pawns = piece_plane_index(Color::Black, Piece::Pawn) * 64;
+        assert_eq!(planes[black_pawns + Square::A7 as usize], 1);
+        assert_eq!(planes[black_pawns + Square::A2 as usize], 0);
+        assert!(
+            planes[HALFMOVE_CLOCK_PLANE * 64..]
+                .iter()
+                .all(|&cell| cell == 0)
+        );
+    }
+
+    #[test]
+    fn test_side_to_move_en_passant_and_clock_planes() {
+        let position = Position::<1, { Color::Black }>::from_fen(
+            "rnbqkbnr/pppppppp/8/8/3pP3/8/PPPP1PPP/RNBQK2R b Kkq e3 0 1",
+        )
+        .unwrap();
+        let packed = position.to_packed_planes();
+        assert_eq!(packed.planes[SIDE_TO_MOVE_PLANE], Bitboard::MAX);
+        assert_eq!(packed.planes[CASTLING_PLANES_START + 1], 0);
+        assert_eq!(packed.planes[EN_PASSANT_PLANE], Square::E3.mask());
+
+        let position =
+            Position::<1, { Color::White }>::from_fen("8/8/8/4k3/8/8/8/4K3 w - - 42 80").unwrap();
+        let planes = position.to_planes();
+        assert_eq!(position.to_packed_planes().halfmove_clock, 42);
+        assert!(
+            planes[HALFMOVE_CLOCK_PLANE * 64..]
+                .iter()
+                .all(|&cell| cell == 42)
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! Repetition counting over the context stack, by stored hash or by FIDE position identity.
+
+use crate::{
+    logic::zobrist_hash::has_en_passant_capturer,
+    types::{
+        Color,
+        ConstDoublePawnPushFile,
+        DoublePawnPushFile,
+        MoveFlag,
+        MoveList,
+        Position,
+        ZobristPolicy,
+    },
+};
+
+/// How earlier positions are matched against the current one when counting repetitions.
+///
+/// Both rules compare the stored hash, which covers every piece with its color and square, the
+/// side to move, castling rights and the en passant file, so positions differing in castling
+/// rights never match, as FIDE requires. They differ only in how the en passant file counts.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum RepetitionRule {
+    /// Match hashes exactly. The en passant file is hashed after every double pawn push, so the
+    /// position right after one never matches a later recurrence, even if no capture was
+    /// possible.
+    Hash,
+    /// FIDE Article 9.2: the en passant right only distinguishes positions when an en passant
+    /// capture is legal, so a double pawn push that cannot be taken still counts toward
+    /// repetitions.
+    Fide,
+}
+
+impl<const N: usize, const STM: Color, Z: ZobristPolicy<HashState = u64>> Position<N, STM, Z> {
+    /// Number of earlier occurrences of the current position under `rule`.
+    ///
+    /// Only positions since the last capture or pawn move are examined, and only those still on
+    /// the context stack (see [`Self::truncate_history`]). Matching is by the 64-bit hash of
+    /// the hashing policy `Z`; with [`crate::types::WithZobrist`] a false match needs a hash
+    /// collision.
+    pub fn repetition_count(&self, rule: RepetitionRule) -> usize {
+        let contexts = self.context_slice();
+        let current = self.context();
+        let reversible = (current.halfmove_clock as usize).min(contexts.len() - 1);
+        contexts[contexts.len() - 1 - reversible..contexts.len() - 1]
+            .iter()
+            .rev()
+            .skip(1)
+            .step_by(2)
+            .filter(|earlier| {
+                if earlier.zobrist_hash == current.zobrist_hash {
+                    return true;
+                }
+                // Within the reversible window, only its first position (right after the
+                // double pawn push) can have an en passant file, and the current one cannot.
+                let file = earlier.double_pawn_push_file;
+                if rule == RepetitionRule::Hash || !file.has_file() {
+                    return false;
+                }
+                let mut without_file = earlier.zobrist_hash;
+                Z::on_double_pawn_push_file_change(
+                    &mut without_file,
+                    file,
+                    has_en_passant_capturer(&self.board, file, STM),
+                    DoublePawnPushFile::NONE,
+                    false,
+                );
+                // The boards match, so the capture is judged on the current board.
+                without_file == current.zobrist_hash && !self.has_legal_en_passant(file)
+            })
+            .count()
+    }
+
+    /// Whether the current position has occurred at least twice before under `rule`, so a
+    /// draw may be claimed by threefold repetition.
+    pub fn is_threefold_repetition(&self, rule: RepetitionRule) -> bool {
+        self.repetition_count(rule) >= 2
+    }
+
+    /// Whether the side to move could legally capture en passant on `file` in this placement.
+    fn has_legal_en_passant(&self, file: DoublePawnPushFile) -> bool {
+        let mut position = self.clone_current::<1>();
+        position.mut_context().double_pawn_push_file = file;
+        let mut moves = MoveList::new();
+        position.generate_moves(&mut moves);
+        moves
+            .as_slice()
+            .iter()
+            .any(|move_| move_.flag() == MoveFlag::EnPassant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Move, Square};
+
+    /// Plays the knight shuffle Nf6 Nf3 Ng8 Ng1 from a position with Black to move.
+    fn shuffle_knights(position: Position<16, { Color::Black }>) -> Position<16, { Color::Black }> {
+        let step = |from, to| Move::new_non_promotion(from, to, MoveFlag::NormalMove);
+        let (position, _) = position.make_move_owned(step(Square::G8, Square::F6));
+        let (position, _) = position.make_move_owned(step(Square::G1, Square::F3));
+        let (position, _) = position.make_move_owned(step(Square::F6, Square::G8));
+        let (position, _) = position.make_move_owned(step(Square::F3, Square::G1));
+        position
+    }
+
+    #[test]
+    fn test_repetition_count_by_rule() {
+        // No black pawn can take on e3, so FIDE treats the en passant right as absent.
+        let after_e4 = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
+        let position = Position::<16, { Color::Black }>::from_fen(after_e4).unwrap();
+        let once = shuffle_knights(position);
+        assert_eq!(once.repetition_count(RepetitionRule::Hash), 0);
+        assert_eq!(once.repetition_count(RepetitionRule::Fide), 1);
+        let twice = shuffle_knights(once);
+        assert_eq!(twice.repetition_count(RepetitionRule::Hash), 1);
+        assert!(twice.is_threefold_repetition(RepetitionRule::Fide));
+        assert!(!twice.is_threefold_repetition(RepetitionRule::Hash));
+
+        // Here dxe3 is legal, so the first position really differs.
+        let capturable = "rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 3";
+        let position = Position::<16, { Color::Black }>::from_fen(capturable).unwrap();
+        let once = shuffle_knights(position);
+        assert_eq!(once.repetition_count(RepetitionRule::Fide), 0);
+        assert_eq!(
+            shuffle_knights(once).repetition_count(RepetitionRule::Fide),
+            1
+        );
+    }
+
+    #[test]
+    fn test_piece_colors_break_repetition() {
+        // The rooks trade corners, so the final placement only differs from the first by color.
+        let position =
+            Position::<16, { Color::White }>::from_fen("7r/8/4k3/8/8/4K3/8/R7 w - - 0 1").unwrap();
+        let step = |from, to| Move::new_non_promotion(from, to, MoveFlag::NormalMove);
+        let (position, _) = position.make_move_owned(step(Square::A1, Square::A4));
+        let (position, _) = position.make_move_owned(step(Square::H8, Square::H5));
+        let (position, _) = position.make_move_owned(step(Square::A4, Square::G4));
+        let (position, _) = position.make_move_owned(step(Square::H5, Square::B5));
+        let (position, _) = position.make_move_owned(step(Square::G4, Square::G8));
+        let (position, _) = position.make_move_owned(step(Square::B5, Square::B1));
+        let (position, _) = position.make_move_owned(step(Square::G8, Square::H8));
+        let (position, _) = position.make_move_owned(step(Square::B1, Square::A1));
+        assert_eq!(position.context().halfmove_clock, 8);
+        assert_eq!(position.repetition_count(RepetitionRule::Hash), 0);
+        assert_eq!(position.repetition_count(RepetitionRule::Fide), 0);
+    }
+}
@@ -1,6 +1,8 @@
 //! Standard Algebraic Notation (SAN) rendering for moves.
 
-use crate::types::{File, Move, MoveFlag, Piece};
+use std::fmt;
+
+use crate::types::{Color, File, Move, MoveFlag, MoveList, Piece, Position, ZobristPolicy};
 
 impl Move {
     /// Renders this move in SAN format with full disambiguation and check/mate indicators.
@@ -9,6 +11,9 @@ impl Move {
     /// `disambiguation_str` is the already-computed SAN disambiguator (e.g. `"b"` / `"3"` / `"b3"`).
     /// `is_check` and `is_checkmate` refer to the resulting position.
     /// `is_capture` should reflect board semantics (including en-passant).
+    ///
+    /// The flags are written as given. [`Move::display_with`] works all of them out from the
+    /// position instead, by playing the move on a copy, and is what most callers want.
     pub fn san(
         &self,
         moved_piece: Piece,
@@ -71,3 +76,130 @@ impl Move {
         format!("{}{}", move_str, check_or_checkmate_str)
     }
 }
+
+impl Move {
+    /// Returns an adapter whose [`Display`](fmt::Display) writes this move in SAN as played in
+    /// `position`, with disambiguation and check or mate markers worked out from the position.
+    ///
+    /// The move must be legal in `position`. Without a position, [`Move`]'s own `Display` writes
+    /// UCI text.
+    pub fn display_with<'a, const N: usize, const STM: Color, Z: ZobristPolicy>(
+        &self,
+        position: &'a Position<N, STM, Z>,
+    ) -> MoveSanDisplay<'a, N, STM, Z> {
+        MoveSanDisplay {
+            move_: *self,
+            position,
+        }
+    }
+
+    /// Returns the file, rank, or square that tells this move apart from other legal moves of the
+    /// same piece type to the same square, or an empty string if none is needed.
+    pub fn san_disambiguation<const N: usize, const STM: Color, Z: ZobristPolicy>(
+        &self,
+        position: &Position<N, STM, Z>,
+    ) -> String {
+        let from = self.from();
+        let moved_piece = position.board.piece_at(from);
+        if matches!(moved_piece, Piece::Pawn | Piece::King) {
+            return String::new();
+        }
+
+        let mut legal = MoveList::new();
+        position.generate_moves(&mut legal);
+        let mut is_ambiguous = false;
+        let mut is_file_ambiguous = false;
+        let mut is_rank_ambiguous = false;
+        for other in legal.as_slice().iter() {
+            if *other == *self
+                || other.to() != self.to()
+                || position.board.piece_at(other.from()) != moved_piece
+            {
+                continue;
+            }
+            is_ambiguous = true;
+            is_file_ambiguous |= other.from().file() == from.file();
+            is_rank_ambiguous |= other.from().rank() == from.rank();
+        }
+        match (is_ambiguous, is_file_ambiguous, is_rank_ambiguous) {
+            (false, _, _) => String::new(),
+            (true, true, true) => from.to_string(),
+            (true, true, false) => from.rank_char().to_string(),
+            (true, false, _) => from.file_char().to_string(),
+        }
+    }
+
+    /// Plays this move on a copy of `position` and reports whether it gives check and whether it
+    /// gives mate.
+    pub fn check_status<const N: usize, const STM: Color, Z: ZobristPolicy>(
+        &self,
+        position: &Position<N, STM, Z>,
+    ) -> (bool, bool) {
+        let mut next = position.clone_current::<2>();
+        next.make_move(*self);
+        if next.context().checkers == 0 {
+            return (false, false);
+        }
+        let has_replies = match STM {
+            Color::White => next.rebrand_stm::<{ Color::Black }>().has_legal_moves(),
+            Color::Black => next.rebrand_stm::<{ Color::White }>().has_legal_moves(),
+        };
+        (true, !has_replies)
+    }
+}
+
+/// Writes a move in SAN for a given position; see [`Move::display_with`].
+pub struct MoveSanDisplay<'a, const N: usize, const STM: Color, Z: ZobristPolicy> {
+    move_: Move,
+    position: &'a Position<N, STM, Z>,
+}
+
+impl<const N: usize, const STM: Color, Z: ZobristPolicy> fmt::Display
+    for MoveSanDisplay<'_, N, STM, Z>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let move_ = self.move_;
+        let (is_check, is_checkmate) = move_.check_status(self.position);
+        f.write_str(&move_.san(
+            self.position.board.piece_at(move_.from()),
+            &move_.san_disambiguation(self.position),
+            is_check,
+            is_checkmate,
+            move_.is_capture(self.position),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{Color, Move, MoveFlag, Piece, Position, Square};
+
+    #[test]
+    fn test_display_with_position() {
+        let position =
+            Position::<1, { Color::White }>::from_fen("6k1/1P3ppp/8/8/8/8/5PPP/R3R1K1 w - - 0 1")
+                .unwrap();
+        let rook_move = Move::new_non_promotion(Square::A1, Square::D1, MoveFlag::NormalMove);
+        assert_eq!(rook_move.display_with(&position).to_string(), "Rad1");
+        let mate = Move::new_non_promotion(Square::E1, Square::E8, MoveFlag::NormalMove);
+        assert_eq!(mate.display_with(&position).to_string(), "Re8#");
+        let promotion = Move::new(Square::B7, Square::B8, Piece::Queen, MoveFlag::Promotion);
+        assert_eq!(promotion.display_with(&position).to_string(), "b8=Q#");
+        assert_eq!(promotion.to_string(), "b7b8q");
+
+        let position =
+            Position::<1, { Color::Black }>::from_fen("4k3/8/8/8/1n3n2/8/8/4K3 b - - 0 1").unwrap();
+        let knight_check = Move::new_non_promotion(Square::B4, Square::D3, MoveFlag::NormalMove);
+        assert_eq!(knight_check.display_with(&position).to_string(), "Nbd3+");
+
+        // Only the promoted piece decides whether the capture gives check or mate.
+        let position =
+            Position::<1, { Color::White }>::from_fen("3bk2r/3pppP1/8/8/8/8/8/4K3 w - - 0 1")
+                .unwrap();
+        let promote_to = |piece| Move::new(Square::G7, Square::H8, piece, MoveFlag::Promotion);
+        let san = |piece| promote_to(piece).display_with(&position).to_string();
+        assert_eq!(san(Piece::Queen), "gxh8=Q#");
+        assert_eq!(san(Piece::Bishop), "gxh8=B");
+        assert_eq!(san(Piece::Knight), "gxh8=N");
+    }
+}
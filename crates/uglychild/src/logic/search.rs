@@ -0,0 +1,425 @@
+//! A small reference alpha-beta searcher built only on this crate's public engine-support APIs.
+//!
+//! [`Searcher`] runs iterative deepening over a negamax alpha-beta search with a transposition
+//! table, null-move pruning, killer moves, and a captures-only quiescence search, ordering moves
+//! with [`MovePicker`]. Evaluation is material ([`Piece::value`]) plus [`Position::mobility`].
+//! It is meant as a correct, readable example and an integration test for those APIs, not as a
+//! strong engine.
+
+use crate::{
+    logic::{move_picker::MovePicker, repetition::RepetitionRule},
+    types::{Color, Move, MoveFlag, MoveList, Piece, Position},
+};
+
+/// Score of being checkmated at the root; mate in `n` plies scores `MATE_SCORE - n`.
+pub const MATE_SCORE: i32 = 30_000;
+
+/// Scores beyond this magnitude encode a forced mate.
+const MATE_THRESHOLD: i32 = MATE_SCORE - 1_000;
+
+const INFINITY: i32 = MATE_SCORE + 1;
+
+/// Bonus per square a knight, bishop, rook, or queen can move to.
+const MOBILITY_WEIGHT: i32 = 2;
+
+/// Depth reduction of the null-move search.
+const NULL_MOVE_REDUCTION: u8 = 2;
+
+/// Evaluates `$body` with `$child` bound to `$position` rebranded for the other side to move,
+/// after the caller has made a move or null move on it.
+macro_rules! with_child {
+    ($position:expr, $stm:expr, |$child:ident| $body:expr) => {
+        match $stm {
+            Color::White => {
+                let $child = unsafe { $position.rebrand_stm_mut::<{ Color::Black }>() };
+                $body
+            }
+            Color::Black => {
+                let $child = unsafe { $position.rebrand_stm_mut::<{ Color::White }>() };
+                $body
+            }
+        }
+    };
+}
+
+/// Static evaluation of `position` in centipawns, from the side to move's point of view.
+pub fn evaluate<const N: usize, const STM: Color>(position: &Position<N, STM>) -> i32 {
+    let board = &position.board;
+    let mut score = 0;
+    for color in [Color::White, Color::Black] {
+        let sign = if color == STM { 1 } else { -1 };
+        let own = board.color_mask_at(color);
+        for piece in Piece::PIECES {
+            let count = (board.piece_mask_at(piece) & own).count_ones() as i32;
+            score += sign * count * piece.value();
+        }
+        let [_, knights, bishops, rooks, queens, _] = position.mobility(color);
+        let mobility = (knights.count_ones()
+            + bishops.count_ones()
+            + rooks.count_ones()
+            + queens.count_ones()) as i32;
+        score += sign * mobility * MOBILITY_WEIGHT;
+    }
+    score
+}
+
+/// How a stored score relates to the true score of its position.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Bound {
+    Exact,
+    /// The true score is at least the stored one (a beta cutoff).
+    Lower,
+    /// The true score is at most the stored one (no move raised alpha).
+    Upper,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct TtEntry {
+    key: u64,
+    depth: u8,
+    score: i32,
+    bound: Bound,
+    best_move: Option<Move>,
+}
+
+/// Fixed-size, always-replace transposition table keyed by Zobrist hash.
+pub struct TranspositionTable {
+    entries: Vec<Option<TtEntry>>,
+    index_mask: usize,
+}
+
+impl TranspositionTable {
+    /// Creates a table using about `size_mb` megabytes (at least one entry).
+    pub fn with_size_mb(size_mb: usize) -> TranspositionTable {
+        let max_entries = (size_mb * 1024 * 1024 / size_of::<Option<TtEntry>>()).max(1);
+        // Round down to a power of two so that indexing is a mask.
+        let num_entries = 1usize << (usize::BITS - 1 - max_entries.leading_zeros());
+        TranspositionTable {
+            entries: vec![None; num_entries],
+            index_mask: num_entries - 1,
+        }
+    }
+
+    /// Forgets every stored entry.
+    pub fn clear(&mut self) {
+        self.entries.fill(None);
+    }
+
+    fn probe(&self, key: u64) -> Option<TtEntry> {
+        self.entries[key as usize & self.index_mask].filter(|entry| entry.key == key)
+    }
+
+    fn store(&mut self, entry: TtEntry) {
+        self.entries[entry.key as usize & self.index_mask] = Some(entry);
+    }
+}
+
+/// Mate scores are stored relative to the node, not the root, so they stay valid when the same
+/// position is reached at a different ply.
+fn score_to_tt(score: i32, ply: u8) -> i32 {
+    match score {
+        s if s > MATE_THRESHOLD => s + ply as i32,
+        s if s < -MATE_THRESHOLD => s - ply as i32,
+        s => s,
+    }
+}
+
+fn score_from_tt(score: i32, ply: u8) -> i32 {
+    match score {
+        s if s > MATE_THRESHOLD => s - ply as i32,
+        s if s < -MATE_THRESHOLD => s + ply as i32,
+        s => s,
+    }
+}
+
+/// Outcome of [`Searcher::search`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SearchResult {
+    /// Best move found, or `None` if the side to move has no legal moves.
+    pub best_move: Option<Move>,
+    /// Score of the best move in centipawns for the side to move; see [`MATE_SCORE`].
+    pub score: i32,
+    /// Depth of the last completed iteration.
+    pub depth: u8,
+    /// Nodes visited over all iterations.
+    pub nodes: u64,
+}
+
+/// Iterative-deepening alpha-beta searcher; reuse it across searches to keep its table.
+pub struct Searcher {
+    tt: TranspositionTable,
+    /// Two killer moves per ply.
+    killers: Vec<[Option<Move>; 2]>,
+    nodes: u64,
+    root_best_move: Option<Move>,
+}
+
+impl Searcher {
+    /// Creates a searcher with a transposition table of about `tt_size_mb` megabytes.
+    pub fn new(tt_size_mb: usize) -> Searcher {
+        Searcher {
+            tt: TranspositionTable::with_size_mb(tt_size_mb),
+            killers: Vec::new(),
+            nodes: 0,
+            root_best_move: None,
+        }
+    }
+
+    /// Searches `position` to `max_depth` plies, deepening one ply at a time.
+    ///
+    /// `position` is restored before returning. Its context stack must have room for the
+    /// search: quiescence stops extending once it is full, so larger `N` searches deeper lines.
+    pub fn search<const N: usize, const STM: Color>(
+        &mut self,
+        position: &mut Position<N, STM>,
+        max_depth: u8,
+    ) -> SearchResult {
+        self.nodes = 0;
+        self.killers = vec![[None; 2]; N];
+        let mut result = SearchResult {
+            best_move: None,
+            score: 0,
+            depth: 0,
+            nodes: 0,
+        };
+        for depth in 1..=max_depth {
+            self.root_best_move = None;
+            let score = self.negamax(position, depth, 0, -INFINITY, INFINITY, true);
+            result = SearchResult {
+                best_move: self.root_best_move,
+                score,
+                depth,
+                nodes: self.nodes,
+            };
+            if score.abs() > MATE_THRESHOLD {
+                break;
+            }
+        }
+        result
+    }
+
+    fn negamax<const N: usize, const STM: Color>(
+        &mut self,
+        position: &mut Position<N, STM>,
+        depth: u8,
+        ply: u8,
+        mut alpha: i32,
+        beta: i32,
+        allow_null_move: bool,
+    ) -> i32 {
+        if ply > 0 && is_draw(position) {
+            self.nodes += 1;
+            return 0;
+        }
+        if depth == 0 || position.num_contexts() >= N {
+            return self.quiescence(position, ply, alpha, beta);
+        }
+        self.nodes += 1;
+
+        let key = position.context().zobrist_hash;
+        let tt_entry = self.tt.probe(key);
+        if ply > 0
+            && let Some(entry) = tt_entry
+            && entry.depth >= depth
+        {
+            let score = score_from_tt(entry.score, ply);
+            match entry.bound {
+                Bound::Exact => return score,
+                Bound::Lower if score >= beta => return score,
+                Bound::Upper if score <= alpha => return score,
+                _ => {}
+            }
+        }
+
+        let in_check = position.is_current_side_in_check();
+        if allow_null_move
+            && ply > 0
+            && !in_check
+            && depth > NULL_MOVE_REDUCTION
+            && has_non_pawn_material(position)
+        {
+            position.make_null_move();
+            let score = with_child!(position, STM, |child| {
+                let score = -self.negamax(
+                    child,
+                    depth - 1 - NULL_MOVE_REDUCTION,
+                    ply + 1,
+                    -beta,
+                    -beta + 1,
+                    false,
+                );
+                child.unmake_null_move();
+                score
+            });
+            if score >= beta {
+                return beta;
+            }
+        }
+
+        // The picker borrows the position immutably, so drain it before making any moves.
+        let mut moves: MoveList = MoveList::new();
+        let tt_move = tt_entry.and_then(|entry| entry.best_move);
+        for move_ in MovePicker::new(position, tt_move, self.killers[ply as usize]) {
+            moves.push(move_);
+        }
+        if moves.is_empty() {
+            return if in_check {
+                -MATE_SCORE + ply as i32
+            } else {
+                0
+            };
+        }
+
+        let original_alpha = alpha;
+        let mut best_score = -INFINITY;
+        let mut best_move = None;
+        for &move_ in moves.as_slice() {
+            let is_quiet = !move_.is_capture(position) && move_.flag() != MoveFlag::Promotion;
+            position.make_move(move_);
+            let score = with_child!(position, STM, |child| {
+                let score = -self.negamax(child, depth - 1, ply + 1, -beta, -alpha, true);
+                child.unmake_move(move_);
+                score
+            });
+
+            if score > best_score {
+                best_score = score;
+                best_move = Some(move_);
+            }
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                if is_quiet {
+                    let killers = &mut self.killers[ply as usize];
+                    if killers[0] != Some(move_) {
+                        killers[1] = killers[0];
+                        killers[0] = Some(move_);
+                    }
+                }
+                break;
+            }
+        }
+
+        if ply == 0 {
+            self.root_best_move = best_move;
+        }
+        let bound = if best_score >= beta {
+            Bound::Lower
+        } else if best_score > original_alpha {
+            Bound::Exact
+        } else {
+            Bound::Upper
+        };
+        self.tt.store(TtEntry {
+            key,
+            depth,
+            score: score_to_tt(best_score, ply),
+            bound,
+            best_move,
+        });
+        best_score
+    }
+
+    /// Searches captures and promotions (every move when in check) until the position is quiet.
+    fn quiescence<const N: usize, const STM: Color>(
+        &mut self,
+        position: &mut Position<N, STM>,
+        ply: u8,
+        mut alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        self.nodes += 1;
+        let in_check = position.is_current_side_in_check();
+        if position.num_contexts() >= N {
+            return evaluate(position);
+        }
+        if !in_check {
+            let stand_pat = evaluate(position);
+            if stand_pat >= beta {
+                return stand_pat;
+            }
+            alpha = alpha.max(stand_pat);
+        }
+
+        let mut moves: MoveList = MoveList::new();
+        let mut picker = MovePicker::new(position, None, [None; 2]);
+        if !in_check {
+            picker.skip_quiets();
+        }
+        for move_ in picker {
+            moves.push(move_);
+        }
+        if in_check && moves.is_empty() {
+            return -MATE_SCORE + ply as i32;
+        }
+
+        for &move_ in moves.as_slice() {
+            position.make_move(move_);
+            let score = with_child!(position, STM, |child| {
+                let score = -self.quiescence(child, ply + 1, -beta, -alpha);
+                child.unmake_move(move_);
+                score
+            });
+            if score >= beta {
+                return score;
+            }
+            alpha = alpha.max(score);
+        }
+        alpha
+    }
+}
+
+/// Fifty-move rule, insufficient material, or a repetition of any earlier position.
+///
+/// A single repetition is scored as a draw, since the side that repeats could repeat again.
+fn is_draw<const N: usize, const STM: Color>(position: &Position<N, STM>) -> bool {
+    let context = position.context();
+    if context.halfmove_clock >= 100
+        || position
+            .board
+            .are_both_sides_insufficient_material::<false>()
+    {
+        return true;
+    }
+    position.repetition_count(RepetitionRule::Hash) > 0
+}
+
+/// Null-move pruning is unsound in pawn endings, where zugzwang is common.
+fn has_non_pawn_material<const N: usize, const STM: Color>(position: &Position<N, STM>) -> bool {
+    let board = &position.board;
+    let pieces = board.piece_mask::<{ Piece::Knight }>()
+        | board.piece_mask::<{ Piece::Bishop }>()
+        | board.piece_mask::<{ Piece::Rook }>()
+        | board.piece_mask::<{ Piece::Queen }>();
+    pieces & board.color_mask_at(STM) != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Square;
+
+    #[test]
+    fn test_finds_back_rank_mate() {
+        let mut position =
+            Position::<16, { Color::White }>::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1")
+                .unwrap();
+        let baseline = position.clone();
+        let result = Searcher::new(1).search(&mut position, 3);
+        let best_move = result.best_move.unwrap();
+        assert_eq!((best_move.from(), best_move.to()), (Square::A1, Square::A8));
+        assert_eq!(result.score, MATE_SCORE - 1);
+        assert_eq!(position, baseline);
+    }
+
+    #[test]
+    fn test_wins_hanging_queen() {
+        let mut position = Position::<16, { Color::White }>::from_fen(
+            "4k3/ppp2ppp/8/3q4/8/2N5/PPP2PPP/4K3 w - - 0 1",
+        )
+        .unwrap();
+        let result = Searcher::new(1).search(&mut position, 4);
+        let best_move = result.best_move.unwrap();
+        assert_eq!((best_move.from(), best_move.to()), (Square::C3, Square::D5));
+        assert!(result.score > Piece::Knight.value() / 2);
+    }
+}
@@ -0,0 +1,184 @@
+//! Threat queries for candidate moves: what a move attacks, whether it hangs the moved piece,
+//! and how forcing it is.
+
+use crate::{
+    logic::attacks::attacks_from,
+    types::{
+        Bitboard,
+        BitboardUtils,
+        Color,
+        Move,
+        MoveFlag,
+        MoveList,
+        Piece,
+        Position,
+        Square,
+        ZobristPolicy,
+    },
+};
+
+/// How forcing a move is, from [`Position::forcingness`] and [`Position::forcing_moves`].
+///
+/// The kinds are independent: a capture may also give check.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Forcingness {
+    /// The move gives check, directly or by discovery.
+    pub check: bool,
+    /// The move captures, including en passant.
+    pub capture: bool,
+    /// The moved piece attacks an enemy piece, other than the king, that is worth more than it
+    /// and that the opponent does not defend.
+    pub threat: bool,
+}
+
+impl Forcingness {
+    /// Returns whether the move is forcing in any way.
+    pub const fn is_forcing(&self) -> bool {
+        self.check || self.capture || self.threat
+    }
+}
+
+impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
+    /// Returns every square the side to move attacks after playing `move_`.
+    ///
+    /// Only the board is updated (no context push), so this is much cheaper than making and
+    /// unmaking the move. `move_` must be legal in this position.
+    pub fn attacked_after(&self, move_: Move) -> Bitboard {
+        let mut board = self.board.clone();
+        board.apply_move(move_);
+        board.attacked_squares(STM)
+    }
+
+    /// Returns whether `square` is attacked by `color`'s pieces, i.e. whether `color` defends a
+    /// piece of its own standing there (or could recapture there).
+    #[inline]
+    pub fn is_square_defended(&self, square: Square, color: Color) -> bool {
+        // Lift the piece off the square so it does not count as defending itself.
+        self.board
+            .is_square_attacked_after_move(square, color, square.mask())
+    }
+
+    /// Returns whether the piece moved by `move_` cannot be captured right after the move,
+    /// i.e. its destination is not attacked by the opponent. `move_` must be legal.
+    pub fn is_move_safe(&self, move_: Move) -> bool {
+        let mut board = self.board.clone();
+        board.apply_move(move_);
+        !board.is_square_attacked(move_.to(), STM.other())
+    }
+
+    /// Classifies `move_` as a check, capture and/or threat. `move_` must be legal.
+    ///
+    /// Only the board is updated, as in [`Self::attacked_after`], so this is cheap enough to call
+    /// for every move in quiescence search.
+    pub fn forcingness(&self, move_: Move) -> Forcingness {
+        let capture = move_.is_capture(self);
+        let moved_piece = match move_.flag() {
+            MoveFlag::Promotion => move_.promotion(),
+            _ => self.board.piece_at(move_.from()),
+        };
+        let mut board = self.board.clone();
+        board.apply_move(move_);
+
+        let enemies = board.color_mask_at(STM.other());
+        let enemy_king = enemies & board.piece_mask::<{ Piece::King }>();
+        let check = board.attacked_squares(STM) & enemy_king != 0;
+
+        let occupied = board.piece_mask::<{ Piece::ALL_PIECES }>();
+        let targets = attacks_from(moved_piece, STM, move_.to(), occupied) & enemies & !enemy_king;
+        let threat = targets.iter_set_bits_as_squares().any(|square| {
+            board.piece_at(square).value() > moved_piece.value()
+                && !board.is_square_attacked_after_move(square, STM.other(), square.mask())
+        });
+
+        Forcingness {
+            check,
+            capture,
+            threat,
+        }
+    }
+
+    /// Returns every legal move that is forcing, with how it is forcing, in generation order.
+    ///
+    /// Meant for quiescence extensions and puzzle filters; see [`Forcingness`].
+    pub fn forcing_moves(&self) -> Vec<(Move, Forcingness)> {
+        let mut moves = MoveList::new();
+        self.generate_moves(&mut moves);
+        moves
+            .as_slice()
+            .iter()
+            .map(|&move_| (move_, self.forcingness(move_)))
+            .filter(|(_, forcingness)| forcingness.is_forcing())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Forcingness;
+    use crate::types::{Color, Move, MoveFlag, Position, Square};
+
+    #[test]
+    fn test_attacked_after_and_safety() {
+        let position = Position::<1, { Color::White }>::from_fen(
+            "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+        )
+        .unwrap();
+
+        let bc4 = Move::new_non_promotion(Square::F1, Square::C4, MoveFlag::NormalMove);
+        let attacked = position.attacked_after(bc4);
+        assert_ne!(attacked & Square::F7.mask(), 0);
+        assert!(position.is_move_safe(bc4));
+
+        let bb5 = Move::new_non_promotion(Square::F1, Square::B5, MoveFlag::NormalMove);
+        assert!(position.is_move_safe(bb5));
+        // Qd8xg5.
+        let ng5 = Move::new_non_promotion(Square::F3, Square::G5, MoveFlag::NormalMove);
+        assert!(!position.is_move_safe(ng5));
+
+        assert!(position.is_square_defended(Square::E5, Color::Black));
+        assert!(position.is_square_defended(Square::E5, Color::White));
+        assert!(!position.is_square_defended(Square::E4, Color::White));
+        assert!(position.is_square_defended(Square::D4, Color::White));
+    }
+
+    #[test]
+    fn test_forcing_moves() {
+        let position =
+            Position::<1, { Color::White }>::from_fen("4k3/8/8/3q4/4P3/8/8/R3K1N1 w - - 0 1")
+                .unwrap();
+        let forcing = position.forcing_moves();
+        let lookup = |from, to| {
+            forcing
+                .iter()
+                .find(|(move_, _)| move_.from() == from && move_.to() == to)
+                .map(|&(_, forcingness)| forcingness)
+        };
+
+        let exd5 = lookup(Square::E4, Square::D5).unwrap();
+        assert!(exd5.capture && !exd5.check && !exd5.threat);
+        assert_eq!(
+            lookup(Square::A1, Square::A8),
+            Some(Forcingness {
+                check: true,
+                capture: false,
+                threat: false,
+            })
+        );
+        // The rook attacks the more valuable queen.
+        assert!(lookup(Square::A1, Square::A5).unwrap().threat);
+        assert!(lookup(Square::A1, Square::D1).unwrap().threat);
+        assert_eq!(lookup(Square::E1, Square::F2), None);
+        assert_eq!(lookup(Square::G1, Square::F3), None);
+        assert!(
+            forcing
+                .iter()
+                .all(|(_, forcingness)| forcingness.is_forcing())
+        );
+
+        // Attacking a defended queen or an undefended pawn is not a threat.
+        let position =
+            Position::<1, { Color::White }>::from_fen("8/p7/4k3/3q4/8/8/8/R3K3 w - - 0 1").unwrap();
+        let ra5 = Move::new_non_promotion(Square::A1, Square::A5, MoveFlag::NormalMove);
+        assert!(!position.forcingness(ra5).is_forcing());
+    }
+}
@@ -35,7 +35,7 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
     }
 
     /// Returns whether the side *not* to move (`STM.other()`) is currently in check.
-    pub fn is_opposite_side_in_check(&self) -> bool {
+    pub const fn is_opposite_side_in_check(&self) -> bool {
         let opponent = STM.other();
         let opponent_king_mask =
             self.board.piece_mask::<{ Piece::King }>() & self.board.color_mask_at(opponent);
@@ -57,7 +57,7 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
     }
 
     /// Checks if the castling rights are consistent with the position of the rooks and kings.
-    pub fn has_valid_castling_rights(&self) -> bool {
+    pub const fn has_valid_castling_rights(&self) -> bool {
         let context = self.context();
 
         let kings_mask = self.board.piece_mask::<{ Piece::King }>();
@@ -114,9 +114,56 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
     }
 
     /// Checks if the double pawn push is consistent with the position of the pawns.
-    pub fn has_valid_double_pawn_push(&self) -> bool {
+    pub const fn has_valid_double_pawn_push(&self) -> bool {
         self.context()
             .double_pawn_push_file
             .ep_target_is_valid(self.halfmove, STM, &self.board)
     }
 }
+
+#[cfg(feature = "strict-debug")]
+impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
+    /// Panics unless the state left by a make or unmake is consistent: the board's masks agree,
+    /// each side has one king, the hash matches the board for `side_to_move`, and the context
+    /// stack and halfmove counter moved by exactly one.
+    ///
+    /// `describe` names the call, such as `make_move(e2e4) from <FEN>`, for the panic message.
+    pub(crate) fn assert_strict_consistency(
+        &self,
+        side_to_move: Color,
+        expected_num_contexts: usize,
+        expected_halfmove: u16,
+        describe: impl FnOnce() -> String,
+    ) {
+        let context = self.context();
+        let mut problems = Vec::new();
+        if !self.board.is_consistent() {
+            problems.push("board masks disagree");
+        }
+        if !self.board.has_valid_kings() {
+            problems.push("each side must have exactly one king");
+        }
+        if !Z::is_consistent(
+            &context.zobrist_hash,
+            &self.board,
+            context.castling_rights,
+            context.double_pawn_push_file,
+            side_to_move,
+        ) {
+            problems.push("zobrist hash does not match the board");
+        }
+        if self.num_contexts != expected_num_contexts {
+            problems.push("context stack is not paired with the move");
+        }
+        if self.halfmove != expected_halfmove || self.halfmove % 2 != side_to_move as u16 {
+            problems.push("halfmove counter does not match the side to move");
+        }
+        if !problems.is_empty() {
+            panic!(
+                "strict-debug: {} left an inconsistent position: {}",
+                describe(),
+                problems.join("; ")
+            );
+        }
+    }
+}
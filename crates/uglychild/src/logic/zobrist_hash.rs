@@ -1,7 +1,20 @@
 //! Zobrist random tables and hash-key helpers.
+//!
+//! The built-in keys used by [`crate::types::WithZobrist`] are readable through
+//! [`piece_square_key`] and its siblings. [`ZobristKeys`] holds a user-supplied schedule (e.g.
+//! Polyglot's) for [`crate::types::WithCustomZobrist`].
 
 use crate::{
-    types::{Board, CastlingRights, Color, DoublePawnPushFile, Piece, Square},
+    types::{
+        Board,
+        CastlingRights,
+        Color,
+        ColoredPiece,
+        ConstDoublePawnPushFile,
+        DoublePawnPushFile,
+        Piece,
+        Square,
+    },
     utilities::{Array, IterableEnum, Prng},
 };
 
@@ -53,14 +66,19 @@ static PIECE_SQUARE_KEYS: Array<Array<u64, 64>, 12> = {
     let keys = copy_keys::<{ NUM_PIECE_SQUARE_KEYS }, { PIECE_SQUARE_KEYS_START }>();
     let mut keys_lookup: Array<Array<u64, 64>, 12> = unsafe { std::mem::transmute(keys) };
 
-    fill(
-        &mut keys_lookup[Piece::Pawn as usize][Square::A8 as usize..=Square::H8 as usize],
-        0,
-    );
-    fill(
-        &mut keys_lookup[Piece::Pawn as usize][Square::A1 as usize..=Square::H1 as usize],
-        0,
-    );
+    let mut color = 0;
+    while color < 2 {
+        let pawn = color * 6;
+        fill(
+            &mut keys_lookup[pawn][Square::A8 as usize..=Square::H8 as usize],
+            0,
+        );
+        fill(
+            &mut keys_lookup[pawn][Square::A1 as usize..=Square::H1 as usize],
+            0,
+        );
+        color += 1;
+    }
 
     keys_lookup
 };
@@ -78,9 +96,13 @@ static DOUBLE_PAWN_PUSH_FILE_KEYS: Array<u64, { NUM_DOUBLE_PAWN_PUSH_FILE_KEYS }
 
 static BLACK_SIDE_TO_MOVE_KEY: u64 = RANDOMS[SIDE_TO_MOVE_KEYS_START];
 
-/// Returns piece-square key contribution for (`piece`, `square`).
-pub const fn piece_square_key(piece: Piece, square: Square) -> u64 {
-    PIECE_SQUARE_KEYS[piece as usize][square as usize]
+/// Returns piece-square key contribution for `color`'s `piece` on `square` (`0` for
+/// [`Piece::Null`], so empty squares contribute nothing).
+pub const fn piece_square_key(color: Color, piece: Piece, square: Square) -> u64 {
+    match piece {
+        Piece::Null => 0,
+        _ => PIECE_SQUARE_KEYS[ColoredPiece::new(color, piece).index()][square as usize],
+    }
 }
 
 /// Returns castling-rights key contribution.
@@ -110,8 +132,7 @@ impl Board {
     pub const fn calc_zobrist_hash(&self) -> u64 {
         let mut hash = 0;
         for square in Square::ALL {
-            let piece = self.piece_at(square);
-            hash ^= piece_square_key(piece, square);
+            hash ^= piece_square_key(self.color_at(square), self.piece_at(square), square);
         }
 
         hash
@@ -131,6 +152,89 @@ pub const fn calc_position_zobrist_hash(
         ^ side_to_move_key(side_to_move)
 }
 
+/// Whether a pawn of `capturer` stands next to the pawn that just double-pushed on
+/// `double_pawn_push_file`, so an en passant capture is at least pseudo-legal.
+pub const fn has_en_passant_capturer(
+    board: &Board,
+    double_pawn_push_file: DoublePawnPushFile,
+    capturer: Color,
+) -> bool {
+    double_pawn_push_file.has_file()
+        && board.piece_mask::<{ Piece::Pawn }>()
+            & board.color_mask_at(capturer)
+            & double_pawn_push_file.ep_possible_src_mask(capturer)
+            != 0
+}
+
+/// A user-supplied Zobrist key schedule, used through [`crate::types::ZobristKeySchedule`].
+///
+/// Like the built-in keys, pieces are keyed by color and empty squares contribute nothing,
+/// which is the layout external books and databases use. To reproduce a schedule with one key
+/// per castling right (as Polyglot does), XOR the keys of the rights present into each of the
+/// 16 [`CastlingRights`] entries.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ZobristKeys {
+    /// Key per colored piece, by [`ColoredPiece::index`], and square.
+    pub piece_square: [[u64; 64]; 12],
+    /// Key per castling-rights combination, by `CastlingRights as usize`.
+    pub castling_rights: [u64; 16],
+    /// Key per en passant file (file A first), applied after a double pawn push.
+    pub en_passant_file: [u64; 8],
+    /// Apply [`Self::en_passant_file`] only when a pawn of the side to move stands next to the
+    /// double-pushed pawn, whether or not the capture is legal (as Polyglot does); otherwise it
+    /// is applied after every double pawn push.
+    pub en_passant_needs_capturer: bool,
+    /// Key applied for the side to move, by `Color as usize` (Polyglot keys White to move).
+    pub side_to_move: [u64; 2],
+}
+
+impl ZobristKeys {
+    /// Returns the key for `color`'s `piece` on `square` (`0` for [`Piece::Null`]).
+    pub const fn piece_square_key(&self, color: Color, piece: Piece, square: Square) -> u64 {
+        match piece {
+            Piece::Null => 0,
+            _ => self.piece_square[ColoredPiece::new(color, piece).index()][square as usize],
+        }
+    }
+
+    /// Returns the en passant key for `double_pawn_push_file` (`0` when there is none, or when
+    /// [`Self::en_passant_needs_capturer`] is set and `capturable` is not).
+    pub const fn en_passant_key(
+        &self,
+        double_pawn_push_file: DoublePawnPushFile,
+        capturable: bool,
+    ) -> u64 {
+        if double_pawn_push_file < 0 || (self.en_passant_needs_capturer && !capturable) {
+            0
+        } else {
+            self.en_passant_file[double_pawn_push_file as usize]
+        }
+    }
+
+    /// Returns the side-to-move key contribution.
+    pub const fn side_to_move_key(&self, side_to_move: Color) -> u64 {
+        self.side_to_move[side_to_move as usize]
+    }
+
+    /// Computes the full hash of a position from scratch with these keys.
+    pub const fn calc_position_hash(
+        &self,
+        board: &Board,
+        castling_rights: CastlingRights,
+        double_pawn_push_file: DoublePawnPushFile,
+        side_to_move: Color,
+    ) -> u64 {
+        let capturable = has_en_passant_capturer(board, double_pawn_push_file, side_to_move);
+        let mut hash = self.castling_rights[castling_rights as usize]
+            ^ self.en_passant_key(double_pawn_push_file, capturable)
+            ^ self.side_to_move_key(side_to_move);
+        for square in Square::ALL {
+            hash ^= self.piece_square_key(board.color_at(square), board.piece_at(square), square);
+        }
+        hash
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -151,14 +255,14 @@ mod tests {
         randoms
     }
 
-    fn expected_piece_square_key(piece: Piece, square: Square) -> u64 {
-        if piece == Piece::Pawn
-            && ((Square::A1 as usize..=Square::H1 as usize).contains(&(square as usize))
-                || (Square::A8 as usize..=Square::H8 as usize).contains(&(square as usize)))
-        {
+    fn expected_piece_square_key(color: Color, piece: Piece, square: Square) -> u64 {
+        let back_rank = (Square::A1 as usize..=Square::H1 as usize).contains(&(square as usize))
+            || (Square::A8 as usize..=Square::H8 as usize).contains(&(square as usize));
+        if piece == Piece::Null || (piece == Piece::Pawn && back_rank) {
             0
         } else {
-            RANDOMS[PIECE_SQUARE_KEYS_START + piece as usize * 64 + square as usize]
+            let index = ColoredPiece::new(color, piece).index();
+            RANDOMS[PIECE_SQUARE_KEYS_START + index * 64 + square as usize]
         }
     }
 
@@ -222,23 +326,27 @@ mod tests {
             Square::G8,
             Square::H8,
         ] {
-            assert_eq!(
-                piece_square_key(Piece::Pawn, square),
-                0,
-                "pawn key on {square:?} should be zero"
-            );
+            for color in [Color::White, Color::Black] {
+                assert_eq!(
+                    piece_square_key(color, Piece::Pawn, square),
+                    0,
+                    "{color:?} pawn key on {square:?} should be zero"
+                );
+            }
         }
     }
 
     #[test]
     fn piece_square_keys_match_expected_table_values() {
-        for piece in Piece::ALL {
-            for square in Square::ALL {
-                assert_eq!(
-                    piece_square_key(piece, square),
-                    expected_piece_square_key(piece, square),
-                    "bad piece-square key for {piece:?} on {square:?}"
-                );
+        for color in [Color::White, Color::Black] {
+            for piece in Piece::ALL {
+                for square in Square::ALL {
+                    assert_eq!(
+                        piece_square_key(color, piece, square),
+                        expected_piece_square_key(color, piece, square),
+                        "bad piece-square key for {color:?} {piece:?} on {square:?}"
+                    );
+                }
             }
         }
     }
@@ -298,9 +406,24 @@ mod tests {
 
         let mut expected = 0;
         for square in Square::ALL {
-            expected ^= piece_square_key(board.piece_at(square), square);
+            expected ^= piece_square_key(board.color_at(square), board.piece_at(square), square);
         }
 
         assert_eq!(board.calc_zobrist_hash(), expected);
     }
+
+    #[test]
+    fn hash_distinguishes_piece_colors() {
+        // The two knights trade colors; nothing else differs.
+        let hash = |fen| {
+            crate::types::Position::<1, { Color::White }>::from_fen(fen)
+                .unwrap()
+                .context()
+                .zobrist_hash
+        };
+        assert_ne!(
+            hash("4k3/8/8/8/8/8/8/1N2K1n1 w - - 0 1"),
+            hash("4k3/8/8/8/8/8/8/1n2K1N1 w - - 0 1")
+        );
+    }
 }
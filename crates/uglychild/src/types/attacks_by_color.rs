@@ -0,0 +1,262 @@
+//! Per-color, per-piece attack sets and per-square attacker counts for a [`Board`].
+
+use super::{
+    bitboard::{Bitboard, BitboardUtils},
+    board::Board,
+    color::Color,
+    colored_piece::ColoredPiece,
+    piece::Piece,
+    square::Square,
+};
+use crate::logic::{
+    accumulator::AccumulatorDelta,
+    attacks::{
+        multi_pawn_attacks,
+        single_bishop_attacks,
+        single_king_attacks,
+        single_knight_attacks,
+        single_queen_attacks,
+        single_rook_attacks,
+    },
+};
+
+/// Materialized attack information for both colors.
+///
+/// Stores one attack bitboard per (color, piece type), the union per color, and how many
+/// pieces of each color attack every square (x-rays are not counted).
+///
+/// It is kept up to date by passing it as the listener to
+/// [`crate::types::Position::make_move_with`] and [`crate::types::Position::unmake_move_with`]:
+/// each placement change refreshes only the piece placed or removed and the sliders whose rays
+/// cross its square. Moves made without it leave it describing the old board; rebuild it with
+/// [`AttacksByColor::new`] then.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttacksByColor {
+    /// Indexed by `[color][piece]`; [`Piece::ALL_PIECES`] holds the union over all piece types.
+    piece_attacks: [[Bitboard; Piece::LIMIT as usize]; 2],
+    /// Indexed by `[color][square]`.
+    attacker_counts: [[u8; 64]; 2],
+    /// Piece placement mirrored from the deltas, indexed by `[color][piece]` like
+    /// `piece_attacks`.
+    piece_masks: [[Bitboard; Piece::LIMIT as usize]; 2],
+    /// Piece on each square, indexed by [`Square`].
+    pieces: [Option<ColoredPiece>; 64],
+    /// Squares attacked by the piece on each square, indexed by [`Square`].
+    square_attacks: [Bitboard; 64],
+}
+
+impl AttacksByColor {
+    /// Computes attack sets and attacker counts for every piece on `board`.
+    pub fn new(board: &Board) -> AttacksByColor {
+        let mut result = AttacksByColor {
+            piece_attacks: [[0; Piece::LIMIT as usize]; 2],
+            attacker_counts: [[0; 64]; 2],
+            piece_masks: [[0; Piece::LIMIT as usize]; 2],
+            pieces: [None; 64],
+            square_attacks: [0; 64],
+        };
+        for square in board.pieces().iter_set_bits_as_squares() {
+            result.on_piece_added(board.color_at(square), board.piece_at(square), square);
+        }
+        result
+    }
+
+    /// All squares attacked by `color`.
+    #[inline]
+    pub const fn attacks(&self, color: Color) -> Bitboard {
+        self.piece_attacks[color as usize][Piece::ALL_PIECES as usize]
+    }
+
+    /// Squares attacked by `color`'s pieces of type `piece` ([`Piece::ALL_PIECES`] for the union).
+    #[inline]
+    pub const fn attacks_by(&self, piece: Piece, color: Color) -> Bitboard {
+        self.piece_attacks[color as usize][piece as usize]
+    }
+
+    /// Number of `color`'s pieces directly attacking `square`.
+    #[inline]
+    pub const fn attacker_count(&self, square: Square, color: Color) -> u8 {
+        self.attacker_counts[color as usize][square as usize]
+    }
+
+    /// Squares attacked by `color` at least `min_count` times.
+    pub fn attacked_at_least(&self, color: Color, min_count: u8) -> Bitboard {
+        let mut mask = 0;
+        for (index, &count) in self.attacker_counts[color as usize].iter().enumerate() {
+            if count >= min_count {
+                mask |= 1 << (63 - index);
+            }
+        }
+        mask
+    }
+
+    fn occupied(&self) -> Bitboard {
+        self.piece_masks[0][Piece::ALL_PIECES as usize]
+            | self.piece_masks[1][Piece::ALL_PIECES as usize]
+    }
+
+    /// Squares attacked by the piece on `square` under the mirrored occupancy.
+    fn attacks_from(&self, square: Square) -> Bitboard {
+        let Some(colored_piece) = self.pieces[square as usize] else {
+            return 0;
+        };
+        let occupied = self.occupied();
+        match colored_piece.piece() {
+            Piece::Pawn => multi_pawn_attacks(square.mask(), colored_piece.color()),
+            Piece::Knight => single_knight_attacks(square),
+            Piece::Bishop => single_bishop_attacks(square, occupied),
+            Piece::Rook => single_rook_attacks(square, occupied),
+            Piece::Queen => single_queen_attacks(square, occupied),
+            Piece::King => single_king_attacks(square),
+            Piece::Null => 0,
+        }
+    }
+
+    /// Sliders of either color whose attacks reach `square`, and so change when its occupancy
+    /// does.
+    fn sliders_reaching(&self, square: Square) -> Bitboard {
+        let mut sliders = 0;
+        for color in [Color::White, Color::Black] {
+            let masks = &self.piece_masks[color as usize];
+            sliders |= masks[Piece::Bishop as usize]
+                | masks[Piece::Rook as usize]
+                | masks[Piece::Queen as usize];
+        }
+        let mut reaching = 0;
+        for slider in sliders.iter_set_bits_as_squares() {
+            if self.square_attacks[slider as usize] & square.mask() != 0 {
+                reaching |= slider.mask();
+            }
+        }
+        reaching
+    }
+
+    /// Replaces the attacks of `color`'s piece on `square`, adjusting the attacker counts.
+    fn set_square_attacks(&mut self, color: Color, square: Square, attacks: Bitboard) {
+        let counts = &mut self.attacker_counts[color as usize];
+        for attacked_square in self.square_attacks[square as usize].iter_set_bits_as_squares() {
+            counts[attacked_square as usize] -= 1;
+        }
+        for attacked_square in attacks.iter_set_bits_as_squares() {
+            counts[attacked_square as usize] += 1;
+        }
+        self.square_attacks[square as usize] = attacks;
+    }
+
+    /// Recomputes the attacks of the sliders in `sliders`, then every attack union.
+    fn refresh(&mut self, sliders: Bitboard) {
+        for slider in sliders.iter_set_bits_as_squares() {
+            if let Some(colored_piece) = self.pieces[slider as usize] {
+                let attacks = self.attacks_from(slider);
+                self.set_square_attacks(colored_piece.color(), slider, attacks);
+            }
+        }
+        for color in [Color::White, Color::Black] {
+            let mut all = 0;
+            for piece in Piece::PIECES {
+                let mut attacks = 0;
+                let squares = self.piece_masks[color as usize][piece as usize];
+                for square in squares.iter_set_bits_as_squares() {
+                    attacks |= self.square_attacks[square as usize];
+                }
+                self.piece_attacks[color as usize][piece as usize] = attacks;
+                all |= attacks;
+            }
+            self.piece_attacks[color as usize][Piece::ALL_PIECES as usize] = all;
+        }
+    }
+}
+
+impl AccumulatorDelta for AttacksByColor {
+    fn on_piece_added(&mut self, color: Color, piece: Piece, square: Square) {
+        let sliders = self.sliders_reaching(square);
+        let masks = &mut self.piece_masks[color as usize];
+        masks[piece as usize] |= square.mask();
+        masks[Piece::ALL_PIECES as usize] |= square.mask();
+        self.pieces[square as usize] = Some(ColoredPiece::new(color, piece));
+        let attacks = self.attacks_from(square);
+        self.set_square_attacks(color, square, attacks);
+        self.refresh(sliders);
+    }
+
+    fn on_piece_removed(&mut self, color: Color, piece: Piece, square: Square) {
+        let sliders = self.sliders_reaching(square);
+        self.set_square_attacks(color, square, 0);
+        let masks = &mut self.piece_masks[color as usize];
+        masks[piece as usize] &= !square.mask();
+        masks[Piece::ALL_PIECES as usize] &= !square.mask();
+        self.pieces[square as usize] = None;
+        self.refresh(sliders);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MoveList, Position, Rank};
+
+    #[test]
+    fn test_initial_position_attacks() {
+        let attacks = AttacksByColor::new(&Board::initial());
+
+        assert_eq!(
+            attacks.attacks_by(Piece::Pawn, Color::White),
+            Rank::Three.mask()
+        );
+        assert_eq!(
+            attacks.attacks_by(Piece::Pawn, Color::Black),
+            Rank::Six.mask()
+        );
+        assert_eq!(
+            attacks.attacks_by(Piece::Queen, Color::White),
+            Square::C1.mask()
+                | Square::E1.mask()
+                | Square::C2.mask()
+                | Square::D2.mask()
+                | Square::E2.mask()
+        );
+        assert_eq!(attacks.attacker_count(Square::F3, Color::White), 3);
+        assert_eq!(attacks.attacker_count(Square::E4, Color::White), 0);
+        assert_eq!(attacks.attacks(Color::White) & Rank::Six.mask(), 0);
+    }
+
+    #[test]
+    fn test_attacker_counts_match_attack_sets() {
+        let position = Position::<1, { Color::White }>::from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        )
+        .unwrap();
+        let attacks = AttacksByColor::new(&position.board);
+        for color in [Color::White, Color::Black] {
+            assert_eq!(attacks.attacked_at_least(color, 1), attacks.attacks(color));
+            for square in attacks.attacks(color).iter_set_bits_as_squares() {
+                assert!(position.board.is_square_attacked(square, color));
+            }
+        }
+    }
+
+    #[test]
+    fn test_make_unmake_updates_attacks() {
+        let mut position = Position::<2, { Color::White }>::from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        )
+        .unwrap();
+        let mut attacks = AttacksByColor::new(&position.board);
+        let mut moves = MoveList::new();
+        position.generate_moves(&mut moves);
+
+        for &move_ in moves.as_slice() {
+            let before = attacks.clone();
+            position.make_move_with(move_, &mut attacks);
+            assert_eq!(
+                attacks,
+                AttacksByColor::new(&position.board),
+                "after make {}",
+                move_.uci()
+            );
+            let child = unsafe { position.rebrand_stm_mut::<{ Color::Black }>() };
+            child.unmake_move_with(move_, &mut attacks);
+            assert_eq!(attacks, before, "after unmake {}", move_.uci());
+        }
+    }
+}
@@ -1,10 +1,16 @@
 //! Bitboard type and utility trait for 64-bit square masks.
 
 use super::{
+    File,
     QueenLikeMoveDirection,
     square::{Square, same_line},
 };
-use crate::utilities::{BitCombinationsIterator, MaskBitsIterator, MaskSquaresIterator};
+use crate::utilities::{
+    BitCombinationsIterator,
+    MaskBitsIterator,
+    MaskSquaresIterator,
+    SquaresTwoToOneMapping,
+};
 
 /// A 64-bit bitboard where each bit represents a chess square.
 ///
@@ -36,15 +42,23 @@ pub const trait BitboardUtils: private::Sealed {
 
     /// Returns an iterator that generates all possible set bit combinations of the bitboard.
     fn iter_bit_combinations(self) -> BitCombinationsIterator;
+
+    /// Moves every set square one step in `direction`. Squares that would leave the board,
+    /// including across the A/H file edge, are dropped rather than wrapped.
+    fn shift(self, direction: QueenLikeMoveDirection) -> Bitboard;
+
+    /// Moves every set square `steps` steps in `direction`, dropping squares that leave the
+    /// board on the way (see [`shift`](BitboardUtils::shift)).
+    fn shift_by(self, direction: QueenLikeMoveDirection, steps: u32) -> Bitboard;
 }
 
 impl const BitboardUtils for Bitboard {
     fn between(sq1: Square, sq2: Square) -> Bitboard {
-        MASK_BETWEEN_DATA[(sq1 as usize) * 64 + (sq2 as usize)]
+        MASK_BETWEEN_DATA.get(sq1, sq2)
     }
 
     fn edge_to_edge_ray(sq1: Square, sq2: Square) -> Bitboard {
-        EDGE_TO_EDGE_RAY_DATA[(sq1 as usize) * 64 + (sq2 as usize)]
+        EDGE_TO_EDGE_RAY_DATA.get(sq1, sq2)
     }
 
     fn iter_set_bits_as_masks(self) -> MaskBitsIterator {
@@ -58,6 +72,29 @@ impl const BitboardUtils for Bitboard {
     fn iter_bit_combinations(self) -> BitCombinationsIterator {
         self.into()
     }
+
+    fn shift(self, direction: QueenLikeMoveDirection) -> Bitboard {
+        match direction {
+            QueenLikeMoveDirection::Up => self << 8,
+            QueenLikeMoveDirection::Down => self >> 8,
+            QueenLikeMoveDirection::Left => (self << 1) & !File::H.mask(),
+            QueenLikeMoveDirection::Right => (self >> 1) & !File::A.mask(),
+            QueenLikeMoveDirection::UpLeft => (self << 9) & !File::H.mask(),
+            QueenLikeMoveDirection::UpRight => (self << 7) & !File::A.mask(),
+            QueenLikeMoveDirection::DownLeft => (self >> 7) & !File::H.mask(),
+            QueenLikeMoveDirection::DownRight => (self >> 9) & !File::A.mask(),
+        }
+    }
+
+    fn shift_by(self, direction: QueenLikeMoveDirection, steps: u32) -> Bitboard {
+        let mut shifted = self;
+        let mut step = 0;
+        while step < steps && shifted != 0 {
+            shifted = shifted.shift(direction);
+            step += 1;
+        }
+        shifted
+    }
 }
 
 const fn calc_between(sq1: Square, sq2: Square) -> Bitboard {
@@ -115,26 +152,41 @@ const fn calc_edge_to_edge_ray(sq1: Square, sq2: Square) -> Bitboard {
     }
 }
 
-static MASK_BETWEEN_DATA: [Bitboard; 64 * 64] = {
-    let mut arr = [0u64; 64 * 64];
-    let mut i = 0usize;
-    while i < 64 * 64 {
-        let sq1 = unsafe { Square::try_from((i / 64) as u8).unwrap_unchecked() };
-        let sq2 = unsafe { Square::try_from((i % 64) as u8).unwrap_unchecked() };
-        arr[i] = calc_between(sq1, sq2);
-        i += 1;
+static MASK_BETWEEN_DATA: SquaresTwoToOneMapping<Bitboard> =
+    SquaresTwoToOneMapping::init(calc_between);
+
+static EDGE_TO_EDGE_RAY_DATA: SquaresTwoToOneMapping<Bitboard> =
+    SquaresTwoToOneMapping::init(calc_edge_to_edge_ray);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::IterableEnum;
+
+    #[test]
+    fn test_shift_matches_neighbor_squares() {
+        for square in Square::ALL {
+            for direction in QueenLikeMoveDirection::ALL {
+                let expected = square
+                    .neighbor_in_direction(direction)
+                    .map_or(0, Square::mask);
+                assert_eq!(square.mask().shift(direction), expected);
+            }
+        }
     }
-    arr
-};
 
-static EDGE_TO_EDGE_RAY_DATA: [Bitboard; 64 * 64] = {
-    let mut arr = [0u64; 64 * 64];
-    let mut i = 0usize;
-    while i < 64 * 64 {
-        let sq1 = unsafe { Square::try_from((i / 64) as u8).unwrap_unchecked() };
-        let sq2 = unsafe { Square::try_from((i % 64) as u8).unwrap_unchecked() };
-        arr[i] = calc_edge_to_edge_ray(sq1, sq2);
-        i += 1;
+    #[test]
+    fn test_shift_by() {
+        let mask = Square::B2.mask() | Square::G7.mask();
+        assert_eq!(
+            mask.shift_by(QueenLikeMoveDirection::Right, 2),
+            Square::D2.mask()
+        );
+        assert_eq!(
+            mask.shift_by(QueenLikeMoveDirection::UpRight, 5),
+            Square::G7.mask()
+        );
+        assert_eq!(mask.shift_by(QueenLikeMoveDirection::Down, 0), mask);
+        assert_eq!(mask.shift_by(QueenLikeMoveDirection::Left, 8), 0);
     }
-    arr
-};
+}
@@ -3,6 +3,7 @@
 use super::{
     bitboard::{Bitboard, BitboardUtils},
     color::Color,
+    colored_piece::ColoredPiece,
     piece::Piece,
     rank::Rank,
     square::Square,
@@ -22,6 +23,29 @@ pub struct Board {
     color_masks: [Bitboard; 2],
     /// Piece type per square (`Square` index 0..64); empty squares are [`Piece::Null`].
     pieces: [Piece; 64],
+    /// Piece counts packed by [`material_key_unit`]; see [`Self::material_key`].
+    material_key: u64,
+}
+
+/// Material key increment for one of `color`'s `piece`: six bits per colored piece from pawn to
+/// queen, White's first; kings are not counted.
+const fn material_key_unit(color: Color, piece: Piece) -> u64 {
+    match piece {
+        Piece::Null | Piece::King => 0,
+        _ => 1 << material_key_shift(color, piece),
+    }
+}
+
+/// Bit offset of the count of `color`'s `piece` in the material key; `piece` must be a pawn
+/// through a queen.
+const fn material_key_shift(color: Color, piece: Piece) -> u32 {
+    6 * (5 * color as u32 + piece as u32 - 1)
+}
+
+/// Mask of the bits holding the count of `color`'s `piece` in [`Board::material_key`]; `0` for
+/// [`Piece::Null`] and the king.
+pub(crate) const fn material_key_field(color: Color, piece: Piece) -> u64 {
+    63 * material_key_unit(color, piece)
 }
 
 impl Board {
@@ -67,11 +91,14 @@ impl Board {
             WQ | BQ,
             WK | BK,
         ]);
-        Board {
+        let mut board = Board {
             piece_masks: PM.0,
             color_masks: [STARTING_WHITE, STARTING_BLACK],
             pieces: Self::mailbox_from_piece_masks(&PM.0),
-        }
+            material_key: 0,
+        };
+        board.material_key = board.calc_material_key();
+        board
     }
 
     /// The board for a blank position with no pieces on it.
@@ -80,6 +107,7 @@ impl Board {
             piece_masks: [0; Piece::LIMIT as usize],
             color_masks: [0; 2],
             pieces: [Piece::Null; 64],
+            material_key: 0,
         }
     }
 
@@ -126,7 +154,7 @@ impl Board {
     }
 
     /// True if any sliding attacker in `attackers` sees `square` along a ray with `occupied` blockers.
-    fn is_square_attacked_by_sliding(
+    const fn is_square_attacked_by_sliding(
         &self,
         square: Square,
         occupied: Bitboard,
@@ -154,12 +182,30 @@ impl Board {
 
     #[inline]
     /// Returns non-sliding attackers (pawn/knight/king) on `square`.
-    pub fn non_sliding_attacks_on_square(&self, square: Square, by: Color) -> Bitboard {
+    pub const fn non_sliding_attacks_on_square(&self, square: Square, by: Color) -> Bitboard {
         (multi_pawn_attacks(square.mask(), by.other()) & self.piece_mask::<{ Piece::Pawn }>())
             | (single_knight_attacks(square) & self.piece_mask::<{ Piece::Knight }>())
             | (single_king_attacks(square) & self.piece_mask::<{ Piece::King }>())
     }
 
+    /// Returns every square attacked by `by_color`'s pieces (the attackers' own squares may be
+    /// included when defended).
+    pub fn attacked_squares(&self, by_color: Color) -> Bitboard {
+        let attackers = self.color_mask_at(by_color);
+        let occupied = self.pieces();
+        let mut attacks =
+            multi_pawn_attacks(attackers & self.piece_mask::<{ Piece::Pawn }>(), by_color)
+                | multi_knight_attacks(attackers & self.piece_mask::<{ Piece::Knight }>())
+                | multi_king_attacks(attackers & self.piece_mask::<{ Piece::King }>());
+        for square in (attackers & self.diagonal_sliders()).iter_set_bits_as_squares() {
+            attacks |= single_bishop_attacks(square, occupied);
+        }
+        for square in (attackers & self.orthogonal_sliders()).iter_set_bits_as_squares() {
+            attacks |= single_rook_attacks(square, occupied);
+        }
+        attacks
+    }
+
     /// Returns whether any square in `mask` is attacked by `by_color`.
     pub fn is_mask_attacked(&self, mask: Bitboard, by_color: Color) -> bool {
         let attackers = self.color_mask_at(by_color);
@@ -178,13 +224,13 @@ impl Board {
 
     #[inline]
     /// Returns whether `square` is attacked by `by_color`.
-    pub fn is_square_attacked(&self, square: Square, by_color: Color) -> bool {
+    pub const fn is_square_attacked(&self, square: Square, by_color: Color) -> bool {
         self.is_square_attacked_after_move(square, by_color, 0)
     }
 
     #[inline]
     /// Returns whether `square` is attacked by `by_color` after applying `move_mask` occupancy delta.
-    pub fn is_square_attacked_after_move(
+    pub const fn is_square_attacked_after_move(
         &self,
         square: Square,
         by_color: Color,
@@ -204,6 +250,9 @@ impl Board {
     }
 
     /// Populates a square with `piece`, but no color.
+    ///
+    /// Leaves [`Self::material_key`] alone, as the piece has no color yet; use
+    /// [`Self::put_piece_and_color`] to place a piece that counts.
     #[inline]
     pub const fn put_piece_at(&mut self, piece: Piece, square: Square) {
         let mask = square.mask();
@@ -215,6 +264,7 @@ impl Board {
     /// Populates a square with both `color` and `piece`.
     #[inline]
     pub const fn put_piece_and_color(&mut self, color: Color, piece: Piece, square: Square) {
+        self.material_key += material_key_unit(color, piece);
         self.put_color_at(color, square);
         self.put_piece_at(piece, square);
     }
@@ -227,8 +277,15 @@ impl Board {
     }
 
     /// Removes `piece` from a square, but not color.
+    ///
+    /// Leaves [`Self::material_key`] alone; use [`Self::remove_piece_and_color`] to take a piece
+    /// off the board.
     #[inline]
     pub const fn remove_piece_at(&mut self, piece: Piece, square: Square) {
+        debug_assert!(
+            self.piece_at(square) as u8 == piece as u8,
+            "removed piece is not on the square"
+        );
         let mask = square.mask();
         self.piece_masks[piece as usize] &= !mask;
         self.piece_masks[Piece::ALL_PIECES as usize] &= !mask;
@@ -238,8 +295,13 @@ impl Board {
     /// Removes both `color` and `piece` from a square.
     #[inline]
     pub const fn remove_piece_and_color(&mut self, color: Color, piece: Piece, square: Square) {
-        self.remove_color_at(color, square);
+        debug_assert!(
+            self.color_masks[color as usize] & square.mask() != 0,
+            "removed piece does not have the given color"
+        );
         self.remove_piece_at(piece, square);
+        self.material_key -= material_key_unit(color, piece);
+        self.remove_color_at(color, square);
     }
 
     /// Moves `piece` from `from` to `to`.
@@ -300,6 +362,76 @@ impl Board {
         Color::from_is_black(self.color_masks[Color::Black as usize] & mask != 0)
     }
 
+    /// Returns the colored piece at `square`, or `None` if it is empty.
+    pub const fn colored_piece_at(&self, square: Square) -> Option<ColoredPiece> {
+        match self.is_occupied_at(square) {
+            true => Some(ColoredPiece::new(
+                self.color_at(square),
+                self.piece_at(square),
+            )),
+            false => None,
+        }
+    }
+
+    /// Lists the squares whose contents differ from `other`, as `(square, here, there)` in
+    /// [`Square::ALL`] order, where `here` is this board's piece and `there` is `other`'s.
+    ///
+    /// Useful for animating a jump between two positions or explaining a failed comparison.
+    pub fn diff(&self, other: &Board) -> Vec<(Square, Option<ColoredPiece>, Option<ColoredPiece>)> {
+        Square::ALL
+            .into_iter()
+            .filter_map(|square| {
+                let here = self.colored_piece_at(square);
+                let there = other.colored_piece_at(square);
+                (here != there).then_some((square, here, there))
+            })
+            .collect()
+    }
+
+    /// Number of `color`'s pieces of type `piece` (`0` for [`Piece::Null`]).
+    ///
+    /// Read from [`Self::material_key`], except for kings, which it leaves out.
+    #[inline]
+    pub const fn piece_count(&self, color: Color, piece: Piece) -> u8 {
+        match piece {
+            Piece::Null => 0,
+            Piece::King => (self.piece_masks[Piece::King as usize]
+                & self.color_masks[color as usize])
+                .count_ones() as u8,
+            _ => ((self.material_key >> material_key_shift(color, piece)) & 63) as u8,
+        }
+    }
+
+    /// Number of pieces of both colors on the board, kings included.
+    #[inline]
+    pub const fn total_piece_count(&self) -> u32 {
+        self.pieces().count_ones()
+    }
+
+    /// Packed piece counts of both colors, six bits per colored piece from pawn to queen with
+    /// White's first, kept up to date as pieces are put and removed. Kings are left out, so every
+    /// board fits without one count overflowing into the next.
+    ///
+    /// Boards with the same material have the same key, so it can index endgame tables or
+    /// tablebase availability without counting pieces.
+    #[inline]
+    pub const fn material_key(&self) -> u64 {
+        self.material_key
+    }
+
+    /// Recomputes [`Self::material_key`] from the piece and color masks.
+    const fn calc_material_key(&self) -> u64 {
+        let mut key = 0;
+        for color in Color::ALL {
+            for piece in Piece::PIECES {
+                let count = (self.piece_masks[piece as usize] & self.color_masks[color as usize])
+                    .count_ones() as u64;
+                key += count * material_key_unit(color, piece);
+            }
+        }
+        key
+    }
+
     /// Checks if the board is consistent (color masks, individual piece type masks, all occupancy).
     pub const fn is_consistent(&self) -> bool {
         let white_mask = self.color_masks[Color::White as usize];
@@ -351,7 +483,7 @@ impl Board {
             }
         }
 
-        true
+        self.material_key == self.calc_material_key()
     }
 
     /// Checks if the board has one king of each color.
@@ -386,3 +518,100 @@ mod const_eval_smoke_tests {
         assert_eq!(PAWN_MASK, INITIAL.piece_mask::<{ Piece::Pawn }>());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Board;
+    use crate::types::{Color, ColoredPiece, Piece, Position, Square};
+
+    #[test]
+    fn test_piece_counts_follow_edits() {
+        let mut board = Board::initial();
+        assert_eq!(board.piece_count(Color::White, Piece::Pawn), 8);
+        assert_eq!(board.piece_count(Color::Black, Piece::Knight), 2);
+        assert_eq!(board.piece_count(Color::Black, Piece::Null), 0);
+        assert_eq!(board.total_piece_count(), 32);
+        let initial_key = board.material_key();
+
+        // Black captures on d2, and White promotes on e8 by capturing the piece there.
+        board.remove_piece_and_color(Color::White, Piece::Pawn, Square::D2);
+        board.move_piece_and_color(Color::Black, Piece::Pawn, Square::D7, Square::D2);
+        board.remove_piece_and_color(Color::Black, Piece::King, Square::E8);
+        board.move_piece_and_color(Color::White, Piece::Pawn, Square::E2, Square::E8);
+        board.remove_piece_and_color(Color::White, Piece::Pawn, Square::E8);
+        board.put_piece_and_color(Color::White, Piece::Queen, Square::E8);
+        assert!(board.is_consistent());
+        assert_eq!(board.piece_count(Color::White, Piece::Pawn), 6);
+        assert_eq!(board.piece_count(Color::White, Piece::Queen), 2);
+        assert_eq!(board.piece_count(Color::Black, Piece::Pawn), 8);
+        assert_eq!(board.piece_count(Color::Black, Piece::King), 0);
+        assert_eq!(board.total_piece_count(), 30);
+        assert_ne!(board.material_key(), initial_key);
+
+        // Material alone decides the key.
+        let mut developed = Board::initial();
+        developed.move_piece_and_color(Color::White, Piece::Knight, Square::G1, Square::F3);
+        assert_eq!(developed.material_key(), initial_key);
+    }
+
+    #[test]
+    fn test_material_key_counts_only_colored_pieces() {
+        let mut board = Board::blank();
+        board.put_piece_at(Piece::Knight, Square::C3);
+        assert_eq!(board.material_key(), 0);
+        board.remove_piece_at(Piece::Knight, Square::C3);
+
+        board.put_piece_and_color(Color::Black, Piece::Knight, Square::C3);
+        assert_eq!(board.piece_count(Color::Black, Piece::Knight), 1);
+        assert_eq!(board.piece_count(Color::White, Piece::Knight), 0);
+        board.remove_piece_and_color(Color::Black, Piece::Knight, Square::C3);
+        assert_eq!(board.material_key(), 0);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "removed piece is not on the square")]
+    fn test_removing_a_missing_piece_panics_in_debug() {
+        let mut board = Board::initial();
+        board.remove_piece_at(Piece::Pawn, Square::E4);
+    }
+
+    #[test]
+    fn test_piece_counts_do_not_overflow() {
+        let position =
+            Position::<1, { Color::White }>::from_fen("k7/8/8/NNNNNNNN/NNNNNNNN/8/8/7K w - - 0 1")
+                .unwrap();
+        let board = &position.board;
+        assert_eq!(board.piece_count(Color::White, Piece::Knight), 16);
+        assert_eq!(board.piece_count(Color::White, Piece::Bishop), 0);
+        assert!(!board.are_both_sides_insufficient_material::<true>());
+
+        let mut fewer = board.clone();
+        fewer.remove_piece_and_color(Color::White, Piece::Knight, Square::A5);
+        assert_ne!(fewer.material_key(), board.material_key());
+        assert!(fewer.is_consistent());
+    }
+
+    #[test]
+    fn test_diff() {
+        let initial = Board::initial();
+        assert!(initial.diff(&initial).is_empty());
+
+        let mut after_e4 = initial.clone();
+        after_e4.move_piece_and_color(Color::White, Piece::Pawn, Square::E2, Square::E4);
+        assert_eq!(
+            initial.diff(&after_e4),
+            [
+                (Square::E4, None, Some(ColoredPiece::WhitePawn)),
+                (Square::E2, Some(ColoredPiece::WhitePawn), None),
+            ]
+        );
+        assert_eq!(
+            after_e4.diff(&initial),
+            [
+                (Square::E4, Some(ColoredPiece::WhitePawn), None),
+                (Square::E2, None, Some(ColoredPiece::WhitePawn)),
+            ]
+        );
+    }
+}
@@ -1,6 +1,12 @@
 //! Piece color and side-to-move marker.
 
-use super::{rank::Rank, square::Square};
+use std::{fmt, str::FromStr};
+
+use super::{
+    rank::Rank,
+    square::Square,
+    square_delta::{SquareDelta, SquareDeltaUtils},
+};
 use crate::utilities::{Array, IterableEnum, impl_u8_conversions};
 
 /// Chess color (White/Black) used for pieces and as a const-generic side-to-move marker.
@@ -23,6 +29,23 @@ impl Color {
         unsafe { std::mem::transmute::<bool, Color>(is_black) }
     }
 
+    /// Parses a FEN side-to-move letter (`'w'` or `'b'`).
+    pub const fn from_char(c: char) -> Option<Color> {
+        match c {
+            'w' => Some(Color::White),
+            'b' => Some(Color::Black),
+            _ => None,
+        }
+    }
+
+    /// FEN side-to-move letter (`'w'` or `'b'`).
+    pub const fn as_char(self) -> char {
+        match self {
+            Color::White => 'w',
+            Color::Black => 'b',
+        }
+    }
+
     /// The opposite color (White ↔ Black).
     ///
     /// Takes `self` by value so this method can be used in const-generic position:
@@ -44,19 +67,84 @@ impl Color {
 
     /// The rank where pawns of this color can capture en passant.
     pub const fn en_passant_capture_rank(self) -> Rank {
-        match self {
-            Self::White => Rank::Five,
-            Self::Black => Rank::Four,
-        }
+        EN_PASSANT_CAPTURE_RANK[self as usize]
+    }
+
+    /// The rank a pawn of this color lands on when capturing en passant.
+    pub const fn en_passant_target_rank(self) -> Rank {
+        EN_PASSANT_TARGET_RANK[self as usize]
+    }
+
+    /// The rank this color's king and rooks start on (rank 1 for White, rank 8 for Black).
+    pub const fn back_rank(self) -> Rank {
+        BACK_RANK[self as usize]
+    }
+
+    /// The rank this color's pawns start on.
+    pub const fn pawn_start_rank(self) -> Rank {
+        PAWN_START_RANK[self as usize]
+    }
+
+    /// The rank this color's pawns land on after a double push.
+    pub const fn pawn_double_push_rank(self) -> Rank {
+        PAWN_DOUBLE_PUSH_RANK[self as usize]
+    }
+
+    /// The rank this color's pawns promote on.
+    pub const fn promotion_rank(self) -> Rank {
+        PROMOTION_RANK[self as usize]
+    }
+
+    /// `rank` as seen from this color's side: unchanged for White, mirrored for Black, so
+    /// `color.relative_rank(Rank::Three)` is the rank of a single pawn push from the start.
+    pub const fn relative_rank(self, rank: Rank) -> Rank {
+        rank.from_perspective(self)
+    }
+
+    /// Square-index delta of a single pawn push for this color.
+    pub const fn pawn_push_delta(self) -> SquareDelta {
+        PAWN_PUSH_DELTA[self as usize]
     }
 }
 
+// Per-color tables, indexed by `Color as usize`, so color-dependent constants are plain lookups.
+const EN_PASSANT_CAPTURE_RANK: [Rank; 2] = [Rank::Five, Rank::Four];
+const EN_PASSANT_TARGET_RANK: [Rank; 2] = [Rank::Six, Rank::Three];
+const BACK_RANK: [Rank; 2] = [Rank::One, Rank::Eight];
+const PAWN_START_RANK: [Rank; 2] = [Rank::Two, Rank::Seven];
+const PAWN_DOUBLE_PUSH_RANK: [Rank; 2] = [Rank::Four, Rank::Five];
+const PROMOTION_RANK: [Rank; 2] = [Rank::Eight, Rank::One];
+const PAWN_PUSH_DELTA: [SquareDelta; 2] = [SquareDelta::UP, SquareDelta::DOWN];
+
 impl const IterableEnum<2> for Color {
     const ALL: Array<Color, 2> = Array([Color::White, Color::Black]);
 }
 
 impl_u8_conversions!(Color, 2);
 
+/// Formats the color as `White` or `Black`.
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::White => write!(f, "White"),
+            Color::Black => write!(f, "Black"),
+        }
+    }
+}
+
+/// Parses `w`, `b`, `white` or `black`, ignoring case.
+impl FromStr for Color {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "w" | "white" => Ok(Color::White),
+            "b" | "black" => Ok(Color::Black),
+            _ => Err("Color must be w, b, white or black"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,4 +158,42 @@ mod tests {
         assert_eq!(Color::from_is_black(false), Color::White);
         assert_eq!(Color::from_is_black(true), Color::Black);
     }
+
+    #[test]
+    fn test_color_text() {
+        for color in Color::ALL {
+            assert_eq!(Color::from_char(color.as_char()), Some(color));
+            assert_eq!(color.to_string().parse::<Color>(), Ok(color));
+            assert_eq!(color.as_char().to_string().parse::<Color>(), Ok(color));
+        }
+        assert_eq!(Color::from_char('W'), None);
+        assert_eq!("BLACK".parse::<Color>(), Ok(Color::Black));
+        assert!("red".parse::<Color>().is_err());
+        assert_eq!(Color::White.to_string(), "White");
+    }
+
+    #[test]
+    fn test_per_color_ranks_mirror() {
+        for color in Color::ALL {
+            let other = color.other();
+            assert_eq!(color.back_rank(), other.back_rank().mirrored());
+            assert_eq!(color.pawn_start_rank(), other.pawn_start_rank().mirrored());
+            assert_eq!(color.promotion_rank(), other.back_rank());
+            assert_eq!(
+                color.pawn_double_push_rank(),
+                other.en_passant_capture_rank()
+            );
+            assert_eq!(color.pawn_push_delta(), -other.pawn_push_delta());
+        }
+        assert_eq!(Color::White.pawn_start_rank(), Rank::Two);
+        assert_eq!(Color::Black.en_passant_target_rank(), Rank::Three);
+        assert_eq!(Color::White.relative_rank(Rank::Three), Rank::Three);
+        assert_eq!(Color::Black.relative_rank(Rank::Three), Rank::Six);
+        assert_eq!(Square::E2.relative_to(Color::Black), Square::E7);
+        assert_eq!(Square::E2.relative_to(Color::White), Square::E2);
+        assert_eq!(
+            Square::E2.relative(Color::White.pawn_push_delta()),
+            Some(Square::E3)
+        );
+    }
 }
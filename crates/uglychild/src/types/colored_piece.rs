@@ -1,4 +1,5 @@
 use super::{color::Color, piece::Piece};
+use crate::utilities::Array;
 
 #[repr(u8)]
 #[derive(Clone, Copy, Eq, Debug)]
@@ -26,6 +27,22 @@ impl ColoredPiece {
     /// The numeric difference between the white and black colored pieces values.
     pub const COLOR_DIFFERENCE: u8 = 8;
 
+    /// All actual colored pieces (excludes `NoPiece`), in [`Self::index`] order.
+    pub const ALL: Array<ColoredPiece, 12> = Array([
+        ColoredPiece::WhitePawn,
+        ColoredPiece::WhiteKnight,
+        ColoredPiece::WhiteBishop,
+        ColoredPiece::WhiteRook,
+        ColoredPiece::WhiteQueen,
+        ColoredPiece::WhiteKing,
+        ColoredPiece::BlackPawn,
+        ColoredPiece::BlackKnight,
+        ColoredPiece::BlackBishop,
+        ColoredPiece::BlackRook,
+        ColoredPiece::BlackQueen,
+        ColoredPiece::BlackKing,
+    ]);
+
     /// Returns a new ColoredPiece.
     pub const fn new(color: Color, piece: Piece) -> ColoredPiece {
         let is_piece = piece as u8 != Piece::Null as u8;
@@ -43,6 +60,41 @@ impl ColoredPiece {
         unsafe { std::mem::transmute::<u8, Piece>(*self as u8 & 0b111) }
     }
 
+    /// Dense index in `0..12`: white pawn through king, then black pawn through king.
+    ///
+    /// Must not be called on `NoPiece`.
+    pub const fn index(&self) -> usize {
+        debug_assert!(
+            !matches!(self, ColoredPiece::NoPiece),
+            "NoPiece has no index"
+        );
+        self.color() as usize * 6 + self.piece() as usize - 1
+    }
+
+    /// Inverse of [`Self::index`]; `None` for indices `12` and above.
+    pub const fn from_index(index: usize) -> Option<ColoredPiece> {
+        if index < 12 {
+            Some(ColoredPiece::ALL.0[index])
+        } else {
+            None
+        }
+    }
+
+    /// Parses a FEN piece letter (uppercase for White, lowercase for Black).
+    pub const fn from_fen_char(c: char) -> Option<ColoredPiece> {
+        match ColoredPiece::from_ascii(c) {
+            ColoredPiece::NoPiece => None,
+            colored_piece => Some(colored_piece),
+        }
+    }
+
+    /// Returns the FEN piece letter (uppercase for White, lowercase for Black).
+    ///
+    /// `NoPiece` maps to a space, which is not valid FEN.
+    pub const fn to_fen_char(&self) -> char {
+        self.ascii()
+    }
+
     /// Returns a ColoredPiece from an ASCII character.
     pub const fn from_ascii(c: char) -> ColoredPiece {
         match c {
@@ -142,4 +194,19 @@ mod tests {
         assert_eq!(ColoredPiece::BlackPawn.unicode(), '♟');
         assert_eq!(ColoredPiece::NoPiece.unicode(), ' ');
     }
+
+    #[test]
+    fn test_fen_chars_and_indices() {
+        for (index, colored_piece) in ColoredPiece::ALL.into_iter().enumerate() {
+            assert_eq!(colored_piece.index(), index);
+            assert_eq!(ColoredPiece::from_index(index), Some(colored_piece));
+            assert_eq!(
+                ColoredPiece::from_fen_char(colored_piece.to_fen_char()),
+                Some(colored_piece)
+            );
+        }
+        assert_eq!(ColoredPiece::from_index(12), None);
+        assert_eq!(ColoredPiece::from_fen_char('x'), None);
+        assert_eq!(ColoredPiece::BlackKnight.to_fen_char(), 'n');
+    }
 }
@@ -1,12 +1,12 @@
 //! Sentinel-encoded file for the side that just double-pushed a pawn (`-1` = none, `0..=7` = `File` index).
 
 use super::{
-    bitboard::Bitboard,
+    QueenLikeMoveDirection,
+    bitboard::{Bitboard, BitboardUtils},
     board::Board,
     color::Color,
     file::File,
     piece::Piece,
-    rank::Rank,
     square::Square,
 };
 
@@ -45,7 +45,7 @@ pub const trait ConstDoublePawnPushFile: private::Sealed {
 }
 
 /// [`ConstDoublePawnPushFile`] plus validation that needs a [`Board`] read.
-pub trait DoublePawnPushFileUtils: ConstDoublePawnPushFile + private::Sealed {
+pub const trait DoublePawnPushFileUtils: ConstDoublePawnPushFile + private::Sealed {
     /// Whether this value is consistent with pawn placement (used by FEN / position validation).
     fn ep_target_is_valid(self, halfmove: u16, side_to_move: Color, board: &Board) -> bool;
 }
@@ -89,13 +89,11 @@ impl const ConstDoublePawnPushFile for DoublePawnPushFile {
             let value = self as u8;
             unsafe { File::try_from(value).unwrap_unchecked() }
         };
-        let double_pawn_push_dst = match stm {
-            Color::White => Square::from_rank_and_file(Rank::Five, f).mask(),
-            Color::Black => Square::from_rank_and_file(Rank::Four, f).mask(),
-        };
+        let double_pawn_push_dst =
+            Square::from_rank_and_file(stm.en_passant_capture_rank(), f).mask();
 
-        ((double_pawn_push_dst << 1) & !File::H.mask())
-            | ((double_pawn_push_dst >> 1) & !File::A.mask())
+        double_pawn_push_dst.shift(QueenLikeMoveDirection::Left)
+            | double_pawn_push_dst.shift(QueenLikeMoveDirection::Right)
     }
 
     fn ep_dst_square(self, stm: Color) -> Square {
@@ -104,10 +102,7 @@ impl const ConstDoublePawnPushFile for DoublePawnPushFile {
             let value = self as u8;
             unsafe { File::try_from(value).unwrap_unchecked() }
         };
-        match stm {
-            Color::White => Square::from_rank_and_file(Rank::Six, f),
-            Color::Black => Square::from_rank_and_file(Rank::Three, f),
-        }
+        Square::from_rank_and_file(stm.en_passant_target_rank(), f)
     }
 
     fn ep_capture_square(self, stm: Color) -> Square {
@@ -116,14 +111,11 @@ impl const ConstDoublePawnPushFile for DoublePawnPushFile {
             let value = self as u8;
             unsafe { File::try_from(value).unwrap_unchecked() }
         };
-        match stm {
-            Color::White => Square::from_rank_and_file(Rank::Five, f),
-            Color::Black => Square::from_rank_and_file(Rank::Four, f),
-        }
+        Square::from_rank_and_file(stm.en_passant_capture_rank(), f)
     }
 }
 
-impl DoublePawnPushFileUtils for DoublePawnPushFile {
+impl const DoublePawnPushFileUtils for DoublePawnPushFile {
     fn ep_target_is_valid(self, halfmove: u16, side_to_move: Color, board: &Board) -> bool {
         if !self.has_file() {
             return true;
@@ -136,10 +128,7 @@ impl DoublePawnPushFileUtils for DoublePawnPushFile {
         let colored_pawns_mask = pawns_mask & board.color_mask_at(color_just_moved);
         debug_assert!(self.has_file());
         let file_mask = unsafe { File::try_from(self as u8).unwrap_unchecked() }.mask();
-        let rank_mask = match color_just_moved {
-            Color::White => Rank::Four.mask(),
-            Color::Black => Rank::Five.mask(),
-        };
+        let rank_mask = color_just_moved.pawn_double_push_rank().mask();
         colored_pawns_mask & file_mask & rank_mask != 0
     }
 }
@@ -1,5 +1,7 @@
 //! Chess files a–h. Line masks derived from a single file-a bitboard, shifted by file index (chmog-style).
 
+use std::fmt;
+
 use super::{bitboard::Bitboard, flank::Flank};
 use crate::utilities::{Array, IterableEnum, impl_u8_conversions};
 
@@ -31,6 +33,39 @@ impl File {
         let is_queenside = self as u8 <= File::D as u8;
         Flank::from_bool(is_queenside)
     }
+
+    /// File `delta` steps toward [`File::H`] (negative toward [`File::A`]), or `None` off the board.
+    #[inline]
+    pub const fn offset(self, delta: i8) -> Option<File> {
+        let value = self as i8 + delta;
+        if value >= 0 && value < 8 {
+            Some(unsafe { File::try_from(value as u8).unwrap_unchecked() })
+        } else {
+            None
+        }
+    }
+
+    /// Lowercase file letter (`'a'`–`'h'`).
+    #[inline]
+    pub const fn as_char(self) -> char {
+        (b'a' + self as u8) as char
+    }
+
+    /// Parses a file letter (`'a'`–`'h'`, case-insensitive).
+    pub const fn from_char(c: char) -> Option<File> {
+        match c.to_ascii_lowercase() {
+            'a'..='h' => Some(unsafe {
+                File::try_from(c.to_ascii_lowercase() as u8 - b'a').unwrap_unchecked()
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for File {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_char())
+    }
 }
 
 impl const PartialEq for File {
@@ -53,3 +88,26 @@ impl const IterableEnum<8> for File {
 }
 
 impl_u8_conversions!(File, 8);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset() {
+        assert_eq!(File::A.offset(7), Some(File::H));
+        assert_eq!(File::E.offset(-2), Some(File::C));
+        assert_eq!(File::A.offset(-1), None);
+        assert_eq!(File::H.offset(1), None);
+    }
+
+    #[test]
+    fn test_char_round_trip() {
+        for file in File::ALL {
+            assert_eq!(File::from_char(file.as_char()), Some(file));
+        }
+        assert_eq!(File::from_char('E'), Some(File::E));
+        assert_eq!(File::from_char('i'), None);
+        assert_eq!(File::C.to_string(), "c");
+    }
+}
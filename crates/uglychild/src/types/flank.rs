@@ -2,7 +2,7 @@
 
 use std::mem;
 
-use super::{bitboard::Bitboard, color::Color, file::File, rank::Rank, square::Square};
+use super::{bitboard::Bitboard, color::Color, file::File, square::Square};
 use crate::utilities::{Array, IterableEnum, impl_u8_conversions};
 
 #[repr(u8)]
@@ -40,11 +40,10 @@ impl Flank {
     }
 
     /// Empty squares required between king and rook in the starting layout (per color and flank).
+    ///
+    /// Also available as the `*_CASTLING_GAP` constants in [`masks`](crate::logic::masks).
     pub const fn castling_gap_mask(self, color: Color) -> Bitboard {
-        let back = match color {
-            Color::White => Rank::One,
-            Color::Black => Rank::Eight,
-        };
+        let back = color.back_rank();
         let gap_files = match self {
             Flank::Kingside => File::F.mask() | File::G.mask(),
             Flank::Queenside => File::B.mask() | File::C.mask() | File::D.mask(),
@@ -53,6 +52,8 @@ impl Flank {
     }
 
     /// Squares the king passes through or lands on (excluding start); used for attack tests when castling.
+    ///
+    /// Also available as the `*_CASTLING_KING_PATH` constants in [`masks`](crate::logic::masks).
     pub const fn king_path_mask(self, color: Color) -> Bitboard {
         match (color, self) {
             (Color::White, Flank::Kingside) => Square::F1.mask() | Square::G1.mask(),
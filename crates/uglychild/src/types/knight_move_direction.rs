@@ -21,27 +21,29 @@ pub enum KnightMoveDirection {
 impl KnightMoveDirection {
     /// Returns the KnightMoveDirection opposite to the current direction.
     pub const fn opposite(&self) -> KnightMoveDirection {
-        unsafe { KnightMoveDirection::from(7u8.wrapping_sub(*self as u8)) }
+        unsafe { KnightMoveDirection::from_unchecked(7u8.wrapping_sub(*self as u8)) }
     }
 
-    /// Returns the KnightMoveDirection corresponding to the given value.
+    /// Returns the KnightMoveDirection corresponding to the given value, without checking it.
+    /// Use `KnightMoveDirection::try_from` for a checked conversion.
     /// # Safety
     /// The value must be in the range 0..=7.
-    pub const unsafe fn from(value: u8) -> KnightMoveDirection {
+    pub const unsafe fn from_unchecked(value: u8) -> KnightMoveDirection {
         unsafe { std::mem::transmute::<u8, KnightMoveDirection>(value) }
     }
 
     pub fn lookup(src_square: Square, dst_square: Square) -> Option<KnightMoveDirection> {
-        unsafe {
-            super::MOVE_DIRECTION_LOOKUP[src_square as usize][dst_square as usize].as_knight_like()
-        }
+        super::MOVE_DIRECTION_LOOKUP
+            .get(src_square, dst_square)
+            .as_knight_like()
     }
 
     /// # Safety
     /// `src_square` and `dst_square` must form a legal knight displacement.
     pub unsafe fn lookup_unchecked(src_square: Square, dst_square: Square) -> KnightMoveDirection {
         unsafe {
-            super::MOVE_DIRECTION_LOOKUP[src_square as usize][dst_square as usize]
+            super::MOVE_DIRECTION_LOOKUP
+                .get(src_square, dst_square)
                 .as_knight_like_unchecked()
         }
     }
@@ -4,6 +4,7 @@
 //! `PositionContext`), move primitives (`Move`, `MoveList`, `MoveFlag`), geometry
 //! (`Square`, `File`, `Rank`), and hashing policy types.
 
+mod attacks_by_color;
 mod bitboard;
 mod board;
 mod castling_rights;
@@ -15,8 +16,10 @@ mod flank;
 mod knight_move_direction;
 mod r#move;
 mod move_flag;
+mod move_kind;
 mod move_list;
 mod piece;
+mod piece_lists;
 mod position;
 mod position_context;
 mod queen_like_move_direction;
@@ -25,10 +28,12 @@ mod square;
 mod square_delta;
 mod typed_position;
 mod unified_move_direction;
+mod with_custom_zobrist;
 mod with_zobrist;
 mod without_zobrist;
 mod zobrist_policy;
 
+pub use attacks_by_color::*;
 pub use bitboard::*;
 pub use board::*;
 pub use castling_rights::*;
@@ -40,8 +45,10 @@ pub use flank::*;
 pub use knight_move_direction::*;
 pub use r#move::*;
 pub use move_flag::*;
+pub use move_kind::*;
 pub use move_list::*;
 pub use piece::*;
+pub use piece_lists::*;
 pub use position::*;
 pub use position_context::*;
 pub use queen_like_move_direction::*;
@@ -50,15 +57,16 @@ pub use square::*;
 pub use square_delta::*;
 pub use typed_position::*;
 pub use unified_move_direction::*;
+pub use with_custom_zobrist::*;
 pub use with_zobrist::*;
 pub use without_zobrist::*;
 pub use zobrist_policy::*;
 
-use crate::utilities::Array;
+use crate::utilities::{Array, SquaresTwoToOneMapping};
 
 /// Static lookup table for move directions between any two squares.
 /// This is used by QueenLikeMoveDirection, KnightMoveDirection, and UnifiedMoveDirection.
-static MOVE_DIRECTION_LOOKUP: Array<Array<UnifiedMoveDirection, 64>, 64> = {
+static MOVE_DIRECTION_LOOKUP: SquaresTwoToOneMapping<UnifiedMoveDirection> = {
     use crate::types::{KnightMoveDirection, QueenLikeMoveDirection, Square, same_line};
 
     const fn unified_move_direction_at(
@@ -77,13 +85,5 @@ static MOVE_DIRECTION_LOOKUP: Array<Array<UnifiedMoveDirection, 64>, 64> = {
         }
     }
 
-    let mut arr = [UnifiedMoveDirection::NULL; 64 * 64];
-    let mut i = 0usize;
-    while i < 64 * 64 {
-        let src_square = unsafe { Square::try_from((i / 64) as u8).unwrap_unchecked() };
-        let dst_square = unsafe { Square::try_from((i % 64) as u8).unwrap_unchecked() };
-        arr[i] = unified_move_direction_at(src_square, dst_square);
-        i += 1;
-    }
-    unsafe { std::mem::transmute(arr) }
+    SquaresTwoToOneMapping::init(unified_move_direction_at)
 };
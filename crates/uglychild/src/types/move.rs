@@ -1,6 +1,14 @@
 //! Compact 16-bit chess move encoding.
 
-use super::{board::Board, move_flag::MoveFlag, piece::Piece, square::Square};
+use super::{
+    board::Board,
+    color::Color,
+    move_flag::MoveFlag,
+    piece::Piece,
+    position::Position,
+    square::Square,
+    zobrist_policy::ZobristPolicy,
+};
 
 /// A chess move encoded in 16 bits.
 ///
@@ -64,13 +72,13 @@ impl Move {
     /// Gets the promotion piece type of the move.
     pub const fn promotion(&self) -> Piece {
         let promotion_int = ((self.value & 0b0000000000001100) >> 2) as u8;
-        unsafe { Piece::from(promotion_int + 2) }
+        unsafe { Piece::from_unchecked(promotion_int + 2) }
     }
 
     /// Gets the flag of the move.
     pub const fn flag(&self) -> MoveFlag {
         let flag_int = (self.value & 0b0000000000000011) as u8;
-        unsafe { MoveFlag::from(flag_int) }
+        unsafe { MoveFlag::from_unchecked(flag_int) }
     }
 
     /// Returns `true` if this move captures a piece on `board`.
@@ -85,10 +93,41 @@ impl Move {
         }
     }
 
-    /// Returns the UCI (Universal Chess Interface) representation of the move.
+    /// Returns `true` if this move captures a piece in `position` (see [`Self::is_capture_on_board`]).
+    #[inline]
+    pub const fn is_capture<const N: usize, const STM: Color, Z: ZobristPolicy>(
+        &self,
+        position: &Position<N, STM, Z>,
+    ) -> bool {
+        self.is_capture_on_board(&position.board)
+    }
+
+    /// Returns `true` if no later move can restore the position before this move.
+    ///
+    /// Pawn moves, captures, castling, and moves that remove castling rights are irreversible;
+    /// positions before an irreversible move can never repeat after it.
+    pub const fn is_irreversible<const N: usize, const STM: Color, Z: ZobristPolicy>(
+        &self,
+        position: &Position<N, STM, Z>,
+    ) -> bool {
+        let board = &position.board;
+        let castling_rights = position.context().castling_rights;
+        self.flag() != MoveFlag::NormalMove
+            || board.piece_at(self.from()) == Piece::Pawn
+            || board.is_occupied_at(self.to())
+            || castling_rights
+                .after_move(self.from())
+                .after_move(self.to())
+                != castling_rights
+    }
+
+    /// Returns the UCI (Universal Chess Interface) representation of the move, e.g. `e2e4` or
+    /// `a7a8q`.
+    ///
+    /// Castling is written as the king's move (`e1g1`). For SAN, see [`Move::display_with`].
     pub fn uci(&self) -> String {
         let promotion_str = match self.flag() {
-            MoveFlag::Promotion => self.promotion().uppercase_ascii().to_string(),
+            MoveFlag::Promotion => self.promotion().lowercase_ascii().to_string(),
             _ => "".to_string(),
         };
         format!(
@@ -100,6 +139,7 @@ impl Move {
     }
 }
 
+/// Writes the move as UCI text; see [`Move::uci`].
 impl std::fmt::Display for Move {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.uci())
@@ -116,7 +156,7 @@ impl std::fmt::Debug for Move {
 mod tests {
     use super::{Move, MoveFlag};
     use crate::{
-        types::{Piece, Square},
+        types::{Color, Piece, Position, Square},
         utilities::IterableEnum,
     };
 
@@ -126,7 +166,7 @@ mod tests {
             for from in Square::ALL {
                 for promotion_piece in Piece::PROMOTION_PIECES {
                     for flag_int in 0..4 {
-                        let flag = unsafe { MoveFlag::from(flag_int) };
+                        let flag = MoveFlag::try_from(flag_int).unwrap();
 
                         let move_ = Move::new(from, to, promotion_piece, flag);
                         assert_eq!(move_.to(), to);
@@ -138,4 +178,37 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_display_is_uci() {
+        let promotion = Move::new(Square::A7, Square::A8, Piece::Queen, MoveFlag::Promotion);
+        assert_eq!(promotion.to_string(), "a7a8q");
+        assert_eq!(format!("{promotion:?}"), "a7a8q");
+        let castling = Move::new_non_promotion(Square::E1, Square::G1, MoveFlag::Castling);
+        assert_eq!(castling.to_string(), "e1g1");
+    }
+
+    #[test]
+    fn test_is_capture_and_is_irreversible() {
+        let position =
+            Position::<1, { Color::White }>::from_fen("r3k2r/8/8/8/8/8/4P3/R3K1NR w KQkq - 0 10")
+                .unwrap();
+        let normal = |from, to| Move::new_non_promotion(from, to, MoveFlag::NormalMove);
+
+        let knight_move = normal(Square::G1, Square::F3);
+        assert!(!knight_move.is_capture(&position));
+        assert!(!knight_move.is_irreversible(&position));
+
+        assert!(normal(Square::E2, Square::E3).is_irreversible(&position));
+        assert!(normal(Square::H1, Square::H2).is_irreversible(&position));
+        assert!(normal(Square::E1, Square::F1).is_irreversible(&position));
+
+        let capture = normal(Square::A1, Square::A8);
+        assert!(capture.is_capture(&position));
+        assert!(capture.is_irreversible(&position));
+        assert!(
+            Move::new_non_promotion(Square::E1, Square::C1, MoveFlag::Castling)
+                .is_irreversible(&position)
+        );
+    }
 }
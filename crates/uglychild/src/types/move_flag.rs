@@ -1,5 +1,7 @@
 //! Move classification flags for special move handling.
 
+use crate::utilities::impl_u8_conversions;
+
 /// Classification of special chess move types encoded in the low 2 bits of a [`Move`](crate::types::Move).
 #[repr(u8)]
 #[derive(Clone, Copy, Eq, Debug)]
@@ -16,11 +18,12 @@ pub enum MoveFlag {
 }
 
 impl MoveFlag {
-    /// Creates a `MoveFlag` from its numeric value.
+    /// Creates a `MoveFlag` from its numeric value without checking it.
+    /// Use `MoveFlag::try_from` for a checked conversion.
     ///
     /// # Safety
     /// `value` must be in range `0..4`. Values outside this range are undefined behavior.
-    pub const unsafe fn from(value: u8) -> MoveFlag {
+    pub const unsafe fn from_unchecked(value: u8) -> MoveFlag {
         debug_assert!(value < 4, "Invalid MoveFlag value");
         unsafe { std::mem::transmute::<u8, MoveFlag>(value) }
     }
@@ -36,8 +39,4 @@ impl MoveFlag {
     }
 }
 
-impl From<u8> for MoveFlag {
-    fn from(value: u8) -> MoveFlag {
-        unsafe { MoveFlag::from(value) }
-    }
-}
+impl_u8_conversions!(MoveFlag, 4);
@@ -0,0 +1,144 @@
+//! Rich move classification derived from a move's flag and the board it is played on.
+
+use super::{
+    board::Board,
+    color::Color,
+    double_pawn_push_file::{ConstDoublePawnPushFile, DoublePawnPushFile},
+    flank::Flank,
+    r#move::Move,
+    move_flag::MoveFlag,
+    piece::Piece,
+    position::Position,
+    zobrist_policy::ZobristPolicy,
+};
+
+/// What kind of move a [`Move`] is in a given position.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum MoveKind {
+    /// Non-capturing, non-special move.
+    QuietMove,
+    /// Two-square pawn advance from its starting rank.
+    DoublePawnPush,
+    /// Ordinary capture of `victim`.
+    Capture {
+        /// The captured piece.
+        victim: Piece,
+    },
+    /// En-passant pawn capture.
+    EnPassant,
+    /// Pawn promotion to `piece`, possibly capturing.
+    Promotion {
+        /// The piece promoted to.
+        piece: Piece,
+        /// Whether the promoting pawn captures.
+        is_capture: bool,
+    },
+    /// Castling toward `side`.
+    Castle {
+        /// Kingside or queenside.
+        side: Flank,
+    },
+}
+
+impl Move {
+    /// Classifies this move as played on `board` (the board before the move).
+    pub const fn classify_on_board(&self, board: &Board) -> MoveKind {
+        let from = self.from();
+        let to = self.to();
+        match self.flag() {
+            MoveFlag::Castling => MoveKind::Castle {
+                side: to.file().flank(),
+            },
+            MoveFlag::EnPassant => MoveKind::EnPassant,
+            MoveFlag::Promotion => MoveKind::Promotion {
+                piece: self.promotion(),
+                is_capture: board.is_occupied_at(to),
+            },
+            MoveFlag::NormalMove => {
+                let victim = board.piece_at(to);
+                if victim != Piece::Null {
+                    MoveKind::Capture { victim }
+                } else if board.piece_at(from) == Piece::Pawn
+                    && DoublePawnPushFile::from_pawn_step(from, to).has_file()
+                {
+                    MoveKind::DoublePawnPush
+                } else {
+                    MoveKind::QuietMove
+                }
+            }
+        }
+    }
+
+    /// Classifies this move in `position` (before the move is made).
+    #[inline]
+    pub const fn classify<const N: usize, const STM: Color, Z: ZobristPolicy>(
+        &self,
+        position: &Position<N, STM, Z>,
+    ) -> MoveKind {
+        self.classify_on_board(&position.board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MoveList, Square};
+
+    #[test]
+    fn test_classify() {
+        let position = Position::<1, { Color::White }>::from_fen(
+            "r3k2r/1P6/8/3pP3/8/8/P7/R3K2R w KQkq d6 0 10",
+        )
+        .unwrap();
+        let mut moves = MoveList::new();
+        position.generate_moves(&mut moves);
+        let find = |from: Square, to: Square, promotion: Piece| {
+            *moves
+                .as_slice()
+                .iter()
+                .find(|move_| {
+                    move_.from() == from
+                        && move_.to() == to
+                        && (move_.flag() != MoveFlag::Promotion || move_.promotion() == promotion)
+                })
+                .unwrap()
+        };
+
+        let kind = |from, to| find(from, to, Piece::Queen).classify(&position);
+        assert_eq!(kind(Square::A2, Square::A3), MoveKind::QuietMove);
+        assert_eq!(kind(Square::A2, Square::A4), MoveKind::DoublePawnPush);
+        assert_eq!(kind(Square::E5, Square::D6), MoveKind::EnPassant);
+        assert_eq!(
+            kind(Square::H1, Square::H8),
+            MoveKind::Capture {
+                victim: Piece::Rook
+            }
+        );
+        assert_eq!(
+            kind(Square::E1, Square::G1),
+            MoveKind::Castle {
+                side: Flank::Kingside
+            }
+        );
+        assert_eq!(
+            kind(Square::E1, Square::C1),
+            MoveKind::Castle {
+                side: Flank::Queenside
+            }
+        );
+        assert_eq!(
+            kind(Square::B7, Square::B8),
+            MoveKind::Promotion {
+                piece: Piece::Queen,
+                is_capture: false
+            }
+        );
+        assert_eq!(
+            find(Square::B7, Square::A8, Piece::Knight).classify(&position),
+            MoveKind::Promotion {
+                piece: Piece::Knight,
+                is_capture: true
+            }
+        );
+    }
+}
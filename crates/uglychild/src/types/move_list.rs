@@ -64,6 +64,12 @@ impl<const MAX_MOVES: usize> MoveList<MAX_MOVES> {
         &self.moves[..self.len]
     }
 
+    #[inline]
+    /// Returns a mutable slice view of the populated prefix (e.g. for in-place ordering).
+    pub const fn as_mut_slice(&mut self) -> &mut [Move] {
+        &mut self.moves[..self.len]
+    }
+
     #[inline]
     /// Returns an iterator over populated moves.
     pub const fn iter(&self) -> core::slice::Iter<'_, Move> {
@@ -1,5 +1,7 @@
 //! Chess piece types (including [`Piece::Null`] for empty squares).
 
+use std::{fmt::Display, str::FromStr};
+
 use crate::utilities::{Array, IterableEnum, impl_u8_conversions};
 
 /// Chess piece type without color (used with [`Color`](crate::types::Color) to form [`ColoredPiece`](crate::types::ColoredPiece)).
@@ -35,11 +37,12 @@ impl Piece {
     /// Alias for `Null` representing all pieces when used as a mask selector.
     pub const ALL_PIECES: Piece = Piece::Null;
 
-    /// Creates a `Piece` from its numeric discriminant.
+    /// Creates a `Piece` from its numeric discriminant without checking it.
+    /// Use `Piece::try_from` for a checked conversion.
     ///
     /// # Safety
     /// `piece_int` must be less than [`Piece::LIMIT`]. Violating this is undefined behavior.
-    pub const unsafe fn from(piece_int: u8) -> Piece {
+    pub const unsafe fn from_unchecked(piece_int: u8) -> Piece {
         debug_assert!(piece_int < Piece::LIMIT, "Piece type number out of bounds");
         unsafe { std::mem::transmute::<u8, Piece>(piece_int) }
     }
@@ -49,29 +52,54 @@ impl Piece {
         matches!(*self, Piece::Bishop | Piece::Rook | Piece::Queen)
     }
 
-    /// Parses a piece from an uppercase ASCII character (PNBRQK or any other returns `Null`).
-    pub const fn from_uppercase_char(piece_char: char) -> Piece {
+    /// Returns the conventional material value in centipawns; `0` for `Null` and the king.
+    pub const fn value(&self) -> i32 {
+        match *self {
+            Piece::Pawn => 100,
+            Piece::Knight => 320,
+            Piece::Bishop => 330,
+            Piece::Rook => 500,
+            Piece::Queen => 900,
+            Piece::Null | Piece::King => 0,
+        }
+    }
+
+    /// Parses a piece from an uppercase ASCII character (`PNBRQK`).
+    pub const fn from_uppercase_char(piece_char: char) -> Option<Piece> {
         match piece_char {
-            'P' => Piece::Pawn,
-            'N' => Piece::Knight,
-            'B' => Piece::Bishop,
-            'R' => Piece::Rook,
-            'Q' => Piece::Queen,
-            'K' => Piece::King,
-            _ => Piece::Null,
+            'P' => Some(Piece::Pawn),
+            'N' => Some(Piece::Knight),
+            'B' => Some(Piece::Bishop),
+            'R' => Some(Piece::Rook),
+            'Q' => Some(Piece::Queen),
+            'K' => Some(Piece::King),
+            _ => None,
+        }
+    }
+
+    /// Parses a piece from a lowercase ASCII character (`pnbrqk`).
+    pub const fn from_lowercase_char(piece_char: char) -> Option<Piece> {
+        match piece_char.is_ascii_lowercase() {
+            true => Piece::from_uppercase_char(piece_char.to_ascii_uppercase()),
+            false => None,
         }
     }
 
-    /// Returns the Piece from the given lowercase char.
-    pub const fn from_lowercase_char(piece_char: char) -> Piece {
+    /// Parses a piece from an ASCII letter of either case (`PNBRQK` / `pnbrqk`).
+    pub const fn from_char(piece_char: char) -> Option<Piece> {
+        Piece::from_uppercase_char(piece_char.to_ascii_uppercase())
+    }
+
+    /// Parses a piece from a filled or unfilled Unicode chess glyph.
+    pub const fn from_unicode(piece_char: char) -> Option<Piece> {
         match piece_char {
-            'p' => Piece::Pawn,
-            'n' => Piece::Knight,
-            'b' => Piece::Bishop,
-            'r' => Piece::Rook,
-            'q' => Piece::Queen,
-            'k' => Piece::King,
-            _ => Piece::Null,
+            '♙' | '♟' => Some(Piece::Pawn),
+            '♘' | '♞' => Some(Piece::Knight),
+            '♗' | '♝' => Some(Piece::Bishop),
+            '♖' | '♜' => Some(Piece::Rook),
+            '♕' | '♛' => Some(Piece::Queen),
+            '♔' | '♚' => Some(Piece::King),
+            _ => None,
         }
     }
 
@@ -167,3 +195,69 @@ impl const IterableEnum<7> for Piece {
 }
 
 impl_u8_conversions!(Piece, 7);
+
+/// Parses an ASCII letter of either case or a Unicode glyph (see [`Piece::from_char`] and
+/// [`Piece::from_unicode`]).
+impl const TryFrom<char> for Piece {
+    type Error = &'static str;
+
+    fn try_from(piece_char: char) -> Result<Self, Self::Error> {
+        match Piece::from_char(piece_char) {
+            Some(piece) => Ok(piece),
+            None => match Piece::from_unicode(piece_char) {
+                Some(piece) => Ok(piece),
+                None => Err("Invalid piece character"),
+            },
+        }
+    }
+}
+
+/// Parses a single piece character, as accepted by [`Piece::try_from`].
+impl FromStr for Piece {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(piece_char), None) => Piece::try_from(piece_char),
+            _ => Err("Piece must be exactly one character"),
+        }
+    }
+}
+
+/// Formats the piece as its uppercase ASCII letter (a space for [`Piece::Null`]).
+impl Display for Piece {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.uppercase_ascii())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_conversions() {
+        for piece in Piece::PIECES {
+            assert_eq!(Piece::try_from(piece.uppercase_ascii()), Ok(piece));
+            assert_eq!(Piece::try_from(piece.lowercase_ascii()), Ok(piece));
+            assert_eq!(Piece::try_from(piece.filled_unicode()), Ok(piece));
+            assert_eq!(Piece::try_from(piece.unfilled_unicode()), Ok(piece));
+            assert_eq!(piece.to_string().parse::<Piece>(), Ok(piece));
+            assert_eq!(
+                Piece::from_uppercase_char(piece.uppercase_ascii()),
+                Some(piece)
+            );
+            assert_eq!(
+                Piece::from_lowercase_char(piece.lowercase_ascii()),
+                Some(piece)
+            );
+            assert_eq!(Piece::from_uppercase_char(piece.lowercase_ascii()), None);
+            assert_eq!(Piece::from_lowercase_char(piece.uppercase_ascii()), None);
+        }
+        assert!(Piece::try_from('x').is_err());
+        assert!(Piece::try_from(' ').is_err());
+        assert!("Nf".parse::<Piece>().is_err());
+        assert_eq!(Piece::Knight.to_string(), "N");
+    }
+}
@@ -0,0 +1,188 @@
+//! Per-color, per-piece square lists whose order survives moves.
+
+use super::{board::Board, color::Color, piece::Piece, square::Square};
+use crate::{logic::accumulator::AccumulatorDelta, utilities::IterableEnum};
+
+/// Most pieces of one type a side can have. Legal play allows ten, but a FEN may fill every square
+/// but the kings' with one piece type.
+const MAX_PIECES_PER_TYPE: usize = 62;
+
+#[derive(Clone, Debug)]
+struct PieceList {
+    /// Occupied slots hold a square; `None` marks a slot vacated by a removal.
+    slots: [Option<Square>; MAX_PIECES_PER_TYPE],
+    num_slots: u8,
+    /// Vacated slot indices, most recently vacated last.
+    vacated: [u8; MAX_PIECES_PER_TYPE],
+    num_vacated: u8,
+}
+
+/// Lists are equal when they yield the same squares in the same order.
+impl PartialEq for PieceList {
+    fn eq(&self, other: &PieceList) -> bool {
+        self.squares().eq(other.squares())
+    }
+}
+
+impl Eq for PieceList {}
+
+impl PieceList {
+    const EMPTY: PieceList = PieceList {
+        slots: [None; MAX_PIECES_PER_TYPE],
+        num_slots: 0,
+        vacated: [0; MAX_PIECES_PER_TYPE],
+        num_vacated: 0,
+    };
+
+    fn add(&mut self, square: Square) {
+        let index = if self.num_vacated > 0 {
+            self.num_vacated -= 1;
+            self.vacated[self.num_vacated as usize]
+        } else {
+            self.num_slots += 1;
+            self.num_slots - 1
+        };
+        self.slots[index as usize] = Some(square);
+    }
+
+    fn remove(&mut self, square: Square) {
+        let index = self.slots[..self.num_slots as usize]
+            .iter()
+            .position(|&slot| slot == Some(square))
+            .expect("removed piece is not in the list");
+        self.slots[index] = None;
+        self.vacated[self.num_vacated as usize] = index as u8;
+        self.num_vacated += 1;
+    }
+
+    fn squares(&self) -> impl Iterator<Item = Square> + '_ {
+        self.slots[..self.num_slots as usize]
+            .iter()
+            .flatten()
+            .copied()
+    }
+}
+
+/// The squares of each side's pieces, listed per piece type in a stable order.
+///
+/// Lists start out in [`Square`] order and are kept up to date by passing them as the listener to
+/// [`crate::types::Position::make_move_with`] and [`crate::types::Position::unmake_move_with`].
+/// A moving piece keeps its place in its list, and unmaking a move restores the previous order
+/// exactly, so iteration order depends on the game played rather than on bit positions.
+///
+/// Plain [`crate::types::Position::make_move`] and `unmake_move` do not see the lists: a caller
+/// that keeps lists must make and unmake every move through the `_with` variants, or rebuild the
+/// lists with [`PieceLists::new`] afterwards.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PieceLists {
+    /// Indexed by `[color][piece]`; the [`Piece::Null`] lists stay empty.
+    lists: [[PieceList; Piece::LIMIT as usize]; 2],
+}
+
+impl PieceLists {
+    /// Lists every piece on `board`, in [`Square`] order (a8 first).
+    pub fn new(board: &Board) -> PieceLists {
+        let mut result = PieceLists {
+            lists: [const { [PieceList::EMPTY; Piece::LIMIT as usize] }; 2],
+        };
+        for square in Square::ALL {
+            let piece = board.piece_at(square);
+            if piece != Piece::Null {
+                result.on_piece_added(board.color_at(square), piece, square);
+            }
+        }
+        result
+    }
+
+    /// Squares of `color`'s pieces of type `piece`, in list order.
+    pub fn squares(&self, color: Color, piece: Piece) -> impl Iterator<Item = Square> + '_ {
+        self.lists[color as usize][piece as usize].squares()
+    }
+
+    /// Number of `color`'s pieces of type `piece`.
+    pub fn count(&self, color: Color, piece: Piece) -> usize {
+        self.squares(color, piece).count()
+    }
+}
+
+impl AccumulatorDelta for PieceLists {
+    fn on_piece_added(&mut self, color: Color, piece: Piece, square: Square) {
+        self.lists[color as usize][piece as usize].add(square);
+    }
+
+    fn on_piece_removed(&mut self, color: Color, piece: Piece, square: Square) {
+        self.lists[color as usize][piece as usize].remove(square);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Move, MoveFlag, MoveList, Position};
+
+    fn rook_squares(lists: &PieceLists, color: Color) -> Vec<Square> {
+        lists.squares(color, Piece::Rook).collect()
+    }
+
+    #[test]
+    fn test_moved_piece_keeps_its_slot() {
+        let mut position =
+            Position::<4, { Color::White }>::from_fen("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1").unwrap();
+        let mut lists = PieceLists::new(&position.board);
+        assert_eq!(rook_squares(&lists, Color::White), [Square::A1, Square::H1]);
+
+        let move_ = Move::new_non_promotion(Square::A1, Square::A7, MoveFlag::NormalMove);
+        position.make_move_with(move_, &mut lists);
+        assert_eq!(rook_squares(&lists, Color::White), [Square::A7, Square::H1]);
+        let child = unsafe { position.rebrand_stm_mut::<{ Color::Black }>() };
+        child.unmake_move_with(move_, &mut lists);
+        assert_eq!(lists, PieceLists::new(&position.board));
+    }
+
+    #[test]
+    fn test_lists_hold_every_piece_a_fen_allows() {
+        let mut position =
+            Position::<2, { Color::White }>::from_fen("k7/8/8/8/8/8/NNNNNNNN/NNNK4 w - - 0 1")
+                .unwrap();
+        let mut lists = PieceLists::new(&position.board);
+        assert_eq!(lists.count(Color::White, Piece::Knight), 11);
+
+        let move_ = Move::new_non_promotion(Square::B2, Square::C4, MoveFlag::NormalMove);
+        position.make_move_with(move_, &mut lists);
+        assert!(
+            lists
+                .squares(Color::White, Piece::Knight)
+                .any(|square| square == Square::C4)
+        );
+    }
+
+    #[test]
+    fn test_make_unmake_restores_lists() {
+        let mut position =
+            Position::<2, { Color::Black }>::from_fen("1n2k3/8/8/8/3pP3/8/6p1/4K2R b K e3 0 1")
+                .unwrap();
+        let mut lists = PieceLists::new(&position.board);
+        let mut moves = MoveList::new();
+        position.generate_moves(&mut moves);
+
+        for &move_ in moves.as_slice() {
+            let before = lists.clone();
+            position.make_move_with(move_, &mut lists);
+            for color in [Color::White, Color::Black] {
+                for piece in Piece::PIECES {
+                    let mask =
+                        position.board.piece_mask_at(piece) & position.board.color_mask_at(color);
+                    assert_eq!(lists.count(color, piece), mask.count_ones() as usize);
+                    assert!(
+                        lists
+                            .squares(color, piece)
+                            .all(|square| square.mask() & mask != 0)
+                    );
+                }
+            }
+            let child = unsafe { position.rebrand_stm_mut::<{ Color::White }>() };
+            child.unmake_move_with(move_, &mut lists);
+            assert_eq!(lists, before, "after unmake {}", move_.uci());
+        }
+    }
+}
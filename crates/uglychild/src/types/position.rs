@@ -14,7 +14,10 @@ use super::{
     zobrist_policy::ZobristPolicy,
 };
 use crate::{
-    logic::attacks::{multi_pawn_attacks, single_knight_attacks},
+    logic::{
+        attacks::{multi_pawn_attacks, single_knight_attacks},
+        zobrist_hash::has_en_passant_capturer,
+    },
     types::WithoutZobrist,
 };
 
@@ -34,16 +37,41 @@ pub type PositionWithoutZobrist<const N: usize, const STM: Color> =
 /// chain you use (search depth, PGN main line length, etc.) never needs more than **`N` context
 /// slots** (including the root). Pushing beyond that is a **contract violation**: debug builds
 /// panic on `debug_assert!`; release builds may exhibit **undefined behavior** (out-of-bounds write).
-#[derive(Clone)]
+///
+/// # Cloning
+///
+/// A position owns all of its state inline (no heap data, no shared pointers), so every clone is
+/// fully independent. [`Clone::clone`] copies the whole context stack; [`Clone::clone_from`]
+/// copies only the active contexts. When the history is not needed (e.g. one position per node
+/// of a game tree), [`Position::clone_current`] copies just the board and the current context,
+/// and [`Position::clone_with_capacity`] moves the history into a differently sized stack.
 pub struct Position<const N: usize, const STM: Color, Z: ZobristPolicy = WithZobrist> {
-    /// Piece placement and color occupancy masks.
-    pub board: Board,
+    /// Piece placement and color occupancy masks; read through [`Position::board`].
+    pub(crate) board: Board,
     /// Half-move index from the game start (`0` at initial position).
     pub halfmove: u16,
     pub(crate) contexts: [PositionContext<Z::HashState>; N],
     pub(crate) num_contexts: usize,
 }
 
+impl<const N: usize, const STM: Color, Z: ZobristPolicy> Clone for Position<N, STM, Z> {
+    fn clone(&self) -> Self {
+        Position {
+            board: self.board.clone(),
+            halfmove: self.halfmove,
+            contexts: self.contexts,
+            num_contexts: self.num_contexts,
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.board.clone_from(&source.board);
+        self.halfmove = source.halfmove;
+        self.contexts[..source.num_contexts].copy_from_slice(source.context_slice());
+        self.num_contexts = source.num_contexts;
+    }
+}
+
 impl<const N: usize, const STM: Color, Z: ZobristPolicy> fmt::Debug for Position<N, STM, Z> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Position")
@@ -98,6 +126,58 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
         unsafe { &mut *(self as *mut Self).cast::<Position<N, NEXT, Z>>() }
     }
 
+    /// Copies the board and the current context into a fresh stack of capacity `M`, with the
+    /// current context as its root.
+    ///
+    /// The copy plays and generates moves exactly like `self`, but cannot unmake past its root.
+    pub fn clone_current<const M: usize>(&self) -> Position<M, STM, Z> {
+        debug_assert!(
+            M >= 1,
+            "Position context stack capacity M must be at least 1"
+        );
+        let mut contexts = [PositionContext::<Z::HashState>::blank(); M];
+        contexts[0] = *self.context();
+        Position {
+            board: self.board.clone(),
+            halfmove: self.halfmove,
+            contexts,
+            num_contexts: 1,
+        }
+    }
+
+    /// Copies this position, with its full history, into a stack of capacity `M`.
+    ///
+    /// Returns `None` if the active contexts do not fit in `M`.
+    pub fn clone_with_capacity<const M: usize>(&self) -> Option<Position<M, STM, Z>> {
+        if self.num_contexts > M {
+            return None;
+        }
+        let mut contexts = [PositionContext::<Z::HashState>::blank(); M];
+        contexts[..self.num_contexts].copy_from_slice(self.context_slice());
+        Some(Position {
+            board: self.board.clone(),
+            halfmove: self.halfmove,
+            contexts,
+            num_contexts: self.num_contexts,
+        })
+    }
+
+    /// Drops the oldest contexts so that at most `keep_last_n` remain (the current one counts),
+    /// returning how many were dropped.
+    ///
+    /// Contexts since the last capture or pawn move are always kept, whatever `keep_last_n`, since
+    /// only those positions can repeat the current one; so is the current context. The oldest
+    /// kept context becomes the root, and moves can no longer be unmade past it. This bounds how
+    /// much of `N` a long game uses, so that a position can keep playing in a small stack.
+    pub fn truncate_history(&mut self, keep_last_n: usize) -> usize {
+        let reversible = self.context().halfmove_clock as usize + 1;
+        let keep = keep_last_n.max(reversible).min(self.num_contexts);
+        let dropped = self.num_contexts - keep;
+        self.contexts.copy_within(dropped..self.num_contexts, 0);
+        self.num_contexts = keep;
+        dropped
+    }
+
     /// Active context stack entries (root at index 0, current at `len - 1`).
     pub fn context_slice(&self) -> &[PositionContext<Z::HashState>] {
         &self.contexts[..self.num_contexts]
@@ -146,6 +226,15 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
         .expect("king present for side")
     }
 
+    /// Returns the piece placement and color occupancy masks.
+    ///
+    /// The board is read-only from outside the crate, so it cannot drift from the context stack
+    /// or the material key.
+    #[inline]
+    pub const fn board(&self) -> &Board {
+        &self.board
+    }
+
     /// Returns the current (top) context entry.
     pub const fn context(&self) -> &PositionContext<Z::HashState> {
         debug_assert!(self.num_contexts > 0);
@@ -153,13 +242,13 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
     }
 
     /// Returns a mutable reference to the current (top) context entry.
-    pub const fn mut_context(&mut self) -> &mut PositionContext<Z::HashState> {
+    pub(crate) const fn mut_context(&mut self) -> &mut PositionContext<Z::HashState> {
         debug_assert!(self.num_contexts > 0);
         &mut self.contexts[self.num_contexts - 1]
     }
 
     /// Pushes a context entry onto the stack (requires spare capacity in `N`).
-    pub const fn push_context(&mut self, context: PositionContext<Z::HashState>) {
+    pub(crate) const fn push_context(&mut self, context: PositionContext<Z::HashState>) {
         debug_assert!(self.num_contexts < N);
         self.contexts[self.num_contexts] = context;
         self.num_contexts += 1;
@@ -168,6 +257,7 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
     /// Pops and returns the top context entry.
     ///
     /// The root context cannot be popped.
+    #[deprecated(note = "leaves the board out of sync with the context stack; use `unmake_move`")]
     pub const fn pop_context(&mut self) -> PositionContext<Z::HashState> {
         debug_assert!(self.num_contexts > 1);
         let popped = self.contexts[self.num_contexts - 1];
@@ -182,37 +272,47 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
 
     #[inline(always)]
     /// Places `piece` on `square` and updates hash state according to `Z`.
+    ///
+    /// The square's color must already be set, since `Z` may hash by color. Like
+    /// [`Board::put_piece_at`], this leaves the material key alone.
     pub fn put_piece_at(&mut self, piece: Piece, square: Square) {
         self.board.put_piece_at(piece, square);
-        Z::on_put_piece(&mut self.mut_context().zobrist_hash, piece, square);
+        let color = self.board.color_at(square);
+        Z::on_put_piece(&mut self.mut_context().zobrist_hash, color, piece, square);
     }
 
     #[inline(always)]
     /// Places both `color` and `piece` on `square`, including hash updates.
     pub fn put_piece_and_color(&mut self, color: Color, piece: Piece, square: Square) {
         self.board.put_piece_and_color(color, piece, square);
-        Z::on_put_piece(&mut self.mut_context().zobrist_hash, piece, square);
+        Z::on_put_piece(&mut self.mut_context().zobrist_hash, color, piece, square);
     }
 
     #[inline(always)]
     /// Removes `piece` from `square` and updates hash state according to `Z`.
+    ///
+    /// Like [`Board::remove_piece_at`], this leaves the material key alone.
     pub fn remove_piece_at(&mut self, piece: Piece, square: Square) {
+        let color = self.board.color_at(square);
         self.board.remove_piece_at(piece, square);
-        Z::on_remove_piece(&mut self.mut_context().zobrist_hash, piece, square);
+        Z::on_remove_piece(&mut self.mut_context().zobrist_hash, color, piece, square);
     }
 
     #[inline(always)]
     /// Removes both `color` and `piece` from `square`, including hash updates.
     pub fn remove_piece_and_color(&mut self, color: Color, piece: Piece, square: Square) {
         self.board.remove_piece_and_color(color, piece, square);
-        Z::on_remove_piece(&mut self.mut_context().zobrist_hash, piece, square);
+        Z::on_remove_piece(&mut self.mut_context().zobrist_hash, color, piece, square);
     }
 
     #[inline(always)]
     /// Moves `piece` from `from` to `to` and updates hash state according to `Z`.
+    ///
+    /// The color must already have moved (see [`Self::move_color`]), since `Z` may hash by color.
     pub fn move_piece(&mut self, piece: Piece, from: Square, to: Square) {
         self.board.move_piece(piece, from, to);
-        Z::on_move_piece(&mut self.mut_context().zobrist_hash, piece, from, to);
+        let color = self.board.color_at(to);
+        Z::on_move_piece(&mut self.mut_context().zobrist_hash, color, piece, from, to);
     }
 
     #[inline(always)]
@@ -225,7 +325,7 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
     /// Moves `piece` and color occupancy from `from` to `to`, including hash updates.
     pub fn move_piece_and_color(&mut self, color: Color, piece: Piece, from: Square, to: Square) {
         self.board.move_piece_and_color(color, piece, from, to);
-        Z::on_move_piece(&mut self.mut_context().zobrist_hash, piece, from, to);
+        Z::on_move_piece(&mut self.mut_context().zobrist_hash, color, piece, from, to);
     }
 
     #[inline(always)]
@@ -239,14 +339,27 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
 
     #[inline(always)]
     /// Sets the current en-passant file marker and updates the hash incrementally.
+    ///
+    /// Called while `STM` makes a move: the old file is one `STM` could have captured on, the
+    /// new one is for `STM.other()`.
     pub fn set_double_pawn_push_file(
         &mut self,
         double_pawn_push_file: crate::types::DoublePawnPushFile,
     ) {
+        let old_capturable =
+            has_en_passant_capturer(&self.board, self.context().double_pawn_push_file, STM);
+        let new_capturable =
+            has_en_passant_capturer(&self.board, double_pawn_push_file, STM.other());
         let context = self.mut_context();
         let old = context.double_pawn_push_file;
         context.double_pawn_push_file = double_pawn_push_file;
-        Z::on_double_pawn_push_file_change(&mut context.zobrist_hash, old, double_pawn_push_file);
+        Z::on_double_pawn_push_file_change(
+            &mut context.zobrist_hash,
+            old,
+            old_capturable,
+            double_pawn_push_file,
+            new_capturable,
+        );
     }
 
     #[inline(always)]
@@ -265,15 +378,28 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
         self.update_pins_and_checks_for_stm(STM);
     }
 
-    /// Recomputes [`PositionContext::pinned`] / [`PositionContext::checkers`] for `stm` (must match the board).
+    /// Recomputes [`PositionContext::pinned()`] / [`PositionContext::checkers()`] for `stm` (must match the board).
     pub(crate) const fn update_pins_and_checks_for_stm(&mut self, side_to_move: Color) {
+        if let Some((pinned, checkers)) = self.pins_and_checkers(side_to_move) {
+            let context = self.mut_context();
+            context.pinned = pinned;
+            context.checkers = checkers;
+        }
+    }
+
+    /// Returns the pieces of `side_to_move` pinned to its king and the opponent pieces checking
+    /// it, or `None` if `side_to_move` does not have exactly one king.
+    pub(crate) const fn pins_and_checkers(
+        &self,
+        side_to_move: Color,
+    ) -> Option<(Bitboard, Bitboard)> {
         let opponent = side_to_move.other();
 
         let current_side_king_mask =
             self.board.piece_mask::<{ Piece::King }>() & self.board.color_mask_at(side_to_move);
 
         if current_side_king_mask.count_ones() != 1 {
-            return;
+            return None;
         }
 
         let current_side_king_square = self.king_square(side_to_move);
@@ -317,9 +443,7 @@ impl<const N: usize, const STM: Color, Z: ZobristPolicy> Position<N, STM, Z> {
             & self.board.piece_mask::<{ Piece::Pawn }>()
             & opponent_mask;
 
-        let context = self.mut_context();
-        context.pinned = pinned;
-        context.checkers = checkers;
+        Some((pinned, checkers))
     }
 
     /// Returns whether the current side to move is in check.
@@ -372,6 +496,73 @@ mod state_tests {
         assert_eq!(state.get_fullmove(), 6);
     }
 
+    #[test]
+    fn test_clone_current_and_with_capacity() {
+        use crate::types::MoveList;
+
+        let mut pos = Position::<8, { Color::White }>::initial();
+        let mut ml = MoveList::new();
+        pos.generate_moves(&mut ml);
+        pos.make_move(ml.as_slice()[0]);
+        let pos = pos.rebrand_stm::<{ Color::Black }>();
+
+        let current = pos.clone_current::<2>();
+        assert_eq!(current.num_contexts(), 1);
+        assert_eq!(current.board, pos.board);
+        assert_eq!(current.halfmove, pos.halfmove);
+        assert_eq!(current.context(), pos.context());
+
+        let resized = pos.clone_with_capacity::<2>().unwrap();
+        assert_eq!(resized.context_slice(), pos.context_slice());
+        assert!(pos.clone_with_capacity::<1>().is_none());
+
+        let mut reused =
+            Position::<8, { Color::Black }>::from_fen("4k3/8/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+        reused.clone_from(&pos);
+        assert_eq!(reused, pos);
+    }
+
+    #[test]
+    fn test_truncate_history_keeps_reversible_moves() {
+        use crate::types::{MoveList, Square};
+
+        fn play<const STM: Color>(position: &mut Position<16, STM>, from: Square, to: Square) {
+            let mut moves = MoveList::new();
+            position.generate_moves(&mut moves);
+            let move_ = *moves
+                .as_slice()
+                .iter()
+                .find(|move_| move_.from() == from && move_.to() == to)
+                .expect("legal move");
+            position.make_move(move_);
+        }
+
+        let mut position = Position::<16, { Color::White }>::initial();
+        play(&mut position, Square::G1, Square::F3);
+        let mut position = position.rebrand_stm::<{ Color::Black }>();
+        play(&mut position, Square::G8, Square::F6);
+        let mut position = position.rebrand_stm::<{ Color::White }>();
+        play(&mut position, Square::F3, Square::G1);
+        let mut position = position.rebrand_stm::<{ Color::Black }>();
+        play(&mut position, Square::F6, Square::G8);
+        let mut position = position.rebrand_stm::<{ Color::White }>();
+
+        // Every position since the start could still repeat.
+        assert_eq!(position.truncate_history(1), 0);
+        assert_eq!(position.num_contexts(), 5);
+
+        play(&mut position, Square::E2, Square::E4);
+        let mut position = position.rebrand_stm::<{ Color::Black }>();
+        let current = *position.context();
+        let halfmove = position.halfmove;
+        assert_eq!(position.truncate_history(2), 4);
+        assert_eq!(position.num_contexts(), 2);
+        assert_eq!(*position.context(), current);
+        assert_eq!(position.halfmove, halfmove);
+        assert_eq!(position.truncate_history(0), 1);
+        assert_eq!(position.context_slice(), [current]);
+    }
+
     #[cfg(debug_assertions)]
     #[test]
     fn test_context_stack_overflow_second_move_panics_in_debug() {
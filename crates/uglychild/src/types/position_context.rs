@@ -8,22 +8,26 @@ use super::{
 };
 
 /// A struct containing metadata about the current and past states of the game.
+///
+/// Fields are read through accessors, and contexts are only created and written by this crate,
+/// since make/unmake, FEN parsing and incremental hashing rely on them staying consistent with
+/// the board.
 #[derive(Eq, PartialEq, Clone, Copy, Debug)]
 pub struct PositionContext<H = u64> {
     /// Halfmoves since last pawn move or capture (for 50-move rule).
-    pub halfmove_clock: u8,
+    pub(crate) halfmove_clock: u8,
     /// File index for en passant after a double push; see [`DoublePawnPushFile`].
-    pub double_pawn_push_file: DoublePawnPushFile,
+    pub(crate) double_pawn_push_file: DoublePawnPushFile,
     /// Castling availability mask for the current position.
-    pub castling_rights: CastlingRights,
+    pub(crate) castling_rights: CastlingRights,
     /// Captured piece on the move that produced this context, or [`Piece::Null`].
-    pub captured_piece: Piece,
+    pub(crate) captured_piece: Piece,
     /// Incremental hash state, policy-defined by `H`.
-    pub zobrist_hash: H,
+    pub(crate) zobrist_hash: H,
     /// Friendly pieces pinned to the king for the side to move.
-    pub pinned: Bitboard,
+    pub(crate) pinned: Bitboard,
     /// Enemy pieces currently giving check to the side to move.
-    pub checkers: Bitboard,
+    pub(crate) checkers: Bitboard,
 }
 
 impl<H: Default> PositionContext<H> {
@@ -40,6 +44,42 @@ impl<H: Default> PositionContext<H> {
         }
     }
 
+    /// Halfmoves since last pawn move or capture (for 50-move rule).
+    #[inline]
+    pub const fn halfmove_clock(&self) -> u8 {
+        self.halfmove_clock
+    }
+
+    /// File index for en passant after a double push; see [`DoublePawnPushFile`].
+    #[inline]
+    pub const fn double_pawn_push_file(&self) -> DoublePawnPushFile {
+        self.double_pawn_push_file
+    }
+
+    /// Castling availability mask for the current position.
+    #[inline]
+    pub const fn castling_rights(&self) -> CastlingRights {
+        self.castling_rights
+    }
+
+    /// Captured piece on the move that produced this context, or [`Piece::Null`].
+    #[inline]
+    pub const fn captured_piece(&self) -> Piece {
+        self.captured_piece
+    }
+
+    /// Friendly pieces pinned to the king for the side to move.
+    #[inline]
+    pub const fn pinned(&self) -> Bitboard {
+        self.pinned
+    }
+
+    /// Enemy pieces currently giving check to the side to move.
+    #[inline]
+    pub const fn checkers(&self) -> Bitboard {
+        self.checkers
+    }
+
     /// Checks if the halfmove clock is valid (less than or equal to 100).
     pub const fn has_valid_halfmove_clock(&self) -> bool {
         self.halfmove_clock <= 100
@@ -51,3 +91,11 @@ impl<H: Default> Default for PositionContext<H> {
         Self::blank()
     }
 }
+
+impl<H: Copy> PositionContext<H> {
+    /// Incremental hash state, policy-defined by `H`.
+    #[inline]
+    pub const fn zobrist_hash(&self) -> H {
+        self.zobrist_hash
+    }
+}
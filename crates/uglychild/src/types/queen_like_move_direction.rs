@@ -19,17 +19,18 @@ pub enum QueenLikeMoveDirection {
 }
 
 impl QueenLikeMoveDirection {
-    /// Returns the QueenLikeMoveDirection corresponding to the given value.
+    /// Returns the QueenLikeMoveDirection corresponding to the given value, without checking it.
+    /// Use `QueenLikeMoveDirection::try_from` for a checked conversion.
     /// # Safety
     /// The value must be in the range 0..=7.
-    pub const unsafe fn from(value: u8) -> QueenLikeMoveDirection {
+    pub const unsafe fn from_unchecked(value: u8) -> QueenLikeMoveDirection {
         unsafe { std::mem::transmute::<u8, QueenLikeMoveDirection>(value) }
     }
 
     pub fn lookup(src_square: Square, dst_square: Square) -> Option<QueenLikeMoveDirection> {
-        unsafe {
-            super::MOVE_DIRECTION_LOOKUP[src_square as usize][dst_square as usize].as_queen_like()
-        }
+        super::MOVE_DIRECTION_LOOKUP
+            .get(src_square, dst_square)
+            .as_queen_like()
     }
 
     /// # Safety
@@ -39,14 +40,15 @@ impl QueenLikeMoveDirection {
         dst_square: Square,
     ) -> QueenLikeMoveDirection {
         unsafe {
-            super::MOVE_DIRECTION_LOOKUP[src_square as usize][dst_square as usize]
+            super::MOVE_DIRECTION_LOOKUP
+                .get(src_square, dst_square)
                 .as_queen_like_unchecked()
         }
     }
 
     /// Returns the QueenLikeMoveDirection opposite to the current direction.
     pub const fn opposite(&self) -> QueenLikeMoveDirection {
-        unsafe { QueenLikeMoveDirection::from(7u8.wrapping_sub(*self as u8)) }
+        unsafe { QueenLikeMoveDirection::from_unchecked(7u8.wrapping_sub(*self as u8)) }
     }
 
     /// Returns a QueenLikeMoveDirection as calculated from the source and destination squares.
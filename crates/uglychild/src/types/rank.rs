@@ -1,5 +1,7 @@
 //! Chess ranks 1–8. Line masks: one byte strip per rank, matching [`Square::rank`](crate::types::Square::rank) (0 = first rank).
 
+use std::fmt;
+
 use super::{bitboard::Bitboard, color::Color};
 use crate::utilities::{Array, IterableEnum, impl_u8_conversions};
 
@@ -32,13 +34,46 @@ impl Rank {
 
     #[inline]
     pub const fn from_perspective(self, color: Color) -> Self {
-        match color {
-            Color::White => self,
-            Color::Black => self.mirrored(),
+        // Mirroring a rank index is `7 - r == r ^ 7`, so Black flips all three bits.
+        unsafe {
+            (self as u8 ^ (color as u8 * 7))
+                .try_into()
+                .unwrap_unchecked()
+        }
+    }
+
+    /// Rank `delta` steps toward [`Rank::Eight`] (negative toward [`Rank::One`]), or `None` off the board.
+    #[inline]
+    pub const fn offset(self, delta: i8) -> Option<Rank> {
+        let value = self as i8 + delta;
+        if value >= 0 && value < 8 {
+            Some(unsafe { Rank::try_from(value as u8).unwrap_unchecked() })
+        } else {
+            None
+        }
+    }
+
+    /// Rank digit (`'1'`–`'8'`).
+    #[inline]
+    pub const fn as_char(self) -> char {
+        (b'1' + self as u8) as char
+    }
+
+    /// Parses a rank digit (`'1'`–`'8'`).
+    pub const fn from_char(c: char) -> Option<Rank> {
+        match c {
+            '1'..='8' => Some(unsafe { Rank::try_from(c as u8 - b'1').unwrap_unchecked() }),
+            _ => None,
         }
     }
 }
 
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_char())
+    }
+}
+
 impl const IterableEnum<8> for Rank {
     const ALL: Array<Rank, 8> = Array([
         Rank::One,
@@ -53,3 +88,25 @@ impl const IterableEnum<8> for Rank {
 }
 
 impl_u8_conversions!(Rank, 8);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset() {
+        assert_eq!(Rank::One.offset(7), Some(Rank::Eight));
+        assert_eq!(Rank::Four.offset(-3), Some(Rank::One));
+        assert_eq!(Rank::One.offset(-1), None);
+        assert_eq!(Rank::Eight.offset(1), None);
+    }
+
+    #[test]
+    fn test_char_round_trip() {
+        for rank in Rank::ALL {
+            assert_eq!(Rank::from_char(rank.as_char()), Some(rank));
+        }
+        assert_eq!(Rank::from_char('9'), None);
+        assert_eq!(Rank::Five.to_string(), "5");
+    }
+}
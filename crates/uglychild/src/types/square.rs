@@ -1,6 +1,6 @@
 //! Chess board squares (A1-H8) and square geometry operations.
 
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 use super::{
     bitboard::Bitboard,
@@ -9,10 +9,21 @@ use super::{
     square_delta::{SquareDelta, SquareDeltaUtils},
 };
 use crate::{
-    types::{Array, BitboardUtils, QueenLikeMoveDirection},
+    types::{Array, BitboardUtils, Color, QueenLikeMoveDirection},
     utilities::{IterableEnum, impl_u8_conversions},
 };
 
+/// Bounds check for the `_unchecked` square offsets: an `assert!` by default, or only a
+/// `debug_assert!` with the `unchecked-square-math` feature.
+macro_rules! square_math_assert {
+    ($($arg:tt)*) => {
+        #[cfg(not(feature = "unchecked-square-math"))]
+        assert!($($arg)*);
+        #[cfg(feature = "unchecked-square-math")]
+        debug_assert!($($arg)*);
+    };
+}
+
 const fn resolve_square_mask(maybe_square: Option<Square>) -> Bitboard {
     match maybe_square {
         Some(square) => square.mask(),
@@ -171,6 +182,16 @@ impl Square {
         unsafe { (7 - self as u8 / 8).try_into().unwrap_unchecked() }
     }
 
+    /// Returns the file index (`0` = file a … `7` = file h).
+    pub const fn file_index(self) -> u8 {
+        self.file() as u8
+    }
+
+    /// Returns the rank index (`0` = rank 1 … `7` = rank 8).
+    pub const fn rank_index(self) -> u8 {
+        self.rank() as u8
+    }
+
     /// Bitboard mask of the rank and file passing through this square.
     pub const fn orthogonals_mask(self) -> Bitboard {
         self.file().mask() | self.rank().mask()
@@ -212,6 +233,41 @@ impl Square {
         }
     }
 
+    /// Square offset by `delta`, without returning an `Option`.
+    ///
+    /// Panics if the result is off the board, unless the `unchecked-square-math` feature is
+    /// enabled, in which case only debug builds check.
+    ///
+    /// # Safety
+    /// `self as i8 + delta` must be in `0..64`.
+    #[inline]
+    pub const unsafe fn relative_unchecked(self, delta: SquareDelta) -> Square {
+        let idx = self as i8 + delta;
+        square_math_assert!(idx >= 0 && idx <= 63, "square offset off the board");
+        unsafe { self.relative_unasserted(delta) }
+    }
+
+    /// [`Self::relative_unchecked`] without the bounds assert, for callers that assert a stricter
+    /// edge condition themselves.
+    ///
+    /// # Safety
+    /// `self as i8 + delta` must be in `0..64`.
+    #[inline(always)]
+    const unsafe fn relative_unasserted(self, delta: SquareDelta) -> Square {
+        unsafe { std::mem::transmute::<u8, Square>((self as i8 + delta) as u8) }
+    }
+
+    /// Square `rank_delta` ranks up (toward rank 8) and `file_delta` files right (toward file H),
+    /// or `None` if that is off the board. Negative deltas go down and left.
+    pub const fn offset(self, rank_delta: i8, file_delta: i8) -> Option<Square> {
+        let rank = (self.rank_index() as i8).wrapping_add(rank_delta);
+        let file = (self.file_index() as i8).wrapping_add(file_delta);
+        match Square::try_from((rank as u8, file as u8)) {
+            Ok(square) => Some(square),
+            Err(_) => None,
+        }
+    }
+
     /// Square above this one (toward rank 8), or `None` at the top edge.
     pub const fn up(self) -> Option<Square> {
         if self.rank() == Rank::Eight {
@@ -284,6 +340,106 @@ impl Square {
         }
     }
 
+    /// Square above this one, like [`Self::up`] but without the edge branch in release builds with
+    /// `unchecked-square-math`.
+    ///
+    /// # Safety
+    /// `self` must not be on rank 8.
+    #[inline]
+    pub const unsafe fn up_unchecked(self) -> Square {
+        square_math_assert!(self.rank() != Rank::Eight, "square offset off the board");
+        unsafe { self.relative_unasserted(SquareDelta::UP) }
+    }
+
+    /// Square below this one, like [`Self::down`] but without the edge branch in release builds with
+    /// `unchecked-square-math`.
+    ///
+    /// # Safety
+    /// `self` must not be on rank 1.
+    #[inline]
+    pub const unsafe fn down_unchecked(self) -> Square {
+        square_math_assert!(self.rank() != Rank::One, "square offset off the board");
+        unsafe { self.relative_unasserted(SquareDelta::DOWN) }
+    }
+
+    /// Square to the left, like [`Self::left`] but without the edge branch in release builds with
+    /// `unchecked-square-math`.
+    ///
+    /// # Safety
+    /// `self` must not be on file A.
+    #[inline]
+    pub const unsafe fn left_unchecked(self) -> Square {
+        square_math_assert!(self.file() != File::A, "square offset off the board");
+        unsafe { self.relative_unasserted(SquareDelta::LEFT) }
+    }
+
+    /// Square to the right, like [`Self::right`] but without the edge branch in release builds with
+    /// `unchecked-square-math`.
+    ///
+    /// # Safety
+    /// `self` must not be on file H.
+    #[inline]
+    pub const unsafe fn right_unchecked(self) -> Square {
+        square_math_assert!(self.file() != File::H, "square offset off the board");
+        unsafe { self.relative_unasserted(SquareDelta::RIGHT) }
+    }
+
+    /// Square diagonally up-left, like [`Self::up_left`] but without the edge branch in release builds with
+    /// `unchecked-square-math`.
+    ///
+    /// # Safety
+    /// `self` must not be on rank 8 or file A.
+    #[inline]
+    pub const unsafe fn up_left_unchecked(self) -> Square {
+        square_math_assert!(
+            self.rank() != Rank::Eight && self.file() != File::A,
+            "square offset off the board"
+        );
+        unsafe { self.relative_unasserted(SquareDelta::UP_LEFT) }
+    }
+
+    /// Square diagonally up-right, like [`Self::up_right`] but without the edge branch in release builds with
+    /// `unchecked-square-math`.
+    ///
+    /// # Safety
+    /// `self` must not be on rank 8 or file H.
+    #[inline]
+    pub const unsafe fn up_right_unchecked(self) -> Square {
+        square_math_assert!(
+            self.rank() != Rank::Eight && self.file() != File::H,
+            "square offset off the board"
+        );
+        unsafe { self.relative_unasserted(SquareDelta::UP_RIGHT) }
+    }
+
+    /// Square diagonally down-left, like [`Self::down_left`] but without the edge branch in release builds with
+    /// `unchecked-square-math`.
+    ///
+    /// # Safety
+    /// `self` must not be on rank 1 or file A.
+    #[inline]
+    pub const unsafe fn down_left_unchecked(self) -> Square {
+        square_math_assert!(
+            self.rank() != Rank::One && self.file() != File::A,
+            "square offset off the board"
+        );
+        unsafe { self.relative_unasserted(SquareDelta::DOWN_LEFT) }
+    }
+
+    /// Square diagonally down-right, like [`Self::down_right`] but without the edge branch in release builds with
+    /// `unchecked-square-math`.
+    ///
+    /// # Safety
+    /// `self` must not be on rank 1 or file H.
+    #[inline]
+    pub const unsafe fn down_right_unchecked(self) -> Square {
+        square_math_assert!(
+            self.rank() != Rank::One && self.file() != File::H,
+            "square offset off the board"
+        );
+        unsafe { self.relative_unasserted(SquareDelta::DOWN_RIGHT) }
+    }
+
     /// Adjacent square in the given direction, or `None` at board edge.
     pub const fn neighbor_in_direction(self, direction: QueenLikeMoveDirection) -> Option<Square> {
         match direction {
@@ -298,6 +454,19 @@ impl Square {
         }
     }
 
+    /// Squares from this one (exclusive) to the board edge in `direction`, nearest first.
+    pub fn ray_iter(self, direction: QueenLikeMoveDirection) -> impl Iterator<Item = Square> {
+        std::iter::successors(self.neighbor_in_direction(direction), move |square| {
+            square.neighbor_in_direction(direction)
+        })
+    }
+
+    /// This square as seen from `color`'s side: unchanged for White, mirrored across the
+    /// middle of the board for Black (E2 becomes E7).
+    pub const fn relative_to(self, color: Color) -> Square {
+        Square::from_rank_and_file(color.relative_rank(self.rank()), self.file())
+    }
+
     /// The square rotated 180 degrees (view from opponent's perspective).
     pub const fn rotated_perspective(self) -> Square {
         {
@@ -308,12 +477,12 @@ impl Square {
 
     /// Lowercase file letter ('a'-'h').
     pub const fn file_char(self) -> char {
-        (b'a' + self.file() as u8) as char
+        self.file().as_char()
     }
 
     /// Rank digit character ('1'-'8').
     pub const fn rank_char(self) -> char {
-        (b'1' + self.rank() as u8) as char
+        self.rank().as_char()
     }
 
     /// Algebraic notation string (e.g., "e4", "h1").
@@ -445,11 +614,102 @@ impl const IterableEnum<64> for Square {
 
 impl_u8_conversions!(Square, 64);
 
+/// Builds a square from `(rank, file)` indices (`0`..`8` each, rank `0` = rank 1, file `0` = file a).
+impl const TryFrom<(u8, u8)> for Square {
+    type Error = &'static str;
+
+    fn try_from((rank, file): (u8, u8)) -> Result<Self, Self::Error> {
+        match (Rank::try_from(rank), File::try_from(file)) {
+            (Ok(rank), Ok(file)) => Ok(Square::from_rank_and_file(rank, file)),
+            _ => Err("Rank or file out of bounds"),
+        }
+    }
+}
+
+/// Parses algebraic notation such as `"e4"` (file letter is case-insensitive).
+impl FromStr for Square {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(file), Some(rank), None) => {
+                match (File::from_char(file), Rank::from_char(rank)) {
+                    (Some(file), Some(rank)) => Ok(Square::from_rank_and_file(rank, file)),
+                    _ => Err("Invalid square coordinates"),
+                }
+            }
+            _ => Err("Square must be exactly two characters"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::types::{File, Rank};
 
+    #[test]
+    fn test_offset_and_ray_iter() {
+        assert_eq!(Square::E4.offset(0, 0), Some(Square::E4));
+        assert_eq!(Square::E4.offset(2, -1), Some(Square::D6));
+        assert_eq!(Square::E4.offset(-3, 3), Some(Square::H1));
+        assert_eq!(Square::E4.offset(-4, 0), None);
+        assert_eq!(Square::H8.offset(0, 1), None);
+        assert_eq!(Square::A1.offset(i8::MAX, i8::MIN), None);
+        for square in Square::ALL {
+            assert_eq!(square.offset(1, -1), square.up_left());
+            assert_eq!(square.offset(-1, 1), square.down_right());
+        }
+
+        let ray: Vec<Square> = Square::C3
+            .ray_iter(QueenLikeMoveDirection::UpRight)
+            .collect();
+        assert_eq!(
+            ray,
+            [Square::D4, Square::E5, Square::F6, Square::G7, Square::H8]
+        );
+        assert_eq!(
+            Square::A5.ray_iter(QueenLikeMoveDirection::Left).next(),
+            None
+        );
+        for direction in QueenLikeMoveDirection::ALL {
+            let ray_mask = Square::D5
+                .ray_iter(direction)
+                .fold(0, |mask, square| mask | square.mask());
+            assert_eq!(ray_mask & !Square::D5.orthogonals_and_diagonals_mask(), 0);
+        }
+    }
+
+    #[test]
+    fn test_unchecked_offsets_match_checked() {
+        type Offset = (fn(Square) -> Option<Square>, unsafe fn(Square) -> Square);
+        let offsets: [Offset; 8] = [
+            (Square::up, Square::up_unchecked),
+            (Square::down, Square::down_unchecked),
+            (Square::left, Square::left_unchecked),
+            (Square::right, Square::right_unchecked),
+            (Square::up_left, Square::up_left_unchecked),
+            (Square::up_right, Square::up_right_unchecked),
+            (Square::down_left, Square::down_left_unchecked),
+            (Square::down_right, Square::down_right_unchecked),
+        ];
+        for square in Square::ALL {
+            for (checked, unchecked) in offsets {
+                if let Some(expected) = checked(square) {
+                    assert_eq!(unsafe { unchecked(square) }, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "unchecked-square-math"))]
+    #[should_panic(expected = "square offset off the board")]
+    fn test_unchecked_offset_panics_off_the_board() {
+        let _ = unsafe { Square::A8.up_unchecked() };
+    }
+
     #[test]
     fn test_square_values() {
         // Test corners
@@ -472,6 +732,28 @@ mod tests {
         assert_eq!(Square::try_from(36).unwrap(), Square::E4);
     }
 
+    #[test]
+    fn test_from_str() {
+        assert_eq!("e4".parse::<Square>(), Ok(Square::E4));
+        assert_eq!("A8".parse::<Square>(), Ok(Square::A8));
+        for square in Square::ALL {
+            assert_eq!(square.algebraic().parse::<Square>(), Ok(square));
+        }
+        assert!("".parse::<Square>().is_err());
+        assert!("i1".parse::<Square>().is_err());
+        assert!("a9".parse::<Square>().is_err());
+        assert!("e44".parse::<Square>().is_err());
+    }
+
+    #[test]
+    fn test_try_from_rank_and_file_indices() {
+        assert_eq!(Square::try_from((3u8, 4u8)), Ok(Square::E4));
+        assert_eq!(Square::try_from((7u8, 0u8)), Ok(Square::A8));
+        assert!(Square::try_from((8u8, 0u8)).is_err());
+        assert!(Square::try_from((0u8, 8u8)).is_err());
+        assert!(Square::try_from(64u8).is_err());
+    }
+
     #[test]
     fn test_from_rank_and_file() {
         assert_eq!(Square::from_rank_and_file(Rank::Eight, File::A), Square::A8);
@@ -538,6 +820,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_file_and_rank_index() {
+        assert_eq!(Square::E4.file_index(), 4);
+        assert_eq!(Square::E4.rank_index(), 3);
+        assert_eq!(Square::H8.file_index(), 7);
+        assert_eq!(Square::A1.rank_index(), 0);
+    }
+
     #[test]
     fn test_get_rank() {
         assert_eq!(Square::A8.rank() as u8, 7);
@@ -33,9 +33,6 @@ impl const SquareDeltaUtils for SquareDelta {
     const DOWN_RIGHT: SquareDelta = SquareDelta::DOWN + SquareDelta::RIGHT;
 
     fn for_perspective(self, color: Color) -> SquareDelta {
-        self * match color {
-            Color::White => 1,
-            Color::Black => -1,
-        }
+        self * (1 - 2 * color as i8)
     }
 }
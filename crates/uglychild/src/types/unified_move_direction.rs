@@ -4,7 +4,7 @@ use crate::types::{KnightMoveDirection, QueenLikeMoveDirection};
 #[derive(Copy, Clone, Eq, Debug)]
 #[derive_const(PartialEq)]
 pub struct UnifiedMoveDirection {
-    pub value: u8,
+    value: u8,
 }
 
 impl UnifiedMoveDirection {
@@ -27,42 +27,38 @@ impl UnifiedMoveDirection {
         }
     }
 
-    /// # Safety
-    /// The low nibble of `self.value` must encode either a valid queen-like direction (`0..=7`)
-    /// or the queen-like null sentinel (`0b1111`).
-    pub const unsafe fn as_queen_like(&self) -> Option<QueenLikeMoveDirection> {
+    /// Returns the queen-like direction, or `None` if this is not a queen-like direction.
+    pub const fn as_queen_like(&self) -> Option<QueenLikeMoveDirection> {
         let value = self.value & Self::NULL_QUEEN_LIKE;
         if value == Self::NULL_QUEEN_LIKE {
             None
         } else {
-            Some(unsafe { QueenLikeMoveDirection::from(value) })
+            Some(unsafe { QueenLikeMoveDirection::from_unchecked(value) })
         }
     }
 
     /// # Safety
-    /// The low nibble of `self.value` must encode a valid queen-like direction (`0..=7`).
+    /// `self` must be a queen-like direction, i.e. [`Self::as_queen_like`] returns `Some`.
     pub const unsafe fn as_queen_like_unchecked(&self) -> QueenLikeMoveDirection {
         let value = self.value & Self::NULL_QUEEN_LIKE;
-        unsafe { QueenLikeMoveDirection::from(value) }
+        unsafe { QueenLikeMoveDirection::from_unchecked(value) }
     }
 
-    /// # Safety
-    /// The high nibble of `self.value` must encode either a valid knight-like direction (`0..=7`)
-    /// or the knight-like null sentinel (`0b1111`).
-    pub const unsafe fn as_knight_like(&self) -> Option<KnightMoveDirection> {
+    /// Returns the knight-like direction, or `None` if this is not a knight-like direction.
+    pub const fn as_knight_like(&self) -> Option<KnightMoveDirection> {
         let value = self.value & Self::NULL_KNIGHT_LIKE;
         if value == Self::NULL_KNIGHT_LIKE {
             None
         } else {
-            Some(unsafe { KnightMoveDirection::from(value >> 4) })
+            Some(unsafe { KnightMoveDirection::from_unchecked(value >> 4) })
         }
     }
 
     /// # Safety
-    /// The high nibble of `self.value` must encode a valid knight-like direction (`0..=7`).
+    /// `self` must be a knight-like direction, i.e. [`Self::as_knight_like`] returns `Some`.
     pub const unsafe fn as_knight_like_unchecked(&self) -> KnightMoveDirection {
         let value = self.value & Self::NULL_KNIGHT_LIKE;
-        unsafe { KnightMoveDirection::from(value >> 4) }
+        unsafe { KnightMoveDirection::from_unchecked(value >> 4) }
     }
 
     pub const fn is_null(&self) -> bool {
@@ -79,8 +75,8 @@ mod tests {
     fn test_unified_move_direction() {
         assert!(UnifiedMoveDirection::NULL.is_null());
 
-        assert_eq!(unsafe { UnifiedMoveDirection::NULL.as_knight_like() }, None);
-        assert_eq!(unsafe { UnifiedMoveDirection::NULL.as_queen_like() }, None);
+        assert_eq!(UnifiedMoveDirection::NULL.as_knight_like(), None);
+        assert_eq!(UnifiedMoveDirection::NULL.as_queen_like(), None);
 
         for move_direction in KnightMoveDirection::ALL {
             let unified_move_direction = UnifiedMoveDirection::from_knight_like(move_direction);
@@ -88,7 +84,7 @@ mod tests {
                 unsafe { unified_move_direction.as_knight_like_unchecked() },
                 move_direction
             );
-            assert_eq!(unsafe { unified_move_direction.as_queen_like() }, None);
+            assert_eq!(unified_move_direction.as_queen_like(), None);
         }
         for move_direction in QueenLikeMoveDirection::ALL {
             let unified_move_direction = UnifiedMoveDirection::from_queen_like(move_direction);
@@ -96,7 +92,7 @@ mod tests {
                 unsafe { unified_move_direction.as_queen_like_unchecked() },
                 move_direction
             );
-            assert_eq!(unsafe { unified_move_direction.as_knight_like() }, None);
+            assert_eq!(unified_move_direction.as_knight_like(), None);
         }
     }
 }
@@ -0,0 +1,273 @@
+//! Zobrist-hashing policy over a user-supplied key schedule.
+
+use std::{fmt::Debug, marker::PhantomData};
+
+use super::{
+    board::Board,
+    castling_rights::CastlingRights,
+    color::Color,
+    double_pawn_push_file::DoublePawnPushFile,
+    piece::Piece,
+    square::Square,
+    zobrist_policy::{ZobristPolicy, private},
+};
+use crate::logic::zobrist_hash::ZobristKeys;
+
+/// Supplies the keys for [`WithCustomZobrist`], usually from a `static` or a `LazyLock`.
+///
+/// Implement it on a marker type:
+///
+/// ```
+/// use uglychild::{logic::zobrist_hash::ZobristKeys, types::ZobristKeySchedule};
+///
+/// #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+/// struct MyKeys;
+///
+/// static KEYS: ZobristKeys = ZobristKeys {
+///     piece_square: [[1; 64]; 12],
+///     castling_rights: [2; 16],
+///     en_passant_file: [3; 8],
+///     en_passant_needs_capturer: false,
+///     side_to_move: [0, 4],
+/// };
+///
+/// impl ZobristKeySchedule for MyKeys {
+///     fn keys() -> &'static ZobristKeys {
+///         &KEYS
+///     }
+/// }
+/// ```
+pub trait ZobristKeySchedule: Copy + Clone + Eq + PartialEq + Debug + Default + 'static {
+    /// Returns the key schedule.
+    fn keys() -> &'static ZobristKeys;
+}
+
+#[derive(Debug, Default)]
+/// Policy marker enabling incremental Zobrist updates with the keys of `K`, so hashes can match
+/// those of external opening books and databases.
+pub struct WithCustomZobrist<K: ZobristKeySchedule>(PhantomData<K>);
+
+impl<K: ZobristKeySchedule> Clone for WithCustomZobrist<K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: ZobristKeySchedule> Copy for WithCustomZobrist<K> {}
+
+impl<K: ZobristKeySchedule> PartialEq for WithCustomZobrist<K> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<K: ZobristKeySchedule> Eq for WithCustomZobrist<K> {}
+
+impl<K: ZobristKeySchedule> private::Sealed for WithCustomZobrist<K> {}
+
+impl<K: ZobristKeySchedule> ZobristPolicy for WithCustomZobrist<K> {
+    /// 64-bit Zobrist hash value.
+    type HashState = u64;
+
+    #[inline(always)]
+    fn initial_hash(
+        board: &Board,
+        castling_rights: CastlingRights,
+        double_pawn_push_file: DoublePawnPushFile,
+        side_to_move: Color,
+    ) -> Self::HashState {
+        K::keys().calc_position_hash(board, castling_rights, double_pawn_push_file, side_to_move)
+    }
+
+    #[inline(always)]
+    fn on_put_piece(hash: &mut Self::HashState, color: Color, piece: Piece, square: Square) {
+        *hash ^= K::keys().piece_square_key(color, piece, square);
+    }
+
+    #[inline(always)]
+    fn on_remove_piece(hash: &mut Self::HashState, color: Color, piece: Piece, square: Square) {
+        *hash ^= K::keys().piece_square_key(color, piece, square);
+    }
+
+    #[inline(always)]
+    fn on_move_piece(
+        hash: &mut Self::HashState,
+        color: Color,
+        piece: Piece,
+        from: Square,
+        to: Square,
+    ) {
+        let keys = K::keys();
+        *hash ^=
+            keys.piece_square_key(color, piece, from) ^ keys.piece_square_key(color, piece, to);
+    }
+
+    #[inline(always)]
+    fn on_castling_rights_change(
+        hash: &mut Self::HashState,
+        old: CastlingRights,
+        new: CastlingRights,
+    ) {
+        let keys = &K::keys().castling_rights;
+        *hash ^= keys[old as usize] ^ keys[new as usize];
+    }
+
+    #[inline(always)]
+    fn on_double_pawn_push_file_change(
+        hash: &mut Self::HashState,
+        old: DoublePawnPushFile,
+        old_capturable: bool,
+        new: DoublePawnPushFile,
+        new_capturable: bool,
+    ) {
+        let keys = K::keys();
+        *hash ^=
+            keys.en_passant_key(old, old_capturable) ^ keys.en_passant_key(new, new_capturable);
+    }
+
+    #[inline(always)]
+    fn on_side_to_move_flip(hash: &mut Self::HashState) {
+        let keys = &K::keys().side_to_move;
+        *hash ^= keys[Color::White as usize] ^ keys[Color::Black as usize];
+    }
+
+    #[inline(always)]
+    fn is_consistent(
+        hash: &Self::HashState,
+        board: &Board,
+        castling_rights: CastlingRights,
+        double_pawn_push_file: DoublePawnPushFile,
+        side_to_move: Color,
+    ) -> bool {
+        *hash
+            == K::keys().calc_position_hash(
+                board,
+                castling_rights,
+                double_pawn_push_file,
+                side_to_move,
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use super::*;
+    use crate::{
+        types::{ColoredPiece, MoveList, Position},
+        utilities::Prng,
+    };
+
+    #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+    struct TestKeys;
+
+    static KEYS: LazyLock<ZobristKeys> = LazyLock::new(|| {
+        let mut rng = Prng::new(42);
+        let mut keys = ZobristKeys {
+            piece_square: [[0; 64]; 12],
+            castling_rights: [0; 16],
+            en_passant_file: [0; 8],
+            en_passant_needs_capturer: false,
+            side_to_move: [0; 2],
+        };
+        keys.piece_square
+            .iter_mut()
+            .flatten()
+            .chain(&mut keys.castling_rights)
+            .chain(&mut keys.en_passant_file)
+            .chain(&mut keys.side_to_move)
+            .for_each(|key| *key = rng.generate());
+        keys
+    });
+
+    impl ZobristKeySchedule for TestKeys {
+        fn keys() -> &'static ZobristKeys {
+            &KEYS
+        }
+    }
+
+    /// [`TestKeys`], but only hashing en passant files a pawn could capture on.
+    #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+    struct CapturerKeys;
+
+    static CAPTURER_KEYS: LazyLock<ZobristKeys> = LazyLock::new(|| ZobristKeys {
+        en_passant_needs_capturer: true,
+        ..KEYS.clone()
+    });
+
+    impl ZobristKeySchedule for CapturerKeys {
+        fn keys() -> &'static ZobristKeys {
+            &CAPTURER_KEYS
+        }
+    }
+
+    #[test]
+    fn test_custom_keys_stay_consistent_through_moves() {
+        // Castling, en passant and promotions are all available.
+        let mut position = Position::<8, { Color::White }, WithCustomZobrist<TestKeys>>::from_fen(
+            "r3k2r/1P6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 2",
+        )
+        .unwrap();
+        assert!(position.is_zobrist_consistent());
+        let initial_hash = position.context().zobrist_hash;
+
+        let mut moves = MoveList::new();
+        position.generate_moves(&mut moves);
+        for &move_ in moves.as_slice() {
+            position.make_move(move_);
+            let child = unsafe { position.rebrand_stm_mut::<{ Color::Black }>() };
+            assert!(child.is_zobrist_consistent(), "{move_}");
+            child.unmake_move(move_);
+        }
+        assert_eq!(position.context().zobrist_hash, initial_hash);
+
+        // Colors are keyed separately.
+        let white_pawn = ColoredPiece::WhitePawn.index();
+        let black_pawn = ColoredPiece::BlackPawn.index();
+        assert_ne!(
+            KEYS.piece_square[white_pawn][Square::E4 as usize],
+            KEYS.piece_square[black_pawn][Square::E4 as usize]
+        );
+    }
+
+    #[test]
+    fn test_en_passant_key_needs_capturer() {
+        type P = Position<4, { Color::Black }, WithCustomZobrist<CapturerKeys>>;
+        let hash = |fen| P::from_fen(fen).unwrap().context().zobrist_hash;
+        // No black pawn stands next to e4, so the file is not hashed.
+        assert_eq!(
+            hash("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1"),
+            hash("4k3/8/8/8/4P3/8/8/4K3 b - - 0 1")
+        );
+        // The d4 pawn could take, so it is, even though the capture would be illegal here.
+        assert_ne!(
+            hash("8/8/8/8/k2pP2R/8/8/4K3 b - e3 0 1"),
+            hash("8/8/8/8/k2pP2R/8/8/4K3 b - - 0 1")
+        );
+
+        // Incremental updates follow the same rule across double pushes with and without a
+        // capturer, and across the captures themselves.
+        let mut position =
+            Position::<4, { Color::White }, WithCustomZobrist<CapturerKeys>>::from_fen(
+                "4k3/8/8/8/1p1p4/8/P1P1P3/4K3 w - - 0 1",
+            )
+            .unwrap();
+        let mut moves = MoveList::new();
+        position.generate_moves(&mut moves);
+        for &move_ in moves.as_slice() {
+            position.make_move(move_);
+            let child = unsafe { position.rebrand_stm_mut::<{ Color::Black }>() };
+            assert!(child.is_zobrist_consistent(), "{move_}");
+            let mut replies = MoveList::new();
+            child.generate_moves(&mut replies);
+            for &reply in replies.as_slice() {
+                child.make_move(reply);
+                let grandchild = unsafe { child.rebrand_stm_mut::<{ Color::White }>() };
+                assert!(grandchild.is_zobrist_consistent(), "{move_} {reply}");
+                grandchild.unmake_move(reply);
+            }
+            child.unmake_move(move_);
+        }
+    }
+}
@@ -38,21 +38,24 @@ impl ZobristPolicy for WithZobrist {
     }
 
     #[inline(always)]
-    fn on_put_piece(hash: &mut Self::HashState, piece: Piece, square: Square) {
-        *hash ^= piece_square_key(Piece::Null, square) ^ piece_square_key(piece, square);
+    fn on_put_piece(hash: &mut Self::HashState, color: Color, piece: Piece, square: Square) {
+        *hash ^= piece_square_key(color, piece, square);
     }
 
     #[inline(always)]
-    fn on_remove_piece(hash: &mut Self::HashState, piece: Piece, square: Square) {
-        *hash ^= piece_square_key(piece, square) ^ piece_square_key(Piece::Null, square);
+    fn on_remove_piece(hash: &mut Self::HashState, color: Color, piece: Piece, square: Square) {
+        *hash ^= piece_square_key(color, piece, square);
     }
 
     #[inline(always)]
-    fn on_move_piece(hash: &mut Self::HashState, piece: Piece, from: Square, to: Square) {
-        *hash ^= piece_square_key(piece, from)
-            ^ piece_square_key(Piece::Null, from)
-            ^ piece_square_key(Piece::Null, to)
-            ^ piece_square_key(piece, to);
+    fn on_move_piece(
+        hash: &mut Self::HashState,
+        color: Color,
+        piece: Piece,
+        from: Square,
+        to: Square,
+    ) {
+        *hash ^= piece_square_key(color, piece, from) ^ piece_square_key(color, piece, to);
     }
 
     #[inline(always)]
@@ -68,7 +71,9 @@ impl ZobristPolicy for WithZobrist {
     fn on_double_pawn_push_file_change(
         hash: &mut Self::HashState,
         old: DoublePawnPushFile,
+        _old_capturable: bool,
         new: DoublePawnPushFile,
+        _new_capturable: bool,
     ) {
         *hash ^= double_pawn_push_key(old) ^ double_pawn_push_key(new);
     }
@@ -30,13 +30,21 @@ impl ZobristPolicy for WithoutZobrist {
     }
 
     #[inline(always)]
-    fn on_put_piece(_hash: &mut Self::HashState, _piece: Piece, _square: Square) {}
+    fn on_put_piece(_hash: &mut Self::HashState, _color: Color, _piece: Piece, _square: Square) {}
 
     #[inline(always)]
-    fn on_remove_piece(_hash: &mut Self::HashState, _piece: Piece, _square: Square) {}
+    fn on_remove_piece(_hash: &mut Self::HashState, _color: Color, _piece: Piece, _square: Square) {
+    }
 
     #[inline(always)]
-    fn on_move_piece(_hash: &mut Self::HashState, _piece: Piece, _from: Square, _to: Square) {}
+    fn on_move_piece(
+        _hash: &mut Self::HashState,
+        _color: Color,
+        _piece: Piece,
+        _from: Square,
+        _to: Square,
+    ) {
+    }
 
     #[inline(always)]
     fn on_castling_rights_change(
@@ -50,7 +58,9 @@ impl ZobristPolicy for WithoutZobrist {
     fn on_double_pawn_push_file_change(
         _hash: &mut Self::HashState,
         _old: DoublePawnPushFile,
+        _old_capturable: bool,
         _new: DoublePawnPushFile,
+        _new_capturable: bool,
     ) {
     }
 
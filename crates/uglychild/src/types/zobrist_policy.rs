@@ -31,12 +31,18 @@ pub trait ZobristPolicy:
         side_to_move: Color,
     ) -> Self::HashState;
 
-    /// Applies an incremental hash update for placing `piece` on `square`.
-    fn on_put_piece(hash: &mut Self::HashState, piece: Piece, square: Square);
-    /// Applies an incremental hash update for removing `piece` from `square`.
-    fn on_remove_piece(hash: &mut Self::HashState, piece: Piece, square: Square);
-    /// Applies an incremental hash update for moving `piece` from `from` to `to`.
-    fn on_move_piece(hash: &mut Self::HashState, piece: Piece, from: Square, to: Square);
+    /// Applies an incremental hash update for placing `color`'s `piece` on `square`.
+    fn on_put_piece(hash: &mut Self::HashState, color: Color, piece: Piece, square: Square);
+    /// Applies an incremental hash update for removing `color`'s `piece` from `square`.
+    fn on_remove_piece(hash: &mut Self::HashState, color: Color, piece: Piece, square: Square);
+    /// Applies an incremental hash update for moving `color`'s `piece` from `from` to `to`.
+    fn on_move_piece(
+        hash: &mut Self::HashState,
+        color: Color,
+        piece: Piece,
+        from: Square,
+        to: Square,
+    );
     /// Applies an incremental hash update for castling-rights change.
     fn on_castling_rights_change(
         hash: &mut Self::HashState,
@@ -44,10 +50,16 @@ pub trait ZobristPolicy:
         new: CastlingRights,
     );
     /// Applies an incremental hash update for en-passant-file change.
+    ///
+    /// `old_capturable` and `new_capturable` tell whether a pawn of the side that could take en
+    /// passant on that file stands next to the double-pushed pawn, for schedules (like
+    /// Polyglot's) that only hash the file then.
     fn on_double_pawn_push_file_change(
         hash: &mut Self::HashState,
         old: DoublePawnPushFile,
+        old_capturable: bool,
         new: DoublePawnPushFile,
+        new_capturable: bool,
     );
     /// Applies side-to-move toggle to the hash state.
     fn on_side_to_move_flip(hash: &mut Self::HashState);
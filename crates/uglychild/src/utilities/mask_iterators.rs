@@ -2,6 +2,8 @@ use crate::types::{Bitboard, Square};
 
 #[derive(Debug, Clone)]
 /// An iterator that generates the set bits of a bitboard.
+///
+/// Iterates least significant bit first (H1 toward A8); use [`Iterator::rev`] for MSB-first order.
 pub struct MaskBitsIterator {
     current_mask: Bitboard,
 }
@@ -25,10 +27,32 @@ impl const Iterator for MaskBitsIterator {
 
         Some(ls1b_mask)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.current_mask.count_ones() as usize;
+        (count, Some(count))
+    }
 }
 
+impl const DoubleEndedIterator for MaskBitsIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.current_mask == 0 {
+            return None;
+        }
+
+        let ms1b_mask = 1 << (63 - self.current_mask.leading_zeros()); // Isolate the most significant set bit
+        self.current_mask &= !ms1b_mask; // Clear the most significant set bit
+
+        Some(ms1b_mask)
+    }
+}
+
+impl ExactSizeIterator for MaskBitsIterator {}
+
 #[derive(Debug, Clone)]
 /// An iterator that generates the squares of a bitboard.
+///
+/// Iterates least significant bit first (H1 toward A8); use [`Iterator::rev`] for A8-first order.
 pub struct MaskSquaresIterator {
     current_mask: Bitboard,
 }
@@ -52,8 +76,28 @@ impl const Iterator for MaskSquaresIterator {
 
         Square::from_bitboard(ls1b_mask)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.current_mask.count_ones() as usize;
+        (count, Some(count))
+    }
 }
 
+impl const DoubleEndedIterator for MaskSquaresIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.current_mask == 0 {
+            return None;
+        }
+
+        let ms1b_mask = 1 << (63 - self.current_mask.leading_zeros()); // Isolate the most significant set bit
+        self.current_mask &= !ms1b_mask; // Clear the most significant set bit
+
+        Square::from_bitboard(ms1b_mask)
+    }
+}
+
+impl ExactSizeIterator for MaskSquaresIterator {}
+
 #[derive(Debug, Clone)]
 /// An iterator that generates all possible set bit combinations of a bitboard.
 pub struct BitCombinationsIterator {
@@ -97,6 +141,30 @@ mod tests {
     use super::*;
     use crate::types::BitboardUtils;
 
+    #[test]
+    fn test_mask_iterators_both_directions() {
+        let mask = Square::A8.mask() | Square::E4.mask() | Square::H1.mask();
+
+        let squares: Vec<Square> = mask.iter_set_bits_as_squares().collect();
+        assert_eq!(squares, vec![Square::H1, Square::E4, Square::A8]);
+        let squares: Vec<Square> = mask.iter_set_bits_as_squares().rev().collect();
+        assert_eq!(squares, vec![Square::A8, Square::E4, Square::H1]);
+
+        let bits: Vec<Bitboard> = mask.iter_set_bits_as_masks().rev().collect();
+        assert_eq!(
+            bits,
+            vec![Square::A8.mask(), Square::E4.mask(), Square::H1.mask()]
+        );
+
+        let mut iter = mask.iter_set_bits_as_squares();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(Square::H1));
+        assert_eq!(iter.next_back(), Some(Square::A8));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next(), Some(Square::E4));
+        assert_eq!(iter.next_back(), None);
+    }
+
     #[test]
     fn test_generate_bit_combinations() {
         // Test with an empty bitmask
@@ -5,8 +5,10 @@ mod array;
 mod iterable_enum;
 mod mask_iterators;
 mod random;
+mod square_mapping;
 
 pub use array::*;
 pub use iterable_enum::*;
 pub use mask_iterators::*;
 pub use random::*;
+pub use square_mapping::*;
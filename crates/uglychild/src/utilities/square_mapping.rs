@@ -0,0 +1,123 @@
+//! Per-square and per-square-pair lookup tables built at compile time.
+//!
+//! Many chess tables map a square, or an ordered pair of squares, to a small value: king zones,
+//! the squares between two squares, the direction from one square to another, SEE or distance
+//! tables. [`SquareMapping`] and [`SquaresTwoToOneMapping`] fill such a table from a `const fn`,
+//! so it can live in a `static` without hand-written index arithmetic. Building a table in a
+//! `static` or `const` needs `#![feature(const_trait_impl)]` in the calling crate.
+
+use std::{marker::Destruct, ops::Index};
+
+use crate::{types::Square, utilities::IterableEnum};
+
+/// A value for each of the 64 squares, indexed by [`Square`].
+///
+/// ```
+/// #![feature(const_trait_impl)]
+/// use uglychild::{types::Square, utilities::SquareMapping};
+///
+/// const fn file_index(square: Square) -> u8 {
+///     square.file() as u8
+/// }
+///
+/// static FILES: SquareMapping<u8> = SquareMapping::init(file_index);
+/// assert_eq!(FILES[Square::C4], 2);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SquareMapping<T> {
+    values: [T; 64],
+}
+
+impl<T: Copy> SquareMapping<T> {
+    /// Builds the table by calling `value_at` for every square.
+    pub const fn init<F: [const] Fn(Square) -> T + [const] Destruct>(
+        value_at: F,
+    ) -> SquareMapping<T> {
+        let mut values = [value_at(Square::A8); 64];
+        for square in Square::ALL {
+            values[square as usize] = value_at(square);
+        }
+        SquareMapping { values }
+    }
+
+    /// Returns the value for `square`.
+    #[inline]
+    pub const fn get(&self, square: Square) -> T {
+        self.values[square as usize]
+    }
+}
+
+impl<T> Index<Square> for SquareMapping<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, square: Square) -> &T {
+        &self.values[square as usize]
+    }
+}
+
+/// A value for each ordered pair of squares, indexed by `(from, to)`.
+///
+/// The table holds 4096 entries, so keep `T` small and store the table in a `static`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SquaresTwoToOneMapping<T> {
+    values: [[T; 64]; 64],
+}
+
+impl<T: Copy> SquaresTwoToOneMapping<T> {
+    /// Builds the table by calling `value_at` for every ordered pair of squares.
+    pub const fn init<F: [const] Fn(Square, Square) -> T + [const] Destruct>(
+        value_at: F,
+    ) -> SquaresTwoToOneMapping<T> {
+        let mut values = [[value_at(Square::A8, Square::A8); 64]; 64];
+        for from in Square::ALL {
+            for to in Square::ALL {
+                values[from as usize][to as usize] = value_at(from, to);
+            }
+        }
+        SquaresTwoToOneMapping { values }
+    }
+
+    /// Returns the value for the pair `(from, to)`.
+    #[inline]
+    pub const fn get(&self, from: Square, to: Square) -> T {
+        self.values[from as usize][to as usize]
+    }
+}
+
+impl<T> Index<(Square, Square)> for SquaresTwoToOneMapping<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (from, to): (Square, Square)) -> &T {
+        &self.values[from as usize][to as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn chebyshev_distance(from: Square, to: Square) -> u8 {
+        let file_distance = (from.file() as u8).abs_diff(to.file() as u8);
+        let rank_distance = (from.rank() as u8).abs_diff(to.rank() as u8);
+        if file_distance > rank_distance {
+            file_distance
+        } else {
+            rank_distance
+        }
+    }
+
+    static DISTANCES: SquaresTwoToOneMapping<u8> = SquaresTwoToOneMapping::init(chebyshev_distance);
+
+    #[test]
+    fn test_two_to_one_mapping_matches_function() {
+        for from in Square::ALL {
+            for to in Square::ALL {
+                assert_eq!(DISTANCES[(from, to)], chebyshev_distance(from, to));
+                assert_eq!(DISTANCES.get(from, to), DISTANCES.get(to, from));
+            }
+        }
+        assert_eq!(DISTANCES.get(Square::A1, Square::H8), 7);
+    }
+}
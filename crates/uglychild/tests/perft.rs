@@ -1,4 +1,5 @@
 include!(concat!(env!("CARGO_MANIFEST_DIR"), "/perft_case.rs"));
+use uglychild::logic::perft::PerftStats;
 
 macro_rules! define_perft_tests {
     ($($name:ident => ($case:expr, $depth:literal);)+) => {
@@ -22,4 +23,73 @@ define_perft_tests! {
     test_perft_position_3 => (PerftCase::Position3, 7);
     test_perft_position_4 => (PerftCase::Position4, 6);
     test_perft_position_5 => (PerftCase::Position5, 5);
+    test_perft_promotions => (PerftCase::Promotions, 6);
+}
+
+macro_rules! define_hashed_perft_tests {
+    ($($name:ident => ($case:expr, $depth:literal);)+) => {
+        $(
+            #[test]
+            fn $name() {
+                const CONTEXTS_CAPACITY: usize = $depth + 1;
+                const HASH_MB: usize = 16;
+                let nodes_observed = ($case).with_position_with_zobrist::<CONTEXTS_CAPACITY, _>(
+                    |mut p| p.perft_hashed($depth, HASH_MB),
+                    |mut p| p.perft_hashed($depth, HASH_MB),
+                );
+                ($case).verify_perft($depth, nodes_observed);
+            }
+        )+
+    };
+}
+
+define_hashed_perft_tests! {
+    test_perft_hashed_initial_position => (PerftCase::Initial, 6);
+    test_perft_hashed_kiwipete => (PerftCase::Kiwipete, 5);
+    test_perft_hashed_position_3 => (PerftCase::Position3, 7);
+    test_perft_hashed_position_4 => (PerftCase::Position4, 6);
+    test_perft_hashed_position_5 => (PerftCase::Position5, 5);
+    test_perft_hashed_promotions => (PerftCase::Promotions, 6);
+}
+
+macro_rules! define_perft_stats_tests {
+    ($($name:ident => ($case:expr, $depth:literal, $expected:expr);)+) => {
+        $(
+            #[test]
+            fn $name() {
+                const CONTEXTS_CAPACITY: usize = $depth + 1;
+                let stats = ($case).with_position_without_zobrist::<CONTEXTS_CAPACITY, _>(
+                    |mut p| p.perft_stats($depth),
+                    |mut p| p.perft_stats($depth),
+                );
+                let [nodes, captures, en_passants, castles, promotions, checks, discovery_checks, double_checks, checkmates] = $expected;
+                assert_eq!(
+                    stats,
+                    PerftStats {
+                        nodes,
+                        captures,
+                        en_passants,
+                        castles,
+                        promotions,
+                        checks,
+                        discovery_checks,
+                        double_checks,
+                        checkmates,
+                    },
+                    "{}",
+                    ($case).name()
+                );
+            }
+        )+
+    };
+}
+
+// Columns: nodes, captures, e.p., castles, promotions, checks, discovery checks, double checks,
+// checkmates (chessprogramming.org perft results). The published table lists no discovery checks
+// for position 4, but after 1... c4 2. Kf1 (or Ke2) a move of the c4 pawn uncovers the b5 bishop.
+define_perft_stats_tests! {
+    test_perft_stats_initial_position => (PerftCase::Initial, 4, [197281, 1576, 0, 0, 0, 469, 0, 0, 8]);
+    test_perft_stats_kiwipete => (PerftCase::Kiwipete, 3, [97862, 17102, 45, 3162, 0, 993, 0, 0, 1]);
+    test_perft_stats_position_3 => (PerftCase::Position3, 5, [674624, 52051, 1165, 0, 0, 52950, 1292, 3, 0]);
+    test_perft_stats_position_4 => (PerftCase::Position4, 3, [9467, 1021, 4, 0, 120, 38, 2, 0, 22]);
 }